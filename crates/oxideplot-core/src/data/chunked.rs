@@ -0,0 +1,654 @@
+//! Chunked delimited-text import for files too big to hold as text.
+//!
+//! [`ChunkedCsv`] takes a file piece by piece (any split points) and parses
+//! each complete line as it arrives, converting cells straight into typed
+//! column storage: a numeric cell costs its 8-byte `f64`, and only the
+//! unparsed tail of the last chunk is kept as bytes. The result matches
+//! [`load_from_bytes`](super::loader::load_from_bytes) for the same file,
+//! except that each column's kind is decided from its first
+//! [`CLASSIFY_ROWS`] data rows instead of the whole column.
+//!
+//! A file of hundreds of millions of rows can also be thinned as it is
+//! read ([`ImportDecimation`]): every Nth row, one mean row per X bucket,
+//! or only the rows of an X window at full resolution, so it can be
+//! explored before committing the memory to all of it. A window over an
+//! ascending X (a log's timestamps) also ends the read early:
+//! [`ChunkedCsv::past_window`] says when the rest of the file can't match.
+
+use std::borrow::Cow;
+use std::io::Read;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::column::Column;
+use crate::data::datetime::{detect_date_format, format_timestamp, parse_to_timestamp};
+use crate::data::loader::{
+    merge_date_time_cells, normalize_table, rows_to_columns, CsvOptions, LoadedData, NumberFormat,
+};
+use crate::data::parser;
+
+/// Bytes gathered before the delimiter, decimal separator and header row
+/// are detected (or the whole file, if smaller).
+pub const DETECT_BYTES: usize = 1 << 20;
+
+/// Data rows sampled to decide each column's kind.
+pub const CLASSIFY_ROWS: usize = 1000;
+
+/// Read size of [`load_delimited_reader`].
+pub const READ_CHUNK_BYTES: usize = 8 << 20;
+
+/// Rows a chunked import keeps. X is the first numeric or time column.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ImportDecimation {
+    /// Every row.
+    #[default]
+    Off,
+    /// The first of every `n` rows.
+    EveryNth(usize),
+    /// One row per run of rows whose X falls in the same `width`-wide
+    /// bucket: the mean of each numeric or time column, the first text.
+    Bucket(f64),
+    /// Only rows with X in `min..=max`, all of them.
+    Window(f64, f64),
+}
+
+impl ImportDecimation {
+    /// Errs on a zero step, a non-positive bucket or an empty window.
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            ImportDecimation::EveryNth(0) => Err("keep every N rows: N must be at least 1".to_string()),
+            ImportDecimation::Bucket(w) if !(w.is_finite() && w > 0.0) => {
+                Err("bucket width must be a positive number".to_string())
+            }
+            ImportDecimation::Window(a, b) if !(a.is_finite() && b.is_finite() && a <= b) => {
+                Err("X window must be finite with min ≤ max".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// How the series of a file imported this way were thinned, for their
+    /// labels (`None` when nothing was dropped). `time_x`: X is a time
+    /// column, so widths are seconds.
+    pub fn label(&self, time_x: bool) -> Option<String> {
+        let unit = if time_x { " s" } else { "" };
+        match *self {
+            ImportDecimation::Off | ImportDecimation::EveryNth(1) => None,
+            ImportDecimation::EveryNth(n) => Some(format!("1 of every {n} rows")),
+            ImportDecimation::Bucket(w) => Some(format!("means of {w}{unit} X buckets")),
+            ImportDecimation::Window(a, b) if time_x => {
+                Some(format!("only {} … {}", format_timestamp(a), format_timestamp(b)))
+            }
+            ImportDecimation::Window(a, b) => Some(format!("only X {a} … {b}")),
+        }
+    }
+}
+
+/// [`ImportDecimation`] as the frontend sends it and projects save it:
+/// `mode` is `"off"`, `"every"` (`n`), `"bucket"` (`width`) or `"window"`
+/// (`min`, `max`).
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DecimationSpec {
+    pub mode: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+}
+
+impl DecimationSpec {
+    /// The validated decimation; errs on an unknown mode or a missing or
+    /// invalid parameter.
+    pub fn parse(&self) -> Result<ImportDecimation, String> {
+        let missing = |what: &str| format!("{} decimation needs `{what}`", self.mode);
+        let d = match self.mode.as_str() {
+            "" | "off" => ImportDecimation::Off,
+            "every" => ImportDecimation::EveryNth(self.n.ok_or_else(|| missing("n"))?),
+            "bucket" => ImportDecimation::Bucket(self.width.ok_or_else(|| missing("width"))?),
+            "window" => ImportDecimation::Window(
+                self.min.ok_or_else(|| missing("min"))?,
+                self.max.ok_or_else(|| missing("max"))?,
+            ),
+            other => return Err(format!("Unknown decimation mode {other:?}")),
+        };
+        d.validate()?;
+        Ok(d)
+    }
+}
+
+/// Incremental delimited-text parser: [`push`](Self::push) chunks in file
+/// order, then [`finish`](Self::finish).
+pub struct ChunkedCsv {
+    csv: CsvOptions,
+    /// Bytes not parsed yet: everything until detection, then the partial
+    /// line after the last complete one.
+    pending: Vec<u8>,
+    parser: Option<RowParser>,
+    bytes_read: u64,
+    decimation: ImportDecimation,
+}
+
+impl ChunkedCsv {
+    /// Separators left `None` in `csv` are detected from the first
+    /// [`DETECT_BYTES`].
+    pub fn new(csv: CsvOptions) -> Self {
+        ChunkedCsv { csv, pending: Vec::new(), parser: None, bytes_read: 0, decimation: ImportDecimation::Off }
+    }
+
+    /// Thin the rows as they are parsed (builder style).
+    pub fn decimated(mut self, decimation: ImportDecimation) -> Self {
+        self.decimation = decimation;
+        self
+    }
+
+    /// Bytes pushed so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.bytes_read
+    }
+
+    /// True once a [`ImportDecimation::Window`] import has read past the
+    /// window's end with X ascending so far: no later row can be in it, so
+    /// the caller can stop reading and [`finish`](Self::finish).
+    pub fn past_window(&self) -> bool {
+        self.parser.as_ref().and_then(|p| p.table.as_ref()).is_some_and(TypedTable::past_window)
+    }
+
+    /// Parse the complete lines now available.
+    pub fn push(&mut self, chunk: &[u8]) -> Result<(), String> {
+        self.bytes_read += chunk.len() as u64;
+        self.pending.extend_from_slice(chunk);
+        if self.parser.is_none() {
+            if self.pending.len() < DETECT_BYTES {
+                return Ok(());
+            }
+            self.start()?;
+        }
+        let cut = complete_lines(&self.pending);
+        if let Some(parser) = &mut self.parser {
+            parser.parse(&self.pending[..cut]);
+        }
+        self.pending.drain(..cut);
+        Ok(())
+    }
+
+    /// Parse the rest and type the columns.
+    pub fn finish(mut self) -> Result<LoadedData, String> {
+        if self.parser.is_none() {
+            self.start()?;
+        }
+        let mut parser = self.parser.take().ok_or("No data found in file")?;
+        parser.parse(&self.pending);
+        parser.finish()
+    }
+
+    /// Detect the separators and header row from the bytes gathered so far.
+    fn start(&mut self) -> Result<(), String> {
+        let cut = complete_lines(&self.pending);
+        let prefix = if cut > 0 { &self.pending[..cut] } else { &self.pending[..] };
+        let resolved = self.csv.resolve(prefix);
+        let delimiter = resolved.delimiter.unwrap_or(b',');
+        let numbers = NumberFormat { decimal: resolved.decimal.unwrap_or('.'), thousands: resolved.thousands };
+        let sample: Vec<Vec<String>> = reader(&decode(prefix), delimiter)
+            .records()
+            .take(50)
+            .flatten()
+            .map(|r| r.iter().map(|c| numbers.normalize(c)).collect())
+            .collect();
+        let header_row = parser::detect_header_in_rows(&sample)?;
+        self.parser = Some(RowParser {
+            delimiter,
+            numbers,
+            header_row,
+            seen: 0,
+            header: None,
+            sample: Vec::new(),
+            table: None,
+            decimation: self.decimation,
+        });
+        Ok(())
+    }
+}
+
+/// Parse a whole delimited-text stream, calling `progress` with the bytes
+/// read after each chunk; `progress` returning `false` cancels the import.
+pub fn load_delimited_reader(
+    mut reader: impl Read,
+    csv: CsvOptions,
+    mut progress: impl FnMut(u64) -> bool,
+) -> Result<LoadedData, String> {
+    let mut chunked = ChunkedCsv::new(csv);
+    let mut buf = vec![0u8; READ_CHUNK_BYTES];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("Cannot read file: {e}")),
+        };
+        chunked.push(&buf[..n])?;
+        if !progress(chunked.bytes_read()) {
+            return Err("import cancelled".to_string());
+        }
+        if chunked.past_window() {
+            break;
+        }
+    }
+    chunked.finish()
+}
+
+/// Length of the prefix of `bytes` ending with its last newline outside a
+/// quoted field (0 if there is none).
+fn complete_lines(bytes: &[u8]) -> usize {
+    let mut quoted = false;
+    let mut end = 0;
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'"' => quoted = !quoted,
+            b'\n' if !quoted => end = i + 1,
+            _ => {}
+        }
+    }
+    end
+}
+
+/// Text of a block of lines: UTF-8, else read as Latin-1.
+fn decode(bytes: &[u8]) -> Cow<'_, str> {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => Cow::Owned(bytes.iter().map(|&b| b as char).collect()),
+    }
+}
+
+fn reader(text: &str, delimiter: u8) -> csv::Reader<&[u8]> {
+    csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+}
+
+/// Rows after detection: the preamble is skipped, the header kept, the
+/// first data rows sampled, and every later row appended to `table`.
+struct RowParser {
+    delimiter: u8,
+    numbers: NumberFormat,
+    header_row: usize,
+    seen: usize,
+    header: Option<Vec<String>>,
+    /// Data rows until the columns are classified.
+    sample: Vec<Vec<String>>,
+    table: Option<TypedTable>,
+    decimation: ImportDecimation,
+}
+
+impl RowParser {
+    fn parse(&mut self, block: &[u8]) {
+        if block.is_empty() {
+            return;
+        }
+        let text = decode(block);
+        for record in reader(&text, self.delimiter).records().flatten() {
+            let i = self.seen;
+            self.seen += 1;
+            if i < self.header_row {
+                continue;
+            }
+            if i == self.header_row {
+                self.header = Some(record.iter().map(|c| c.trim().to_string()).collect());
+                continue;
+            }
+            let numbers = self.numbers;
+            let cell = |c: usize| record.get(c).map(|s| numbers.normalize(s)).unwrap_or_default();
+            match &mut self.table {
+                Some(table) => table.push(cell),
+                None => {
+                    let width = self.header.as_ref().map_or(0, Vec::len);
+                    self.sample.push((0..width).map(cell).collect());
+                    if self.sample.len() >= CLASSIFY_ROWS {
+                        self.classify();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Decide the column kinds from the sample and start the typed table.
+    fn classify(&mut self) {
+        let mut columns = self.header.clone().unwrap_or_default();
+        let sample = std::mem::take(&mut self.sample);
+        let rows = sample.len();
+        let mut cells = rows_to_columns(&sample, columns.len());
+        let raw_cols = normalize_table(&mut columns, &mut cells);
+        let builders: Vec<Builder> = cells.into_iter().map(Builder::from_sample).collect();
+        let x_col = builders.iter().position(|b| !matches!(b, Builder::Text(_)));
+        let mut table = TypedTable {
+            columns,
+            raw_cols,
+            builders,
+            rows,
+            decimation: self.decimation,
+            x_col,
+            seen: 0,
+            bucket: None,
+            last_x: f64::NEG_INFINITY,
+            ascending: true,
+        };
+        if self.decimation != ImportDecimation::Off {
+            // The sample only decided the kinds: thin it like later rows.
+            table.builders.iter_mut().for_each(Builder::clear);
+            table.rows = 0;
+            for row in &sample {
+                table.push(|c| row.get(c).cloned().unwrap_or_default());
+            }
+        }
+        self.table = Some(table);
+    }
+
+    fn finish(mut self) -> Result<LoadedData, String> {
+        if self.header.is_none() {
+            return Err("No data found after header detection".to_string());
+        }
+        if self.table.is_none() {
+            self.classify();
+        }
+        let mut table = self.table.ok_or("No data found after header detection")?;
+        if table.decimation != ImportDecimation::Off && table.x_col.is_none() {
+            return Err("Decimated import needs a numeric or time X column".to_string());
+        }
+        table.flush_bucket();
+        let column_data = table.builders.into_iter().map(|b| Arc::new(b.finish())).collect();
+        Ok(LoadedData { columns: table.columns, column_data, row_count: table.rows, lazy: None })
+    }
+}
+
+/// Typed columns built row by row once the kinds are known.
+struct TypedTable {
+    columns: Vec<String>,
+    /// Raw file columns behind each column (two for a merged Date+Time).
+    raw_cols: Vec<Vec<usize>>,
+    builders: Vec<Builder>,
+    /// Rows kept so far.
+    rows: usize,
+    decimation: ImportDecimation,
+    /// Column whose values decide [`ImportDecimation::Bucket`] and
+    /// [`ImportDecimation::Window`].
+    x_col: Option<usize>,
+    /// Data rows offered to `push`, kept or not.
+    seen: usize,
+    /// The X bucket being averaged and its running per-column totals.
+    bucket: Option<(f64, Vec<Accum>)>,
+    /// Latest finite X, and whether every X so far was at least the one
+    /// before (for [`TypedTable::past_window`]).
+    last_x: f64,
+    ascending: bool,
+}
+
+impl TypedTable {
+    fn push(&mut self, cell: impl Fn(usize) -> String) {
+        let values: Vec<Value> = self
+            .builders
+            .iter()
+            .zip(&self.raw_cols)
+            .map(|(builder, raw)| match raw.as_slice() {
+                [d, t] => {
+                    let merged = merge_date_time_cells(&[cell(*d)], &[cell(*t)]);
+                    builder.parse(merged.into_iter().next().unwrap_or_default())
+                }
+                group => builder.parse(cell(group[0])),
+            })
+            .collect();
+        let seen = self.seen;
+        self.seen += 1;
+        let x = match self.x_col.map(|c| &values[c]) {
+            Some(Value::Number(x)) => *x,
+            _ => f64::NAN,
+        };
+        if x.is_finite() {
+            self.ascending &= x >= self.last_x;
+            self.last_x = x;
+        }
+        match self.decimation {
+            ImportDecimation::Off => self.keep(values),
+            ImportDecimation::EveryNth(n) => {
+                if seen.is_multiple_of(n.max(1)) {
+                    self.keep(values);
+                }
+            }
+            ImportDecimation::Window(min, max) => {
+                if (min..=max).contains(&x) {
+                    self.keep(values);
+                }
+            }
+            ImportDecimation::Bucket(width) => {
+                if !x.is_finite() {
+                    return;
+                }
+                let key = (x / width).floor();
+                if self.bucket.as_ref().is_some_and(|(k, _)| *k != key) {
+                    self.flush_bucket();
+                }
+                let (_, accums) = self.bucket.get_or_insert_with(|| (key, vec![Accum::default(); values.len()]));
+                for (acc, v) in accums.iter_mut().zip(values) {
+                    acc.add(v);
+                }
+            }
+        }
+    }
+
+    fn past_window(&self) -> bool {
+        matches!(self.decimation, ImportDecimation::Window(_, max) if self.ascending && self.last_x > max)
+    }
+
+    fn keep(&mut self, values: Vec<Value>) {
+        for (builder, v) in self.builders.iter_mut().zip(values) {
+            builder.push_value(v);
+        }
+        self.rows += 1;
+    }
+
+    /// Emit the open bucket's mean row, if any.
+    fn flush_bucket(&mut self) {
+        if let Some((_, accums)) = self.bucket.take() {
+            self.keep(accums.into_iter().map(Accum::mean).collect());
+        }
+    }
+}
+
+/// One parsed cell.
+enum Value {
+    Number(f64),
+    Text(String),
+}
+
+/// Running total of one column within an X bucket.
+#[derive(Clone, Default)]
+struct Accum {
+    sum: f64,
+    count: usize,
+    first_text: Option<String>,
+}
+
+impl Accum {
+    fn add(&mut self, v: Value) {
+        match v {
+            Value::Number(x) if x.is_finite() => {
+                self.sum += x;
+                self.count += 1;
+            }
+            Value::Number(_) => {}
+            Value::Text(t) => {
+                self.first_text.get_or_insert(t);
+            }
+        }
+    }
+
+    fn mean(self) -> Value {
+        match self.first_text {
+            Some(t) => Value::Text(t),
+            None if self.count > 0 => Value::Number(self.sum / self.count as f64),
+            None => Value::Number(f64::NAN),
+        }
+    }
+}
+
+/// One column's storage, in the kind its sampled cells classified as.
+enum Builder {
+    Numeric(Vec<f64>),
+    Time { format: &'static str, values: Vec<f64> },
+    Text(Vec<String>),
+}
+
+impl Builder {
+    fn from_sample(cells: Vec<String>) -> Builder {
+        let format = detect_date_format(&cells);
+        match (Column::from_strings(cells), format) {
+            (Column::Time { values, .. }, Some(format)) => Builder::Time { format, values: values.to_vec() },
+            (Column::Numeric { values, .. }, _) => Builder::Numeric(values.to_vec()),
+            (Column::Text(cells), _) => Builder::Text(cells),
+            // Unreachable kinds: keep the column, as text.
+            (column, _) => Builder::Text((0..column.len()).map(|i| column.cell(i).into_owned()).collect()),
+        }
+    }
+
+    /// `cell` read as this column's kind.
+    fn parse(&self, cell: String) -> Value {
+        match self {
+            Builder::Numeric(_) => Value::Number(cell.trim().parse().unwrap_or(f64::NAN)),
+            Builder::Time { format, .. } => Value::Number(parse_to_timestamp(cell.trim(), format).unwrap_or(f64::NAN)),
+            Builder::Text(_) => Value::Text(cell),
+        }
+    }
+
+    fn push_value(&mut self, v: Value) {
+        match (self, v) {
+            (Builder::Numeric(values) | Builder::Time { values, .. }, Value::Number(x)) => values.push(x),
+            (Builder::Text(cells), Value::Text(t)) => cells.push(t),
+            (Builder::Numeric(values) | Builder::Time { values, .. }, Value::Text(_)) => values.push(f64::NAN),
+            (Builder::Text(cells), Value::Number(_)) => cells.push(String::new()),
+        }
+    }
+
+    /// Drop the stored cells, keeping the kind.
+    fn clear(&mut self) {
+        match self {
+            Builder::Numeric(values) | Builder::Time { values, .. } => values.clear(),
+            Builder::Text(cells) => cells.clear(),
+        }
+    }
+
+    fn finish(self) -> Column {
+        match self {
+            Builder::Numeric(values) => Column::numeric(values),
+            Builder::Time { values, .. } => Column::time(values),
+            Builder::Text(cells) => Column::Text(cells),
+        }
+    }
+}
+
+#[cfg(test)]
+mod chunked_tests {
+    use super::*;
+    use crate::data::loader::load_from_bytes;
+
+    #[test]
+    fn chunked_import_matches_whole_file_at_any_split() {
+        let mut text = String::from("device;x1\n\nDate;Time;Temp;Note\n");
+        for i in 0..1500 {
+            let note = if i == 3 { "\"two\nlines\"" } else { "ok" };
+            text.push_str(&format!("07/07/2026;02:{:02}:{:02} PM;{},{};{note}\n", i / 60 % 60, i % 60, i, i % 10));
+        }
+        text.push_str("07/07/2026;03:00:00 PM;1.234,5;last"); // no trailing newline
+        let bytes = text.as_bytes();
+        let csv = CsvOptions { thousands: Some('.'), ..CsvOptions::default() };
+        let eager = crate::data::loader::load_from_bytes_with(bytes, "log.csv", csv).unwrap();
+        assert_eq!(eager.columns, ["Date Time", "Temp", "Note"]);
+
+        for split in [1, 7, 4096, bytes.len()] {
+            let mut chunked = ChunkedCsv::new(csv);
+            for piece in bytes.chunks(split) {
+                chunked.push(piece).unwrap();
+            }
+            let data = chunked.finish().unwrap();
+            assert_eq!(data.columns, eager.columns);
+            assert_eq!(data.row_count, 1501);
+            for (a, b) in data.column_data.iter().zip(&eager.column_data) {
+                assert_eq!(a.kind(), b.kind());
+                assert_eq!(a.cell(3), b.cell(3));
+                assert_eq!(a.cell(1500), b.cell(1500));
+            }
+            assert_eq!(data.column_data[1].value(1500), 1234.5);
+        }
+
+        let mut seen = Vec::new();
+        let plain = load_from_bytes(b"a,b\n1,2\n3,4\n", "p.csv").unwrap();
+        let streamed = load_delimited_reader(&b"a,b\n1,2\n3,4\n"[..], CsvOptions::default(), |n| {
+            seen.push(n);
+            true
+        })
+        .unwrap();
+        assert_eq!(streamed.column_data[1].to_f64(), plain.column_data[1].to_f64());
+        assert_eq!(seen, [12]);
+        let cancelled = load_delimited_reader(&b"a\n1\n"[..], CsvOptions::default(), |_| false);
+        assert_eq!(cancelled.err().unwrap(), "import cancelled");
+    }
+
+    #[test]
+    fn decimated_imports_thin_the_rows() {
+        let mut text = String::from("t,v,tag\n");
+        for i in 0..2500 {
+            text.push_str(&format!("{}.{},{},{}\n", i / 10, i % 10, i, if i % 2 == 0 { "a" } else { "b" }));
+        }
+        let load = |d: ImportDecimation| {
+            let mut chunked = ChunkedCsv::new(CsvOptions::default()).decimated(d);
+            chunked.push(text.as_bytes()).unwrap();
+            chunked.finish().unwrap()
+        };
+
+        let every = load(ImportDecimation::EveryNth(100));
+        assert_eq!(every.row_count, 25);
+        assert_eq!(every.column_data[1].value(3), 300.0);
+
+        // 1-wide buckets of 10 rows each: the mean of v, the first tag.
+        let buckets = load(ImportDecimation::Bucket(1.0));
+        assert_eq!(buckets.row_count, 250);
+        assert_eq!(buckets.column_data[1].value(0), 4.5);
+        assert_eq!(buckets.column_data[2].cell(1), "a");
+
+        let window = load(ImportDecimation::Window(100.0, 110.0));
+        assert_eq!(window.row_count, 101);
+        assert_eq!(window.column_data[1].value(0), 1000.0);
+
+        // An ascending X past the window's end needs no more input; one that
+        // went backwards might still come back into the window.
+        let big: String = (0..200_000).map(|i| format!("{i},{i}\n")).collect();
+        let read_until_past = |text: &str| {
+            let mut chunked = ChunkedCsv::new(CsvOptions::default()).decimated(ImportDecimation::Window(0.0, 10.0));
+            let mut read = 0;
+            for piece in text.as_bytes().chunks(1 << 16) {
+                chunked.push(piece).unwrap();
+                read += piece.len();
+                if chunked.past_window() {
+                    break;
+                }
+            }
+            (read, chunked.finish().unwrap().row_count)
+        };
+        let (read, rows) = read_until_past(&format!("t,v\n{big}"));
+        assert!(read < big.len());
+        assert_eq!(rows, 11);
+        let (read, rows) = read_until_past(&format!("t,v\n5,0\n{big}"));
+        assert_eq!((read, rows), (big.len() + 8, 12));
+
+        assert!(ImportDecimation::EveryNth(0).validate().is_err());
+        let spec = DecimationSpec { mode: "window".into(), min: Some(1.0), ..DecimationSpec::default() };
+        assert_eq!(spec.parse().unwrap_err(), "window decimation needs `max`");
+        assert_eq!(ImportDecimation::Bucket(0.5).label(true).as_deref(), Some("means of 0.5 s X buckets"));
+        assert_eq!(ImportDecimation::Off.label(false), None);
+    }
+}
@@ -0,0 +1,222 @@
+//! Typed column storage for [`LoadedData`](super::loader::LoadedData).
+//!
+//! Columns are classified and parsed once at import: datetime columns become
+//! epoch seconds, numeric columns become `f64` (unparseable cells → NaN), and
+//! only genuinely textual columns keep their strings. Consumers read the typed
+//! values directly instead of re-parsing strings, and a numeric cell costs
+//! 8 bytes instead of a heap-allocated `String`.
+//!
+//! Typed values live in an `Arc<[f64]>`, so a plotted series, the table view
+//! and exports can all hold the same allocation (see [`Column::shared_values`])
+//! instead of each keeping a copy of a 100M-row column.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+
+use crate::data::datetime::format_timestamp;
+use crate::data::loader::{column_to_f64, column_to_timestamps};
+
+/// One column of a loaded table.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Column {
+    /// Parsed numbers; unparseable cells are NaN.
+    Numeric { values: Arc<[f64]>, valid_frac: f64 },
+    /// Epoch-second timestamps; unparseable cells are NaN.
+    Time { values: Arc<[f64]>, valid_frac: f64 },
+    /// Raw text cells.
+    Text(Vec<String>),
+    /// Not loaded yet (wide file loaded lazily): kind and validity are
+    /// estimated from a sample, and the data is filled in by
+    /// [`LoadedData::materialize`](super::loader::LoadedData::materialize).
+    Deferred { kind: &'static str, valid_frac: f64 },
+}
+
+impl Column {
+    /// Classify and parse a string column: datetime if it parses as
+    /// timestamps, numeric if ≥ 50% of cells parse as f64, else text.
+    pub fn from_strings(cells: Vec<String>) -> Column {
+        if let Some((values, valid_frac)) = column_to_timestamps(&cells) {
+            return Column::Time { values: values.into(), valid_frac };
+        }
+        let (values, valid_frac) = column_to_f64(&cells);
+        if valid_frac >= 0.5 {
+            Column::Numeric { values: values.into(), valid_frac }
+        } else {
+            Column::Text(cells)
+        }
+    }
+
+    /// A numeric column from computed values (derived columns, transforms).
+    pub fn numeric(values: Vec<f64>) -> Column {
+        let valid_frac = finite_frac(&values);
+        Column::Numeric { values: values.into(), valid_frac }
+    }
+
+    /// A timestamp column from decoded epoch seconds (binary formats).
+    pub fn time(values: Vec<f64>) -> Column {
+        let valid_frac = finite_frac(&values);
+        Column::Time { values: values.into(), valid_frac }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Column::Numeric { values, .. } | Column::Time { values, .. } => values.len(),
+            Column::Text(cells) => cells.len(),
+            Column::Deferred { .. } => 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// `"numeric"`, `"datetime"` or `"text"` (the `ColumnMeta.kind` strings).
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Column::Numeric { .. } => "numeric",
+            Column::Time { .. } => "datetime",
+            Column::Text(_) => "text",
+            Column::Deferred { kind, .. } => kind,
+        }
+    }
+
+    /// Numeric or datetime (sorts/filters numerically in the table view).
+    pub fn is_numeric(&self) -> bool {
+        self.kind() != "text"
+    }
+
+    /// Fraction of cells that hold a valid value (finite number / parsed
+    /// timestamp / non-blank text).
+    pub fn valid_frac(&self) -> f64 {
+        match self {
+            Column::Numeric { valid_frac, .. }
+            | Column::Time { valid_frac, .. }
+            | Column::Deferred { valid_frac, .. } => *valid_frac,
+            Column::Text(cells) if cells.is_empty() => 0.0,
+            Column::Text(cells) => {
+                cells.iter().filter(|c| !c.trim().is_empty()).count() as f64 / cells.len() as f64
+            }
+        }
+    }
+
+    /// Timestamps, when this is a datetime column.
+    pub fn timestamps(&self) -> Option<&[f64]> {
+        match self {
+            Column::Time { values, .. } => Some(values),
+            _ => None,
+        }
+    }
+
+    /// The typed values' own allocation (numeric/datetime columns), for
+    /// holders that want to keep them without copying.
+    pub fn shared_values(&self) -> Option<Arc<[f64]>> {
+        match self {
+            Column::Numeric { values, .. } | Column::Time { values, .. } => Some(Arc::clone(values)),
+            _ => None,
+        }
+    }
+
+    /// Values as f64: borrowed for numeric/datetime columns, parsed (mostly
+    /// NaN) for text columns.
+    pub fn to_f64(&self) -> Cow<'_, [f64]> {
+        match self {
+            Column::Numeric { values, .. } | Column::Time { values, .. } => Cow::Borrowed(values),
+            Column::Text(cells) => Cow::Owned(column_to_f64(cells).0),
+            Column::Deferred { .. } => Cow::Borrowed(&[]),
+        }
+    }
+
+    /// Numeric value of one cell (NaN when missing or unparseable).
+    pub fn value(&self, row: usize) -> f64 {
+        match self {
+            Column::Numeric { values, .. } | Column::Time { values, .. } => {
+                values.get(row).copied().unwrap_or(f64::NAN)
+            }
+            Column::Text(cells) => cells
+                .get(row)
+                .and_then(|s| s.trim().parse::<f64>().ok())
+                .unwrap_or(f64::NAN),
+            Column::Deferred { .. } => f64::NAN,
+        }
+    }
+
+    /// Display text of one cell: numbers in shortest round-trip form,
+    /// timestamps as `YYYY-MM-DD HH:MM:SS[.mmm]`, missing values blank.
+    pub fn cell(&self, row: usize) -> Cow<'_, str> {
+        match self {
+            Column::Numeric { values, .. } => match values.get(row) {
+                Some(v) if !v.is_nan() => Cow::Owned(v.to_string()),
+                _ => Cow::Borrowed(""),
+            },
+            Column::Time { values, .. } => match values.get(row) {
+                Some(v) if v.is_finite() => Cow::Owned(format_timestamp(*v)),
+                _ => Cow::Borrowed(""),
+            },
+            Column::Text(cells) => {
+                cells.get(row).map(|s| Cow::Borrowed(s.as_str())).unwrap_or(Cow::Borrowed(""))
+            }
+            Column::Deferred { .. } => Cow::Borrowed(""),
+        }
+    }
+}
+
+fn finite_frac(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().filter(|v| v.is_finite()).count() as f64 / values.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod column_tests {
+    use super::*;
+
+    fn strings(v: &[&str]) -> Vec<String> {
+        v.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn classifies_numeric_time_and_text() {
+        let n = Column::from_strings(strings(&["1", "2.5", "x", "4"]));
+        assert_eq!(n.kind(), "numeric");
+        assert!((n.valid_frac() - 0.75).abs() < 1e-12);
+        assert!(n.value(2).is_nan());
+
+        let t = Column::from_strings(strings(&[
+            "2024-01-01 00:00:00",
+            "2024-01-01 00:00:01",
+            "2024-01-01 00:00:02",
+        ]));
+        assert_eq!(t.kind(), "datetime");
+        let ts = t.timestamps().unwrap();
+        assert_eq!(ts[1] - ts[0], 1.0);
+
+        let s = Column::from_strings(strings(&["a", "b", ""]));
+        assert_eq!(s.kind(), "text");
+        assert!(!s.is_numeric());
+        assert!((s.valid_frac() - 2.0 / 3.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn cells_render_typed_values() {
+        let n = Column::from_strings(strings(&["20.0", "1.5", "oops"]));
+        assert_eq!(n.cell(0), "20");
+        assert_eq!(n.cell(1), "1.5");
+        assert_eq!(n.cell(2), "");
+        assert_eq!(n.cell(99), "");
+        let t = Column::from_strings(strings(&["2024-01-01 00:00:00", "2024-01-02 00:00:00", "2024-01-03 00:00:00"]));
+        assert_eq!(t.cell(1), "2024-01-02 00:00:00");
+    }
+
+    #[test]
+    fn to_f64_borrows_typed_and_parses_text() {
+        let n = Column::numeric(vec![1.0, f64::NAN]);
+        assert!(matches!(n.to_f64(), Cow::Borrowed(_)));
+        assert_eq!(n.valid_frac(), 0.5);
+        let s = Column::Text(strings(&["a", "3"]));
+        let v = s.to_f64();
+        assert!(v[0].is_nan());
+        assert_eq!(v[1], 3.0);
+    }
+}
@@ -0,0 +1,100 @@
+//! Lossless in-memory compression of sample arrays.
+//!
+//! A workspace with dozens of loaded-but-idle graphs holds every series at
+//! 8 bytes a value. [`CompressedSeries`] packs an array while its graph is
+//! idle: each value's bits are XORed with the previous value's (a regular X
+//! or a smooth signal leaves the sign, exponent and high mantissa bytes
+//! zero), the bytes are regrouped by significance so those zeros form long
+//! runs, and the result is deflated. zstd would squeeze a little more but
+//! doesn't build for the wasm target; flate2 is already a dependency.
+
+use std::io::{Read, Write};
+
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use flate2::Compression;
+
+/// A packed `f64` array; [`CompressedSeries::decompress`] gives back the
+/// exact bits, NaN payloads and signed zeros included.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompressedSeries {
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+impl CompressedSeries {
+    pub fn compress(values: &[f64]) -> CompressedSeries {
+        let n = values.len();
+        let mut shuffled = vec![0u8; n * 8];
+        let mut prev = 0u64;
+        for (i, v) in values.iter().enumerate() {
+            let bits = v.to_bits();
+            for (b, byte) in (bits ^ prev).to_le_bytes().into_iter().enumerate() {
+                shuffled[b * n + i] = byte;
+            }
+            prev = bits;
+        }
+        let mut enc = DeflateEncoder::new(Vec::new(), Compression::fast());
+        // Writing to a Vec can't fail.
+        enc.write_all(&shuffled).expect("deflate into memory");
+        CompressedSeries { len: n, bytes: enc.finish().expect("deflate into memory") }
+    }
+
+    pub fn decompress(&self) -> Vec<f64> {
+        let n = self.len;
+        let mut shuffled = Vec::with_capacity(n * 8);
+        // Only ever inflates bytes `compress` deflated.
+        DeflateDecoder::new(&self.bytes[..]).read_to_end(&mut shuffled).expect("inflate packed series");
+        let mut prev = 0u64;
+        (0..n)
+            .map(|i| {
+                let mut le = [0u8; 8];
+                for (b, byte) in le.iter_mut().enumerate() {
+                    *byte = shuffled[b * n + i];
+                }
+                prev ^= u64::from_le_bytes(le);
+                f64::from_bits(prev)
+            })
+            .collect()
+    }
+
+    /// Number of values.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Packed size in bytes.
+    pub fn packed_bytes(&self) -> usize {
+        self.bytes.len()
+    }
+}
+
+#[cfg(test)]
+mod compressed_tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_keeps_every_bit() {
+        let values = [1.5, f64::NAN, -0.0, 0.0, f64::INFINITY, f64::MIN_POSITIVE, -3.25e300];
+        let back = CompressedSeries::compress(&values).decompress();
+        let bits = |v: &[f64]| v.iter().map(|x| x.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&back), bits(&values));
+        assert!(CompressedSeries::compress(&[]).decompress().is_empty());
+    }
+
+    #[test]
+    fn regular_and_smooth_arrays_shrink() {
+        let xs: Vec<f64> = (0..100_000).map(|i| i as f64 * 0.5).collect();
+        let ys: Vec<f64> = (0..100_000).map(|i| ((i / 100) as f64 * 0.1).sin()).collect();
+        for v in [&xs, &ys] {
+            let c = CompressedSeries::compress(v);
+            assert_eq!(c.len(), v.len());
+            assert!(c.packed_bytes() * 3 < v.len() * 8, "{} bytes", c.packed_bytes());
+            assert_eq!(&c.decompress(), v);
+        }
+    }
+}
@@ -176,12 +176,12 @@ pub fn fix_error_timestamps(
             }
             if start > 0 {
                 let base = corrected[start - 1];
-                for k in start..i {
-                    corrected[k] = base + increment * (k - start + 1) as f64;
+                for (off, c) in corrected[start..i].iter_mut().enumerate() {
+                    *c = base + increment * (off + 1) as f64;
                 }
             } else {
-                for k in start..i {
-                    corrected[k] = threshold + increment * (k - start + 1) as f64;
+                for (off, c) in corrected[start..i].iter_mut().enumerate() {
+                    *c = threshold + increment * (off + 1) as f64;
                 }
             }
         } else {
@@ -0,0 +1,162 @@
+//! Event markers: externally logged moments (operator notes, ECU trouble
+//! codes) drawn as labelled vertical lines over a graph.
+//!
+//! An events file is read like any other table: its first datetime column
+//! (else its first numeric one, both judged on the non-blank cells) is the
+//! time, a column headed `color` /
+//! `colour` holds `#rrggbb` line colours, and the first remaining text
+//! column is the label. [`events_to_csv`] writes the same layout back, so
+//! placed notes round-trip through a spreadsheet.
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::column::Column;
+use crate::data::datetime::{detect_date_format, format_timestamp, parse_to_timestamp};
+use crate::data::loader::load_from_bytes;
+
+/// Most markers read from one file; more than this is a data file, not an
+/// event log.
+pub const MAX_EVENTS: usize = 10_000;
+
+/// One vertical event line at `x` (epoch seconds on a datetime axis).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EventMarker {
+    pub x: f64,
+    pub label: String,
+    /// Line colour (RGB 0–1); `None` uses the theme accent.
+    #[serde(default)]
+    pub color: Option<[f32; 3]>,
+}
+
+/// `#rrggbb` / `#rgb` (the `#` optional) as RGB 0–1.
+fn parse_hex_color(s: &str) -> Option<[f32; 3]> {
+    let hex = s.trim().trim_start_matches('#');
+    let wide: Cow<'_, str> = match hex.len() {
+        3 => Cow::Owned(hex.chars().flat_map(|c| [c, c]).collect()),
+        6 => Cow::Borrowed(hex),
+        _ => return None,
+    };
+    let channel = |i: usize| u8::from_str_radix(wide.get(i..i + 2)?, 16).ok().map(|v| v as f32 / 255.0);
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+fn hex_color(c: [f32; 3]) -> String {
+    let b = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!("#{:02x}{:02x}{:02x}", b(c[0]), b(c[1]), b(c[2]))
+}
+
+/// RFC 4180 quoting for a label cell.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// A column's values as event times: `(is_datetime, values)`. Text columns
+/// qualify when most of their non-blank cells parse (the import's own
+/// classification counts blanks against them).
+fn time_values(col: &Column) -> Option<(bool, Cow<'_, [f64]>)> {
+    let cells = match col {
+        Column::Time { values, .. } => return Some((true, Cow::Borrowed(values))),
+        Column::Numeric { values, .. } => return Some((false, Cow::Borrowed(values))),
+        Column::Text(cells) => cells,
+        Column::Deferred { .. } => return None,
+    };
+    let filled: Vec<String> = cells.iter().map(|c| c.trim()).filter(|c| !c.is_empty()).map(String::from).collect();
+    let mostly = |values: &[f64]| values.iter().filter(|v| v.is_finite()).count() * 2 > filled.len();
+    if let Some(format) = detect_date_format(&filled) {
+        let ts: Vec<f64> = cells.iter().map(|c| parse_to_timestamp(c.trim(), format).unwrap_or(f64::NAN)).collect();
+        if mostly(&ts) {
+            return Some((true, Cow::Owned(ts)));
+        }
+    }
+    let nums: Vec<f64> = cells.iter().map(|c| c.trim().parse().unwrap_or(f64::NAN)).collect();
+    mostly(&nums).then_some((false, Cow::Owned(nums)))
+}
+
+/// Parse event markers from file bytes, sorted by time; rows without a
+/// time are skipped.
+pub fn load_events(bytes: &[u8], filename: &str) -> Result<Vec<EventMarker>, String> {
+    let data = load_from_bytes(bytes, filename)?;
+    let cols = &data.column_data;
+    let times: Vec<_> = cols.iter().map(|c| time_values(c)).collect();
+    let (x_col, (_, xs)) = times
+        .iter()
+        .enumerate()
+        .filter_map(|(c, t)| Some((c, t.as_ref()?)))
+        .min_by_key(|&(c, (is_time, _))| (!is_time, c))
+        .ok_or("events file has no time or numeric column")?;
+    let color_col = (0..cols.len()).find(|&c| {
+        c != x_col && matches!(data.columns[c].trim().to_ascii_lowercase().as_str(), "color" | "colour")
+    });
+    let others = || (0..cols.len()).filter(|&c| c != x_col && Some(c) != color_col);
+    let label_col = others().find(|&c| cols[c].kind() == "text").or_else(|| others().next());
+
+    let mut events: Vec<EventMarker> = (0..data.row_count)
+        .filter(|&r| xs.get(r).is_some_and(|x| x.is_finite()))
+        .map(|r| EventMarker {
+            x: xs[r],
+            label: label_col.map(|c| cols[c].cell(r).trim().to_string()).unwrap_or_default(),
+            color: color_col.and_then(|c| parse_hex_color(&cols[c].cell(r))),
+        })
+        .collect();
+    if events.is_empty() {
+        return Err("events file has no rows with a valid time".to_string());
+    }
+    if events.len() > MAX_EVENTS {
+        return Err(format!("events file has {} rows (limit {MAX_EVENTS})", events.len()));
+    }
+    events.sort_by(|a, b| a.x.total_cmp(&b.x));
+    Ok(events)
+}
+
+/// `time,label,color` CSV of `events` (`x,…` when the axis isn't datetime),
+/// readable by [`load_events`]. Markers without a colour leave it blank.
+pub fn events_to_csv(events: &[EventMarker], x_is_time: bool) -> String {
+    let mut out = String::from(if x_is_time { "time,label,color\n" } else { "x,label,color\n" });
+    for e in events {
+        let x = if x_is_time { format_timestamp(e.x) } else { e.x.to_string() };
+        let color = e.color.map(hex_color).unwrap_or_default();
+        out.push_str(&format!("{x},{},{color}\n", csv_field(&e.label)));
+    }
+    out
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+
+    #[test]
+    fn reads_time_label_and_color_columns() {
+        let csv = b"color,when,note\n#ff0000,2024-03-01 10:00:05,P0301 misfire\n,2024-03-01 10:00:00,start\nxyz,,dropped\n";
+        let events = load_events(csv, "log.csv").unwrap();
+        assert_eq!(events.len(), 2, "row without a time skipped");
+        assert_eq!(events[0].label, "start", "sorted by time");
+        assert_eq!(events[0].color, None);
+        assert_eq!(events[1].label, "P0301 misfire");
+        assert_eq!(events[1].color, Some([1.0, 0.0, 0.0]));
+        assert_eq!(events[1].x - events[0].x, 5.0);
+        assert!(load_events(b"a\nx\n", "t.csv").is_err());
+    }
+
+    #[test]
+    fn csv_export_round_trips() {
+        let events = vec![
+            EventMarker { x: 1_709_287_200.0, label: "valve, open".into(), color: Some([0.0, 0.5, 1.0]) },
+            EventMarker { x: 1_709_287_260.5, label: "stop".into(), color: None },
+        ];
+        let csv = events_to_csv(&events, true);
+        assert!(csv.starts_with("time,label,color\n2024-03-01 10:00:00,\"valve, open\",#0080ff\n"), "{csv}");
+        let back = load_events(csv.as_bytes(), "events.csv").unwrap();
+        assert_eq!(back.iter().map(|e| (e.x, e.label.as_str())).collect::<Vec<_>>(), [
+            (1_709_287_200.0, "valve, open"),
+            (1_709_287_260.5, "stop"),
+        ]);
+        assert_eq!(back[0].color, Some([0.0, 128.0 / 255.0, 1.0]));
+        assert_eq!(parse_hex_color("#fff"), Some([1.0, 1.0, 1.0]));
+    }
+}
@@ -0,0 +1,174 @@
+//! JSON (`.json`) and newline-delimited JSON (`.ndjson`, `.jsonl`) tables.
+//!
+//! Each object is a row and each key a column, in order of first
+//! appearance; rows missing a key get an empty cell. Nested objects are
+//! flattened to dotted names (`gps.lat`), arrays to `field[i]`. A `.json`
+//! file is an array of objects, or an object whose only array of objects is
+//! the table (`{"meta": …, "rows": [...]}`); a file that isn't one JSON
+//! value is read as one object per line.
+//!
+//! Cells are classified like CSV ones: numbers (and numeric strings) load
+//! as numbers, booleans as 1/0, timestamp strings as time, the rest as text.
+
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::data::loader::LoadedData;
+
+/// Nesting depth flattened; deeper values load as their JSON text.
+const MAX_DEPTH: usize = 16;
+
+/// Decode a JSON array or NDJSON file into one column per (flattened) key.
+pub fn load_json(bytes: &[u8]) -> Result<LoadedData, String> {
+    let text = std::str::from_utf8(bytes).map_err(|_| "JSON file is not valid UTF-8".to_string())?;
+    let text = text.trim_start_matches('\u{feff}');
+    let mut table = Table::default();
+    match serde_json::from_str::<Value>(text) {
+        Ok(value) => {
+            for row in rows_of(value)? {
+                table.push_row(row)?;
+            }
+        }
+        // Not a single value: one object per line.
+        Err(_) => {
+            for (i, line) in text.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() {
+                    continue;
+                }
+                let row = serde_json::from_str(line).map_err(|e| format!("line {}: {e}", i + 1))?;
+                table.push_row(row).map_err(|e| format!("line {}: {e}", i + 1))?;
+            }
+        }
+    }
+    if table.columns.is_empty() {
+        return Err("no rows in JSON file".to_string());
+    }
+    Ok(LoadedData::from_string_columns(table.columns, table.cells, table.rows))
+}
+
+/// The rows of a whole-file JSON value.
+fn rows_of(value: Value) -> Result<Vec<Value>, String> {
+    match value {
+        Value::Array(rows) => Ok(rows),
+        Value::Object(obj) => {
+            let tables: Vec<&String> = obj.iter().filter(|(_, v)| is_table(v)).map(|(k, _)| k).collect();
+            // No (or several) embedded tables: the object is one row.
+            let [key] = tables[..] else {
+                return Ok(vec![Value::Object(obj)]);
+            };
+            let key = key.clone();
+            let mut obj = obj;
+            match obj.remove(&key) {
+                Some(Value::Array(rows)) => Ok(rows),
+                _ => Ok(Vec::new()),
+            }
+        }
+        _ => Err("JSON file is neither an array of objects nor one object per line".to_string()),
+    }
+}
+
+/// A non-empty array of objects.
+fn is_table(value: &Value) -> bool {
+    matches!(value, Value::Array(rows) if !rows.is_empty() && rows.iter().all(Value::is_object))
+}
+
+/// Column-major cells built row by row.
+#[derive(Default)]
+struct Table {
+    columns: Vec<String>,
+    index: HashMap<String, usize>,
+    cells: Vec<Vec<String>>,
+    rows: usize,
+}
+
+impl Table {
+    fn push_row(&mut self, row: Value) -> Result<(), String> {
+        let Value::Object(obj) = row else {
+            return Err("expected a JSON object per row".to_string());
+        };
+        let mut flat = Vec::new();
+        flatten(String::new(), Value::Object(obj), 0, &mut flat);
+        for (name, cell) in flat {
+            let col = match self.index.get(&name) {
+                Some(&col) => col,
+                None => {
+                    self.index.insert(name.clone(), self.columns.len());
+                    self.columns.push(name);
+                    self.cells.push(vec![String::new(); self.rows]);
+                    self.columns.len() - 1
+                }
+            };
+            let column = &mut self.cells[col];
+            if column.len() > self.rows {
+                // A flattened name seen twice in this row (`"a.b"` and
+                // `"a": {"b"}`): the last value wins.
+                column[self.rows] = cell;
+            } else {
+                column.resize(self.rows, String::new());
+                column.push(cell);
+            }
+        }
+        self.rows += 1;
+        for column in &mut self.cells {
+            column.resize(self.rows, String::new());
+        }
+        Ok(())
+    }
+}
+
+/// Append the `(dotted name, cell text)` leaves of `value` to `out`.
+fn flatten(name: String, value: Value, depth: usize, out: &mut Vec<(String, String)>) {
+    let child = |key: &str| if name.is_empty() { key.to_string() } else { format!("{name}.{key}") };
+    match value {
+        Value::Object(obj) if depth < MAX_DEPTH && !obj.is_empty() => {
+            for (key, v) in obj {
+                flatten(child(&key), v, depth + 1, out);
+            }
+        }
+        Value::Array(items) if depth < MAX_DEPTH && !items.is_empty() => {
+            for (i, v) in items.into_iter().enumerate() {
+                flatten(format!("{name}[{i}]"), v, depth + 1, out);
+            }
+        }
+        Value::Object(obj) if obj.is_empty() => out.push((name, String::new())),
+        Value::Array(items) if items.is_empty() => out.push((name, String::new())),
+        Value::Null => out.push((name, String::new())),
+        Value::Bool(b) => out.push((name, (b as u8).to_string())),
+        Value::Number(n) => out.push((name, n.to_string())),
+        Value::String(s) => out.push((name, s)),
+        other => out.push((name, Value::to_string(&other))),
+    }
+}
+
+#[cfg(test)]
+mod json_tests {
+    use super::*;
+
+    #[test]
+    fn arrays_and_ndjson_flatten_nested_keys() {
+        let text = br#"{"meta": {"device": "x1"}, "rows": [
+            {"t": "2024-03-01T10:00:00Z", "gps": {"lat": 51.5, "fix": true}, "v": [1, 2]},
+            {"t": "2024-03-01T10:00:01Z", "gps": {"lat": 51.6, "fix": false}, "note": "late"}
+        ]}"#;
+        let data = load_json(text).unwrap();
+        assert_eq!(data.columns, ["t", "gps.lat", "gps.fix", "v[0]", "v[1]", "note"]);
+        assert_eq!(data.row_count, 2);
+        assert!(data.column_data[0].timestamps().is_some());
+        assert_eq!(data.column_data[1].to_f64()[..], [51.5, 51.6]);
+        assert_eq!(data.column_data[2].to_f64()[..], [1.0, 0.0]);
+        assert!(data.column_data[3].to_f64()[1].is_nan());
+        assert_eq!(data.column_data[5].cell(1), "late");
+
+        let lines = b"{\"a\": 1, \"b\": {\"c\": 2}}\n\n{\"b\": {\"c\": 4}, \"a\": 3}\n";
+        let data = load_json(lines).unwrap();
+        assert_eq!(data.columns, ["a", "b.c"]);
+        assert_eq!(data.column_data[0].to_f64()[..], [1.0, 3.0]);
+        assert_eq!(data.column_data[1].to_f64()[..], [2.0, 4.0]);
+
+        assert!(load_json(b"{\"a\": 1}\n{oops\n").err().unwrap().starts_with("line 2"));
+        assert!(load_json(b"42").is_err());
+        assert_eq!(load_json(br#"[{"x": {}, "y": []}]"#).unwrap().columns, ["x", "y"]);
+    }
+}
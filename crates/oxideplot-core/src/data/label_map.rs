@@ -0,0 +1,104 @@
+//! Value→label maps for enumerated channels.
+//!
+//! A state channel stores small integers (0 = OFF, 1 = IDLE, 2 = RUN, …).
+//! Attaching a [`LabelMap`] to a series or an axis makes ticks, cursor
+//! readouts and the table show the state names instead of the raw numbers.
+
+use serde::{Deserialize, Serialize};
+
+/// Values closer than this to an entry's value match it exactly.
+const MATCH_EPS: f64 = 1e-9;
+
+/// Sorted value→label entries.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LabelMap {
+    entries: Vec<(f64, String)>,
+}
+
+impl LabelMap {
+    /// Parse `value=label` pairs separated by commas, semicolons or newlines,
+    /// e.g. `0=OFF, 1=IDLE, 2="RUN"`. Labels may be quoted; a later entry for
+    /// the same value replaces an earlier one.
+    pub fn parse(spec: &str) -> Result<LabelMap, String> {
+        let mut entries: Vec<(f64, String)> = Vec::new();
+        for part in spec.split([',', ';', '\n']).map(str::trim).filter(|p| !p.is_empty()) {
+            let (value, label) = part
+                .split_once('=')
+                .ok_or_else(|| format!("expected value=label, got \"{part}\""))?;
+            let value: f64 = value
+                .trim()
+                .parse()
+                .ok()
+                .filter(|v: &f64| v.is_finite())
+                .ok_or_else(|| format!("\"{}\" is not a number", value.trim()))?;
+            let label = label.trim().trim_matches('"').to_string();
+            if label.is_empty() {
+                return Err(format!("empty label for {value}"));
+            }
+            entries.retain(|(v, _)| (v - value).abs() > MATCH_EPS);
+            entries.push((value, label));
+        }
+        if entries.is_empty() {
+            return Err("label map is empty".to_string());
+        }
+        entries.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Ok(LabelMap { entries })
+    }
+
+    /// Entries in ascending value order.
+    pub fn entries(&self) -> &[(f64, String)] {
+        &self.entries
+    }
+
+    /// Label for exactly `v`, if mapped.
+    pub fn label(&self, v: f64) -> Option<&str> {
+        self.entries.iter().find(|(k, _)| (k - v).abs() <= MATCH_EPS).map(|(_, l)| l.as_str())
+    }
+
+    /// Label of the entry nearest `v`, if `v` lies within half the smallest
+    /// gap between entries (for readouts at arbitrary positions).
+    pub fn nearest(&self, v: f64) -> Option<&str> {
+        let reach = self
+            .entries
+            .windows(2)
+            .map(|w| (w[1].0 - w[0].0) / 2.0)
+            .fold(0.5, f64::min);
+        self.entries
+            .iter()
+            .min_by(|a, b| (a.0 - v).abs().total_cmp(&(b.0 - v).abs()))
+            .filter(|(k, _)| (k - v).abs() <= reach)
+            .map(|(_, l)| l.as_str())
+    }
+
+    /// Canonical text form, accepted by [`LabelMap::parse`].
+    pub fn to_spec(&self) -> String {
+        self.entries.iter().map(|(v, l)| format!("{v}={l}")).collect::<Vec<_>>().join(", ")
+    }
+}
+
+#[cfg(test)]
+mod label_map_tests {
+    use super::*;
+
+    #[test]
+    fn parses_sorts_and_round_trips() {
+        let m = LabelMap::parse("2=\"RUN\"; 0=OFF\n1 = IDLE").unwrap();
+        assert_eq!(m.to_spec(), "0=OFF, 1=IDLE, 2=RUN");
+        assert_eq!(LabelMap::parse(&m.to_spec()).unwrap(), m);
+        assert_eq!(m.label(1.0), Some("IDLE"));
+        assert_eq!(m.label(1.5), None);
+        assert!(LabelMap::parse("0=OFF, x=ON").is_err());
+        assert!(LabelMap::parse("0=").is_err());
+        assert!(LabelMap::parse(" ").is_err());
+    }
+
+    #[test]
+    fn nearest_matches_within_half_a_step() {
+        let m = LabelMap::parse("0=OFF, 10=ON").unwrap();
+        assert_eq!(m.nearest(0.4), Some("OFF"));
+        assert_eq!(m.nearest(3.0), None, "reach is capped at 0.5");
+        let fine = LabelMap::parse("0=a, 0.2=b").unwrap();
+        assert_eq!(fine.nearest(0.15), Some("b"));
+        assert_eq!(fine.nearest(0.35), None);
+    }
+}
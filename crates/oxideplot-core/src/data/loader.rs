@@ -1,11 +1,130 @@
 use std::path::Path;
-use crate::data::parser;
+use std::sync::Arc;
+use crate::data::column::Column;
+use crate::data::split::{detect_long_format, LongFormat};
+use crate::data::{chunked, json, mdf, parser, ulog};
+#[cfg(feature = "parquet")]
+use crate::data::parquet;
 
-/// Result of loading a data file: column names and column data as strings
+/// Result of loading a data file: column names and typed column data
 pub struct LoadedData {
     pub columns: Vec<String>,
-    pub column_data: Vec<Vec<String>>,  // column-major: column_data[col_idx][row_idx]
+    /// Column-major: `column_data[col_idx]`, `row_count` cells each. Shared
+    /// (`Arc`) so graphs showing the same file can reference one copy — see
+    /// [`crate::data::store`].
+    pub column_data: Vec<Arc<Column>>,
     pub row_count: usize,
+    /// Source bytes for materializing [`Column::Deferred`] columns of a
+    /// lazily-loaded file (see [`load_from_bytes_lazy`]). None = fully loaded.
+    pub lazy: Option<LazySource>,
+}
+
+/// Retained file bytes plus, per loaded column, the raw file columns it is
+/// built from (two for a merged Date+Time column).
+pub struct LazySource {
+    bytes: Vec<u8>,
+    format: TableFormat,
+    raw_cols: Vec<Vec<usize>>,
+}
+
+impl LoadedData {
+    /// Build from raw string columns, classifying and parsing each column into
+    /// its typed form (in parallel).
+    pub fn from_string_columns(
+        columns: Vec<String>,
+        column_data: Vec<Vec<String>>,
+        row_count: usize,
+    ) -> Self {
+        let column_data = par_map_owned(column_data, |c| Arc::new(Column::from_strings(c)));
+        LoadedData { columns, column_data, row_count, lazy: None }
+    }
+
+    /// Build from decoded numeric channels (binary formats), padding
+    /// shorter channels with NaN to the longest one.
+    pub fn from_numeric_columns(columns: Vec<String>, values: Vec<Vec<f64>>) -> Self {
+        let row_count = values.iter().map(Vec::len).max().unwrap_or(0);
+        let column_data = par_map_owned(values, |mut v| {
+            v.resize(row_count, f64::NAN);
+            Arc::new(Column::numeric(v))
+        });
+        LoadedData { columns, column_data, row_count, lazy: None }
+    }
+
+    /// Load the full data of any still-deferred columns among `cols` by
+    /// re-reading the retained file bytes (one pass for all of them). A no-op
+    /// for eagerly-loaded data and already-materialized columns.
+    pub fn materialize(&mut self, cols: &[usize]) -> Result<(), String> {
+        let Some(lazy) = &self.lazy else {
+            return Ok(());
+        };
+        let mut wanted: Vec<usize> = cols
+            .iter()
+            .copied()
+            .filter(|&c| c < lazy.raw_cols.len())
+            .filter(|&c| matches!(self.column_data.get(c).map(|c| &**c), Some(Column::Deferred { .. })))
+            .collect();
+        wanted.sort_unstable();
+        wanted.dedup();
+        if wanted.is_empty() {
+            return Ok(());
+        }
+
+        let mut raw: Vec<usize> = wanted.iter().flat_map(|&c| lazy.raw_cols[c].iter().copied()).collect();
+        raw.sort_unstable();
+        raw.dedup();
+        let table = read_raw_table(&lazy.bytes, lazy.format, None, Some(&raw))?;
+        let mut raw_data = rows_to_columns(&table.rows, raw.len());
+        let mut take = |r: usize| match raw.binary_search(&r) {
+            Ok(k) => std::mem::take(&mut raw_data[k]),
+            Err(_) => Vec::new(),
+        };
+
+        let built: Vec<(usize, Vec<String>)> = wanted
+            .iter()
+            .map(|&c| {
+                let cells = match lazy.raw_cols[c].as_slice() {
+                    [d, t] => {
+                        let (d, t) = (take(*d), take(*t));
+                        merge_date_time_cells(&d, &t)
+                    }
+                    group => take(group[0]),
+                };
+                (c, cells)
+            })
+            .collect();
+        for (c, col) in par_map_owned(built, |(c, cells)| (c, Column::from_strings(cells))) {
+            self.column_data[c] = Arc::new(col);
+        }
+        Ok(())
+    }
+}
+
+/// Evenly spaced X for data without a usable X column (raw ADC dumps):
+/// row `i` is at `start + i * interval`, e.g. an interval of 0.001 for a
+/// 1 kHz capture.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize)]
+pub struct SyntheticX {
+    pub start: f64,
+    pub interval: f64,
+}
+
+impl SyntheticX {
+    /// Plain row numbers.
+    pub const INDEX: SyntheticX = SyntheticX { start: 0.0, interval: 1.0 };
+
+    /// X of every row; `Err` for a non-finite start or a non-positive interval.
+    pub fn values(&self, rows: usize) -> Result<Vec<f64>, String> {
+        if !(self.start.is_finite() && self.interval.is_finite() && self.interval > 0.0) {
+            return Err(format!("invalid synthetic X (start {}, interval {})", self.start, self.interval));
+        }
+        Ok((0..rows).map(|i| self.start + i as f64 * self.interval).collect())
+    }
+
+    /// Axis name: `"index"` for row numbers, else `"t [s]"` (the interval
+    /// being a sample period).
+    pub fn name(&self) -> &'static str {
+        if *self == SyntheticX::INDEX { "index" } else { "t [s]" }
+    }
 }
 
 /// Metadata struct for the WASM boundary — serializable column+row summary.
@@ -13,74 +132,381 @@ pub struct LoadedData {
 pub struct ColumnMeta {
     pub name: String,
     pub kind: String,
+    /// Fraction of cells holding a valid value for this kind (0–1).
+    pub valid: f64,
 }
 
 #[derive(serde::Serialize)]
 pub struct FileMeta {
     pub columns: Vec<ColumnMeta>,
     pub rows: usize,
+    /// How a delimited-text file was read (absent for other formats).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csv: Option<CsvMeta>,
+    /// Channel-name and value columns when the table looks like a long
+    /// (`timestamp, channel, value`) log that should be pivoted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_format: Option<LongFormat>,
+    /// How a decimated import thinned the rows (`ImportDecimation::label`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimation: Option<String>,
+}
+
+/// The delimited-text options a file was read with, detected ones filled
+/// in, as shown by the import options panel.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct CsvMeta {
+    pub delimiter: char,
+    pub decimal: char,
+    pub thousands: Option<char>,
+}
+
+impl CsvMeta {
+    /// The resolved options `csv` stands for on `bytes`.
+    pub fn resolved(csv: &CsvOptions, bytes: &[u8]) -> Self {
+        let r = csv.resolve(bytes);
+        CsvMeta {
+            delimiter: r.delimiter.unwrap_or(b',') as char,
+            decimal: r.decimal.unwrap_or('.'),
+            thousands: r.thousands,
+        }
+    }
 }
 
 impl FileMeta {
+    /// Summarise every column's kind and validity (classified at import).
     pub fn from_loaded(data: &LoadedData) -> Self {
         let columns = data.columns.iter().zip(data.column_data.iter()).map(|(name, col)| {
-            let kind = if column_to_timestamps(col).is_some() {
-                "datetime".to_string()
-            } else {
-                let (_, frac) = column_to_f64(col);
-                if frac >= 0.5 {
-                    "numeric".to_string()
-                } else {
-                    "text".to_string()
-                }
-            };
-            ColumnMeta { name: name.clone(), kind }
+            ColumnMeta { name: name.clone(), kind: col.kind().to_string(), valid: col.valid_frac() }
         }).collect();
 
         FileMeta {
             columns,
             rows: data.row_count,
+            csv: None,
+            long_format: detect_long_format(&data.column_data),
+            decimation: None,
         }
     }
+
+    /// Per-column "is numeric" mask (numeric or datetime → true, text → false),
+    /// the rule the table view's numeric filters use. Saves re-parsing every
+    /// column after `from_loaded` already classified them.
+    pub fn numeric_mask(&self) -> Vec<bool> {
+        self.columns.iter().map(|c| c.kind != "text").collect()
+    }
 }
 
-/// Load from raw bytes, dispatching by the extension of `filename`.
-/// This is the primary entry point for the WASM path (bytes already read by Tauri/JS).
-pub fn load_from_bytes(bytes: &[u8], filename: &str) -> Result<LoadedData, String> {
-    let ext = std::path::Path::new(filename)
+/// Map `f` over `items`, across threads when the `parallel` feature is on
+/// (the native default; the single-threaded wasm build turns it off).
+pub fn par_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.iter().map(f).collect()
+    }
+}
+
+/// [`par_map`] over owned items, consuming them.
+pub fn par_map_owned<T: Send, R: Send>(items: Vec<T>, f: impl Fn(T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        items.into_par_iter().map(f).collect()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        items.into_iter().map(f).collect()
+    }
+}
+
+/// Transpose parsed rows into column-major storage, padding short rows with
+/// empty cells. Each column is gathered independently (in parallel).
+pub(crate) fn rows_to_columns(rows: &[Vec<String>], num_cols: usize) -> Vec<Vec<String>> {
+    let cols: Vec<usize> = (0..num_cols).collect();
+    par_map(&cols, |&c| {
+        rows.iter()
+            .map(|row| row.get(c).cloned().unwrap_or_default())
+            .collect()
+    })
+}
+
+/// How delimited text is split and its numbers written (the import
+/// options panel). `None` fields are detected from the file.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CsvOptions {
+    /// Field delimiter (an ASCII character).
+    pub delimiter: Option<u8>,
+    /// Decimal separator: `'.'` or `','` (European exports).
+    pub decimal: Option<char>,
+    /// Digit-group separator inside numbers (`1.234,5`, `1 234.5`); none
+    /// when `None` — it is never guessed.
+    pub thousands: Option<char>,
+}
+
+impl CsvOptions {
+    /// `Err` for separators that would make numbers or fields ambiguous.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(d) = self.delimiter {
+            if !d.is_ascii() || d == b'"' || d == b'\n' || d == b'\r' {
+                return Err(format!("unsupported delimiter {:?}", d as char));
+            }
+        }
+        if let Some(dec) = self.decimal.filter(|c| !matches!(c, '.' | ',')) {
+            return Err(format!("decimal separator must be '.' or ',', not {dec:?}"));
+        }
+        if let Some(t) = self.thousands {
+            if t.is_ascii_digit() || matches!(t, '-' | '+' | 'e' | 'E') || Some(t) == self.decimal {
+                return Err(format!("unsupported thousands separator {t:?}"));
+            }
+            if self.delimiter.is_some_and(|d| d as char == t) {
+                return Err("thousands separator can't be the delimiter".to_string());
+            }
+        }
+        if self.decimal == Some(',') && self.delimiter == Some(b',') {
+            return Err("decimal comma needs a delimiter other than ','".to_string());
+        }
+        Ok(())
+    }
+
+    /// Options with the delimiter and decimal separator detected from
+    /// `bytes` filled in (what auto resolves to, for the options panel).
+    pub fn resolve(&self, bytes: &[u8]) -> CsvOptions {
+        let delimiter = self.delimiter.unwrap_or_else(|| detect_delimiter(bytes));
+        let decimal = self.decimal.unwrap_or_else(|| detect_decimal(bytes, delimiter));
+        CsvOptions { delimiter: Some(delimiter), decimal: Some(decimal), thousands: self.thousands }
+    }
+}
+
+/// Rewrites cells holding numbers in a file's notation (decimal comma,
+/// digit groups) to `f64` syntax; other cells pass through unchanged.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct NumberFormat {
+    pub(crate) decimal: char,
+    pub(crate) thousands: Option<char>,
+}
+
+impl NumberFormat {
+    fn is_plain(&self) -> bool {
+        self.decimal == '.' && self.thousands.is_none()
+    }
+
+    pub(crate) fn normalize(&self, cell: &str) -> String {
+        if self.is_plain() {
+            return cell.to_string();
+        }
+        let cell = cell.trim();
+        match self.as_f64_syntax(cell) {
+            Some(number) => number,
+            // `1.234` under a decimal comma is a digit group the options
+            // don't allow; read literally it would be off by 1000×.
+            None if self.decimal == ',' && cell.contains('.') && cell.parse::<f64>().is_ok() => String::new(),
+            None => cell.to_string(),
+        }
+    }
+
+    /// `cell` in `f64` syntax, or `None` when it isn't a number in this
+    /// notation (dates like `01.02.2024`, times, text).
+    fn as_f64_syntax(self, cell: &str) -> Option<String> {
+        let (int, frac) = match cell.split_once(self.decimal) {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (cell, None),
+        };
+        let mut out = String::with_capacity(cell.len());
+        match self.thousands.filter(|t| int.contains(*t)) {
+            Some(t) => {
+                // Digit groups: 1–3 digits (after any sign), then groups of 3.
+                let digits = int.trim_start_matches(['-', '+']);
+                out.push_str(&int[..int.len() - digits.len()]);
+                let mut groups = digits.split(t);
+                let first = groups.next()?;
+                if !(1..=3).contains(&first.len()) {
+                    return None;
+                }
+                out.push_str(first);
+                for g in groups {
+                    if g.len() != 3 || !g.bytes().all(|b| b.is_ascii_digit()) {
+                        return None;
+                    }
+                    out.push_str(g);
+                }
+            }
+            None => out.push_str(int),
+        }
+        // A '.' left in the integer part (decimal comma) isn't a number here.
+        if out.contains('.') {
+            return None;
+        }
+        if let Some(frac) = frac {
+            out.push('.');
+            out.push_str(frac);
+        }
+        out.parse::<f64>().is_ok().then_some(out)
+    }
+}
+
+/// Guess the decimal separator: `','` when the delimiter isn't a comma and
+/// the sampled cells hold more `12,5`-style numbers than `12.5`-style ones.
+/// Three digits after the separator (`1.234`) could be a digit group, so
+/// such cells don't count either way.
+fn detect_decimal(bytes: &[u8], delimiter: u8) -> char {
+    if delimiter == b',' {
+        return '.';
+    }
+    let text = String::from_utf8_lossy(bytes);
+    let (mut comma, mut dot) = (0usize, 0usize);
+    let lines = text.lines().rev().filter(|l| !l.trim().is_empty()).take(40);
+    for cell in lines.flat_map(|l| l.split(delimiter as char)) {
+        let cell = cell.trim().trim_start_matches(['-', '+']);
+        let decimal_number = |sep: char| {
+            cell.split_once(sep).is_some_and(|(i, f)| {
+                !i.is_empty() && !f.is_empty() && f.len() != 3 && i.bytes().chain(f.bytes()).all(|b| b.is_ascii_digit())
+            })
+        };
+        comma += decimal_number(',') as usize;
+        dot += decimal_number('.') as usize;
+    }
+    if comma > dot { ',' } else { '.' }
+}
+
+/// Tabular file formats the loader understands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TableFormat {
+    /// Delimited text (CSV/DAT/TXT/TSV), split and read per the options.
+    Delimited(CsvOptions),
+    Excel,
+}
+
+/// Whether `filename` loads as delimited text (so [`CsvOptions`] apply).
+pub fn is_delimited_text(filename: &str) -> bool {
+    matches!(TableFormat::from_filename(filename, CsvOptions::default()), Ok(TableFormat::Delimited(_)))
+}
+
+/// Lower-cased extension of `filename` ("" when it has none).
+fn file_extension(filename: &str) -> String {
+    std::path::Path::new(filename)
         .extension()
         .and_then(|e| e.to_str())
         .map(|e| e.to_lowercase())
-        .unwrap_or_default();
+        .unwrap_or_default()
+}
+
+impl TableFormat {
+    fn from_filename(filename: &str, csv: CsvOptions) -> Result<Self, String> {
+        let ext = file_extension(filename);
+        match ext.as_str() {
+            "csv" | "dat" | "txt" | "tsv" => Ok(TableFormat::Delimited(csv)),
+            "xls" | "xlsx" => Ok(TableFormat::Excel),
+            _ => Err(format!("Unsupported file format: .{ext}")),
+        }
+    }
+}
+
+/// Self-describing formats (binary ones and JSON), decoded straight to
+/// typed columns rather than through a delimited table (so they never load
+/// lazily). `None` when `filename` isn't one of them.
+fn load_binary(bytes: &[u8], filename: &str) -> Option<Result<LoadedData, String>> {
+    match file_extension(filename).as_str() {
+        "mf4" | "mdf" => Some(mdf::load_mdf(bytes)),
+        "ulg" => Some(ulog::load_ulog(bytes)),
+        "db" | "sqlite" | "sqlite3" => {
+            Some(Err("SQLite databases are read with a SQL query, not loaded directly".to_string()))
+        }
+        #[cfg(feature = "parquet")]
+        "parquet" | "pq" => Some(parquet::load_parquet(bytes)),
+        #[cfg(not(feature = "parquet"))]
+        "parquet" | "pq" => Some(Err("this build has no Parquet support".to_string())),
+        "json" | "ndjson" | "jsonl" => Some(json::load_json(bytes)),
+        _ => None,
+    }
+}
+
+/// Load from raw bytes, dispatching by the extension of `filename`.
+/// This is the primary entry point for the WASM path (bytes already read by Tauri/JS).
+pub fn load_from_bytes(bytes: &[u8], filename: &str) -> Result<LoadedData, String> {
+    load_from_bytes_with(bytes, filename, CsvOptions::default())
+}
 
-    match ext.as_str() {
-        "csv" | "dat" | "txt" | "tsv" => load_csv_from_bytes(bytes),
-        "xls" | "xlsx" => load_excel_from_bytes(bytes),
-        _ => Err(format!("Unsupported file format: .{ext}")),
+/// [`load_from_bytes`] with explicit delimited-text options (ignored for
+/// other formats).
+pub fn load_from_bytes_with(bytes: &[u8], filename: &str, csv: CsvOptions) -> Result<LoadedData, String> {
+    if let Some(loaded) = load_binary(bytes, filename) {
+        return loaded;
     }
+    load_table_from_bytes(bytes, TableFormat::from_filename(filename, csv)?)
 }
 
-/// Load a CSV or Excel file and return the column names and raw string data.
+/// Delimited-text files at least this big are parsed in chunks by
+/// [`load_file`] instead of being read into memory whole.
+pub const STREAM_MIN_BYTES: u64 = 256 << 20;
+
+/// Load a data file (CSV, Excel, MDF 4, JSON, …) and return the column names and
+/// typed column data.
 pub fn load_file(path: &Path) -> Result<LoadedData, String> {
-    let ext = path.extension()
-        .and_then(|e| e.to_str())
-        .map(|e| e.to_lowercase())
-        .unwrap_or_default();
+    let name = path.to_string_lossy();
+    let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    if size >= STREAM_MIN_BYTES && is_delimited_text(&name) {
+        let file = std::fs::File::open(path).map_err(|e| format!("Cannot read file: {e}"))?;
+        return chunked::load_delimited_reader(file, CsvOptions::default(), |_| true);
+    }
+    let bytes = std::fs::read(path).map_err(|e| format!("Cannot read file: {e}"))?;
+    load_from_bytes(&bytes, &path.to_string_lossy())
+}
+
+/// Files at least this wide load lazily via [`load_from_bytes_lazy`].
+pub const LAZY_MIN_COLUMNS: usize = 64;
 
-    match ext.as_str() {
-        "csv" | "dat" | "txt" | "tsv" => load_csv(path),
-        "xls" | "xlsx" => load_excel(path),
-        _ => Err(format!("Unsupported file format: .{ext}")),
+/// Rows parsed up front to classify each column of a lazily-loaded file.
+pub const LAZY_SAMPLE_ROWS: usize = 500;
+
+/// Like [`load_from_bytes`], but for wide files (≥ [`LAZY_MIN_COLUMNS`]
+/// columns) only the header and a [`LAZY_SAMPLE_ROWS`]-row sample are parsed:
+/// every column starts as [`Column::Deferred`] (kind and validity estimated
+/// from the sample), and [`LoadedData::materialize`] re-reads the retained
+/// bytes for just the columns actually used. Narrow files load eagerly.
+pub fn load_from_bytes_lazy(bytes: Vec<u8>, filename: &str, csv: CsvOptions) -> Result<LoadedData, String> {
+    if let Some(loaded) = load_binary(&bytes, filename) {
+        return loaded;
+    }
+    let format = TableFormat::from_filename(filename, csv)?;
+    let sample = read_raw_table(&bytes, format, Some(LAZY_SAMPLE_ROWS), None)?;
+    if sample.header.len() < LAZY_MIN_COLUMNS {
+        return load_table_from_bytes(&bytes, format);
     }
+    let num_cols = sample.header.len();
+    let mut columns = sample.header;
+    let mut sample_data = rows_to_columns(&sample.rows, num_cols);
+    let raw_cols = normalize_table(&mut columns, &mut sample_data);
+    let column_data = par_map_owned(sample_data, |cells| {
+        let typed = Column::from_strings(cells);
+        Arc::new(Column::Deferred { kind: typed.kind(), valid_frac: typed.valid_frac() })
+    });
+    Ok(LoadedData {
+        columns,
+        column_data,
+        row_count: sample.total_rows,
+        lazy: Some(LazySource { bytes, format, raw_cols }),
+    })
 }
 
-/// Finalize a freshly-parsed table: merge adjacent Date+Time column pairs into
-/// single datetime columns, then build the `LoadedData`.
-fn finalize_loaded(
-    mut columns: Vec<String>,
-    mut column_data: Vec<Vec<String>>,
-    row_count: usize,
-) -> LoadedData {
+/// Parse the whole table and type every column.
+fn load_table_from_bytes(bytes: &[u8], format: TableFormat) -> Result<LoadedData, String> {
+    let raw = read_raw_table(bytes, format, None, None)?;
+    let num_cols = raw.header.len();
+    let mut columns = raw.header;
+    let mut column_data = rows_to_columns(&raw.rows, num_cols);
+    normalize_table(&mut columns, &mut column_data);
+    Ok(LoadedData::from_string_columns(columns, column_data, raw.total_rows))
+}
+
+/// Normalize a freshly-parsed table in place: label blank headers and merge
+/// adjacent Date+Time column pairs into single datetime columns. Returns, per
+/// output column, the raw file-column indices it was built from.
+pub(crate) fn normalize_table(columns: &mut Vec<String>, column_data: &mut Vec<Vec<String>>) -> Vec<Vec<usize>> {
     // Give blank header cells a generic label (common in instrument exports where
     // e.g. the frequency/index column is left unnamed) so they read clearly and
     // are selectable in the column picker rather than showing as empty.
@@ -89,39 +515,47 @@ fn finalize_loaded(
             *name = format!("Column {}", i + 1);
         }
     }
-    merge_date_time_columns(&mut columns, &mut column_data);
-    LoadedData { columns, column_data, row_count }
+    merge_date_time_columns(columns, column_data)
 }
 
 /// Merge an adjacent date-only + time-only column pair (a common instrument-log
 /// export shape, e.g. `Date` = "07/07/2026" and `Time` = "02:55:14 PM") into a
 /// single datetime column whose cells are "date time", so it's detected and
 /// plotted as a proper timestamp axis instead of a constant date + a text time.
-fn merge_date_time_columns(columns: &mut Vec<String>, column_data: &mut Vec<Vec<String>>) {
+/// Returns the raw column indices behind each resulting column.
+fn merge_date_time_columns(columns: &mut Vec<String>, column_data: &mut Vec<Vec<String>>) -> Vec<Vec<usize>> {
     use crate::data::datetime::{is_date_only_column, is_time_only_column};
+    let mut groups: Vec<Vec<usize>> = (0..columns.len()).map(|i| vec![i]).collect();
     let mut i = 0;
     while i + 1 < columns.len() {
         if is_date_only_column(&column_data[i]) && is_time_only_column(&column_data[i + 1]) {
             let merged_name = format!("{} {}", columns[i].trim(), columns[i + 1].trim());
-            let merged: Vec<String> = column_data[i]
-                .iter()
-                .zip(column_data[i + 1].iter())
-                .map(|(d, t)| {
-                    let (d, t) = (d.trim(), t.trim());
-                    if d.is_empty() || t.is_empty() {
-                        String::new()
-                    } else {
-                        format!("{d} {t}")
-                    }
-                })
-                .collect();
+            column_data[i] = merge_date_time_cells(&column_data[i], &column_data[i + 1]);
             columns[i] = merged_name;
-            column_data[i] = merged;
             columns.remove(i + 1);
             column_data.remove(i + 1);
+            let t = groups.remove(i + 1);
+            groups[i].extend(t);
         }
         i += 1;
     }
+    groups
+}
+
+/// Join date and time cells row-wise as "date time" (blank if either is blank).
+pub(crate) fn merge_date_time_cells(dates: &[String], times: &[String]) -> Vec<String> {
+    dates
+        .iter()
+        .zip(times.iter())
+        .map(|(d, t)| {
+            let (d, t) = (d.trim(), t.trim());
+            if d.is_empty() || t.is_empty() {
+                String::new()
+            } else {
+                format!("{d} {t}")
+            }
+        })
+        .collect()
 }
 
 /// Auto-detect the field delimiter (tab, comma, semicolon, or pipe) of a
@@ -140,7 +574,7 @@ fn detect_delimiter(bytes: &[u8]) -> u8 {
     let sample: Vec<&str> = lines.iter().rev().take(40).copied().collect();
     let mut best = b',';
     let mut best_score = 0.0_f64;
-    for &d in &[b'\t', b',', b';', b'|'] {
+    for &d in b"\t,;|" {
         let dc = d as char;
         let counts: Vec<usize> = sample.iter().map(|l| l.matches(dc).count() + 1).collect();
         let max_cols = *counts.iter().max().unwrap_or(&1);
@@ -166,67 +600,113 @@ fn detect_delimiter(bytes: &[u8]) -> u8 {
     best
 }
 
-/// Parse a delimited-text file (CSV/DAT/TXT/TSV) from raw bytes, auto-detecting
-/// the delimiter (core implementation).
-pub fn load_csv_from_bytes(bytes: &[u8]) -> Result<LoadedData, String> {
-    let delimiter = detect_delimiter(bytes);
-    let header_row = parser::detect_csv_header_from_bytes(bytes, delimiter, 50)?;
+/// Header and data rows read from a delimited-text or Excel file.
+struct RawTable {
+    header: Vec<String>,
+    /// Data rows after the header (truncated/projected per `read_raw_table`).
+    rows: Vec<Vec<String>>,
+    /// Total data rows in the file, including any not kept in `rows`.
+    total_rows: usize,
+}
 
-    let text = String::from_utf8(bytes.to_vec())
-        .unwrap_or_else(|_| bytes.iter().map(|&b| b as char).collect());
+/// Accumulates rows for [`read_raw_table`] from either reader.
+struct RowCollector<'a> {
+    header_row: usize,
+    max_rows: Option<usize>,
+    cols: Option<&'a [usize]>,
+    seen: usize,
+    header: Option<Vec<String>>,
+    rows: Vec<Vec<String>>,
+    total_rows: usize,
+}
 
-    let mut reader = csv::ReaderBuilder::new()
-        .delimiter(delimiter)
-        .has_headers(false)
-        .flexible(true)
-        .from_reader(text.as_bytes());
-
-    let mut all_rows: Vec<Vec<String>> = Vec::new();
-    for result in reader.records() {
-        match result {
-            Ok(record) => {
-                all_rows.push(record.iter().map(|s| s.to_string()).collect());
-            }
-            Err(_) => continue,
-        }
+impl<'a> RowCollector<'a> {
+    fn new(header_row: usize, max_rows: Option<usize>, cols: Option<&'a [usize]>) -> Self {
+        Self { header_row, max_rows, cols, seen: 0, header: None, rows: Vec::new(), total_rows: 0 }
     }
 
-    if all_rows.is_empty() || header_row >= all_rows.len() {
-        return Err("No data found after header detection".to_string());
+    /// Offer one file row of `len` cells; `cell(c)` yields cell `c`.
+    fn push(&mut self, len: usize, cell: impl Fn(usize) -> Option<String>) {
+        let i = self.seen;
+        self.seen += 1;
+        if i < self.header_row {
+            return;
+        }
+        if i == self.header_row {
+            self.header = Some((0..len).map(|c| cell(c).unwrap_or_default().trim().to_string()).collect());
+            return;
+        }
+        self.total_rows += 1;
+        if self.max_rows.is_some_and(|m| self.rows.len() >= m) {
+            return;
+        }
+        let row = match self.cols {
+            Some(cols) => cols.iter().map(|&c| cell(c).unwrap_or_default()).collect(),
+            None => (0..len).map(|c| cell(c).unwrap_or_default()).collect(),
+        };
+        self.rows.push(row);
     }
 
-    let columns: Vec<String> = all_rows[header_row].iter()
-        .map(|s| s.trim().to_string())
-        .collect();
+    fn finish(self) -> Result<RawTable, String> {
+        let header = self.header.ok_or("No data found after header detection")?;
+        Ok(RawTable { header, rows: self.rows, total_rows: self.total_rows })
+    }
+}
 
-    let data_rows = &all_rows[header_row + 1..];
-    let num_cols = columns.len();
+/// Read the header and data rows of a file, keeping at most `max_rows` data
+/// rows (the rest are only counted) and, when `cols` is given, only those raw
+/// columns of each row, in that order.
+fn read_raw_table(
+    bytes: &[u8],
+    format: TableFormat,
+    max_rows: Option<usize>,
+    cols: Option<&[usize]>,
+) -> Result<RawTable, String> {
+    match format {
+        TableFormat::Delimited(csv) => read_delimited(bytes, csv, max_rows, cols),
+        TableFormat::Excel => read_excel(bytes, max_rows, cols),
+    }
+}
 
-    let mut column_data: Vec<Vec<String>> = vec![Vec::new(); num_cols];
-    let row_count = data_rows.len();
+/// Delimited-text (CSV/DAT/TXT/TSV) rows, detecting whatever `csv` leaves
+/// open. Numbers in the file's notation are rewritten to `f64` syntax.
+fn read_delimited(bytes: &[u8], csv: CsvOptions, max_rows: Option<usize>, cols: Option<&[usize]>) -> Result<RawTable, String> {
+    let resolved = csv.resolve(bytes);
+    let delimiter = resolved.delimiter.unwrap_or(b',');
+    let numbers = NumberFormat { decimal: resolved.decimal.unwrap_or('.'), thousands: resolved.thousands };
 
-    for row in data_rows {
-        for (col_idx, col_data) in column_data.iter_mut().enumerate() {
-            if col_idx < row.len() {
-                col_data.push(row[col_idx].clone());
-            } else {
-                col_data.push(String::new());
-            }
-        }
-    }
+    let text = String::from_utf8(bytes.to_vec())
+        .unwrap_or_else(|_| bytes.iter().map(|&b| b as char).collect());
+    let reader = || {
+        csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(text.as_bytes())
+    };
 
-    Ok(finalize_loaded(columns, column_data, row_count))
-}
+    let header_row = if numbers.is_plain() {
+        parser::detect_csv_header_from_bytes(bytes, delimiter, 50)?
+    } else {
+        let sample: Vec<Vec<String>> = reader()
+            .records()
+            .take(50)
+            .flatten()
+            .map(|r| r.iter().map(|c| numbers.normalize(c)).collect())
+            .collect();
+        parser::detect_header_in_rows(&sample)?
+    };
 
-/// Path-based shim: read file then delegate to load_csv_from_bytes (legacy support).
-fn load_csv(path: &Path) -> Result<LoadedData, String> {
-    let bytes = std::fs::read(path).map_err(|e| format!("Cannot read file: {e}"))?;
-    load_csv_from_bytes(&bytes)
+    let mut collector = RowCollector::new(header_row, max_rows, cols);
+    for record in reader().records().flatten() {
+        collector.push(record.len(), |c| record.get(c).map(|cell| numbers.normalize(cell)));
+    }
+    collector.finish()
 }
 
-/// Parse Excel from raw bytes using calamine's reader-based API.
-pub fn load_excel_from_bytes(bytes: &[u8]) -> Result<LoadedData, String> {
-    use calamine::{Reader, Data};
+/// Excel rows from the first sheet, via calamine's reader-based API.
+fn read_excel(bytes: &[u8], max_rows: Option<usize>, cols: Option<&[usize]>) -> Result<RawTable, String> {
+    use calamine::Reader;
     use std::io::Cursor;
 
     let cursor = Cursor::new(bytes.to_vec());
@@ -249,52 +729,37 @@ pub fn load_excel_from_bytes(bytes: &[u8]) -> Result<LoadedData, String> {
     let range = workbook2.worksheet_range(&sheet_name)
         .map_err(|e| format!("Cannot read sheet: {e}"))?;
 
-    let all_rows: Vec<Vec<String>> = range.rows().map(|row| {
-        row.iter().map(|cell| {
-            match cell {
-                Data::Empty => String::new(),
-                Data::String(s) => s.clone(),
-                Data::Float(f) => f.to_string(),
-                Data::Int(i) => i.to_string(),
-                Data::Bool(b) => b.to_string(),
-                Data::DateTime(dt) => dt.to_string(),
-                Data::DateTimeIso(s) => s.clone(),
-                Data::DurationIso(s) => s.clone(),
-                Data::Error(e) => format!("{e:?}"),
-            }
-        }).collect()
-    }).collect();
-
-    if all_rows.is_empty() || header_row >= all_rows.len() {
-        return Err("No data found after header detection".to_string());
+    let mut collector = RowCollector::new(header_row, max_rows, cols);
+    for row in range.rows() {
+        collector.push(row.len(), |c| row.get(c).map(excel_cell_to_string));
     }
+    collector.finish()
+}
 
-    let columns: Vec<String> = all_rows[header_row].iter()
-        .map(|s| s.trim().to_string())
-        .collect();
-
-    let data_rows = &all_rows[header_row + 1..];
-    let num_cols = columns.len();
-    let mut column_data: Vec<Vec<String>> = vec![Vec::new(); num_cols];
-    let row_count = data_rows.len();
-
-    for row in data_rows {
-        for (col_idx, col_data) in column_data.iter_mut().enumerate() {
-            if col_idx < row.len() {
-                col_data.push(row[col_idx].clone());
-            } else {
-                col_data.push(String::new());
-            }
-        }
+fn excel_cell_to_string(cell: &calamine::Data) -> String {
+    use calamine::Data;
+    match cell {
+        Data::Empty => String::new(),
+        Data::String(s) => s.clone(),
+        Data::Float(f) => f.to_string(),
+        Data::Int(i) => i.to_string(),
+        Data::Bool(b) => b.to_string(),
+        Data::DateTime(dt) => dt.to_string(),
+        Data::DateTimeIso(s) => s.clone(),
+        Data::DurationIso(s) => s.clone(),
+        Data::Error(e) => format!("{e:?}"),
     }
+}
 
-    Ok(finalize_loaded(columns, column_data, row_count))
+/// Parse a delimited-text file (CSV/DAT/TXT/TSV) from raw bytes, auto-detecting
+/// the delimiter (core implementation).
+pub fn load_csv_from_bytes(bytes: &[u8]) -> Result<LoadedData, String> {
+    load_table_from_bytes(bytes, TableFormat::Delimited(CsvOptions::default()))
 }
 
-/// Path-based shim: read file then delegate to load_excel_from_bytes (legacy support).
-fn load_excel(path: &Path) -> Result<LoadedData, String> {
-    let bytes = std::fs::read(path).map_err(|e| format!("Cannot read file: {e}"))?;
-    load_excel_from_bytes(&bytes)
+/// Parse Excel from raw bytes (first sheet).
+pub fn load_excel_from_bytes(bytes: &[u8]) -> Result<LoadedData, String> {
+    load_table_from_bytes(bytes, TableFormat::Excel)
 }
 
 /// Extract numeric f64 values from a string column.
@@ -346,6 +811,15 @@ pub fn column_to_timestamps(data: &[String]) -> Option<(Vec<f64>, f64)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn synthetic_x_is_evenly_spaced() {
+        let x = SyntheticX { start: 2.0, interval: 0.001 };
+        assert_eq!(x.values(3).unwrap(), [2.0, 2.001, 2.002]);
+        assert_eq!(x.name(), "t [s]");
+        assert_eq!(SyntheticX::INDEX.name(), "index");
+        assert!(SyntheticX { start: 0.0, interval: 0.0 }.values(3).is_err());
+    }
+
     #[test]
     fn load_csv_from_bytes_detects_columns() {
         let csv = b"time,temp\n0,20.0\n1,21.5\n";
@@ -376,6 +850,30 @@ Frequency, Hz\tg(rms)/Hz\n\
         assert_eq!(meta.columns[1].kind, "numeric", "channel column is numeric");
     }
 
+    #[test]
+    fn european_csv_detects_decimal_comma_and_honours_thousands() {
+        let csv = "Datum;Temperatur;Zähler\n01.02.2024 10:00;21,5;1.234\n01.02.2024 10:01;-0,25;12.345\n";
+        let d = load_from_bytes(csv.as_bytes(), "messung.csv").unwrap();
+        assert_eq!(d.columns, ["Datum", "Temperatur", "Zähler"]);
+        assert_eq!(d.column_data[1].to_f64()[..], [21.5, -0.25]);
+        // Without a thousands separator "1.234" isn't a decimal-comma number.
+        assert!(d.column_data[2].to_f64()[0].is_nan());
+        assert_ne!(d.column_data[0].kind(), "numeric");
+
+        let opts = CsvOptions { thousands: Some('.'), ..CsvOptions::default() };
+        assert_eq!(opts.resolve(csv.as_bytes()), CsvOptions { delimiter: Some(b';'), decimal: Some(','), thousands: Some('.') });
+        let d = load_from_bytes_with(csv.as_bytes(), "messung.csv", opts).unwrap();
+        assert_eq!(d.column_data[2].to_f64()[..], [1234.0, 12345.0]);
+        // Dates aren't digit groups: left as they are.
+        assert_eq!(d.column_data[0].cell(0), "01.02.2024 10:00");
+
+        // Manual override: a pipe-delimited file with decimal commas.
+        let opts = CsvOptions { delimiter: Some(b'|'), decimal: Some(','), thousands: None };
+        let d = load_from_bytes_with(b"a|b\n1,5|2\n", "x.txt", opts).unwrap();
+        assert_eq!(d.column_data[0].to_f64()[..], [1.5]);
+        assert!(CsvOptions { delimiter: Some(b','), decimal: Some(','), thousands: None }.validate().is_err());
+    }
+
     #[test]
     fn merges_adjacent_date_and_12h_time_columns() {
         // Separate Date (constant day) + 12-hour Time columns (instrument-log
@@ -386,7 +884,7 @@ Frequency, Hz\tg(rms)/Hz\n\
         assert_eq!(d.columns[0], "Date Time");
         let meta = FileMeta::from_loaded(&d);
         assert_eq!(meta.columns[0].kind, "datetime", "merged column must be datetime");
-        let (ts, _) = column_to_timestamps(&d.column_data[0]).expect("merged col parses to timestamps");
+        let ts = d.column_data[0].timestamps().expect("merged col parses to timestamps");
         assert!(ts[1] > ts[0], "merged timestamps must advance (real time axis)");
     }
 
@@ -436,9 +934,98 @@ Frequency, Hz\tg(rms)/Hz\n\
         assert_eq!(meta.columns[1].kind, "numeric");
     }
 
+    #[test]
+    fn ragged_rows_pad_to_header_width() {
+        let csv = b"a,b,c\n1,2,3\n4\n5,6,7,8\n";
+        let d = load_from_bytes(csv, "x.csv").unwrap();
+        assert_eq!(d.row_count, 3);
+        let cells = |c: usize| -> Vec<String> {
+            (0..3).map(|r| d.column_data[c].cell(r).into_owned()).collect()
+        };
+        assert_eq!(cells(0), vec!["1", "4", "5"]);
+        assert_eq!(cells(1), vec!["2", "", "6"]);
+        assert_eq!(cells(2), vec!["3", "", "7"]);
+    }
+
+    #[test]
+    fn numeric_mask_matches_kinds() {
+        let csv = b"ts,label,val\n2024-01-01 00:00:00,a,1\n2024-01-02 00:00:00,b,2\n2024-01-03 00:00:00,c,3\n";
+        let d = load_from_bytes(csv, "x.csv").unwrap();
+        assert_eq!(FileMeta::from_loaded(&d).numeric_mask(), vec![true, false, true]);
+    }
+
+    #[test]
+    fn import_produces_typed_columns_with_validity() {
+        let csv = b"ts,val\n2024-01-01 00:00:00,10\n2024-01-02 00:00:00,20\n2024-01-03 00:00:00,x\n";
+        let d = load_from_bytes(csv, "x.csv").unwrap();
+        let val = &d.column_data[1];
+        assert_eq!(val.kind(), "numeric");
+        assert_eq!(val.to_f64()[..2], [10.0, 20.0]);
+        assert!(val.value(2).is_nan());
+        let ts = d.column_data[0].timestamps().expect("ts column is datetime");
+        assert!(ts[1] > ts[0]);
+        let meta = FileMeta::from_loaded(&d);
+        assert!((meta.columns[1].valid - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(meta.columns[0].valid, 1.0);
+    }
+
+    /// A `LAZY_MIN_COLUMNS`-wide CSV: Date, Time, then numeric channels with
+    /// one text channel, over `rows` rows.
+    fn wide_csv(rows: usize) -> Vec<u8> {
+        let n = LAZY_MIN_COLUMNS;
+        let mut s = String::from("Date,Time");
+        for c in 2..n {
+            s.push_str(&format!(",ch{c}"));
+        }
+        s.push('\n');
+        for r in 0..rows {
+            s.push_str(&format!("07/07/2026,02:{:02}:{:02} PM", r / 60, r % 60));
+            for c in 2..n {
+                if c == 5 {
+                    s.push_str(&format!(",tag{r}"));
+                } else {
+                    s.push_str(&format!(",{}", r * c));
+                }
+            }
+            s.push('\n');
+        }
+        s.into_bytes()
+    }
+
+    #[test]
+    fn wide_file_loads_lazily_and_materializes_like_eager() {
+        let bytes = wide_csv(LAZY_SAMPLE_ROWS + 100);
+        let eager = load_from_bytes(&bytes, "wide.csv").unwrap();
+        let mut lazy = load_from_bytes_lazy(bytes, "wide.csv", CsvOptions::default()).unwrap();
+
+        assert!(lazy.lazy.is_some());
+        assert_eq!(lazy.columns, eager.columns);
+        assert_eq!(lazy.columns[0], "Date Time");
+        assert_eq!(lazy.row_count, LAZY_SAMPLE_ROWS + 100, "row count covers the whole file");
+        assert!(lazy.column_data.iter().all(|c| matches!(**c, Column::Deferred { .. })));
+        // Kinds come from the sample before anything is read in full.
+        let meta = FileMeta::from_loaded(&lazy);
+        assert_eq!(meta.columns[0].kind, "datetime");
+        assert_eq!(meta.columns[1].kind, "numeric");
+        assert_eq!(meta.columns[4].kind, "text");
+
+        lazy.materialize(&[0, 7]).unwrap();
+        assert_eq!(lazy.column_data[0], eager.column_data[0], "merged Date+Time rebuilt");
+        assert_eq!(lazy.column_data[7], eager.column_data[7]);
+        assert!(matches!(*lazy.column_data[1], Column::Deferred { .. }), "unselected stays deferred");
+    }
+
+    #[test]
+    fn narrow_file_loads_eagerly_through_lazy_entry() {
+        let mut d = load_from_bytes_lazy(b"time,temp\n0,20.0\n1,21.5\n".to_vec(), "x.csv", CsvOptions::default()).unwrap();
+        assert!(d.lazy.is_none());
+        assert_eq!(d.column_data[1].to_f64()[..], [20.0, 21.5]);
+        d.materialize(&[0, 1]).unwrap();
+    }
+
     #[test]
     fn unsupported_extension_errors() {
-        let result = load_from_bytes(b"data", "file.json");
+        let result = load_from_bytes(b"data", "file.xyz");
         assert!(result.is_err());
     }
 }
@@ -458,11 +1045,11 @@ mod resolve_tests {
     use super::*;
 
     fn ld() -> LoadedData {
-        LoadedData {
-            columns: vec!["time".into(), "temp".into(), "pressure".into()],
-            column_data: vec![vec![], vec![], vec![]],
-            row_count: 0,
-        }
+        LoadedData::from_string_columns(
+            vec!["time".into(), "temp".into(), "pressure".into()],
+            vec![vec![], vec![], vec![]],
+            0,
+        )
     }
 
     #[test]
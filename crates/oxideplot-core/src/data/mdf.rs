@@ -0,0 +1,623 @@
+//! ASAM MDF 4 (`.mf4`) measurement files, the automotive DAQ standard.
+//!
+//! Each channel group becomes a set of numeric columns: its master (time)
+//! channel and every value channel, with physical values (the channel's
+//! conversion applied) and the unit appended to the column name as
+//! `"name [unit]"`. With several channel groups, column names are prefixed
+//! with the group's acquisition name (or `CG<n>`) since each group has its
+//! own time base; shorter groups are padded with NaN.
+//!
+//! Data may be stored plain (`##DT`), split across a data list (`##DL`) or
+//! header list (`##HL`), and deflate-compressed (`##DZ`, with or without
+//! transposition). Sorted and unsorted data groups are both read. Sample
+//! reduction blocks (`##SR`, precomputed min/mean/max of a group) are
+//! skipped: columns always hold the full-resolution records.
+//!
+//! Not supported: channels other than integers and IEEE floats (strings,
+//! byte arrays, …), variable-length signal data, and text or algebraic
+//! conversions (those channels keep their raw values).
+
+use std::collections::HashSet;
+use std::io::Read;
+
+use crate::data::loader::LoadedData;
+
+/// Most blocks followed along any one linked list; guards against
+/// corrupt files whose links loop.
+const MAX_CHAIN: usize = 100_000;
+
+/// A decoded channel: column name and physical values.
+type NamedValues = (String, Vec<f64>);
+
+/// Whether `bytes` starts with an MDF identification block.
+pub fn is_mdf(bytes: &[u8]) -> bool {
+    bytes.len() >= 64 && &bytes[..3] == b"MDF"
+}
+
+/// Decode an MDF 4.x file into typed columns.
+pub fn load_mdf(bytes: &[u8]) -> Result<LoadedData, String> {
+    if !is_mdf(bytes) {
+        return Err("not an MDF file (missing MDF identification block)".to_string());
+    }
+    let version = String::from_utf8_lossy(&bytes[8..16]).trim().trim_end_matches('\0').to_string();
+    if !version.starts_with('4') {
+        return Err(format!("MDF version {version} is not supported (MDF 4.x only)"));
+    }
+    let file = Mdf { bytes };
+    let hd = file.block(64, b"HD")?;
+
+    let mut groups: Vec<(String, Vec<NamedValues>)> = Vec::new();
+    for dg_at in file.chain(hd.link(0), 0)? {
+        let dg = file.block(dg_at, b"DG")?;
+        let rec_id_size = *dg.data.first().unwrap_or(&0) as usize;
+        let cgs: Vec<ChannelGroup> =
+            file.chain(dg.link(1), 0)?.into_iter().map(|at| file.channel_group(at)).collect::<Result<_, _>>()?;
+        let raw = file.data_bytes(dg.link(2))?;
+        let records = split_records(&raw, rec_id_size, &cgs)?;
+        for (cg, recs) in cgs.iter().zip(records) {
+            if cg.is_vlsd {
+                continue;
+            }
+            let cols = cg.channels.iter().map(|cn| (cn.column_name(), cn.decode(&recs, cg.data_bytes))).collect();
+            groups.push((cg.name.clone(), cols));
+        }
+    }
+    groups.retain(|(_, cols)| !cols.is_empty());
+    if groups.is_empty() {
+        return Err("MDF file has no channels".to_string());
+    }
+
+    let prefix = groups.len() > 1;
+    let mut columns = Vec::new();
+    let mut values = Vec::new();
+    for (gi, (group, cols)) in groups.into_iter().enumerate() {
+        let group = if group.is_empty() { format!("CG{}", gi + 1) } else { group };
+        for (name, v) in cols {
+            columns.push(if prefix { format!("{group}/{name}") } else { name });
+            values.push(v);
+        }
+    }
+    Ok(LoadedData::from_numeric_columns(columns, values))
+}
+
+struct Mdf<'a> {
+    bytes: &'a [u8],
+}
+
+struct Block<'a> {
+    links: Vec<u64>,
+    data: &'a [u8],
+}
+
+impl Block<'_> {
+    fn link(&self, i: usize) -> u64 {
+        self.links.get(i).copied().unwrap_or(0)
+    }
+}
+
+struct ChannelGroup {
+    name: String,
+    record_id: u64,
+    /// Variable-length signal data group (no fixed records, not plotted).
+    is_vlsd: bool,
+    data_bytes: usize,
+    inval_bytes: usize,
+    channels: Vec<Channel>,
+}
+
+impl ChannelGroup {
+    fn record_len(&self) -> usize {
+        self.data_bytes + self.inval_bytes
+    }
+}
+
+struct Channel {
+    name: String,
+    unit: String,
+    /// 0 fixed-length, 2 master, 3 virtual master, 6 virtual data, …
+    kind: u8,
+    data_type: u8,
+    bit_offset: u32,
+    byte_offset: usize,
+    bit_count: u32,
+    /// Position of this channel's invalidation bit, when it has one.
+    inval_bit: Option<u32>,
+    conversion: Conversion,
+}
+
+enum Conversion {
+    Identity,
+    Linear { offset: f64, factor: f64 },
+    Rational([f64; 6]),
+    /// Key/value table: interpolated, or nearest key when `interpolate` is off.
+    Table { pairs: Vec<(f64, f64)>, interpolate: bool },
+}
+
+impl Conversion {
+    fn apply(&self, x: f64) -> f64 {
+        match self {
+            Conversion::Identity => x,
+            Conversion::Linear { offset, factor } => offset + factor * x,
+            Conversion::Rational([p1, p2, p3, p4, p5, p6]) => {
+                (p1 * x * x + p2 * x + p3) / (p4 * x * x + p5 * x + p6)
+            }
+            Conversion::Table { pairs, interpolate } => table_lookup(pairs, x, *interpolate),
+        }
+    }
+}
+
+fn table_lookup(pairs: &[(f64, f64)], x: f64, interpolate: bool) -> f64 {
+    let (Some(first), Some(last)) = (pairs.first(), pairs.last()) else { return x };
+    if x <= first.0 {
+        return first.1;
+    }
+    if x >= last.0 {
+        return last.1;
+    }
+    let i = pairs.partition_point(|p| p.0 <= x);
+    let (a, b) = (pairs[i - 1], pairs[i]);
+    if interpolate {
+        a.1 + (b.1 - a.1) * (x - a.0) / (b.0 - a.0)
+    } else if x - a.0 <= b.0 - x {
+        a.1
+    } else {
+        b.1
+    }
+}
+
+impl Channel {
+    fn column_name(&self) -> String {
+        if self.unit.is_empty() { self.name.clone() } else { format!("{} [{}]", self.name, self.unit) }
+    }
+
+    /// Physical values for every record (NaN where invalid or unreadable).
+    fn decode(&self, records: &[&[u8]], data_bytes: usize) -> Vec<f64> {
+        records
+            .iter()
+            .enumerate()
+            .map(|(i, rec)| {
+                if let Some(bit) = self.inval_bit {
+                    let byte = data_bytes + (bit / 8) as usize;
+                    if rec.get(byte).is_some_and(|b| b >> (bit % 8) & 1 == 1) {
+                        return f64::NAN;
+                    }
+                }
+                let raw = if self.kind == 3 || self.kind == 6 { Some(i as f64) } else { self.raw_value(rec) };
+                raw.map_or(f64::NAN, |v| self.conversion.apply(v))
+            })
+            .collect()
+    }
+
+    fn raw_value(&self, rec: &[u8]) -> Option<f64> {
+        let bits = self.bit_count as usize;
+        let nbytes = (self.bit_offset as usize + bits).div_ceil(8);
+        let field = rec.get(self.byte_offset..self.byte_offset + nbytes)?;
+        match self.data_type {
+            // Unsigned / signed integers, little- or big-endian.
+            0..=3 if (1..=64).contains(&bits) && nbytes <= 8 => {
+                let mut buf = [0u8; 8];
+                if self.data_type.is_multiple_of(2) {
+                    buf[..nbytes].copy_from_slice(field);
+                } else {
+                    for (k, b) in field.iter().rev().enumerate() {
+                        buf[k] = *b;
+                    }
+                }
+                let word = u64::from_le_bytes(buf) >> self.bit_offset;
+                let word = if bits == 64 { word } else { word & ((1u64 << bits) - 1) };
+                if self.data_type >= 2 && bits < 64 && word >> (bits - 1) & 1 == 1 {
+                    Some((word as i64 - (1i64 << bits)) as f64)
+                } else if self.data_type >= 2 {
+                    Some(word as i64 as f64)
+                } else {
+                    Some(word as f64)
+                }
+            }
+            // IEEE floats, little- or big-endian.
+            4 | 5 if self.bit_offset == 0 && (bits == 32 || bits == 64) => {
+                let le = self.data_type == 4;
+                Some(if bits == 32 {
+                    let b: [u8; 4] = field.try_into().ok()?;
+                    (if le { f32::from_le_bytes(b) } else { f32::from_be_bytes(b) }) as f64
+                } else {
+                    let b: [u8; 8] = field.try_into().ok()?;
+                    if le { f64::from_le_bytes(b) } else { f64::from_be_bytes(b) }
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl<'a> Mdf<'a> {
+    fn u64_at(&self, at: usize) -> Result<u64, String> {
+        self.bytes
+            .get(at..at + 8)
+            .map(|b| u64::from_le_bytes(b.try_into().unwrap()))
+            .ok_or_else(|| format!("MDF block at {at} runs past the end of the file"))
+    }
+
+    /// Read the block at `at`, checking its `##XX` id.
+    fn block(&self, at: u64, id: &[u8; 2]) -> Result<Block<'a>, String> {
+        let b = self.any_block(at)?;
+        if &b.0 != id {
+            return Err(format!(
+                "expected MDF ##{} block at {at}, found ##{}",
+                String::from_utf8_lossy(id),
+                String::from_utf8_lossy(&b.0)
+            ));
+        }
+        Ok(b.1)
+    }
+
+    fn any_block(&self, at: u64) -> Result<([u8; 2], Block<'a>), String> {
+        let out_of_range = || format!("MDF block at {at} is out of range");
+        let at = usize::try_from(at).map_err(|_| out_of_range())?;
+        let head = at.checked_add(24).and_then(|e| self.bytes.get(at..e)).ok_or_else(out_of_range)?;
+        if &head[..2] != b"##" {
+            return Err(format!("no MDF block at {at}"));
+        }
+        let id = [head[2], head[3]];
+        let bad_length = || format!("MDF block at {at} has a bad length");
+        let len = usize::try_from(self.u64_at(at + 8)?).map_err(|_| bad_length())?;
+        let link_count = usize::try_from(self.u64_at(at + 16)?).map_err(|_| bad_length())?;
+        let data_at = link_count.checked_mul(8).and_then(|n| n.checked_add(at + 24)).ok_or_else(bad_length)?;
+        let end = at.checked_add(len).filter(|&e| e <= self.bytes.len() && e >= data_at);
+        let end = end.ok_or_else(bad_length)?;
+        let links = (0..link_count)
+            .map(|i| self.u64_at(at.checked_add(24 + i * 8).ok_or_else(bad_length)?))
+            .collect::<Result<_, _>>()?;
+        Ok((id, Block { links, data: &self.bytes[data_at..end] }))
+    }
+
+    /// Offsets of a linked list of blocks whose "next" link is link `next`.
+    fn chain(&self, first: u64, next: usize) -> Result<Vec<u64>, String> {
+        let mut out = Vec::new();
+        let mut seen = HashSet::new();
+        let mut at = first;
+        while at != 0 {
+            if !seen.insert(at) || out.len() >= MAX_CHAIN {
+                return Err("MDF block list loops".to_string());
+            }
+            out.push(at);
+            at = self.any_block(at)?.1.link(next);
+        }
+        Ok(out)
+    }
+
+    /// Text of a `##TX` block, or the `<TX>` element of a `##MD` block.
+    fn text(&self, at: u64) -> Result<String, String> {
+        if at == 0 {
+            return Ok(String::new());
+        }
+        let (id, b) = self.any_block(at)?;
+        let text = String::from_utf8_lossy(b.data);
+        let text = text.trim_end_matches('\0');
+        Ok(match &id {
+            b"MD" => match (text.find("<TX>"), text.find("</TX>")) {
+                (Some(s), Some(e)) if e > s => text[s + 4..e].trim().to_string(),
+                _ => String::new(),
+            },
+            _ => text.trim().to_string(),
+        })
+    }
+
+    fn channel_group(&self, at: u64) -> Result<ChannelGroup, String> {
+        let cg = self.block(at, b"CG")?;
+        let d = cg.data;
+        if d.len() < 32 {
+            return Err("MDF ##CG block is too short".to_string());
+        }
+        let u64d = |o: usize| u64::from_le_bytes(d[o..o + 8].try_into().unwrap());
+        let flags = u16::from_le_bytes([d[16], d[17]]);
+        let data_bytes = u32::from_le_bytes(d[24..28].try_into().unwrap()) as usize;
+        let inval_bytes = u32::from_le_bytes(d[28..32].try_into().unwrap()) as usize;
+        let mut channels: Vec<Channel> =
+            self.chain(cg.link(1), 0)?.into_iter().map(|at| self.channel(at)).collect::<Result<_, _>>()?;
+        // Master channel first, so it reads as the group's X column.
+        channels.sort_by_key(|c| !(c.kind == 2 || c.kind == 3));
+        channels.retain(|c| matches!(c.kind, 0 | 2 | 3 | 4 | 6));
+        Ok(ChannelGroup {
+            name: self.text(cg.link(2))?,
+            record_id: u64d(0),
+            is_vlsd: flags & 1 == 1,
+            data_bytes,
+            inval_bytes,
+            channels,
+        })
+    }
+
+    fn channel(&self, at: u64) -> Result<Channel, String> {
+        let cn = self.block(at, b"CN")?;
+        let d = cn.data;
+        if d.len() < 24 {
+            return Err("MDF ##CN block is too short".to_string());
+        }
+        let u32d = |o: usize| u32::from_le_bytes(d[o..o + 4].try_into().unwrap());
+        let flags = u32d(12);
+        let conversion = self.conversion(cn.link(4))?;
+        let mut unit = self.text(cn.link(6))?;
+        if unit.is_empty() {
+            unit = self.conversion_unit(cn.link(4))?;
+        }
+        Ok(Channel {
+            name: self.text(cn.link(2))?,
+            unit,
+            kind: d[0],
+            data_type: d[2],
+            bit_offset: d[3] as u32,
+            byte_offset: u32d(4) as usize,
+            bit_count: u32d(8),
+            inval_bit: (flags & 0b10 != 0).then(|| u32d(16)),
+            conversion,
+        })
+    }
+
+    fn conversion(&self, at: u64) -> Result<Conversion, String> {
+        if at == 0 {
+            return Ok(Conversion::Identity);
+        }
+        let cc = self.block(at, b"CC")?;
+        let d = cc.data;
+        if d.len() < 24 {
+            return Err("MDF ##CC block is too short".to_string());
+        }
+        let val_count = u16::from_le_bytes([d[6], d[7]]) as usize;
+        let vals: Vec<f64> = (0..val_count)
+            .filter_map(|i| d.get(24 + i * 8..32 + i * 8))
+            .map(|b| f64::from_le_bytes(b.try_into().unwrap()))
+            .collect();
+        Ok(match (d[0], vals.as_slice()) {
+            (1, [offset, factor, ..]) => Conversion::Linear { offset: *offset, factor: *factor },
+            (2, [a, b, c, e, f, g, ..]) => Conversion::Rational([*a, *b, *c, *e, *f, *g]),
+            (4 | 5, v) if v.len() >= 2 => {
+                let mut pairs: Vec<(f64, f64)> = v.chunks_exact(2).map(|p| (p[0], p[1])).collect();
+                pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+                Conversion::Table { pairs, interpolate: d[0] == 4 }
+            }
+            _ => Conversion::Identity,
+        })
+    }
+
+    fn conversion_unit(&self, at: u64) -> Result<String, String> {
+        if at == 0 {
+            return Ok(String::new());
+        }
+        self.text(self.block(at, b"CC")?.link(1))
+    }
+
+    /// The data group's records, with data lists followed and compressed
+    /// blocks inflated.
+    fn data_bytes(&self, at: u64) -> Result<Vec<u8>, String> {
+        let mut out = Vec::new();
+        self.append_data(at, &mut out, 0)?;
+        Ok(out)
+    }
+
+    fn append_data(&self, at: u64, out: &mut Vec<u8>, depth: usize) -> Result<(), String> {
+        if at == 0 {
+            return Ok(());
+        }
+        if depth > 4 {
+            return Err("MDF data lists nest too deeply".to_string());
+        }
+        let (id, b) = self.any_block(at)?;
+        match &id {
+            b"DT" | b"DV" => out.extend_from_slice(b.data),
+            b"DZ" => out.extend(inflate_dz(b.data)?),
+            b"HL" => self.append_data(b.link(0), out, depth + 1)?,
+            b"DL" => {
+                for dl_at in self.chain(at, 0)? {
+                    let dl = self.block(dl_at, b"DL")?;
+                    for &part in dl.links.iter().skip(1) {
+                        self.append_data(part, out, depth + 1)?;
+                    }
+                }
+            }
+            other => return Err(format!("unsupported MDF data block ##{}", String::from_utf8_lossy(other))),
+        }
+        Ok(())
+    }
+}
+
+/// Inflate a `##DZ` block's payload, undoing byte transposition if used.
+fn inflate_dz(d: &[u8]) -> Result<Vec<u8>, String> {
+    if d.len() < 24 {
+        return Err("MDF ##DZ block is too short".to_string());
+    }
+    let zip_type = d[2];
+    let columns = u32::from_le_bytes(d[4..8].try_into().unwrap()) as usize;
+    let org_len = u64::from_le_bytes(d[8..16].try_into().unwrap()) as usize;
+    let mut out = Vec::with_capacity(org_len);
+    flate2::read::ZlibDecoder::new(&d[24..])
+        .read_to_end(&mut out)
+        .map_err(|e| format!("MDF ##DZ inflate failed: {e}"))?;
+    if zip_type == 1 && columns > 0 {
+        // Transposed: M rows of `columns` bytes were stored column by column;
+        // any tail shorter than a row was left as is.
+        let rows = out.len() / columns;
+        let body = rows * columns;
+        let mut t = vec![0u8; out.len()];
+        for c in 0..columns {
+            for r in 0..rows {
+                t[r * columns + c] = out[c * rows + r];
+            }
+        }
+        t[body..].copy_from_slice(&out[body..]);
+        out = t;
+    }
+    Ok(out)
+}
+
+/// Cut a data group's bytes into per-channel-group record slices.
+fn split_records<'b>(raw: &'b [u8], rec_id_size: usize, cgs: &[ChannelGroup]) -> Result<Vec<Vec<&'b [u8]>>, String> {
+    let mut out: Vec<Vec<&[u8]>> = vec![Vec::new(); cgs.len()];
+    if rec_id_size == 0 {
+        // Sorted: one group, fixed-size records back to back.
+        if let Some(cg) = cgs.first() {
+            let len = cg.record_len();
+            if len > 0 {
+                out[0] = raw.chunks_exact(len).collect();
+            }
+        }
+        return Ok(out);
+    }
+    let mut at = 0;
+    while at + rec_id_size <= raw.len() {
+        let mut id = [0u8; 8];
+        id[..rec_id_size.min(8)].copy_from_slice(&raw[at..at + rec_id_size.min(8)]);
+        let id = u64::from_le_bytes(id);
+        at += rec_id_size;
+        let k = cgs.iter().position(|c| c.record_id == id).ok_or_else(|| format!("MDF record id {id} has no channel group"))?;
+        let len = if cgs[k].is_vlsd {
+            let n = raw.get(at..at + 4).ok_or("truncated MDF record")?;
+            4 + u32::from_le_bytes(n.try_into().unwrap()) as usize
+        } else {
+            cgs[k].record_len()
+        };
+        let rec = raw.get(at..at + len).ok_or("truncated MDF record")?;
+        if !cgs[k].is_vlsd {
+            out[k].push(rec);
+        }
+        at += len;
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod mdf_tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Minimal MDF 4 writer: blocks appended in order, links patched by the
+    /// caller passing offsets it computed from earlier `push` results.
+    struct Writer {
+        out: Vec<u8>,
+    }
+
+    impl Writer {
+        fn new() -> Self {
+            let mut out = b"MDF     4.10    OxPlot  ".to_vec();
+            out.resize(64, 0);
+            Writer { out }
+        }
+
+        fn push(&mut self, id: &[u8; 2], links: &[u64], data: &[u8]) -> u64 {
+            while !self.out.len().is_multiple_of(8) {
+                self.out.push(0);
+            }
+            let at = self.out.len() as u64;
+            let len = 24 + links.len() * 8 + data.len();
+            self.out.extend_from_slice(b"##");
+            self.out.extend_from_slice(id);
+            self.out.extend_from_slice(&[0; 4]);
+            self.out.extend_from_slice(&(len as u64).to_le_bytes());
+            self.out.extend_from_slice(&(links.len() as u64).to_le_bytes());
+            for l in links {
+                self.out.extend_from_slice(&l.to_le_bytes());
+            }
+            self.out.extend_from_slice(data);
+            at
+        }
+
+        fn text(&mut self, s: &str) -> u64 {
+            let mut d = s.as_bytes().to_vec();
+            d.push(0);
+            self.push(b"TX", &[], &d)
+        }
+
+        /// Patch link `i` of the block at `at`.
+        fn link(&mut self, at: u64, i: usize, to: u64) {
+            let o = at as usize + 24 + i * 8;
+            self.out[o..o + 8].copy_from_slice(&to.to_le_bytes());
+        }
+    }
+
+    fn cn_data(kind: u8, data_type: u8, byte_offset: u32, bit_count: u32) -> Vec<u8> {
+        let mut d = vec![kind, 0, data_type, 0];
+        d.extend_from_slice(&byte_offset.to_le_bytes());
+        d.extend_from_slice(&bit_count.to_le_bytes());
+        d.extend_from_slice(&[0; 12]); // flags, inval bit pos, precision, reserved, attachments
+        d.extend_from_slice(&[0; 48]); // ranges and limits
+        d
+    }
+
+    /// One sorted group: f64 master `t` [s] and a u16 `rpm` with a ×0.5
+    /// linear conversion. Records are stored via `store` (DT or DZ).
+    fn build(store: impl FnOnce(&mut Writer, &[u8]) -> u64) -> Vec<u8> {
+        let mut w = Writer::new();
+        let hd = w.push(b"HD", &[0; 6], &[0; 32]);
+        let dg = w.push(b"DG", &[0; 4], &[0; 8]);
+        w.link(hd, 0, dg);
+        let mut cg_data = vec![0u8; 32];
+        cg_data[8..16].copy_from_slice(&3u64.to_le_bytes());
+        cg_data[24..28].copy_from_slice(&10u32.to_le_bytes());
+        let cg = w.push(b"CG", &[0; 6], &cg_data);
+        w.link(dg, 1, cg);
+        let t_name = w.text("t");
+        let s_unit = w.text("s");
+        let t = w.push(b"CN", &[0, 0, t_name, 0, 0, 0, s_unit, 0], &cn_data(2, 4, 0, 64));
+        let rpm_name = w.text("rpm");
+        let mut cc = vec![1u8, 0, 0, 0, 0, 0, 2, 0];
+        cc.extend_from_slice(&[0; 16]);
+        cc.extend_from_slice(&0.0f64.to_le_bytes());
+        cc.extend_from_slice(&0.5f64.to_le_bytes());
+        let cc_unit = w.text("1/min");
+        let conv = w.push(b"CC", &[0, cc_unit, 0, 0], &cc);
+        let rpm = w.push(b"CN", &[0, 0, rpm_name, 0, conv, 0, 0, 0], &cn_data(0, 0, 8, 16));
+        w.link(cg, 1, t);
+        w.link(t, 0, rpm);
+        let mut records = Vec::new();
+        for (time, raw) in [(0.0f64, 1000u16), (0.1, 2000), (0.2, 3000)] {
+            records.extend_from_slice(&time.to_le_bytes());
+            records.extend_from_slice(&raw.to_le_bytes());
+        }
+        let data = store(&mut w, &records);
+        w.link(dg, 2, data);
+        w.out
+    }
+
+    #[test]
+    fn reads_channels_with_units_and_conversion() {
+        let bytes = build(|w, records| w.push(b"DT", &[], records));
+        assert!(is_mdf(&bytes));
+        let d = load_mdf(&bytes).unwrap();
+        assert_eq!(d.columns, vec!["t [s]".to_string(), "rpm [1/min]".to_string()]);
+        assert_eq!(d.row_count, 3);
+        assert_eq!(d.column_data[0].to_f64().as_ref(), &[0.0, 0.1, 0.2]);
+        assert_eq!(d.column_data[1].to_f64().as_ref(), &[500.0, 1000.0, 1500.0]);
+    }
+
+    #[test]
+    fn reads_deflated_data_blocks() {
+        let bytes = build(|w, records| {
+            let mut enc = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            enc.write_all(records).unwrap();
+            let packed = enc.finish().unwrap();
+            let mut d = b"DT".to_vec();
+            d.extend_from_slice(&[0, 0]); // deflate, reserved
+            d.extend_from_slice(&0u32.to_le_bytes());
+            d.extend_from_slice(&(records.len() as u64).to_le_bytes());
+            d.extend_from_slice(&(packed.len() as u64).to_le_bytes());
+            d.extend_from_slice(&packed);
+            w.push(b"DZ", &[], &d)
+        });
+        let d = load_mdf(&bytes).unwrap();
+        assert_eq!(d.column_data[1].to_f64().as_ref(), &[500.0, 1000.0, 1500.0]);
+        assert!(load_mdf(b"not an mdf file").is_err());
+    }
+
+    #[test]
+    fn corrupt_block_offsets_are_errors() {
+        let mut w = Writer::new();
+        let at = w.push(b"TX", &[], b"x\0");
+        let file = Mdf { bytes: &w.out };
+        assert!(file.any_block(u64::MAX).is_err());
+        // A link count whose byte size overflows when added to the offset.
+        let mut bytes = w.out.clone();
+        let count = at as usize + 16;
+        bytes[count..count + 8].copy_from_slice(&(u64::MAX / 8).to_le_bytes());
+        let err = Mdf { bytes: &bytes }.any_block(at).err().unwrap_or_default();
+        assert!(err.contains("bad length"), "{err}");
+    }
+}
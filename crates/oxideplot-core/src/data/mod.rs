@@ -1,5 +1,21 @@
+pub mod column;
 pub mod loader;
 pub mod parser;
 pub mod datetime;
 pub mod unit_inference;
+pub mod unit_convert;
+pub mod split;
 pub mod table;
+pub mod store;
+pub mod reference;
+pub mod label_map;
+pub mod mdf;
+pub mod ulog;
+pub mod streaming;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod json;
+pub mod chunked;
+pub mod synthetic;
+pub mod compressed;
+pub mod events;
@@ -28,14 +28,19 @@ pub fn detect_csv_header_from_bytes(bytes: &[u8], delimiter: u8, max_lines: usiz
             Err(_) => continue,
         }
     }
+    detect_header_in_rows(&rows)
+}
 
+/// Header row index among the first rows of a delimited file, already split
+/// into cells (with numbers in `f64` syntax).
+pub fn detect_header_in_rows(rows: &[Vec<String>]) -> Result<usize, String> {
     if rows.is_empty() {
         return Err("No data found in file".to_string());
     }
 
     // Find most common column count
     let mut counts: HashMap<usize, usize> = HashMap::new();
-    for row in &rows {
+    for row in rows {
         *counts.entry(row.len()).or_insert(0) += 1;
     }
     let most_common = counts.into_iter().max_by_key(|&(_, c)| c).map(|(len, _)| len).unwrap_or(0);
@@ -155,16 +160,14 @@ fn detect_header_from_rows(rows: &[Vec<Option<String>>]) -> Result<usize, String
         if non_empty_count < used_cols { continue; }
 
         let mut all_strings = true;
-        for cell in row {
-            if let Some(val) = cell {
-                if val.parse::<f64>().is_ok() {
-                    all_strings = false;
-                    break;
-                }
-                if is_date_like(val) {
-                    all_strings = false;
-                    break;
-                }
+        for val in row.iter().flatten() {
+            if val.parse::<f64>().is_ok() {
+                all_strings = false;
+                break;
+            }
+            if is_date_like(val) {
+                all_strings = false;
+                break;
             }
         }
 
@@ -0,0 +1,83 @@
+//! Reference curves: small baseline datasets (spec limits, golden runs) kept
+//! by the app rather than by one graph, and overlaid on any graph on demand.
+//!
+//! A reference file is read like any other table; its first numeric or
+//! datetime column is X and the next one is Y. A file with a single numeric
+//! column is plotted against the row index.
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::loader::load_from_bytes;
+
+/// Largest reference curve accepted, in points. References are meant to be
+/// small; bigger files belong in a graph of their own.
+pub const REFERENCE_MAX_POINTS: usize = 100_000;
+
+/// A named baseline curve, sorted by X with non-finite points dropped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceCurve {
+    pub name: String,
+    pub x_name: String,
+    pub xs: Vec<f64>,
+    pub ys: Vec<f64>,
+}
+
+/// Parse a reference curve from file bytes. The curve is named after the
+/// file (without extension) and its Y column.
+pub fn load_reference(bytes: &[u8], filename: &str) -> Result<ReferenceCurve, String> {
+    let data = load_from_bytes(bytes, filename)?;
+    let numeric: Vec<usize> =
+        (0..data.column_data.len()).filter(|&c| data.column_data[c].is_numeric()).collect();
+    let (x_name, xs, y_col) = match numeric.as_slice() {
+        [] => return Err("reference file has no numeric column".to_string()),
+        [y] => ("index".to_string(), (0..data.row_count).map(|i| i as f64).collect(), *y),
+        [x, y, ..] => (data.columns[*x].clone(), data.column_data[*x].to_f64().into_owned(), *y),
+    };
+    let ys = data.column_data[y_col].to_f64();
+    let mut pts: Vec<(f64, f64)> = xs
+        .iter()
+        .zip(ys.iter())
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .map(|(&x, &y)| (x, y))
+        .collect();
+    if pts.is_empty() {
+        return Err("reference file has no finite points".to_string());
+    }
+    if pts.len() > REFERENCE_MAX_POINTS {
+        return Err(format!(
+            "reference curve has {} points (limit {REFERENCE_MAX_POINTS})",
+            pts.len()
+        ));
+    }
+    pts.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let stem = filename.rsplit_once('.').map_or(filename, |(s, _)| s);
+    Ok(ReferenceCurve {
+        name: format!("{stem}: {}", data.columns[y_col]),
+        x_name,
+        xs: pts.iter().map(|p| p.0).collect(),
+        ys: pts.iter().map(|p| p.1).collect(),
+    })
+}
+
+#[cfg(test)]
+mod reference_tests {
+    use super::*;
+
+    #[test]
+    fn first_two_numeric_columns_are_x_and_y() {
+        let csv = b"label,t,limit\na,2,20\nb,0,10\nc,1,x\n";
+        let r = load_reference(csv, "spec.csv").unwrap();
+        assert_eq!(r.name, "spec: limit");
+        assert_eq!(r.x_name, "t");
+        assert_eq!(r.xs, vec![0.0, 2.0], "sorted by X, unparseable row dropped");
+        assert_eq!(r.ys, vec![10.0, 20.0]);
+    }
+
+    #[test]
+    fn single_column_plots_against_index() {
+        let r = load_reference(b"v\n5\n6\n", "golden.csv").unwrap();
+        assert_eq!(r.x_name, "index");
+        assert_eq!(r.xs, vec![0.0, 1.0]);
+        assert!(load_reference(b"a\nx\ny\n", "t.csv").is_err());
+    }
+}
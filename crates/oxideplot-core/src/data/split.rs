@@ -0,0 +1,161 @@
+//! Splitting one Y column into a series per category.
+//!
+//! A log with a `TestPhase` column holds every phase in one file; plotting
+//! `Pressure` split by `TestPhase` gives one series per phase value, each
+//! with only that phase's rows, instead of filtering the file elsewhere.
+//!
+//! Long ("tidy") logs are the same split seen the other way round: rows of
+//! `(timestamp, channel, value)` pivot into one series per channel, named
+//! after the channel ([`detect_long_format`] spots them at import).
+
+use std::sync::Arc;
+
+use serde::Serialize;
+
+use crate::data::column::Column;
+use crate::data::unit_inference::bracketed_unit;
+
+/// Most distinct values a split column may have: more is almost certainly a
+/// measurement, not a category, and would flood the series list.
+pub const MAX_SPLIT_CATEGORIES: usize = 64;
+
+/// One category's rows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitPart {
+    /// The category's cell text (blank cells group under `""`).
+    pub value: String,
+    pub xs: Vec<f64>,
+    pub ys: Vec<f64>,
+}
+
+/// Group the finite `(xs[i], ys[i])` pairs by `category`'s cell text, in
+/// order of first appearance. Errs when the column has more than
+/// [`MAX_SPLIT_CATEGORIES`] values.
+pub fn split_by_category(category: &Column, xs: &[f64], ys: &[f64]) -> Result<Vec<SplitPart>, String> {
+    let mut parts: Vec<SplitPart> = Vec::new();
+    for (row, (&x, &y)) in xs.iter().zip(ys).enumerate() {
+        if !(x.is_finite() && y.is_finite()) {
+            continue;
+        }
+        let value = category.cell(row);
+        let i = match parts.iter().position(|p| p.value == value.as_ref()) {
+            Some(i) => i,
+            None => {
+                if parts.len() == MAX_SPLIT_CATEGORIES {
+                    return Err(format!(
+                        "split column has more than {MAX_SPLIT_CATEGORIES} distinct values; pick a category column"
+                    ));
+                }
+                parts.push(SplitPart { value: value.into_owned(), xs: vec![], ys: vec![] });
+                parts.len() - 1
+            }
+        };
+        parts[i].xs.push(x);
+        parts[i].ys.push(y);
+    }
+    Ok(parts)
+}
+
+/// Series name for `y_name` restricted to `by = value`, e.g.
+/// `Pressure (TestPhase=Ramp) [kPa]`. A trailing `[unit]` stays last so the
+/// unit is still inferred.
+pub fn split_name(y_name: &str, by: &str, value: &str) -> String {
+    let value = if value.is_empty() { "(blank)" } else { value };
+    let tag = format!("({by}={value})");
+    match bracketed_unit(y_name) {
+        Some(_) => {
+            let body = y_name.trim_end();
+            let open = body.rfind('[').unwrap_or(body.len());
+            format!("{} {tag} {}", body[..open].trim_end(), &body[open..])
+        }
+        None => format!("{} {tag}", y_name.trim_end()),
+    }
+}
+
+/// Series name of a pivoted channel: the channel's own name.
+pub fn pivot_name(value: &str) -> String {
+    if value.trim().is_empty() { "(blank)".to_string() } else { value.trim().to_string() }
+}
+
+/// Columns of a long-format table: `key_col` names each row's channel and
+/// `value_col` holds its reading.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LongFormat {
+    pub key_col: usize,
+    pub value_col: usize,
+}
+
+/// Guess whether a table is long format: a text column with between 2 and
+/// [`MAX_SPLIT_CATEGORIES`] distinct names, each repeated (fewer names than
+/// half the rows), next to a numeric value column. The first such text
+/// column is the key and the first numeric column after it the value.
+pub fn detect_long_format(columns: &[Arc<Column>]) -> Option<LongFormat> {
+    let key_col = columns.iter().position(|c| match &**c {
+        Column::Text(cells) => {
+            let mut names: Vec<&str> = Vec::new();
+            for cell in cells.iter().map(|c| c.trim()).filter(|c| !c.is_empty()) {
+                if !names.contains(&cell) {
+                    if names.len() == MAX_SPLIT_CATEGORIES {
+                        return false;
+                    }
+                    names.push(cell);
+                }
+            }
+            names.len() >= 2 && names.len() * 2 <= cells.len()
+        }
+        _ => false,
+    })?;
+    let value_col = (key_col + 1..columns.len())
+        .chain(0..key_col)
+        .find(|&i| columns[i].kind() == "numeric")?;
+    Some(LongFormat { key_col, value_col })
+}
+
+#[cfg(test)]
+mod split_tests {
+    use super::*;
+
+    fn phases(v: &[&str]) -> Column {
+        Column::from_strings(v.iter().map(|s| s.to_string()).collect())
+    }
+
+    #[test]
+    fn rows_group_by_category_in_first_seen_order() {
+        let cat = phases(&["ramp", "hold", "ramp", "hold", "vent"]);
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = [10.0, 20.0, f64::NAN, 40.0, 50.0];
+        let parts = split_by_category(&cat, &xs, &ys).unwrap();
+        let values: Vec<&str> = parts.iter().map(|p| p.value.as_str()).collect();
+        assert_eq!(values, ["ramp", "hold", "vent"]);
+        assert_eq!(parts[0].xs, vec![0.0]);
+        assert_eq!(parts[1].ys, vec![20.0, 40.0]);
+    }
+
+    #[test]
+    fn too_many_values_is_an_error() {
+        let cells: Vec<String> = (0..=MAX_SPLIT_CATEGORIES).map(|i| format!("c{i}")).collect();
+        let n = cells.len();
+        let cat = Column::from_strings(cells);
+        let xs: Vec<f64> = (0..n).map(|i| i as f64).collect();
+        assert!(split_by_category(&cat, &xs, &xs).is_err());
+    }
+
+    #[test]
+    fn long_tables_are_detected_by_a_repeated_name_column() {
+        let time = Column::numeric(vec![0.0, 0.0, 1.0, 1.0]);
+        let channel = phases(&["RPM", "Oil [\u{00B0}C]", "RPM", "Oil [\u{00B0}C]"]);
+        let value = Column::numeric(vec![800.0, 90.0, 820.0, 91.0]);
+        let cols = [time.clone(), channel, value.clone()].map(Arc::new);
+        assert_eq!(detect_long_format(&cols), Some(LongFormat { key_col: 1, value_col: 2 }));
+        // A text column of one-off notes isn't a channel name.
+        let notes = phases(&["a", "b", "c", "d"]);
+        assert_eq!(detect_long_format(&[time, notes, value].map(Arc::new)), None);
+        assert_eq!(pivot_name(" RPM "), "RPM");
+    }
+
+    #[test]
+    fn names_keep_the_unit_last() {
+        assert_eq!(split_name("Pressure [kPa]", "TestPhase", "Ramp"), "Pressure (TestPhase=Ramp) [kPa]");
+        assert_eq!(split_name("Speed", "Gear", ""), "Speed (Gear=(blank))");
+    }
+}
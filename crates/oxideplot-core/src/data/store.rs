@@ -0,0 +1,187 @@
+//! Shared storage for data displayed by several graphs at once.
+//!
+//! Each graph owns its own [`LoadedData`], so loading the same file into three
+//! graphs used to hold three copies of every column. [`SharedStore`] interns
+//! values by key (a file fingerprint plus column index, say) and hands out
+//! `Arc`s, so graphs showing the same data point at one allocation. The store
+//! only keeps `Weak` handles: a value is freed as soon as the last graph drops
+//! it, and its dead entry is pruned later.
+
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Weak};
+
+use crate::data::column::Column;
+use crate::data::loader::LoadedData;
+
+/// Interning map from `K` to reference-counted `V`, holding values weakly.
+pub struct SharedStore<K, V: ?Sized> {
+    entries: HashMap<K, Weak<V>>,
+}
+
+impl<K, V: ?Sized> Default for SharedStore<K, V> {
+    fn default() -> Self {
+        Self { entries: HashMap::new() }
+    }
+}
+
+impl<K: Hash + Eq, V: ?Sized> SharedStore<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The live value for `key`, if some holder still references it.
+    pub fn get(&self, key: &K) -> Option<Arc<V>> {
+        self.entries.get(key).and_then(Weak::upgrade)
+    }
+
+    /// Return the live value already stored for `key`, or store and return
+    /// `value`. Callers should replace their copy with the returned `Arc`.
+    pub fn intern(&mut self, key: K, value: Arc<V>) -> Arc<V> {
+        if let Some(existing) = self.get(&key) {
+            return existing;
+        }
+        self.entries.insert(key, Arc::downgrade(&value));
+        value
+    }
+
+    /// Drop entries whose value has been freed.
+    pub fn prune(&mut self) {
+        self.entries.retain(|_, w| w.strong_count() > 0);
+    }
+
+    /// Number of entries whose value is still alive.
+    pub fn live_count(&self) -> usize {
+        self.entries.values().filter(|w| w.strong_count() > 0).count()
+    }
+}
+
+/// Columns keyed by (file fingerprint, column index).
+pub type ColumnStore = SharedStore<(u64, usize), Column>;
+
+/// Identify a file's contents for sharing: identical name and bytes give the
+/// same fingerprint, so re-loading a file in another graph finds its columns.
+pub fn fingerprint(filename: &str, bytes: &[u8]) -> u64 {
+    let mut h = std::collections::hash_map::DefaultHasher::new();
+    filename.hash(&mut h);
+    bytes.hash(&mut h);
+    h.finish()
+}
+
+/// Swap the listed file columns of `data` for the store's shared copies
+/// (interning any not yet stored). Deferred columns are skipped — share them
+/// once materialized. Only pass columns read from the file, never derived
+/// ones: derived columns aren't identified by their index.
+pub fn share_columns(store: &mut ColumnStore, file: u64, data: &mut LoadedData, cols: &[usize]) {
+    store.prune();
+    for &c in cols {
+        let Some(col) = data.column_data.get(c) else { continue };
+        if matches!(**col, Column::Deferred { .. }) {
+            continue;
+        }
+        data.column_data[c] = store.intern((file, c), Arc::clone(col));
+    }
+}
+
+/// A plotted series' X/Y arrays from two columns, in row order, with gaps
+/// kept as in [`gapped_pairs`]. When X is all finite and Y has no infinities
+/// or breaks at its ends (the usual case) these are the columns' own
+/// allocations, so plotting a column costs no copy.
+pub fn series_arrays(x: &Column, y: &Column) -> (Arc<[f64]>, Arc<[f64]>) {
+    if let (Some(xs), Some(ys)) = (x.shared_values(), y.shared_values()) {
+        let ends_finite = ys.first().is_some_and(|v| v.is_finite()) && ys.last().is_some_and(|v| v.is_finite());
+        if xs.len() == ys.len() && ends_finite && xs.iter().all(|v| v.is_finite()) && !ys.iter().any(|v| v.is_infinite()) {
+            return (xs, ys);
+        }
+    }
+    let (xs, ys) = gapped_pairs(&x.to_f64(), &y.to_f64());
+    (xs.into(), ys.into())
+}
+
+/// Row-ordered X/Y pairs with gaps kept. Rows without a finite X are dropped
+/// (X has to stay sortable); each run of rows with a missing or infinite Y
+/// becomes one NaN, which the renderer draws as a line break, so missing
+/// data shows as a gap instead of a straight line across it. Breaks at
+/// either end are trimmed.
+pub fn gapped_pairs(xv: &[f64], yv: &[f64]) -> (Vec<f64>, Vec<f64>) {
+    let (mut xs, mut ys) = (Vec::with_capacity(xv.len()), Vec::with_capacity(yv.len()));
+    for (&x, &y) in xv.iter().zip(yv) {
+        if !x.is_finite() {
+            continue;
+        }
+        if y.is_finite() {
+            xs.push(x);
+            ys.push(y);
+        } else if ys.last().is_some_and(|v: &f64| v.is_finite()) {
+            xs.push(x);
+            ys.push(f64::NAN);
+        }
+    }
+    if ys.last().is_some_and(|v| v.is_nan()) {
+        xs.pop();
+        ys.pop();
+    }
+    (xs, ys)
+}
+
+#[cfg(test)]
+mod store_tests {
+    use super::*;
+    use crate::data::loader::load_from_bytes;
+
+    #[test]
+    fn same_file_in_two_graphs_shares_columns() {
+        let csv = b"t,v\n0,1.5\n1,2.5\n";
+        let key = fingerprint("a.csv", csv);
+        assert_eq!(key, fingerprint("a.csv", csv));
+        assert_ne!(key, fingerprint("b.csv", csv));
+
+        let mut store = ColumnStore::new();
+        let mut g1 = load_from_bytes(csv, "a.csv").unwrap();
+        let mut g2 = load_from_bytes(csv, "a.csv").unwrap();
+        share_columns(&mut store, key, &mut g1, &[0, 1]);
+        share_columns(&mut store, key, &mut g2, &[0, 1]);
+        assert!(Arc::ptr_eq(&g1.column_data[1], &g2.column_data[1]));
+        assert_eq!(store.live_count(), 2);
+    }
+
+    #[test]
+    fn values_are_freed_with_their_last_holder() {
+        let mut store: SharedStore<u32, [f64]> = SharedStore::new();
+        let a = store.intern(7, Arc::from(vec![1.0, 2.0]));
+        let b = store.intern(7, Arc::from(vec![9.0]));
+        assert!(Arc::ptr_eq(&a, &b), "second intern returns the live value");
+        drop((a, b));
+        assert!(store.get(&7).is_none());
+        assert_eq!(store.live_count(), 0);
+        store.prune();
+        let c = store.intern(7, Arc::from(vec![3.0]));
+        assert_eq!(c[0], 3.0);
+    }
+
+    #[test]
+    fn finite_columns_plot_without_copying() {
+        let (x, y) = (Column::numeric(vec![0.0, 1.0, 2.0]), Column::numeric(vec![5.0, 6.0, 7.0]));
+        let (xs, ys) = series_arrays(&x, &y);
+        assert!(Arc::ptr_eq(&xs, &x.shared_values().unwrap()));
+        assert!(Arc::ptr_eq(&ys, &y.shared_values().unwrap()));
+
+        // A missing Y mid-series is a gap, still shared.
+        let gappy = Column::numeric(vec![5.0, f64::NAN, 7.0]);
+        let (xs, ys) = series_arrays(&x, &gappy);
+        assert!(Arc::ptr_eq(&ys, &gappy.shared_values().unwrap()));
+        assert_eq!(xs.len(), 3);
+    }
+
+    #[test]
+    fn missing_values_become_single_breaks() {
+        let nan = f64::NAN;
+        let xv = [0.0, 1.0, 2.0, 3.0, nan, 5.0, 6.0, 7.0, 8.0];
+        let yv = [nan, 1.0, nan, f64::INFINITY, 4.0, nan, 6.0, 7.0, nan];
+        let (xs, ys) = gapped_pairs(&xv, &yv);
+        assert_eq!(xs, [1.0, 2.0, 6.0, 7.0]);
+        assert_eq!(ys[0], 1.0);
+        assert!(ys[1].is_nan());
+        assert_eq!(&ys[2..], [6.0, 7.0]);
+    }
+}
@@ -0,0 +1,326 @@
+//! Live data from line-oriented streams (serial ports, sockets).
+//!
+//! Devices print one sample per line: numeric fields separated by a
+//! delimiter, e.g. `12.5,3.3,0.91`. Chunks arrive split at arbitrary points,
+//! so [`LineStream`] keeps the unfinished tail until its newline arrives.
+//! A line with no numeric fields just before the first sample, and with as
+//! many fields, names the channels; otherwise they are `ch1`, `ch2`, … Each
+//! channel is a [`DataSeries`] whose X is the configured column, or the
+//! sample index.
+//!
+//! A line that is a JSON object (`{"t": 1.5, "temp": 21.3}`) is a sample of
+//! its numeric members, each feeding the channel of its key; `x_column`
+//! then counts members in order.
+//!
+//! Lines without any number (boot banners, debug prints) are skipped, as are
+//! non-numeric fields of a sample.
+
+use crate::data::unit_inference::infer_unit;
+use crate::state::data_series::{color_for_index, DataSeries};
+
+/// Samples kept per channel by default; older ones are dropped.
+pub const DEFAULT_MAX_POINTS: usize = 1_000_000;
+
+/// How the fields of a line are separated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Runs of spaces/tabs, and commas or semicolons (`auto`).
+    Auto,
+    Char(char),
+}
+
+impl Delimiter {
+    /// `"auto"`, `"comma"`, `"semicolon"`, `"tab"`, `"space"`, or a single
+    /// character.
+    pub fn parse(s: &str) -> Option<Delimiter> {
+        Some(match s {
+            "auto" | "" => Delimiter::Auto,
+            "comma" => Delimiter::Char(','),
+            "semicolon" => Delimiter::Char(';'),
+            "tab" => Delimiter::Char('\t'),
+            "space" => Delimiter::Char(' '),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Delimiter::Char(c),
+                    _ => return None,
+                }
+            }
+        })
+    }
+
+    fn split(self, line: &str) -> Vec<&str> {
+        match self {
+            Delimiter::Auto => line
+                .split(|c: char| c.is_whitespace() || c == ',' || c == ';')
+                .filter(|f| !f.is_empty())
+                .collect(),
+            Delimiter::Char(' ') => line.split_whitespace().collect(),
+            Delimiter::Char(c) => line.split(c).map(str::trim).collect(),
+        }
+    }
+}
+
+/// Options for a [`LineStream`].
+#[derive(Debug, Clone)]
+pub struct StreamConfig {
+    pub delimiter: Delimiter,
+    /// Field holding each sample's X; `None` uses the sample index.
+    pub x_column: Option<usize>,
+    /// Samples kept per channel.
+    pub max_points: usize,
+}
+
+impl Default for StreamConfig {
+    fn default() -> Self {
+        StreamConfig { delimiter: Delimiter::Auto, x_column: None, max_points: DEFAULT_MAX_POINTS }
+    }
+}
+
+/// Incremental parser turning text chunks into per-channel series.
+pub struct LineStream {
+    config: StreamConfig,
+    /// Text after the last newline, completed by the next chunk.
+    pending: String,
+    /// Last non-numeric line before the first sample (header candidate).
+    header: Vec<String>,
+    /// Channel names from the header (excluding the X field).
+    names: Vec<String>,
+    x_name: String,
+    channels: Vec<DataSeries>,
+    /// Samples appended so far (the X of index-based streams).
+    samples: u64,
+}
+
+impl LineStream {
+    pub fn new(config: StreamConfig) -> Self {
+        let x_name = match config.x_column {
+            Some(i) => format!("col{}", i + 1),
+            None => "sample".to_string(),
+        };
+        LineStream {
+            config,
+            pending: String::new(),
+            header: Vec::new(),
+            names: Vec::new(),
+            x_name,
+            channels: Vec::new(),
+            samples: 0,
+        }
+    }
+
+    /// Feed a chunk of text; returns the number of samples appended.
+    pub fn push(&mut self, chunk: &str) -> usize {
+        self.pending.push_str(chunk);
+        let Some(end) = self.pending.rfind('\n') else {
+            return 0;
+        };
+        let complete: String = self.pending.drain(..=end).collect();
+        let appended = complete.lines().filter(|line| self.push_line(line)).count();
+        if appended > 0 {
+            self.trim();
+        }
+        appended
+    }
+
+    /// Channel series, in field order.
+    pub fn channels(&self) -> &[DataSeries] {
+        &self.channels
+    }
+
+    /// Name of the X values: the header's X field, `colN`, or `sample`.
+    pub fn x_name(&self) -> &str {
+        &self.x_name
+    }
+
+    /// Samples appended since the stream started.
+    pub fn samples(&self) -> u64 {
+        self.samples
+    }
+
+    fn push_line(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        if line.starts_with('{') {
+            return self.push_json(line);
+        }
+        let fields = self.config.delimiter.split(line);
+        let values: Vec<Option<f64>> = fields.iter().map(|f| f.trim().parse::<f64>().ok()).collect();
+        if values.iter().all(Option::is_none) {
+            if self.samples == 0 {
+                self.header = fields.iter().map(|f| f.to_string()).collect();
+            }
+            return false;
+        }
+        if self.samples == 0 && self.header.len() == fields.len() {
+            self.take_header();
+        }
+        let x_column = self.config.x_column;
+        let x = match x_column {
+            Some(i) => match values.get(i).copied().flatten() {
+                Some(x) if x.is_finite() => x,
+                _ => return false,
+            },
+            None => self.samples as f64,
+        };
+        let ys = values.iter().enumerate().filter(|&(i, _)| Some(i) != x_column).map(|(_, v)| *v);
+        for (ch, y) in ys.enumerate() {
+            let Some(y) = y.filter(|y| y.is_finite()) else {
+                continue;
+            };
+            if ch >= self.channels.len() {
+                self.add_channels(ch + 1);
+            }
+            let s = &mut self.channels[ch];
+            s.x.push(x);
+            s.y.push(y);
+            s.needs_resample = true;
+        }
+        self.samples += 1;
+        true
+    }
+
+    /// One JSON-object sample: numeric members (numbers, numeric strings,
+    /// booleans as 0/1) by key.
+    fn push_json(&mut self, line: &str) -> bool {
+        let Ok(obj) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(line) else {
+            return false;
+        };
+        let values: Vec<(&String, f64)> = obj
+            .iter()
+            .filter_map(|(k, v)| {
+                let n = match v {
+                    serde_json::Value::Number(n) => n.as_f64(),
+                    serde_json::Value::String(s) => s.trim().parse().ok(),
+                    serde_json::Value::Bool(b) => Some(*b as u8 as f64),
+                    _ => None,
+                };
+                Some((k, n?)).filter(|(_, n)| n.is_finite())
+            })
+            .collect();
+        let x_key = match self.config.x_column {
+            Some(i) => match obj.keys().nth(i) {
+                Some(key) => Some(key),
+                None => return false,
+            },
+            None => None,
+        };
+        let x = match x_key {
+            Some(key) => match values.iter().find(|(k, _)| *k == key) {
+                Some(&(_, x)) => x,
+                None => return false,
+            },
+            None => self.samples as f64,
+        };
+        if let Some(key) = x_key.filter(|k| **k != self.x_name) {
+            self.x_name = key.clone();
+        }
+        let mut any = false;
+        for &(key, y) in values.iter().filter(|(k, _)| Some(*k) != x_key) {
+            let ch = match self.channels.iter().position(|c| &c.label == key) {
+                Some(ch) => ch,
+                None => {
+                    self.add_channel(key.clone());
+                    self.channels.len() - 1
+                }
+            };
+            let s = &mut self.channels[ch];
+            s.x.push(x);
+            s.y.push(y);
+            s.needs_resample = true;
+            any = true;
+        }
+        if any {
+            self.samples += 1;
+        }
+        any
+    }
+
+    fn take_header(&mut self) {
+        for (i, f) in std::mem::take(&mut self.header).into_iter().enumerate() {
+            if Some(i) == self.config.x_column {
+                self.x_name = f;
+            } else {
+                self.names.push(f);
+            }
+        }
+    }
+
+    fn add_channels(&mut self, count: usize) {
+        for ch in self.channels.len()..count {
+            let name = self.names.get(ch).cloned().unwrap_or_else(|| format!("ch{}", ch + 1));
+            self.add_channel(name);
+        }
+    }
+
+    fn add_channel(&mut self, name: String) {
+        let unit = infer_unit(&name);
+        let color = color_for_index(self.channels.len());
+        self.channels.push(DataSeries::new(name, Vec::new(), Vec::new(), color, unit));
+    }
+
+    /// Drop the oldest samples past `max_points`, a tenth of the limit at a
+    /// time so the shift is amortised.
+    fn trim(&mut self) {
+        let max = self.config.max_points.max(1);
+        for s in &mut self.channels {
+            if s.x.len() > max {
+                let drop = (s.x.len() - max).max(max / 10).min(s.x.len());
+                s.x.drain(..drop);
+                s.y.drain(..drop);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod streaming_tests {
+    use super::*;
+
+    #[test]
+    fn chunks_split_mid_line_are_reassembled() {
+        let mut s = LineStream::new(StreamConfig::default());
+        assert_eq!(s.push("OxideLogger v2\ntemp,volts\n1.5,3."), 0);
+        assert_eq!(s.push("3\r\nbooting...\n2.5,3.4\n"), 2);
+        let ch = s.channels();
+        assert_eq!((ch[0].label.as_str(), ch[1].label.as_str()), ("temp", "volts"));
+        assert_eq!(ch[0].x, [0.0, 1.0]);
+        assert_eq!(ch[1].y, [3.3, 3.4]);
+        // A late extra field adds a channel.
+        s.push("3.5 3.5 9\n");
+        assert_eq!(s.channels()[2].label, "ch3");
+        assert_eq!(s.channels()[2].x, [2.0]);
+
+        // A banner that doesn't match the samples isn't a header.
+        let mut s = LineStream::new(StreamConfig::default());
+        s.push("ready\n1 2\n");
+        assert_eq!(s.channels()[1].label, "ch2");
+    }
+
+    #[test]
+    fn x_column_and_point_limit() {
+        let config = StreamConfig { delimiter: Delimiter::Char(';'), x_column: Some(0), max_points: 10 };
+        let mut s = LineStream::new(config);
+        s.push("t;a\n");
+        let text: String = (0..25).map(|i| format!("{};{}\n", i as f64 * 0.5, i)).collect();
+        assert_eq!(s.push(&text), 25);
+        assert_eq!(s.x_name(), "t");
+        let a = &s.channels()[0];
+        assert!(a.x.len() <= 10);
+        assert_eq!((*a.x.last().unwrap(), *a.y.last().unwrap()), (12.0, 24.0));
+        assert_eq!(Delimiter::parse("tab"), Some(Delimiter::Char('\t')));
+        assert_eq!(Delimiter::parse("ab"), None);
+    }
+
+    #[test]
+    fn json_lines_feed_channels_by_key() {
+        let config = StreamConfig { x_column: Some(0), ..StreamConfig::default() };
+        let mut s = LineStream::new(config);
+        let text = "{\"t\": 0.5, \"temp\": 21.0, \"state\": \"on\"}\n{\"t\": 1.0, \"rpm\": \"900\", \"temp\": 21.5}\n";
+        assert_eq!(s.push(text), 2);
+        let ch = s.channels();
+        assert_eq!(s.x_name(), "t");
+        assert_eq!((ch[0].label.as_str(), ch[0].x.as_slice(), ch[0].y.as_slice()), ("temp", &[0.5, 1.0][..], &[21.0, 21.5][..]));
+        assert_eq!((ch[1].label.as_str(), ch[1].y.as_slice()), ("rpm", &[900.0][..]));
+        assert_eq!(s.push("{not json\n"), 0);
+    }
+}
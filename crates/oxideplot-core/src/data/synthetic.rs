@@ -0,0 +1,123 @@
+//! Synthetic datasets for benchmarking.
+//!
+//! Performance work needs the same data every run, at sizes no sample file
+//! has. [`synthetic`] generates one series of a [`SyntheticKind`] sampled at
+//! 1 kHz, deterministically from a seed, up to [`MAX_SYNTHETIC_POINTS`].
+
+/// Largest synthetic dataset, in points over all series.
+pub const MAX_SYNTHETIC_POINTS: usize = 100_000_000;
+
+/// Sample spacing of synthetic X (seconds): 1 kHz.
+pub const SYNTHETIC_DT: f64 = 1e-3;
+
+/// Shape of a synthetic series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntheticKind {
+    /// Unit sine whose frequency rises linearly from 0.1 Hz to a quarter of
+    /// the sample rate: every zoom level has detail.
+    SineSweep,
+    /// Gaussian white noise: the worst case for min/max decimation.
+    Noise,
+    /// Random levels held for 500 samples: long flat runs and sharp edges.
+    StepTrain,
+}
+
+impl SyntheticKind {
+    /// Parse `"sine-sweep"`, `"noise"` or `"step-train"`.
+    pub fn parse(name: &str) -> Option<SyntheticKind> {
+        Some(match name {
+            "sine-sweep" => SyntheticKind::SineSweep,
+            "noise" => SyntheticKind::Noise,
+            "step-train" => SyntheticKind::StepTrain,
+            _ => return None,
+        })
+    }
+
+    /// Series name stem (`"Sine sweep"`).
+    pub fn label(self) -> &'static str {
+        match self {
+            SyntheticKind::SineSweep => "Sine sweep",
+            SyntheticKind::Noise => "Noise",
+            SyntheticKind::StepTrain => "Step train",
+        }
+    }
+}
+
+/// SplitMix64: tiny, fast and good enough for test signals.
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in (0, 1].
+    fn unit(&mut self) -> f64 {
+        ((self.next_u64() >> 11) + 1) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Synthetic X: `points` samples [`SYNTHETIC_DT`] apart from zero.
+pub fn synthetic_xs(points: usize) -> Vec<f64> {
+    (0..points).map(|i| i as f64 * SYNTHETIC_DT).collect()
+}
+
+/// `points` Y values of `kind`. The same `seed` gives the same values.
+pub fn synthetic(kind: SyntheticKind, points: usize, seed: u64) -> Vec<f64> {
+    let mut rng = Rng(seed);
+    match kind {
+        SyntheticKind::SineSweep => {
+            let (f0, f1) = (0.1, 0.25 / SYNTHETIC_DT);
+            let span = (points.max(1) as f64) * SYNTHETIC_DT;
+            let phase0 = rng.unit() * std::f64::consts::TAU;
+            (0..points)
+                .map(|i| {
+                    let t = i as f64 * SYNTHETIC_DT;
+                    (phase0 + std::f64::consts::TAU * (f0 * t + (f1 - f0) * t * t / (2.0 * span))).sin()
+                })
+                .collect()
+        }
+        SyntheticKind::Noise => (0..points)
+            .map(|_| {
+                // Box-Muller.
+                let (u, v) = (rng.unit(), rng.unit());
+                (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+            })
+            .collect(),
+        SyntheticKind::StepTrain => {
+            let mut level = 0.0;
+            (0..points)
+                .map(|i| {
+                    if i % 500 == 0 {
+                        level = (rng.unit() * 10.0).floor();
+                    }
+                    level
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod synthetic_tests {
+    use super::*;
+
+    #[test]
+    fn generators_are_deterministic_and_bounded() {
+        for kind in [SyntheticKind::SineSweep, SyntheticKind::Noise, SyntheticKind::StepTrain] {
+            let a = synthetic(kind, 10_000, 7);
+            assert_eq!(a.len(), 10_000);
+            assert_eq!(a, synthetic(kind, 10_000, 7), "{kind:?}");
+            assert!(a.iter().all(|v| v.is_finite()));
+        }
+        assert!(synthetic(SyntheticKind::SineSweep, 1000, 1).iter().all(|v| v.abs() <= 1.0));
+        let steps = synthetic(SyntheticKind::StepTrain, 1000, 3);
+        assert!(steps[..500].iter().all(|&v| v == steps[0]));
+        assert_eq!(synthetic_xs(3), vec![0.0, 0.001, 0.002]);
+        assert_eq!(SyntheticKind::parse("noise"), Some(SyntheticKind::Noise));
+    }
+}
@@ -1,8 +1,11 @@
 //! Read-only table view logic over `LoadedData`: filtering (global search +
 //! per-column) and sorting (numeric-aware) into a view-order row index, plus
-//! windowed row extraction. Pure + native-testable; the wasm layer wraps this.
+//! windowed row extraction and [`Decimation`] for files too long to scroll.
+//! Pure + native-testable; the wasm layer wraps this.
 
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::ops::Range;
 use crate::data::loader::LoadedData;
 
 #[derive(Clone, Debug)]
@@ -26,12 +29,17 @@ pub struct TableQuery {
 }
 
 #[inline]
-fn cell<'a>(data: &'a LoadedData, col: usize, row: usize) -> &'a str {
+fn cell(data: &LoadedData, col: usize, row: usize) -> Cow<'_, str> {
     data.column_data
         .get(col)
-        .and_then(|c| c.get(row))
-        .map(|s| s.as_str())
-        .unwrap_or("")
+        .map(|c| c.cell(row))
+        .unwrap_or(Cow::Borrowed(""))
+}
+
+/// Typed numeric value of a cell (NaN when missing/unparseable).
+#[inline]
+fn value(data: &LoadedData, col: usize, row: usize) -> f64 {
+    data.column_data.get(col).map(|c| c.value(row)).unwrap_or(f64::NAN)
 }
 
 fn is_numeric(q: &TableQuery, col: usize) -> bool {
@@ -50,15 +58,14 @@ fn row_passes(data: &LoadedData, q: &TableQuery, row: usize) -> bool {
     }
     // Per-column filters (all must pass).
     for (&col, filt) in &q.col_filters {
-        let val = cell(data, col, row);
         let ok = match filt {
             ColFilter::Text(s) => {
-                s.is_empty() || val.to_lowercase().contains(&s.to_lowercase())
+                s.is_empty() || cell(data, col, row).to_lowercase().contains(&s.to_lowercase())
+            }
+            ColFilter::Range { min, max } => {
+                let v = value(data, col, row);
+                !v.is_nan() && min.is_none_or(|lo| v >= lo) && max.is_none_or(|hi| v <= hi)
             }
-            ColFilter::Range { min, max } => match val.trim().parse::<f64>() {
-                Ok(v) => min.map_or(true, |lo| v >= lo) && max.map_or(true, |hi| v <= hi),
-                Err(_) => false,
-            },
         };
         if !ok {
             return false;
@@ -73,9 +80,8 @@ pub fn compute_view_index(data: &LoadedData, q: &TableQuery) -> Vec<usize> {
 
     if let Some((col, ascending)) = q.sort {
         if is_numeric(q, col) {
-            // Numeric: parse to f64; unparseable/non-finite sort to the end.
-            let key = |r: &usize| cell(data, col, *r).trim().parse::<f64>().ok()
-                .filter(|v| v.is_finite());
+            // Numeric: typed f64; unparseable/non-finite sort to the end.
+            let key = |r: &usize| Some(value(data, col, *r)).filter(|v| v.is_finite());
             idx.sort_by(|a, b| {
                 let (ka, kb) = (key(a), key(b));
                 let ord = match (ka, kb) {
@@ -88,7 +94,7 @@ pub fn compute_view_index(data: &LoadedData, q: &TableQuery) -> Vec<usize> {
             });
         } else {
             idx.sort_by(|a, b| {
-                let ord = cell(data, col, *a).cmp(cell(data, col, *b));
+                let ord = cell(data, col, *a).cmp(&cell(data, col, *b));
                 if ascending { ord } else { ord.reverse() }
             });
         }
@@ -120,10 +126,184 @@ pub fn window_rows(
     };
     view_index[start.min(view_index.len())..end]
         .iter()
-        .map(|&r| cols.iter().map(|&c| cell(data, c, r).to_string()).collect())
+        .map(|&r| cols.iter().map(|&c| cell(data, c, r).into_owned()).collect())
         .collect()
 }
 
+/// How many raw rows stand behind each table row. Applied after filtering and
+/// sorting, to the view index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Decimation {
+    /// Every row.
+    #[default]
+    Off,
+    /// The first of every `n` rows.
+    EveryNth(usize),
+    /// One row per `n` consecutive rows (a time bucket when the view is in
+    /// time order): the first column's first cell, then min / mean / max of
+    /// every other numeric column.
+    Buckets(usize),
+}
+
+impl Decimation {
+    /// `mode` is `"off"`, `"nth"` or `"buckets"`; `n` below 2 means off.
+    pub fn parse(mode: &str, n: usize) -> Option<Self> {
+        match mode {
+            "nth" if n >= 2 => Some(Decimation::EveryNth(n)),
+            "buckets" if n >= 2 => Some(Decimation::Buckets(n)),
+            "off" | "nth" | "buckets" => Some(Decimation::Off),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Decimation::Off => "off",
+            Decimation::EveryNth(_) => "nth",
+            Decimation::Buckets(_) => "buckets",
+        }
+    }
+
+    /// Raw rows per table row.
+    pub fn step(self) -> usize {
+        match self {
+            Decimation::Off => 1,
+            Decimation::EveryNth(n) | Decimation::Buckets(n) => n.max(1),
+        }
+    }
+
+    /// Table rows for a view of `view_len` raw rows.
+    pub fn row_count(self, view_len: usize) -> usize {
+        view_len.div_ceil(self.step())
+    }
+
+    /// Positions in the view index behind table rows `first..=last`, for
+    /// drilling down to the raw rows.
+    pub fn raw_span(self, first: usize, last: usize, view_len: usize) -> Range<usize> {
+        let (first, last) = (first.min(last), first.max(last));
+        let step = self.step();
+        (first * step).min(view_len)..((last + 1) * step).min(view_len)
+    }
+}
+
+/// Column headers under `dec`, as `(name, numeric)`. Bucket mode splits each
+/// numeric column after the first into `name (min)`, `name (mean)`,
+/// `name (max)`; other modes keep the columns as they are.
+pub fn decimated_columns(columns: &[(String, bool)], dec: Decimation) -> Vec<(String, bool)> {
+    if !matches!(dec, Decimation::Buckets(_)) {
+        return columns.to_vec();
+    }
+    let mut out = Vec::with_capacity(columns.len() * 3);
+    for (j, (name, numeric)) in columns.iter().enumerate() {
+        if j > 0 && *numeric {
+            for agg in ["min", "mean", "max"] {
+                out.push((format!("{name} ({agg})"), true));
+            }
+        } else {
+            out.push((name.clone(), *numeric));
+        }
+    }
+    out
+}
+
+/// An aggregate to about seven significant digits, blank when not finite.
+fn short(v: f64) -> String {
+    if !v.is_finite() {
+        return String::new();
+    }
+    format!("{v:.6e}").parse::<f64>().map(|r| r.to_string()).unwrap_or_default()
+}
+
+/// [`window_rows`] under `dec`: `count` table rows from table row `start`,
+/// with [`decimated_columns`]' layout for `cols`.
+pub fn decimated_window(
+    data: &LoadedData,
+    q: &TableQuery,
+    view_index: &[usize],
+    dec: Decimation,
+    start: usize,
+    count: usize,
+    cols: &[usize],
+) -> Vec<Vec<String>> {
+    let end = start.saturating_add(count).min(dec.row_count(view_index.len()));
+    let start = start.min(end);
+    match dec {
+        Decimation::Off => window_rows(data, view_index, start, count, Some(cols)),
+        Decimation::EveryNth(n) => {
+            let picked: Vec<usize> = (start..end).map(|i| view_index[i * n]).collect();
+            window_rows(data, &picked, 0, picked.len(), Some(cols))
+        }
+        Decimation::Buckets(_) => (start..end)
+            .map(|i| {
+                let rows = &view_index[dec.raw_span(i, i, view_index.len())];
+                let mut out = Vec::with_capacity(cols.len() * 3);
+                for (j, &c) in cols.iter().enumerate() {
+                    if j > 0 && is_numeric(q, c) {
+                        let vals: Vec<f64> =
+                            rows.iter().map(|&r| value(data, c, r)).filter(|v| v.is_finite()).collect();
+                        let (min, max) = vals
+                            .iter()
+                            .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+                        let mean = vals.iter().sum::<f64>() / vals.len() as f64;
+                        out.extend([short(min), short(mean), short(max)]);
+                    } else {
+                        out.push(cell(data, c, rows[0]).into_owned());
+                    }
+                }
+                out
+            })
+            .collect(),
+    }
+}
+
+/// How to search the view for an X under `dec`: `(positions, step, div)` —
+/// position `t` is view row `t * step` and falls in table row `t / div`.
+/// Buckets search every raw row, since any row of a bucket is in it.
+fn x_search_layout(dec: Decimation, view_len: usize) -> (usize, usize, usize) {
+    match dec {
+        Decimation::Buckets(n) => (view_len, 1, n.max(1)),
+        _ => (dec.row_count(view_len), dec.step(), 1),
+    }
+}
+
+/// The table row under `dec` whose X (`xs`, indexed by file row) is nearest
+/// `x`, for syncing a plot hover to the table. `ordered`: the view runs in
+/// ascending X (unsorted file in time order, or sorted by X), so a binary
+/// search will do; otherwise every row is checked.
+pub fn nearest_table_row(xs: &[f64], view_index: &[usize], dec: Decimation, x: f64, ordered: bool) -> Option<usize> {
+    if !x.is_finite() {
+        return None;
+    }
+    let (rows, step, div) = x_search_layout(dec, view_index.len());
+    let at = |t: usize| xs.get(view_index[t * step]).copied().filter(|v| v.is_finite());
+    let dist = |t: usize| at(t).map(|v| (t, (v - x).abs()));
+    let best = if ordered {
+        // First row at or past `x`, judging a missing value by the next
+        // finite one; then the nearest finite rows either side.
+        let (mut lo, mut hi) = (0, rows);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            match (mid..hi).find_map(|t| at(t).map(|v| (t, v))) {
+                Some((t, v)) if v < x => lo = t + 1,
+                _ => hi = mid,
+            }
+        }
+        let before = (0..lo).rev().find_map(dist);
+        let after = (lo..rows).find_map(dist);
+        before.into_iter().chain(after).min_by(|a, b| a.1.total_cmp(&b.1))
+    } else {
+        (0..rows).filter_map(dist).min_by(|a, b| a.1.total_cmp(&b.1))
+    };
+    best.map(|(t, _)| t / div)
+}
+
+/// X of table row `row` under `dec` (a bucket's first row), or `None`
+/// past the end or on a missing value.
+pub fn table_row_x(xs: &[f64], view_index: &[usize], dec: Decimation, row: usize) -> Option<f64> {
+    let pos = row.checked_mul(dec.step())?;
+    view_index.get(pos).and_then(|&r| xs.get(r)).copied().filter(|v| v.is_finite())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -131,14 +311,14 @@ mod tests {
     use std::collections::HashMap;
 
     fn fixture() -> LoadedData {
-        LoadedData {
-            columns: vec!["n".into(), "name".into()],
-            column_data: vec![
+        LoadedData::from_string_columns(
+            vec!["n".into(), "name".into()],
+            vec![
                 vec!["2".into(), "10".into(), "1".into()],          // col 0: numeric
                 vec!["bob".into(), "alice".into(), "carol".into()], // col 1: text
             ],
-            row_count: 3,
-        }
+            3,
+        )
     }
     fn q() -> TableQuery { TableQuery { numeric_cols: vec![true, false], ..Default::default() } }
 
@@ -209,6 +389,63 @@ mod tests {
         ]);
     }
     #[test]
+    fn every_nth_keeps_the_first_of_each_group() {
+        let d = fixture();
+        let dec = Decimation::EveryNth(2);
+        assert_eq!(dec.row_count(3), 2);
+        let rows = decimated_window(&d, &q(), &[0, 1, 2], dec, 0, 10, &[1]);
+        assert_eq!(rows, vec![vec!["bob".to_string()], vec!["carol".to_string()]]);
+    }
+    #[test]
+    fn buckets_aggregate_numeric_columns() {
+        let d = LoadedData::from_string_columns(
+            vec!["t".into(), "v".into()],
+            vec![
+                vec!["0".into(), "1".into(), "2".into(), "3".into(), "4".into()],
+                vec!["4".into(), "8".into(), "6".into(), "1".into(), "".into()],
+            ],
+            5,
+        );
+        let query = TableQuery { numeric_cols: vec![true, true], ..Default::default() };
+        let dec = Decimation::Buckets(3);
+        let cols = decimated_columns(&[("t".into(), true), ("v".into(), true)], dec);
+        assert_eq!(cols[1].0, "v (min)");
+        assert_eq!(cols.len(), 4);
+        let rows = decimated_window(&d, &query, &[0, 1, 2, 3, 4], dec, 0, 10, &[0, 1]);
+        assert_eq!(rows, vec![
+            vec!["0".to_string(), "4".into(), "6".into(), "8".into()],
+            vec!["3".to_string(), "1".into(), "1".into(), "1".into()],
+        ]);
+    }
+    #[test]
+    fn drill_down_span_covers_the_selected_rows() {
+        let dec = Decimation::Buckets(10);
+        assert_eq!(dec.raw_span(3, 1, 35), 10..35);
+        assert_eq!(dec.raw_span(0, 0, 35), 0..10);
+        assert_eq!(Decimation::parse("nth", 1), Some(Decimation::Off));
+        assert_eq!(Decimation::parse("rows", 5), None);
+    }
+    #[test]
+    fn plot_x_finds_the_nearest_table_row() {
+        let xs = [0.0, 1.0, 2.0, f64::NAN, 4.0, 5.0];
+        let view: Vec<usize> = (0..6).collect();
+        for ordered in [true, false] {
+            assert_eq!(nearest_table_row(&xs, &view, Decimation::Off, 1.2, ordered), Some(1));
+            assert_eq!(nearest_table_row(&xs, &view, Decimation::Off, 3.4, ordered), Some(4));
+            assert_eq!(nearest_table_row(&xs, &view, Decimation::Off, 99.0, ordered), Some(5));
+        }
+        // Every 2nd row: table rows are file rows 0, 2, 4.
+        assert_eq!(nearest_table_row(&xs, &view, Decimation::EveryNth(2), 3.9, true), Some(2));
+        // Buckets of 4: row 5 is in the second bucket.
+        assert_eq!(nearest_table_row(&xs, &view, Decimation::Buckets(4), 5.0, true), Some(1));
+        // A sorted-descending view is scanned.
+        let desc: Vec<usize> = (0..6).rev().collect();
+        assert_eq!(nearest_table_row(&xs, &desc, Decimation::Off, 0.9, false), Some(4));
+        assert_eq!(table_row_x(&xs, &desc, Decimation::Off, 1), Some(4.0));
+        assert_eq!(table_row_x(&xs, &view, Decimation::Buckets(4), 1), Some(4.0));
+        assert_eq!(table_row_x(&xs, &view, Decimation::Off, 3), None);
+    }
+    #[test]
     fn window_rows_visible_cols_can_reorder_columns() {
         let d = fixture();
         // Request column 1 before column 0 — output should follow that order.
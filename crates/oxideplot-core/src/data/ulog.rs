@@ -0,0 +1,288 @@
+//! PX4 ULog (`.ulg`) flight logs.
+//!
+//! Every logged topic instance becomes a block of numeric columns named
+//! `topic/field`: first `topic/time [s]` (the message timestamp, seconds
+//! since boot), then each field, with arrays expanded to `field[i]` and
+//! nested message types to `field.sub`. A topic logged several times (multi
+//! instance) gets `topic_1/…`, `topic_2/…`. Topics have their own rates, so
+//! shorter topics are padded with NaN.
+//!
+//! `_padding` fields, `char` arrays and logged strings are skipped.
+
+use std::collections::HashMap;
+
+use crate::data::loader::LoadedData;
+
+const MAGIC: &[u8; 7] = b"ULog\x01\x12\x35";
+const HEADER_LEN: usize = 16;
+
+/// Whether `bytes` starts with the ULog file magic.
+pub fn is_ulog(bytes: &[u8]) -> bool {
+    bytes.len() >= HEADER_LEN && &bytes[..7] == MAGIC
+}
+
+/// Decode a ULog file into one column per topic field.
+pub fn load_ulog(bytes: &[u8]) -> Result<LoadedData, String> {
+    if !is_ulog(bytes) {
+        return Err("not a ULog file (bad magic)".to_string());
+    }
+    let mut formats: HashMap<String, Vec<Field>> = HashMap::new();
+    let mut subs: HashMap<u16, Subscription> = HashMap::new();
+    // Subscription ids in the order they were added (column order).
+    let mut order: Vec<u16> = Vec::new();
+
+    let mut at = HEADER_LEN;
+    while at + 3 <= bytes.len() {
+        let size = u16::from_le_bytes([bytes[at], bytes[at + 1]]) as usize;
+        let kind = bytes[at + 2];
+        let Some(msg) = bytes.get(at + 3..at + 3 + size) else {
+            break; // truncated final message (log cut off mid-write)
+        };
+        at += 3 + size;
+        match kind {
+            b'F' => {
+                let text = String::from_utf8_lossy(msg);
+                let (name, fields) = text.split_once(':').ok_or("ULog format message without ':'")?;
+                formats.insert(name.to_string(), parse_fields(fields)?);
+            }
+            b'A' if msg.len() >= 3 => {
+                let multi_id = msg[0];
+                let msg_id = u16::from_le_bytes([msg[1], msg[2]]);
+                let topic = String::from_utf8_lossy(&msg[3..]).to_string();
+                let mut leaves = Vec::new();
+                flatten(&formats, &topic, "", &mut 0, &mut leaves, 0)?;
+                let label = if multi_id == 0 { topic.clone() } else { format!("{topic}_{multi_id}") };
+                if subs.insert(msg_id, Subscription::new(label, leaves)).is_none() {
+                    order.push(msg_id);
+                }
+            }
+            b'D' if msg.len() >= 2 => {
+                let msg_id = u16::from_le_bytes([msg[0], msg[1]]);
+                if let Some(sub) = subs.get_mut(&msg_id) {
+                    sub.push(&msg[2..]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut columns = Vec::new();
+    let mut values = Vec::new();
+    for id in order {
+        let sub = subs.remove(&id).expect("ordered ids are subscribed");
+        if sub.rows == 0 {
+            continue;
+        }
+        for (leaf, v) in sub.leaves.into_iter().zip(sub.values) {
+            columns.push(if leaf.name == "timestamp" {
+                format!("{}/time [s]", sub.label)
+            } else {
+                format!("{}/{}", sub.label, leaf.name)
+            });
+            values.push(v);
+        }
+    }
+    if columns.is_empty() {
+        return Err("ULog file has no logged data".to_string());
+    }
+    Ok(LoadedData::from_numeric_columns(columns, values))
+}
+
+/// One `type name` entry of a format definition.
+struct Field {
+    ty: String,
+    name: String,
+    /// Element count for arrays (1 for scalars).
+    count: usize,
+}
+
+fn parse_fields(text: &str) -> Result<Vec<Field>, String> {
+    text.split(';')
+        .map(str::trim)
+        .filter(|f| !f.is_empty())
+        .map(|f| {
+            let (ty, name) = f.rsplit_once(' ').ok_or_else(|| format!("bad ULog field {f:?}"))?;
+            let (ty, count) = match ty.split_once('[') {
+                Some((base, n)) => {
+                    let n = n.trim_end_matches(']').parse().map_err(|_| format!("bad ULog array size in {f:?}"))?;
+                    (base, n)
+                }
+                None => (ty, 1),
+            };
+            Ok(Field { ty: ty.to_string(), name: name.to_string(), count })
+        })
+        .collect()
+}
+
+/// A primitive value at a fixed offset in a topic's messages.
+struct Leaf {
+    name: String,
+    offset: usize,
+    ty: Primitive,
+}
+
+#[derive(Clone, Copy)]
+enum Primitive {
+    I8,
+    U8,
+    I16,
+    U16,
+    I32,
+    U32,
+    I64,
+    U64,
+    F32,
+    F64,
+    Bool,
+}
+
+impl Primitive {
+    fn parse(ty: &str) -> Option<Primitive> {
+        Some(match ty {
+            "int8_t" => Primitive::I8,
+            "uint8_t" => Primitive::U8,
+            "int16_t" => Primitive::I16,
+            "uint16_t" => Primitive::U16,
+            "int32_t" => Primitive::I32,
+            "uint32_t" => Primitive::U32,
+            "int64_t" => Primitive::I64,
+            "uint64_t" => Primitive::U64,
+            "float" => Primitive::F32,
+            "double" => Primitive::F64,
+            "bool" => Primitive::Bool,
+            _ => return None,
+        })
+    }
+
+    fn size(self) -> usize {
+        match self {
+            Primitive::I8 | Primitive::U8 | Primitive::Bool => 1,
+            Primitive::I16 | Primitive::U16 => 2,
+            Primitive::I32 | Primitive::U32 | Primitive::F32 => 4,
+            Primitive::I64 | Primitive::U64 | Primitive::F64 => 8,
+        }
+    }
+
+    fn read(self, b: &[u8]) -> f64 {
+        macro_rules! le {
+            ($t:ty) => {
+                <$t>::from_le_bytes(b.try_into().unwrap()) as f64
+            };
+        }
+        match self {
+            Primitive::I8 => b[0] as i8 as f64,
+            Primitive::U8 | Primitive::Bool => b[0] as f64,
+            Primitive::I16 => le!(i16),
+            Primitive::U16 => le!(u16),
+            Primitive::I32 => le!(i32),
+            Primitive::U32 => le!(u32),
+            Primitive::I64 => le!(i64),
+            Primitive::U64 => le!(u64),
+            Primitive::F32 => le!(f32),
+            Primitive::F64 => le!(f64),
+        }
+    }
+}
+
+/// Lay out format `ty` into primitive leaves starting at `*offset`.
+fn flatten(
+    formats: &HashMap<String, Vec<Field>>,
+    ty: &str,
+    prefix: &str,
+    offset: &mut usize,
+    out: &mut Vec<Leaf>,
+    depth: usize,
+) -> Result<(), String> {
+    if depth > 8 {
+        return Err(format!("ULog format {ty:?} nests too deeply"));
+    }
+    let fields = formats.get(ty).ok_or_else(|| format!("ULog topic {ty:?} has no format definition"))?;
+    for f in fields {
+        for i in 0..f.count {
+            let name = match (f.count, prefix) {
+                (1, "") => f.name.clone(),
+                (1, p) => format!("{p}.{}", f.name),
+                (_, "") => format!("{}[{i}]", f.name),
+                (_, p) => format!("{p}.{}[{i}]", f.name),
+            };
+            if let Some(p) = Primitive::parse(&f.ty) {
+                if !f.name.starts_with("_padding") {
+                    out.push(Leaf { name, offset: *offset, ty: p });
+                }
+                *offset += p.size();
+            } else if f.ty == "char" {
+                *offset += 1;
+            } else {
+                flatten(formats, &f.ty, &name, offset, out, depth + 1)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+struct Subscription {
+    label: String,
+    leaves: Vec<Leaf>,
+    values: Vec<Vec<f64>>,
+    rows: usize,
+}
+
+impl Subscription {
+    fn new(label: String, leaves: Vec<Leaf>) -> Self {
+        let values = leaves.iter().map(|_| Vec::new()).collect();
+        Subscription { label, leaves, values, rows: 0 }
+    }
+
+    fn push(&mut self, data: &[u8]) {
+        for (leaf, col) in self.leaves.iter().zip(&mut self.values) {
+            let v = data.get(leaf.offset..leaf.offset + leaf.ty.size()).map_or(f64::NAN, |b| leaf.ty.read(b));
+            // Timestamps are microseconds; store seconds.
+            col.push(if leaf.name == "timestamp" { v * 1e-6 } else { v });
+        }
+        self.rows += 1;
+    }
+}
+
+#[cfg(test)]
+mod ulog_tests {
+    use super::*;
+
+    fn message(out: &mut Vec<u8>, kind: u8, payload: &[u8]) {
+        out.extend_from_slice(&(payload.len() as u16).to_le_bytes());
+        out.push(kind);
+        out.extend_from_slice(payload);
+    }
+
+    #[test]
+    fn topics_become_columns() {
+        let mut log = MAGIC.to_vec();
+        log.push(1);
+        log.extend_from_slice(&0u64.to_le_bytes());
+        message(&mut log, b'F', b"vec2:float x;float y;");
+        message(&mut log, b'F', b"sensor:uint64_t timestamp;vec2 pos;int16_t[2] raw;uint8_t[2] _padding0;");
+        let mut add = vec![0u8];
+        add.extend_from_slice(&7u16.to_le_bytes());
+        add.extend_from_slice(b"sensor");
+        message(&mut log, b'A', &add);
+        for (t, x) in [(1_000_000u64, 1.5f32), (2_000_000, 2.5)] {
+            let mut d = 7u16.to_le_bytes().to_vec();
+            d.extend_from_slice(&t.to_le_bytes());
+            d.extend_from_slice(&x.to_le_bytes());
+            d.extend_from_slice(&(-x).to_le_bytes());
+            d.extend_from_slice(&(-3i16).to_le_bytes());
+            d.extend_from_slice(&4i16.to_le_bytes());
+            message(&mut log, b'D', &d);
+        }
+        assert!(is_ulog(&log));
+        let d = load_ulog(&log).unwrap();
+        assert_eq!(
+            d.columns,
+            ["sensor/time [s]", "sensor/pos.x", "sensor/pos.y", "sensor/raw[0]", "sensor/raw[1]"]
+        );
+        assert_eq!(d.row_count, 2);
+        assert_eq!(d.column_data[0].to_f64().as_ref(), &[1.0, 2.0]);
+        assert_eq!(d.column_data[2].to_f64().as_ref(), &[-1.5, -2.5]);
+        assert_eq!(d.column_data[3].value(1), -3.0);
+        assert!(load_ulog(b"ULog").is_err());
+    }
+}
@@ -0,0 +1,167 @@
+//! Linear unit conversions between units of the same quantity.
+//!
+//! A [`UnitConversion`] maps every sample `y` to `y * scale + offset` and
+//! rewrites the series name's `[unit]` so [`infer_unit`] reports the new
+//! unit. Conversions are kept per graph as rules: re-reading a file column
+//! whose detected unit matches a rule's `from` repeats it ([`apply_rules`]).
+
+use crate::data::unit_inference::{bracketed_unit, infer_unit};
+use serde::{Deserialize, Serialize};
+
+/// Known units: (name, quantity, factor, offset) with
+/// `base = value * factor + offset` in the quantity's base unit.
+const UNITS: &[(&str, &str, f64, f64)] = &[
+    ("\u{00B0}C", "temperature", 1.0, 0.0),
+    ("\u{00B0}F", "temperature", 5.0 / 9.0, -160.0 / 9.0),
+    ("K", "temperature", 1.0, -273.15),
+    ("Pa", "pressure", 1.0, 0.0),
+    ("kPa", "pressure", 1e3, 0.0),
+    ("MPa", "pressure", 1e6, 0.0),
+    ("bar", "pressure", 1e5, 0.0),
+    ("psi", "pressure", 6894.757293168, 0.0),
+    ("atm", "pressure", 101_325.0, 0.0),
+    ("m", "length", 1.0, 0.0),
+    ("mm", "length", 1e-3, 0.0),
+    ("cm", "length", 1e-2, 0.0),
+    ("km", "length", 1e3, 0.0),
+    ("in", "length", 0.0254, 0.0),
+    ("ft", "length", 0.3048, 0.0),
+    ("mi", "length", 1609.344, 0.0),
+    ("m/s", "speed", 1.0, 0.0),
+    ("km/h", "speed", 1.0 / 3.6, 0.0),
+    ("mph", "speed", 0.44704, 0.0),
+    ("kn", "speed", 1852.0 / 3600.0, 0.0),
+    ("V", "voltage", 1.0, 0.0),
+    ("mV", "voltage", 1e-3, 0.0),
+    ("A", "current", 1.0, 0.0),
+    ("mA", "current", 1e-3, 0.0),
+    ("kg", "mass", 1.0, 0.0),
+    ("g", "mass", 1e-3, 0.0),
+    ("lb", "mass", 0.45359237, 0.0),
+    ("Degrees", "angle", 1.0, 0.0),
+    ("rad", "angle", 180.0 / std::f64::consts::PI, 0.0),
+    ("s", "time", 1.0, 0.0),
+    ("ms", "time", 1e-3, 0.0),
+    ("\u{00B5}s", "time", 1e-6, 0.0),
+    ("min", "time", 60.0, 0.0),
+    ("h", "time", 3600.0, 0.0),
+];
+
+fn lookup(unit: &str) -> Option<(&'static str, f64, f64)> {
+    UNITS.iter().find(|u| u.0 == unit).map(|u| (u.1, u.2, u.3))
+}
+
+/// Units `unit` converts to (same quantity, itself excluded); empty for an
+/// unknown unit.
+pub fn targets(unit: &str) -> Vec<&'static str> {
+    let Some((quantity, ..)) = lookup(unit) else {
+        return vec![];
+    };
+    UNITS.iter().filter(|u| u.1 == quantity && u.0 != unit).map(|u| u.0).collect()
+}
+
+/// One conversion rule: every series whose detected unit is `from` is
+/// rescaled to `to`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnitConversion {
+    pub from: String,
+    pub to: String,
+}
+
+impl UnitConversion {
+    /// Errs naming the unit when either is unknown or they measure
+    /// different quantities.
+    pub fn new(from: &str, to: &str) -> Result<Self, String> {
+        let (qf, ..) = lookup(from).ok_or_else(|| format!("can't convert from unit \"{from}\""))?;
+        let (qt, ..) = lookup(to).ok_or_else(|| format!("can't convert to unit \"{to}\""))?;
+        if qf != qt {
+            return Err(format!("{from} ({qf}) can't be converted to {to} ({qt})"));
+        }
+        Ok(Self { from: from.to_string(), to: to.to_string() })
+    }
+
+    /// `(scale, offset)` with `to = from * scale + offset`.
+    fn linear(&self) -> (f64, f64) {
+        let (_, fa, fb) = lookup(&self.from).unwrap_or(("", 1.0, 0.0));
+        let (_, ta, tb) = lookup(&self.to).unwrap_or(("", 1.0, 0.0));
+        (fa / ta, (fb - tb) / ta)
+    }
+
+    /// `ys` in the target unit.
+    pub fn apply(&self, ys: &[f64]) -> Vec<f64> {
+        let (scale, offset) = self.linear();
+        ys.iter().map(|y| y * scale + offset).collect()
+    }
+
+    /// `name` labelled with the target unit: its `[unit]` replaced, or
+    /// `" [to]"` appended when the unit was inferred from the words.
+    pub fn rename(&self, name: &str) -> String {
+        match bracketed_unit(name) {
+            Some(_) => {
+                let body = name.trim_end();
+                let open = body.rfind('[').unwrap_or(body.len());
+                format!("{}[{}]", &body[..open], self.to)
+            }
+            None => format!("{} [{}]", name.trim_end(), self.to),
+        }
+    }
+}
+
+/// Run `rules` in order over a freshly read column: each rule whose `from`
+/// is the column's current detected unit converts it. Returns the new name
+/// and samples, or `None` when no rule matched.
+pub fn apply_rules(rules: &[UnitConversion], name: &str, ys: &[f64]) -> Option<(String, Vec<f64>)> {
+    let mut out: Option<(String, Vec<f64>)> = None;
+    for rule in rules {
+        let current = out.as_ref().map_or(name, |(n, _)| n.as_str());
+        if infer_unit(current) == rule.from {
+            let vals = rule.apply(out.as_ref().map_or(ys, |(_, v)| v.as_slice()));
+            out = Some((rule.rename(current), vals));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod unit_convert_tests {
+    use super::*;
+
+    #[test]
+    fn converts_temperatures_with_offset() {
+        let f_to_c = UnitConversion::new("\u{00B0}F", "\u{00B0}C").unwrap();
+        let c = f_to_c.apply(&[32.0, 212.0]);
+        assert!((c[0] - 0.0).abs() < 1e-9 && (c[1] - 100.0).abs() < 1e-9);
+        let c_to_k = UnitConversion::new("\u{00B0}C", "K").unwrap();
+        assert!((c_to_k.apply(&[0.0])[0] - 273.15).abs() < 1e-9);
+        let psi = UnitConversion::new("bar", "psi").unwrap().apply(&[1.0])[0];
+        assert!((psi - 14.5038).abs() < 1e-3);
+        assert_eq!(UnitConversion::new("ms", "s").unwrap().apply(&[1500.0]), vec![1.5]);
+    }
+
+    #[test]
+    fn rejects_unknown_and_mismatched_units() {
+        assert!(UnitConversion::new("furlong", "m").is_err());
+        assert!(UnitConversion::new("psi", "K").is_err());
+        assert!(targets("K").contains(&"\u{00B0}F"));
+        assert!(targets("units").is_empty());
+    }
+
+    #[test]
+    fn rename_swaps_the_bracketed_unit() {
+        let r = UnitConversion::new("\u{00B0}F", "\u{00B0}C").unwrap();
+        assert_eq!(r.rename("Oil [\u{00B0}F]"), "Oil [\u{00B0}C]");
+        assert_eq!(r.rename("Oil"), "Oil [\u{00B0}C]");
+    }
+
+    #[test]
+    fn rules_repeat_on_a_re_read_column_and_chain() {
+        let rules = vec![
+            UnitConversion::new("\u{00B0}F", "\u{00B0}C").unwrap(),
+            UnitConversion::new("\u{00B0}C", "K").unwrap(),
+        ];
+        let (name, ys) = apply_rules(&rules, "Exhaust [\u{00B0}F]", &[32.0]).unwrap();
+        assert_eq!(name, "Exhaust [K]");
+        assert!((ys[0] - 273.15).abs() < 1e-9);
+        assert!(apply_rules(&rules, "Speed [mph]", &[1.0]).is_none());
+    }
+}
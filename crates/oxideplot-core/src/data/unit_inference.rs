@@ -1,6 +1,21 @@
+/// Fallback of [`infer_unit`] for names it doesn't recognise.
+pub const UNKNOWN_UNIT: &str = "units";
+
+/// The unit written at the end of a name as `"name [unit]"` (how derived
+/// series carry theirs), if any.
+pub fn bracketed_unit(name: &str) -> Option<&str> {
+    let body = name.trim_end().strip_suffix(']')?;
+    let unit = body[body.rfind('[')? + 1..].trim();
+    (!unit.is_empty()).then_some(unit)
+}
+
 /// Infer the measurement unit from a column name.
-/// Matches the Python version's inferUnit function.
+/// Matches the Python version's inferUnit function, except that an explicit
+/// trailing `[unit]` wins.
 pub fn infer_unit(column_name: &str) -> String {
+    if let Some(unit) = bracketed_unit(column_name) {
+        return unit.to_string();
+    }
     let lower = column_name.to_lowercase();
 
     if lower.contains("temp") {
@@ -24,6 +39,21 @@ pub fn infer_unit(column_name: &str) -> String {
     } else if lower.contains("rpm") {
         "RPM".to_string()
     } else {
-        "units".to_string()
+        UNKNOWN_UNIT.to_string()
+    }
+}
+
+#[cfg(test)]
+mod unit_inference_tests {
+    use super::*;
+
+    #[test]
+    fn bracketed_unit_overrides_the_name_heuristics() {
+        assert_eq!(infer_unit("Tool Temp"), "\u{00B0}C");
+        assert_eq!(infer_unit("d/dx(Tool Temp) [\u{00B0}C/s]"), "\u{00B0}C/s");
+        assert_eq!(infer_unit("depth"), UNKNOWN_UNIT);
+        assert_eq!(bracketed_unit("speed [ m/s ]"), Some("m/s"));
+        assert_eq!(bracketed_unit("[] x"), None);
+        assert_eq!(bracketed_unit("x []"), None);
     }
 }
@@ -0,0 +1,98 @@
+//! Per-series alignment of recordings from different loggers: an X offset
+//! for clock skew, and a Y scale and offset for sensors with different
+//! gains or zero points.
+//!
+//! Like a unit conversion, the alignment is applied to the samples
+//! themselves, so readouts, cursors, statistics and exports all see the
+//! aligned values. The [`SeriesAlign`] in effect is kept with the series:
+//! [`SeriesAlign::realign_x`] / [`SeriesAlign::realign_y`] move samples
+//! aligned one way to another (the identity being the raw data), and a
+//! linked project repeats it when it re-reads the column.
+
+use serde::{Deserialize, Serialize};
+
+/// `x + x_offset`, `y * y_scale + y_offset`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SeriesAlign {
+    /// Added to every X (seconds on a datetime axis).
+    #[serde(default)]
+    pub x_offset: f64,
+    #[serde(default)]
+    pub y_offset: f64,
+    /// Applied before `y_offset`; never zero, so it can be undone.
+    #[serde(default = "unit_scale")]
+    pub y_scale: f64,
+}
+
+fn unit_scale() -> f64 {
+    1.0
+}
+
+impl Default for SeriesAlign {
+    fn default() -> Self {
+        SeriesAlign { x_offset: 0.0, y_offset: 0.0, y_scale: 1.0 }
+    }
+}
+
+impl SeriesAlign {
+    pub fn new(x_offset: f64, y_offset: f64, y_scale: f64) -> Result<Self, String> {
+        if !(x_offset.is_finite() && y_offset.is_finite()) {
+            return Err("offsets must be finite numbers".to_string());
+        }
+        if !y_scale.is_finite() || y_scale == 0.0 {
+            return Err("Y scale must be a non-zero number".to_string());
+        }
+        Ok(SeriesAlign { x_offset, y_offset, y_scale })
+    }
+
+    /// Leaves the samples as recorded.
+    pub fn is_identity(&self) -> bool {
+        *self == SeriesAlign::default()
+    }
+
+    /// `xs` aligned by `self`, aligned by `to` instead; `None` when X
+    /// doesn't move.
+    pub fn realign_x(&self, to: &SeriesAlign, xs: &[f64]) -> Option<Vec<f64>> {
+        let shift = to.x_offset - self.x_offset;
+        (shift != 0.0).then(|| xs.iter().map(|x| x + shift).collect())
+    }
+
+    /// `ys` aligned by `self`, aligned by `to` instead; `None` when Y
+    /// doesn't change.
+    pub fn realign_y(&self, to: &SeriesAlign, ys: &[f64]) -> Option<Vec<f64>> {
+        if self.y_scale == to.y_scale {
+            let shift = to.y_offset - self.y_offset;
+            return (shift != 0.0).then(|| ys.iter().map(|y| y + shift).collect());
+        }
+        Some(ys.iter().map(|y| (y - self.y_offset) / self.y_scale * to.y_scale + to.y_offset).collect())
+    }
+}
+
+#[cfg(test)]
+mod align_tests {
+    use super::*;
+
+    #[test]
+    fn realigning_undoes_the_previous_alignment() {
+        let (xs, ys) = ([1_700_000_000.0, 1_700_000_001.0], [2.0, -4.0]);
+        let raw = SeriesAlign::default();
+        let a = SeriesAlign::new(2.5, 1.0, 2.0).unwrap();
+        let ax = raw.realign_x(&a, &xs).unwrap();
+        let ay = raw.realign_y(&a, &ys).unwrap();
+        assert_eq!(ax, [1_700_000_002.5, 1_700_000_003.5]);
+        assert_eq!(ay, [5.0, -7.0]);
+
+        let b = SeriesAlign::new(-1.0, 1.0, 2.0).unwrap();
+        assert_eq!(a.realign_x(&b, &ax).unwrap(), [1_699_999_999.0, 1_700_000_000.0]);
+        assert_eq!(a.realign_y(&b, &ay), None, "same scale and offset");
+        assert_eq!(a.realign_y(&raw, &ay).unwrap(), ys);
+        assert!(raw.realign_x(&raw, &xs).is_none());
+    }
+
+    #[test]
+    fn rejects_a_zero_scale() {
+        assert!(SeriesAlign::new(0.0, 0.0, 0.0).is_err());
+        assert!(SeriesAlign::new(f64::NAN, 0.0, 1.0).is_err());
+        assert!(SeriesAlign::new(0.0, 0.0, 1.0).unwrap().is_identity());
+    }
+}
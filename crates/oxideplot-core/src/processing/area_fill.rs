@@ -0,0 +1,91 @@
+//! Filled areas under a series or between two series.
+//!
+//! A series with an [`AreaFill`] is shaded from its line down (or up) to a
+//! baseline, or to another series (a min/max envelope, a limit curve).
+//! [`area_pairs`] turns the drawn samples and the matching edge values into
+//! the `Band` draw mode's `[x, edge], [x, y]` pairs, adding a point where
+//! the two cross so the fill pinches there instead of twisting.
+
+use serde::{Deserialize, Serialize};
+
+/// What a series' area is filled to.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AreaFill {
+    /// Zero (the bottom of the view in Log scale).
+    Baseline,
+    /// The series of this name, sampled at this series' X.
+    Between(String),
+}
+
+impl AreaFill {
+    /// `"baseline"` or `"between"`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AreaFill::Baseline => "baseline",
+            AreaFill::Between(_) => "between",
+        }
+    }
+
+    /// Name of the other series for [`AreaFill::Between`].
+    pub fn other(&self) -> Option<&str> {
+        match self {
+            AreaFill::Baseline => None,
+            AreaFill::Between(name) => Some(name),
+        }
+    }
+}
+
+/// Band pairs filling between `(xs[i], ys[i])` and `(xs[i], edge[i])`. A
+/// non-finite value on either side becomes a NaN pair (a break in the fill).
+pub fn area_pairs(xs: &[f64], ys: &[f64], edge: &[f64]) -> Vec<[f64; 2]> {
+    let finite = |i: usize| xs[i].is_finite() && ys[i].is_finite() && edge[i].is_finite();
+    let n = xs.len().min(ys.len()).min(edge.len());
+    let mut out = Vec::with_capacity(n * 2);
+    for i in 0..n {
+        if !finite(i) {
+            out.extend_from_slice(&[[f64::NAN; 2]; 2]);
+            continue;
+        }
+        if i > 0 && finite(i - 1) {
+            let (d0, d1) = (ys[i - 1] - edge[i - 1], ys[i] - edge[i]);
+            if d0 * d1 < 0.0 {
+                let t = d0 / (d0 - d1);
+                let x = xs[i - 1] + (xs[i] - xs[i - 1]) * t;
+                let y = ys[i - 1] + (ys[i] - ys[i - 1]) * t;
+                out.extend_from_slice(&[[x, y], [x, y]]);
+            }
+        }
+        out.extend_from_slice(&[[xs[i], edge[i]], [xs[i], ys[i]]]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod area_fill_tests {
+    use super::*;
+
+    #[test]
+    fn fill_to_a_constant_edge() {
+        let pairs = area_pairs(&[0.0, 1.0], &[2.0, 3.0], &[0.0, 0.0]);
+        assert_eq!(pairs, vec![[0.0, 0.0], [0.0, 2.0], [1.0, 0.0], [1.0, 3.0]]);
+    }
+
+    #[test]
+    fn crossings_pinch_the_fill() {
+        // y goes from 1 above the edge to 1 below it: they cross at x = 0.5.
+        let pairs = area_pairs(&[0.0, 1.0], &[1.0, -1.0], &[0.0, 0.0]);
+        assert_eq!(pairs.len(), 6);
+        assert_eq!(pairs[2], [0.5, 0.0]);
+        assert_eq!(pairs[3], [0.5, 0.0]);
+    }
+
+    #[test]
+    fn missing_edge_values_break_the_fill() {
+        let pairs = area_pairs(&[0.0, 1.0, 2.0], &[1.0, 1.0, 1.0], &[0.0, f64::NAN, 0.0]);
+        assert!(pairs[2][0].is_nan() && pairs[3][0].is_nan());
+        assert_eq!(pairs[4], [2.0, 0.0]);
+        assert_eq!(AreaFill::Between("Min".into()).other(), Some("Min"));
+        assert_eq!(AreaFill::Baseline.kind(), "baseline");
+    }
+}
@@ -0,0 +1,147 @@
+//! Expressions over series that don't share an X axis (e.g. channels shown
+//! in different graphs): every operand is aligned onto the first one's X,
+//! then the expression is evaluated row by row with the
+//! [`expr`](super::expr) grammar, operands named `A`, `B`, `C`, ….
+
+use std::collections::{HashMap, HashSet};
+
+use crate::data::loader::LoadedData;
+use crate::processing::expr::{collect_expr_cols, eval_expr, parse_expr};
+
+/// How an operand's Y is read at another series' X.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    /// Linear interpolation between the neighbouring samples.
+    Linear,
+    /// The last sample at or before X (sample-and-hold).
+    Hold,
+    /// The nearest sample, if within `tolerance` of X.
+    Nearest { tolerance: f64 },
+}
+
+impl Align {
+    /// `"linear"`, `"hold"` or `"nearest"` (with `tolerance`, default 0:
+    /// exact X matches only).
+    pub fn parse(s: &str, tolerance: Option<f64>) -> Option<Align> {
+        match s {
+            "linear" | "" => Some(Align::Linear),
+            "hold" => Some(Align::Hold),
+            "nearest" => Some(Align::Nearest { tolerance: tolerance.unwrap_or(0.0).max(0.0) }),
+            _ => None,
+        }
+    }
+}
+
+/// Operand name for position `i`: `A`…`Z`, then `A1`, `B1`, ….
+pub fn operand_name(i: usize) -> String {
+    let letter = (b'A' + (i % 26) as u8) as char;
+    match i / 26 {
+        0 => letter.to_string(),
+        n => format!("{letter}{n}"),
+    }
+}
+
+/// `ys` sampled at each of `grid` per `align`; NaN outside the operand's
+/// X range (no extrapolation) and where no sample qualifies.
+pub fn align_to(grid: &[f64], xs: &[f64], ys: &[f64], align: Align) -> Vec<f64> {
+    let mut pts: Vec<(f64, f64)> =
+        xs.iter().zip(ys).filter(|(x, _)| x.is_finite()).map(|(&x, &y)| (x, y)).collect();
+    pts.sort_by(|a, b| a.0.total_cmp(&b.0));
+    let (Some(&(lo, _)), Some(&(hi, _))) = (pts.first(), pts.last()) else {
+        return vec![f64::NAN; grid.len()];
+    };
+    grid.iter()
+        .map(|&x| {
+            if !x.is_finite() {
+                return f64::NAN;
+            }
+            // First sample with X > x.
+            let k = pts.partition_point(|p| p.0 <= x);
+            match align {
+                Align::Linear => {
+                    if x < lo || x > hi {
+                        f64::NAN
+                    } else if k == 0 || k == pts.len() || pts[k - 1].0 == x {
+                        pts[k.saturating_sub(1)].1
+                    } else {
+                        let (x0, y0) = pts[k - 1];
+                        let (x1, y1) = pts[k];
+                        y0 + (y1 - y0) * (x - x0) / (x1 - x0)
+                    }
+                }
+                Align::Hold => {
+                    if k == 0 || x > hi {
+                        f64::NAN
+                    } else {
+                        pts[k - 1].1
+                    }
+                }
+                Align::Nearest { tolerance } => {
+                    let before = k.checked_sub(1).map(|j| pts[j]);
+                    let after = pts.get(k).copied();
+                    let best = match (before, after) {
+                        (Some(b), Some(a)) => if x - b.0 <= a.0 - x { b } else { a },
+                        (Some(p), None) | (None, Some(p)) => p,
+                        (None, None) => return f64::NAN,
+                    };
+                    if (best.0 - x).abs() <= tolerance { best.1 } else { f64::NAN }
+                }
+            }
+        })
+        .collect()
+}
+
+/// Evaluate `expr` over `operands` (`(xs, ys)` each, named by
+/// [`operand_name`]) on the first operand's X. Returns the finite
+/// `(x, y)` results; `Err` for a bad expression or no finite result.
+pub fn eval_across(operands: &[(&[f64], &[f64])], expr: &str, align: Align) -> Result<(Vec<f64>, Vec<f64>), String> {
+    let Some(&(grid, _)) = operands.first() else {
+        return Err("pick at least one series".to_string());
+    };
+    let names: Vec<String> = (0..operands.len()).map(operand_name).collect();
+    let values: Vec<Vec<f64>> = operands.iter().map(|&(xs, ys)| align_to(grid, xs, ys, align)).collect();
+    let data = LoadedData::from_numeric_columns(names, values);
+    let ast = parse_expr(&data, expr)?;
+    let mut refs = HashSet::new();
+    collect_expr_cols(&ast, &mut refs);
+    let cols: HashMap<usize, Vec<f64>> =
+        refs.into_iter().map(|c| (c, data.column_data[c].to_f64().into_owned())).collect();
+    let (xs, ys): (Vec<f64>, Vec<f64>) = grid
+        .iter()
+        .enumerate()
+        .map(|(row, &x)| (x, eval_expr(&ast, &cols, row)))
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .unzip();
+    if xs.is_empty() {
+        return Err("the expression produced no finite values where the series overlap".to_string());
+    }
+    Ok((xs, ys))
+}
+
+#[cfg(test)]
+mod cross_math_tests {
+    use super::*;
+
+    #[test]
+    fn operands_align_onto_the_first_series() {
+        let (ax, ay) = ([0.0, 1.0, 2.0, 3.0], [10.0, 20.0, 30.0, 40.0]);
+        // B is sampled at half-steps and starts late.
+        let (bx, by) = ([0.5, 1.5, 2.5], [1.0, 3.0, 5.0]);
+        let (xs, ys) = eval_across(&[(&ax, &ay), (&bx, &by)], "A - B", Align::Linear).unwrap();
+        assert_eq!(xs, [1.0, 2.0]);
+        assert_eq!(ys, [18.0, 26.0]);
+
+        let (xs, ys) = eval_across(&[(&ax, &ay), (&bx, &by)], "A + B", Align::Hold).unwrap();
+        assert_eq!(xs, [1.0, 2.0]);
+        assert_eq!(ys, [21.0, 33.0]);
+
+        let near = Align::parse("nearest", Some(0.5)).unwrap();
+        let snapped = align_to(&[0.0, 3.0, 4.0], &bx, &by, near);
+        assert_eq!(snapped[..2], [1.0, 5.0]);
+        assert!(snapped[2].is_nan());
+
+        assert!(eval_across(&[(&ax, &ay)], "A * C", Align::Linear).is_err());
+        assert_eq!(operand_name(1), "B");
+        assert_eq!(operand_name(27), "B1");
+    }
+}
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 /// Largest-Triangle-Three-Buckets (LTTB) downsampling.
 /// Takes x,y arrays and target number of output points.
 /// Returns (downsampled_x, downsampled_y).
@@ -82,12 +84,7 @@ pub fn downsample_for_view(
         return (Vec::new(), Vec::new());
     }
 
-    // Binary search for the start and end of the visible range.
-    // Include one extra point on each side for line continuity.
-    let start = x.partition_point(|&v| v < view_min).saturating_sub(1);
-    let end = (x.partition_point(|&v| v <= view_max) + 1).min(x.len());
-    let slice_x = &x[start..end];
-    let slice_y = &y[start..end];
+    let (slice_x, slice_y) = visible_window(x, y, view_min, view_max);
 
     if slice_x.len() <= max_points {
         return (slice_x.to_vec(), slice_y.to_vec());
@@ -96,6 +93,17 @@ pub fn downsample_for_view(
     lttb_downsample(slice_x, slice_y, max_points)
 }
 
+/// The samples in `[view_min, view_max]` plus one on each side (so a line
+/// crossing into the view from off-screen still draws), borrowed from the
+/// full arrays. Assumes `x` is ascending.
+pub fn visible_window<'a>(x: &'a [f64], y: &'a [f64], view_min: f64, view_max: f64) -> (&'a [f64], &'a [f64]) {
+    let n = x.len().min(y.len());
+    let x = &x[..n];
+    let start = x.partition_point(|&v| v < view_min).saturating_sub(1);
+    let end = (x.partition_point(|&v| v <= view_max) + 1).min(n);
+    (&x[start..end], &y[start..end])
+}
+
 /// Which decimation to use when building the visible render series.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum DownsampleMode {
@@ -116,37 +124,123 @@ impl DownsampleMode {
 
 /// Like `downsample_for_view`, but selects the decimation strategy.
 ///
-/// The visible-X window is computed IDENTICALLY to `downsample_for_view`: the same
-/// `partition_point` bounds PLUS the ±1 line-continuity padding
-/// (`saturating_sub(1)` on the low end, `+ 1` clamped to `len` on the high end) so
-/// a line segment crossing into the view from off-screen still draws. This must be
-/// byte-identical to `downsample_for_view` because Task 3 swaps the renderer's call
-/// site to this function.
-pub fn downsample_for_view_mode(
-    x: &[f64],
-    y: &[f64],
+/// The visible-X window is the same [`visible_window`] `downsample_for_view`
+/// uses. When the window needs no decimation it is returned borrowed from
+/// `x`/`y` — zooming into a huge shared series copies nothing.
+pub fn downsample_for_view_mode<'a>(
+    x: &'a [f64],
+    y: &'a [f64],
     view_min: f64,
     view_max: f64,
     max_points: usize,
     mode: DownsampleMode,
-) -> (Vec<f64>, Vec<f64>) {
-    if x.is_empty() {
-        return (Vec::new(), Vec::new());
+) -> (Cow<'a, [f64]>, Cow<'a, [f64]>) {
+    let (xw, yw) = visible_window(x, y, view_min, view_max);
+    if xw.len() <= max_points || max_points < 3 || mode == DownsampleMode::None {
+        return (Cow::Borrowed(xw), Cow::Borrowed(yw));
     }
+    let (dx, dy) = match mode {
+        DownsampleMode::Lttb => decimate_runs(xw, yw, max_points, lttb_downsample),
+        _ => decimate_runs(xw, yw, max_points, |x, y, n| minmax_envelope(x, y, n / 2)),
+    };
+    (Cow::Owned(dx), Cow::Owned(dy))
+}
 
-    // Same visible window as downsample_for_view, including the ±1 continuity padding.
-    let start = x.partition_point(|&v| v < view_min).saturating_sub(1);
-    let end = (x.partition_point(|&v| v <= view_max) + 1).min(x.len());
-    let xw = &x[start..end];
-    let yw = &y[start..end];
+/// `decimate` applied to each run of finite Y separately, the point budget
+/// shared out by run length, with one NaN break between runs. Decimating
+/// across a gap would average it away (LTTB) or skip it (min/max), joining
+/// the two sides with a straight line. Consecutive runs too short for a
+/// share of their own (frequent dropouts) are reduced together by
+/// [`gapped_envelope`], so they stay within the budget and don't shrink to
+/// lone points that a line never connects.
+fn decimate_runs(
+    x: &[f64],
+    y: &[f64],
+    max_points: usize,
+    decimate: impl Fn(&[f64], &[f64], usize) -> (Vec<f64>, Vec<f64>),
+) -> (Vec<f64>, Vec<f64>) {
+    if y.iter().all(|v| v.is_finite()) {
+        return decimate(x, y, max_points);
+    }
+    let n = y.len();
+    let share = |len: usize| max_points * len / n;
+    let mut runs = Vec::new();
+    let mut start = 0;
+    while start < n {
+        if !y[start].is_finite() {
+            start += 1;
+            continue;
+        }
+        let end = y[start..].iter().position(|v| !v.is_finite()).map_or(n, |k| start + k);
+        runs.push(start..end);
+        start = end;
+    }
+    let (mut ox, mut oy) = (Vec::with_capacity(max_points), Vec::with_capacity(max_points));
+    let mut i = 0;
+    while i < runs.len() {
+        if !ox.is_empty() {
+            ox.push(f64::NAN);
+            oy.push(f64::NAN);
+        }
+        // At least 4 points: LTTB needs 3 to decimate, min/max 2 per bucket.
+        let run = runs[i].clone();
+        if share(run.len()) >= 4 {
+            let (rx, ry) = decimate(&x[run.clone()], &y[run.clone()], share(run.len()));
+            ox.extend(rx);
+            oy.extend(ry);
+            i += 1;
+            continue;
+        }
+        let first = i;
+        while i < runs.len() && share(runs[i].len()) < 4 {
+            i += 1;
+        }
+        let (lo, hi) = (runs[first].start, runs[i - 1].end);
+        gapped_envelope(&x[lo..hi], &y[lo..hi], share(hi - lo), &mut ox, &mut oy);
+    }
+    (ox, oy)
+}
 
-    if xw.len() <= max_points || max_points < 3 {
-        return (xw.to_vec(), yw.to_vec());
+/// Append the min and max finite Y of each of `budget / 2` equal index
+/// buckets to `ox`/`oy` (in X order), with a NaN break only where a whole
+/// bucket is a gap — gaps narrower than a bucket are joined over. Under a
+/// budget of 2 just the first sample is kept. `y[0]` must be finite.
+fn gapped_envelope(x: &[f64], y: &[f64], budget: usize, ox: &mut Vec<f64>, oy: &mut Vec<f64>) {
+    let n = y.len();
+    if budget < 2 {
+        ox.push(x[0]);
+        oy.push(y[0]);
+        return;
     }
-    match mode {
-        DownsampleMode::None => (xw.to_vec(), yw.to_vec()),
-        DownsampleMode::Lttb => lttb_downsample(xw, yw, max_points),
-        DownsampleMode::MinMax => minmax_envelope(xw, yw, max_points / 2),
+    let buckets = (budget / 2).min(n);
+    let mut gap = false;
+    for b in 0..buckets {
+        let mut finite = (b * n / buckets..(b + 1) * n / buckets).filter(|&i| y[i].is_finite());
+        let Some(first) = finite.next() else {
+            gap = true;
+            continue;
+        };
+        let (mut imin, mut imax) = (first, first);
+        for i in finite {
+            if y[i] < y[imin] {
+                imin = i;
+            }
+            if y[i] > y[imax] {
+                imax = i;
+            }
+        }
+        if gap {
+            ox.push(f64::NAN);
+            oy.push(f64::NAN);
+            gap = false;
+        }
+        let (a, c) = if imin <= imax { (imin, imax) } else { (imax, imin) };
+        ox.push(x[a]);
+        oy.push(y[a]);
+        if c != a {
+            ox.push(x[c]);
+            oy.push(y[c]);
+        }
     }
 }
 
@@ -189,6 +283,63 @@ mod tests {
         assert_eq!(out_x.len(), xs.len());
         assert_eq!(out_y.len(), ys.len());
     }
+
+    /// A zoomed-in window under the point budget borrows from the full
+    /// arrays (with the ±1 continuity padding); an over-budget one decimates.
+    #[test]
+    fn view_window_borrows_when_under_budget() {
+        let xs: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x * 2.0).collect();
+        let (wx, wy) = downsample_for_view_mode(&xs, &ys, 10.0, 20.0, 100, DownsampleMode::MinMax);
+        assert!(matches!(wx, Cow::Borrowed(_)) && matches!(wy, Cow::Borrowed(_)));
+        assert_eq!((wx[0], *wx.last().unwrap()), (9.0, 21.0));
+        assert_eq!(wx.as_ptr(), xs[9..].as_ptr());
+        let (dx, _) = downsample_for_view_mode(&xs, &ys, 0.0, 999.0, 100, DownsampleMode::Lttb);
+        assert!(matches!(dx, Cow::Owned(_)) && dx.len() == 100);
+    }
+
+    /// A gap in the data stays a gap (one NaN break) after either decimation.
+    #[test]
+    fn decimation_keeps_gaps() {
+        let xs: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let mut ys: Vec<f64> = xs.iter().map(|x| (x * 0.05).sin()).collect();
+        ys[400..420].fill(f64::NAN);
+        for mode in [DownsampleMode::Lttb, DownsampleMode::MinMax] {
+            let (dx, dy) = downsample_for_view_mode(&xs, &ys, 0.0, 999.0, 100, mode);
+            let breaks: Vec<usize> = (0..dy.len()).filter(|&i| dy[i].is_nan()).collect();
+            assert_eq!(breaks.len(), 1, "{mode:?}");
+            let b = breaks[0];
+            assert!(dx[b - 1] < 400.0 && dx[b + 1] >= 420.0, "{mode:?}");
+            assert!(dy.len() <= 104, "{mode:?}");
+        }
+    }
+
+    /// Frequent short gaps (here every third sample, and a run of lone
+    /// samples) still decimate to the budget, stay drawable as lines, and
+    /// a gap wider than a bucket still breaks the line.
+    #[test]
+    fn decimation_budget_holds_with_many_gaps() {
+        let xs: Vec<f64> = (0..10_000).map(|i| i as f64).collect();
+        let mut ys: Vec<f64> = xs.iter().map(|x| (x * 0.01).sin()).collect();
+        for i in (0..10_000).step_by(3) {
+            ys[i] = f64::NAN;
+        }
+        for (i, y) in ys[7000..].iter_mut().enumerate() {
+            if i % 5 != 0 {
+                *y = f64::NAN;
+            }
+        }
+        ys[4000..5000].fill(f64::NAN);
+        for mode in [DownsampleMode::Lttb, DownsampleMode::MinMax] {
+            let (ox, oy) = downsample_for_view_mode(&xs, &ys, 0.0, 9999.0, 200, mode);
+            let breaks = oy.iter().filter(|v| v.is_nan()).count();
+            assert!(ox.len() <= 200 + breaks, "{mode:?}: {} points, {breaks} breaks", ox.len());
+            let segment = |i: usize| oy[i].is_finite() && oy[i + 1].is_finite();
+            assert!((0..oy.len() - 1).any(|i| segment(i) && ox[i] >= 7000.0), "{mode:?}");
+            let b = (0..oy.len()).find(|&i| oy[i].is_nan() && ox[i - 1] < 4000.0 && ox[i + 1] >= 5000.0);
+            assert!(b.is_some(), "{mode:?}");
+        }
+    }
 }
 
 /// Min/max envelope decimation: split into `buckets` equal index ranges and keep
@@ -1,4 +1,4 @@
-use crate::data::loader::{column_to_f64, resolve_col, LoadedData};
+use crate::data::loader::{resolve_col, LoadedData};
 use std::collections::{HashMap, HashSet};
 
 // ---- Mini arithmetic expression evaluator (derive_column op="expr") ----
@@ -403,7 +403,7 @@ pub fn apply_filter(data: &LoadedData, rows: &[usize], filter: &str) -> Result<V
     collect_expr_cols(&ast, &mut refs);
     let colvals: HashMap<usize, Vec<f64>> = refs
         .iter()
-        .map(|&ci| (ci, column_to_f64(&data.column_data[ci]).0))
+        .map(|&ci| (ci, data.column_data[ci].to_f64().into_owned()))
         .collect();
     Ok(rows
         .iter()
@@ -457,16 +457,17 @@ pub fn rolling_compute(op: &str, cols: &[Vec<f64>], win: usize, n_rows: usize) -
 #[cfg(test)]
 mod expr_tests {
     use super::*;
+    use crate::data::column::Column;
     use crate::data::loader::LoadedData;
 
     fn dataset() -> LoadedData {
         // ax=3, ay=4, az=0 for all 5 rows.
         let col = |v: &str| vec![v.to_string(); 5];
-        LoadedData {
-            columns: vec!["ax".into(), "ay".into(), "az".into()],
-            column_data: vec![col("3"), col("4"), col("0")],
-            row_count: 5,
-        }
+        LoadedData::from_string_columns(
+            vec!["ax".into(), "ay".into(), "az".into()],
+            vec![col("3"), col("4"), col("0")],
+            5,
+        )
     }
 
     fn eval_all(d: &LoadedData, s: &str) -> Vec<f64> {
@@ -475,7 +476,7 @@ mod expr_tests {
         collect_expr_cols(&ast, &mut refs);
         let cols: HashMap<usize, Vec<f64>> = refs
             .iter()
-            .map(|&ci| (ci, column_to_f64(&d.column_data[ci]).0))
+            .map(|&ci| (ci, d.column_data[ci].to_f64().into_owned()))
             .collect();
         (0..d.row_count).map(|r| eval_expr(&ast, &cols, r)).collect()
     }
@@ -490,11 +491,11 @@ mod expr_tests {
     fn quoted_multiword_column_names() {
         // Real instrument headers have spaces/symbols; quotes reference them.
         let col = |v: &str| vec![v.to_string(); 3];
-        let d = LoadedData {
-            columns: vec!["T3 X".into(), "Temp PV °C".into()],
-            column_data: vec![col("3"), col("4")],
-            row_count: 3,
-        };
+        let d = LoadedData::from_string_columns(
+            vec!["T3 X".into(), "Temp PV °C".into()],
+            vec![col("3"), col("4")],
+            3,
+        );
         let out = eval_all(&d, "\"T3 X\" + \"Temp PV °C\"");
         assert!((out[0] - 7.0).abs() < 1e-9);
     }
@@ -523,7 +524,9 @@ mod expr_tests {
     fn filter_selects_rows() {
         // one row where ax=99, rest ax=3
         let mut d = dataset();
-        d.column_data[0][2] = "99".into();
+        let mut ax = d.column_data[0].to_f64().into_owned();
+        ax[2] = 99.0;
+        d.column_data[0] = Column::numeric(ax).into();
         let kept = apply_filter(&d, &(0..5).collect::<Vec<_>>(), "ax > 50").unwrap();
         assert_eq!(kept, vec![2]);
     }
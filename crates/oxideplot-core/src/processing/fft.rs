@@ -0,0 +1,164 @@
+//! Windowed FFT magnitude spectra.
+//!
+//! [`magnitude_spectrum`] gives the one-sided amplitude spectrum of a
+//! uniformly sampled series, scaled so a sine of amplitude `A` shows a peak
+//! of height `A` whatever the [`Window`]. The window trades frequency
+//! resolution (rectangular) against leakage between bins (Hann, Hamming).
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+use std::f64::consts::PI;
+
+/// Taper applied to the samples before the FFT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Window {
+    /// No taper: sharpest peaks, most leakage.
+    Rectangular,
+    /// Raised cosine to zero at both ends.
+    Hann,
+    /// Raised cosine on a pedestal: lower first sidelobe than Hann.
+    Hamming,
+}
+
+impl Window {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "rectangular" => Some(Window::Rectangular),
+            "hann" => Some(Window::Hann),
+            "hamming" => Some(Window::Hamming),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Window::Rectangular => "rectangular",
+            Window::Hann => "hann",
+            Window::Hamming => "hamming",
+        }
+    }
+
+    /// The `n` window weights (symmetric).
+    pub fn coefficients(self, n: usize) -> Vec<f64> {
+        let denom = (n as f64 - 1.0).max(1.0);
+        (0..n)
+            .map(|i| {
+                let c = (2.0 * PI * i as f64 / denom).cos();
+                match self {
+                    Window::Rectangular => 1.0,
+                    Window::Hann => 0.5 - 0.5 * c,
+                    Window::Hamming => 0.54 - 0.46 * c,
+                }
+            })
+            .collect()
+    }
+}
+
+/// One-sided amplitude spectrum of `vals` sampled at `fs` Hz: `(frequency,
+/// amplitude)` for bins 1..n/2 (DC dropped, mean removed). Non-finite
+/// samples inside the record are linearly interpolated (dropping them would
+/// squeeze the time base and shift every frequency) and those at its ends
+/// trimmed; fewer than 4 samples left give empty arrays.
+pub fn magnitude_spectrum(vals: &[f64], fs: f64, window: Window) -> (Vec<f64>, Vec<f64>) {
+    let y = bridge_gaps(vals);
+    let n = y.len();
+    if n < 4 {
+        return (vec![], vec![]);
+    }
+    let mean = y.iter().sum::<f64>() / n as f64;
+    let w = window.coefficients(n);
+    // Coherent gain: divide by the window's sum so peaks read in signal units.
+    let gain: f64 = w.iter().sum();
+    let mut buf: Vec<Complex<f64>> = y
+        .iter()
+        .zip(&w)
+        .map(|(v, w)| Complex::new((v - mean) * w, 0.0))
+        .collect();
+    FftPlanner::new().plan_fft_forward(n).process(&mut buf);
+    (1..n.div_ceil(2))
+        .map(|k| (k as f64 * fs / n as f64, 2.0 * buf[k].norm() / gain))
+        .unzip()
+}
+
+/// `vals` between its first and last finite samples, with each run of
+/// non-finite samples replaced by a straight line across the gap.
+fn bridge_gaps(vals: &[f64]) -> Vec<f64> {
+    let (Some(first), Some(last)) = (vals.iter().position(|v| v.is_finite()), vals.iter().rposition(|v| v.is_finite()))
+    else {
+        return Vec::new();
+    };
+    let mut y = vals[first..=last].to_vec();
+    let mut prev = 0;
+    for i in 1..y.len() {
+        if !y[i].is_finite() {
+            continue;
+        }
+        let (a, b) = (y[prev], y[i]);
+        let span = (i - prev) as f64;
+        for (k, v) in y[prev + 1..i].iter_mut().enumerate() {
+            *v = a + (b - a) * (k + 1) as f64 / span;
+        }
+        prev = i;
+    }
+    y
+}
+
+#[cfg(test)]
+mod fft_tests {
+    use super::*;
+
+    fn sine(n: usize, fs: f64, f: f64, a: f64) -> Vec<f64> {
+        (0..n).map(|i| a * (2.0 * PI * f * i as f64 / fs).sin()).collect()
+    }
+
+    #[test]
+    fn peak_reads_the_sine_amplitude_for_every_window() {
+        // 50 Hz falls exactly on bin 50 of a 1000-point, 1 kHz record.
+        let sig = sine(1000, 1000.0, 50.0, 3.0);
+        for window in [Window::Rectangular, Window::Hann, Window::Hamming] {
+            let (freqs, amps) = magnitude_spectrum(&sig, 1000.0, window);
+            let (k, peak) = amps
+                .iter()
+                .enumerate()
+                .fold((0, 0.0), |m, (k, &a)| if a > m.1 { (k, a) } else { m });
+            assert_eq!(freqs[k], 50.0, "{window:?}");
+            assert!((peak - 3.0).abs() < 0.05, "{window:?}: {peak}");
+        }
+    }
+
+    #[test]
+    fn tapered_windows_leak_less_off_bin() {
+        // 50.5 Hz sits between bins. Hann's sidelobes fall off fast, so it
+        // wins by far 5–20 bins out; Hamming's stay flat near -43 dB, still
+        // under 1% of the peak where the rectangular window's top 5%.
+        let sig = sine(1000, 1000.0, 50.5, 1.0);
+        let spectrum = |w| magnitude_spectrum(&sig, 1000.0, w).1;
+        let max = |a: &[f64]| a.iter().fold(0.0f64, |m, &v| m.max(v));
+        let (rect, hann, hamming) = (spectrum(Window::Rectangular), spectrum(Window::Hann), spectrum(Window::Hamming));
+        assert!(max(&hann[55..70]) < max(&rect[55..70]) / 10.0);
+        assert!(max(&hamming[53..70]) < max(&hamming) / 100.0);
+        assert!(max(&rect[53..70]) > max(&rect) / 20.0);
+    }
+
+    #[test]
+    fn gaps_are_bridged_not_dropped() {
+        // Dropping the NaNs would shorten the record and move the peak.
+        let mut sig = sine(1000, 1000.0, 50.0, 1.0);
+        sig[300..340].fill(f64::NAN);
+        sig[0] = f64::NAN;
+        let (freqs, amps) = magnitude_spectrum(&sig, 1000.0, Window::Hann);
+        let k = (0..amps.len()).fold(0, |m, k| if amps[k] > amps[m] { k } else { m });
+        assert!((freqs[k] - 50.0).abs() < 1.0, "{}", freqs[k]);
+        assert_eq!(bridge_gaps(&[f64::NAN, 1.0, f64::NAN, f64::NAN, 4.0, f64::NAN]), [1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn windows_parse_and_short_input_is_empty() {
+        for w in [Window::Rectangular, Window::Hann, Window::Hamming] {
+            assert_eq!(Window::parse(w.as_str()), Some(w));
+        }
+        assert_eq!(Window::Hann.coefficients(3), vec![0.0, 1.0, 0.0]);
+        assert!(!magnitude_spectrum(&[1.0, 2.0, f64::NAN, 3.0], 1.0, Window::Hann).0.is_empty());
+        assert!(magnitude_spectrum(&[1.0, 2.0, f64::NAN], 1.0, Window::Hann).0.is_empty());
+    }
+}
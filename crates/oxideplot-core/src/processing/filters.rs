@@ -0,0 +1,264 @@
+//! Smoothing filters: rolling statistics over a sliding window.
+//!
+//! Each filter returns a series the same length as its input, so a smoothed
+//! copy plots against the original X. Windows are centred on each sample,
+//! except in [`exponential_smoothing`], which is causal like a first-order
+//! low-pass.
+//!
+//! Despiking is two steps: [`find_spikes`] flags outliers (Hampel or
+//! z-score), so they can be previewed, then they are dropped or bridged
+//! with [`interpolate_spikes`].
+
+/// Centered simple moving average, window >= 1, clamped at the edges.
+pub fn moving_average(ys: &[f64], window: usize) -> Vec<f64> {
+    let n = ys.len();
+    let w = window.max(1);
+    let half = w / 2;
+    (0..n).map(|i| {
+        let lo = i.saturating_sub(half);
+        let hi = (i + half + 1).min(n);
+        let slice = &ys[lo..hi];
+        slice.iter().sum::<f64>() / slice.len() as f64
+    }).collect()
+}
+
+/// Savitzky–Golay smoothing: least-squares fit of a degree-`order` polynomial
+/// over a sliding `window` (made odd, and larger than `order`), evaluated at
+/// each sample. Unlike a moving average it preserves peak height and width
+/// for peaks wider than the window. Near the ends the window is held inside
+/// the data and the fit evaluated off-centre, so edges aren't flattened.
+pub fn savitzky_golay(ys: &[f64], window: usize, order: usize) -> Vec<f64> {
+    let n = ys.len();
+    let mut w = window.max(order + 2);
+    if w.is_multiple_of(2) { w += 1; }
+    if w > n { w = if !n.is_multiple_of(2) { n } else { n.saturating_sub(1) }; }
+    if w <= order || w < 3 { return ys.to_vec(); }
+    let half = w / 2;
+    let coeffs = savgol_coefficients(w, order);
+    (0..n).map(|i| {
+        let start = i.saturating_sub(half).min(n - w);
+        coeffs[i - start].iter().zip(&ys[start..start + w]).map(|(c, y)| c * y).sum()
+    }).collect()
+}
+
+/// Convolution weights for [`savitzky_golay`]: row `t` evaluates the window's
+/// least-squares polynomial at window position `t`. Offsets are scaled to
+/// [-1, 1] to keep the normal equations well conditioned.
+fn savgol_coefficients(window: usize, order: usize) -> Vec<Vec<f64>> {
+    let half = (window / 2) as f64;
+    let m = order + 1;
+    let pos = |j: usize| (j as f64 - half) / half;
+    let powers = |x: f64| -> Vec<f64> { (0..m).scan(1.0, |p, _| { let v = *p; *p *= x; Some(v) }).collect() };
+    let a: Vec<Vec<f64>> = (0..window).map(|j| powers(pos(j))).collect();
+
+    // Invert AᵀA (m×m) by Gauss–Jordan with partial pivoting.
+    let mut ata = vec![vec![0.0; 2 * m]; m];
+    for r in 0..m {
+        for c in 0..m {
+            ata[r][c] = a.iter().map(|row| row[r] * row[c]).sum();
+        }
+        ata[r][m + r] = 1.0;
+    }
+    for col in 0..m {
+        let piv = (col..m)
+            .max_by(|&x, &y| ata[x][col].abs().partial_cmp(&ata[y][col].abs()).unwrap_or(std::cmp::Ordering::Equal))
+            .unwrap_or(col);
+        ata.swap(col, piv);
+        let d = ata[col][col];
+        for v in ata[col].iter_mut() { *v /= d; }
+        for r in 0..m {
+            if r != col {
+                let f = ata[r][col];
+                if f != 0.0 {
+                    let pivot_row = ata[col].clone();
+                    for (v, p) in ata[r].iter_mut().zip(pivot_row) { *v -= f * p; }
+                }
+            }
+        }
+    }
+    let inv: Vec<&[f64]> = ata.iter().map(|row| &row[m..]).collect();
+
+    // coeffs[t][j] = powers(pos(t)) · (AᵀA)⁻¹ · A[j]
+    (0..window).map(|t| {
+        let e = powers(pos(t));
+        let h: Vec<f64> = (0..m).map(|k| (0..m).map(|r| e[r] * inv[r][k]).sum()).collect();
+        a.iter().map(|row| row.iter().zip(&h).map(|(x, y)| x * y).sum()).collect()
+    }).collect()
+}
+
+/// Centred rolling median, window >= 1, clamped at the edges. Removes spikes
+/// a moving average would smear out. NaNs are skipped; a window of nothing
+/// but NaN gives NaN.
+pub fn median_filter(ys: &[f64], window: usize) -> Vec<f64> {
+    let n = ys.len();
+    let half = window.max(1) / 2;
+    let mut buf: Vec<f64> = Vec::with_capacity(2 * half + 1);
+    (0..n).map(|i| {
+        buf.clear();
+        buf.extend(ys[i.saturating_sub(half)..(i + half + 1).min(n)].iter().copied().filter(|v| !v.is_nan()));
+        if buf.is_empty() {
+            return f64::NAN;
+        }
+        buf.sort_by(f64::total_cmp);
+        let m = buf.len() / 2;
+        if !buf.len().is_multiple_of(2) { buf[m] } else { (buf[m - 1] + buf[m]) / 2.0 }
+    }).collect()
+}
+
+/// Exponential smoothing with smoothing factor `2 / (span + 1)` (an EMA of
+/// the given span). Causal: each output depends only on earlier samples.
+/// A NaN sample gives NaN and leaves the running average untouched.
+pub fn exponential_smoothing(ys: &[f64], span: usize) -> Vec<f64> {
+    let alpha = 2.0 / (span.max(1) as f64 + 1.0);
+    let mut state: Option<f64> = None;
+    ys.iter().map(|&y| {
+        if y.is_nan() {
+            return f64::NAN;
+        }
+        let s = state.map_or(y, |s| s + alpha * (y - s));
+        state = Some(s);
+        s
+    }).collect()
+}
+
+/// How [`find_spikes`] tells a spike from the signal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpikeTest {
+    /// Hampel filter: further than `threshold` scaled MADs (a robust σ) from
+    /// the median of a centred `window`. Follows slow trends.
+    Hampel { window: usize, threshold: f64 },
+    /// Further than `threshold` standard deviations from the mean of the
+    /// whole series. Only for a signal without a trend.
+    ZScore { threshold: f64 },
+}
+
+impl SpikeTest {
+    /// `"hampel"` or `"zscore"`, with the window (Hampel only) and threshold.
+    pub fn parse(method: &str, window: usize, threshold: f64) -> Result<SpikeTest, String> {
+        if !(threshold.is_finite() && threshold > 0.0) {
+            return Err("spike threshold must be a positive number".to_string());
+        }
+        match method {
+            "hampel" if window >= 3 => Ok(SpikeTest::Hampel { window, threshold }),
+            "hampel" => Err("Hampel window must be at least 3 samples".to_string()),
+            "zscore" => Ok(SpikeTest::ZScore { threshold }),
+            _ => Err(format!("unknown despike method {method:?}")),
+        }
+    }
+}
+
+/// Scale from the median absolute deviation to σ for normal noise.
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+/// Indices of the samples of `ys` that `test` flags as spikes, ascending.
+/// NaNs are never spikes.
+pub fn find_spikes(ys: &[f64], test: SpikeTest) -> Vec<usize> {
+    match test {
+        SpikeTest::Hampel { window, threshold } => {
+            let medians = median_filter(ys, window);
+            let deviations: Vec<f64> = ys.iter().zip(&medians).map(|(y, m)| (y - m).abs()).collect();
+            let mads = median_filter(&deviations, window);
+            (0..ys.len()).filter(|&i| deviations[i] > threshold * MAD_TO_SIGMA * mads[i]).collect()
+        }
+        SpikeTest::ZScore { threshold } => {
+            let finite: Vec<f64> = ys.iter().copied().filter(|v| v.is_finite()).collect();
+            if finite.len() < 2 {
+                return vec![];
+            }
+            let mean = finite.iter().sum::<f64>() / finite.len() as f64;
+            let std = (finite.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / finite.len() as f64).sqrt();
+            (0..ys.len()).filter(|&i| (ys[i] - mean).abs() > threshold * std).collect()
+        }
+    }
+}
+
+/// `ys` with the samples at `spikes` (ascending) replaced by a straight line
+/// in X between their nearest kept neighbours; a spike at either end takes
+/// the nearest kept value.
+pub fn interpolate_spikes(xs: &[f64], ys: &[f64], spikes: &[usize]) -> Vec<f64> {
+    let mut out = ys.to_vec();
+    let is_spike = |i: usize| spikes.binary_search(&i).is_ok();
+    let kept = |i: usize| !is_spike(i) && ys[i].is_finite() && xs[i].is_finite();
+    for &i in spikes {
+        let before = (0..i).rev().find(|&j| kept(j));
+        let after = (i + 1..ys.len()).find(|&j| kept(j));
+        out[i] = match (before, after) {
+            (Some(a), Some(b)) if xs[b] != xs[a] => ys[a] + (ys[b] - ys[a]) * (xs[i] - xs[a]) / (xs[b] - xs[a]),
+            (Some(a), _) => ys[a],
+            (None, Some(b)) => ys[b],
+            (None, None) => f64::NAN,
+        };
+    }
+    out
+}
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+
+    #[test]
+    fn moving_average_centered_window3() {
+        let ys = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        // centered window 3 (half=1), clamped at edges:
+        // i0:{1,2}=1.5  i1:{1,2,3}=2  i2:{2,3,4}=3  i3:{3,4,5}=4  i4:{4,5}=4.5
+        assert_eq!(moving_average(&ys, 3), vec![1.5, 2.0, 3.0, 4.0, 4.5]);
+    }
+    #[test]
+    fn moving_average_window1_is_identity() {
+        let ys = vec![1.0, 9.0, 4.0];
+        assert_eq!(moving_average(&ys, 1), ys);
+    }
+    #[test]
+    fn savitzky_golay_preserves_polynomials_to_its_order() {
+        let ys: Vec<f64> = (0..30).map(|i| { let x = i as f64; 0.5 * x * x - 3.0 * x + 2.0 }).collect();
+        let sm = savitzky_golay(&ys, 7, 2);
+        for (a, b) in ys.iter().zip(&sm) { assert!((a - b).abs() < 1e-6, "{a} vs {b}"); }
+        // Window length is forced odd and beyond the order; tiny input passes through.
+        assert_eq!(savitzky_golay(&[1.0, 2.0], 5, 2), vec![1.0, 2.0]);
+    }
+    #[test]
+    fn savitzky_golay_keeps_peaks_better_than_moving_average() {
+        let ys: Vec<f64> = (0..101).map(|i| (-((i as f64 - 50.0) / 4.0).powi(2)).exp()).collect();
+        let sg = savitzky_golay(&ys, 11, 4);
+        let ma = moving_average(&ys, 11);
+        assert!((1.0 - sg[50]).abs() < (1.0 - ma[50]).abs() / 4.0, "sg {} ma {}", sg[50], ma[50]);
+    }
+    #[test]
+    fn median_filter_removes_spikes() {
+        let ys = [1.0, 1.0, 50.0, 1.0, 1.0, f64::NAN, 2.0];
+        let m = median_filter(&ys, 3);
+        assert_eq!(m[..5], [1.0, 1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(m[5], 1.5, "NaN skipped within the window");
+        assert!(median_filter(&[f64::NAN], 3)[0].is_nan());
+    }
+    #[test]
+    fn exponential_smoothing_tracks_a_step() {
+        let ys = [0.0, 1.0, 1.0, f64::NAN, 1.0];
+        let e = exponential_smoothing(&ys, 3); // alpha 0.5
+        assert_eq!(e[..3], [0.0, 0.5, 0.75]);
+        assert!(e[3].is_nan());
+        assert_eq!(e[4], 0.875);
+        assert_eq!(exponential_smoothing(&ys[..3], 1), ys[..3].to_vec());
+    }
+
+    #[test]
+    fn hampel_finds_spikes_on_a_trend() {
+        let mut ys: Vec<f64> = (0..40).map(|i| i as f64 * 0.5 + if i % 2 == 0 { 0.05 } else { -0.05 }).collect();
+        ys[10] = 80.0;
+        ys[25] = -30.0;
+        ys[31] = f64::NAN;
+        let spikes = find_spikes(&ys, SpikeTest::Hampel { window: 7, threshold: 3.0 });
+        assert_eq!(spikes, [10, 25], "the ramp itself isn't flagged");
+        let z = find_spikes(&ys, SpikeTest::ZScore { threshold: 3.0 });
+        assert_eq!(z, [10]);
+        assert!(SpikeTest::parse("hampel", 1, 3.0).is_err());
+        assert_eq!(SpikeTest::parse("zscore", 0, 2.5), Ok(SpikeTest::ZScore { threshold: 2.5 }));
+    }
+
+    #[test]
+    fn spikes_are_bridged_linearly() {
+        let xs = [0.0, 1.0, 2.0, 3.0, 5.0];
+        let ys = [9.0, 1.0, 50.0, 3.0, -40.0];
+        assert_eq!(interpolate_spikes(&xs, &ys, &[0, 2, 4]), [1.0, 1.0, 2.0, 3.0, 3.0]);
+    }
+}
@@ -0,0 +1,296 @@
+//! Least-squares curve fits: polynomial (linear is degree 1), exponential
+//! and power law.
+//!
+//! Polynomials are fitted in a variable scaled to `[-1, 1]` over the data
+//! (normal equations on raw epoch seconds would be hopelessly
+//! ill-conditioned) and their coefficients reported in powers of
+//! `x − x₀`, `x₀` chosen by the caller (the first sample of a datetime axis,
+//! zero otherwise). Exponential and power-law fits are linear fits of
+//! `ln y`; they use only the points where the logarithms exist. R² is
+//! always measured on `y` itself.
+
+/// Highest polynomial degree offered.
+pub const MAX_DEGREE: usize = 10;
+
+/// Model family of a fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FitModel {
+    /// `c₀ + c₁u + … + c_d·u^d` with `u = x − x₀`.
+    Polynomial(usize),
+    /// `a·e^(b·u)`.
+    Exponential,
+    /// `a·x^b` (ignores `x₀`; needs positive X).
+    PowerLaw,
+}
+
+impl FitModel {
+    /// Parse `"linear"`, `"polynomial"` (of `degree`), `"exponential"` or
+    /// `"power"`.
+    pub fn parse(kind: &str, degree: usize) -> Option<FitModel> {
+        Some(match kind {
+            "linear" => FitModel::Polynomial(1),
+            "polynomial" if (1..=MAX_DEGREE).contains(&degree) => FitModel::Polynomial(degree),
+            "exponential" => FitModel::Exponential,
+            "power" => FitModel::PowerLaw,
+            _ => return None,
+        })
+    }
+
+    /// Short name for series labels (`"poly3"`).
+    pub fn label(self) -> String {
+        match self {
+            FitModel::Polynomial(1) => "linear".to_string(),
+            FitModel::Polynomial(d) => format!("poly{d}"),
+            FitModel::Exponential => "exp".to_string(),
+            FitModel::PowerLaw => "power".to_string(),
+        }
+    }
+}
+
+/// A fitted curve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fit {
+    pub model: FitModel,
+    /// Polynomial: `c₀ … c_d`; exponential and power law: `[a, b]`.
+    pub coefficients: Vec<f64>,
+    pub x0: f64,
+    /// Coefficient of determination over the points fitted.
+    pub r_squared: f64,
+    /// Points the fit used.
+    pub count: usize,
+    /// Polynomial coefficients in the scaled variable `(x − centre) / half`,
+    /// for evaluation.
+    scaled: Vec<f64>,
+    centre: f64,
+    half: f64,
+}
+
+impl Fit {
+    /// The fitted curve at `x` (NaN where a power law is undefined).
+    pub fn eval(&self, x: f64) -> f64 {
+        match self.model {
+            FitModel::Polynomial(_) => horner(&self.scaled, (x - self.centre) / self.half),
+            FitModel::Exponential => self.coefficients[0] * (self.coefficients[1] * (x - self.x0)).exp(),
+            FitModel::PowerLaw if x > 0.0 => self.coefficients[0] * x.powf(self.coefficients[1]),
+            FitModel::PowerLaw => f64::NAN,
+        }
+    }
+
+    /// The fitted equation, e.g. `y = 1.5 + 0.25·x − 0.01·x²`.
+    pub fn equation(&self) -> String {
+        let u = if self.x0 == 0.0 { "x".to_string() } else { "(x − x₀)".to_string() };
+        let c = &self.coefficients;
+        match self.model {
+            FitModel::Polynomial(_) => {
+                let mut out = format!("y = {}", coef(c[0]));
+                for (k, &v) in c.iter().enumerate().skip(1) {
+                    let sign = if v < 0.0 { '−' } else { '+' };
+                    let power = match k {
+                        1 => u.clone(),
+                        _ => format!("{u}{}", superscript(k)),
+                    };
+                    out.push_str(&format!(" {sign} {}·{power}", coef(v.abs())));
+                }
+                out
+            }
+            FitModel::Exponential => format!("y = {}·e^({}·{u})", coef(c[0]), coef(c[1])),
+            FitModel::PowerLaw => format!("y = {}·x^{}", coef(c[0]), coef(c[1])),
+        }
+    }
+}
+
+fn horner(coefficients: &[f64], t: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * t + c)
+}
+
+/// Six significant figures, scientific outside `[1e-3, 1e6)`.
+fn coef(v: f64) -> String {
+    if v != 0.0 && (v.abs() >= 1e6 || v.abs() < 1e-3) {
+        return format!("{v:.5e}");
+    }
+    let decimals = (5 - v.abs().log10().floor().max(0.0) as i32).max(0) as usize;
+    let s = format!("{v:.decimals$}");
+    if s.contains('.') { s.trim_end_matches('0').trim_end_matches('.').to_string() } else { s }
+}
+
+fn superscript(n: usize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string().chars().map(|d| DIGITS[d as usize - '0' as usize]).collect()
+}
+
+/// Solve `a · x = b` (row-major `n × n`) by Gaussian elimination with
+/// partial pivoting; `None` when singular.
+fn solve(mut a: Vec<f64>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    let scale = a.iter().fold(0.0f64, |m, v| m.max(v.abs()));
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i * n + col].abs().total_cmp(&a[j * n + col].abs()))?;
+        if a[pivot * n + col].abs() <= scale * 1e-13 {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(col * n + k, pivot * n + k);
+            }
+            b.swap(col, pivot);
+        }
+        for row in col + 1..n {
+            let f = a[row * n + col] / a[col * n + col];
+            for k in col..n {
+                a[row * n + k] -= f * a[col * n + k];
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let tail: f64 = (row + 1..n).map(|k| a[row * n + k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row * n + row];
+    }
+    Some(x)
+}
+
+/// Least-squares polynomial of `degree` through `(t, y)`, `t` in `[-1, 1]`.
+fn poly_fit(ts: &[f64], ys: &[f64], degree: usize) -> Option<Vec<f64>> {
+    let m = degree + 1;
+    let mut ata = vec![0.0; m * m];
+    let mut aty = vec![0.0; m];
+    let mut powers = vec![0.0; 2 * m - 1];
+    for (&t, &y) in ts.iter().zip(ys) {
+        let mut p = 1.0;
+        for slot in powers.iter_mut() {
+            *slot = p;
+            p *= t;
+        }
+        for (i, row) in ata.chunks_mut(m).enumerate() {
+            aty[i] += powers[i] * y;
+            for (cell, p) in row.iter_mut().zip(&powers[i..]) {
+                *cell += p;
+            }
+        }
+    }
+    solve(ata, aty)
+}
+
+/// Coefficients of `p((u − shift) / half)` (given in powers of its
+/// argument) re-expressed in powers of `u`.
+fn unscale(scaled: &[f64], shift: f64, half: f64) -> Vec<f64> {
+    // Expand Σ c_k ((u − shift) / half)^k with the binomial theorem.
+    let mut out = vec![0.0; scaled.len()];
+    for (k, &c) in scaled.iter().enumerate() {
+        let mut binom = 1.0;
+        for (j, slot) in out.iter_mut().enumerate().take(k + 1) {
+            // C(k, j) · u^j · (−shift)^(k−j) / half^k
+            *slot += c * binom * (-shift).powi((k - j) as i32) / half.powi(k as i32);
+            binom = binom * (k - j) as f64 / (j + 1) as f64;
+        }
+    }
+    out
+}
+
+/// Fit `model` to the finite points of `(xs, ys)`, coefficients relative to
+/// `x0`.
+pub fn fit(model: FitModel, xs: &[f64], ys: &[f64], x0: f64) -> Result<Fit, String> {
+    let usable = |x: f64, y: f64| match model {
+        FitModel::Polynomial(_) => true,
+        FitModel::Exponential => y > 0.0,
+        FitModel::PowerLaw => x > 0.0 && y > 0.0,
+    };
+    let pts: Vec<(f64, f64)> = xs
+        .iter()
+        .zip(ys)
+        .filter(|(x, y)| x.is_finite() && y.is_finite() && usable(**x, **y))
+        .map(|(&x, &y)| (x, y))
+        .collect();
+    let needed = match model {
+        FitModel::Polynomial(d) => d + 1,
+        _ => 2,
+    };
+    if pts.len() < needed {
+        return Err(match model {
+            FitModel::Polynomial(_) => format!("a degree-{} fit needs at least {needed} points", needed - 1),
+            FitModel::Exponential => "an exponential fit needs at least 2 points with Y > 0".to_string(),
+            FitModel::PowerLaw => "a power-law fit needs at least 2 points with X > 0 and Y > 0".to_string(),
+        });
+    }
+
+    // Polynomial in the scaled variable; the log models are linear fits of ln y.
+    let (fx, fy): (Vec<f64>, Vec<f64>) = match model {
+        FitModel::Polynomial(_) => pts.iter().copied().unzip(),
+        FitModel::Exponential => pts.iter().map(|&(x, y)| (x, y.ln())).unzip(),
+        FitModel::PowerLaw => pts.iter().map(|&(x, y)| (x.ln(), y.ln())).unzip(),
+    };
+    let lo = fx.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = fx.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let centre = (lo + hi) / 2.0;
+    let half = if hi > lo { (hi - lo) / 2.0 } else { 1.0 };
+    let ts: Vec<f64> = fx.iter().map(|x| (x - centre) / half).collect();
+    let degree = match model {
+        FitModel::Polynomial(d) => d,
+        _ => 1,
+    };
+    let scaled = poly_fit(&ts, &fy, degree)
+        .ok_or_else(|| format!("not enough distinct X values for a degree-{degree} fit"))?;
+
+    let (coefficients, x0) = match model {
+        FitModel::Polynomial(_) => (unscale(&scaled, centre - x0, half), x0),
+        FitModel::Exponential => {
+            let line = unscale(&scaled, centre - x0, half);
+            (vec![line[0].exp(), line[1]], x0)
+        }
+        FitModel::PowerLaw => {
+            let line = unscale(&scaled, centre, half);
+            (vec![line[0].exp(), line[1]], 0.0)
+        }
+    };
+    let mut out = Fit { model, coefficients, x0, r_squared: f64::NAN, count: pts.len(), scaled, centre, half };
+
+    let mean = pts.iter().map(|p| p.1).sum::<f64>() / pts.len() as f64;
+    let ss_tot: f64 = pts.iter().map(|p| (p.1 - mean).powi(2)).sum();
+    let ss_res: f64 = pts.iter().map(|&(x, y)| (y - out.eval(x)).powi(2)).sum();
+    out.r_squared = if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else if ss_res == 0.0 { 1.0 } else { f64::NAN };
+    Ok(out)
+}
+
+#[cfg(test)]
+mod fitting_tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() <= 1e-6 * b.abs().max(1.0)
+    }
+
+    #[test]
+    fn polynomial_recovers_coefficients_relative_to_x0() {
+        let xs: Vec<f64> = (0..50).map(|i| 1.7e9 + i as f64 * 60.0).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| { let u = x - 1.7e9; 2.0 - 0.5 * u + 2e-3 * u * u }).collect();
+        let f = fit(FitModel::Polynomial(2), &xs, &ys, 1.7e9).unwrap();
+        assert!(close(f.coefficients[0], 2.0) && close(f.coefficients[1], -0.5) && close(f.coefficients[2], 2e-3), "{:?}", f.coefficients);
+        assert!(close(f.r_squared, 1.0));
+        assert!(close(f.eval(1.7e9 + 90.0), 2.0 - 45.0 + 16.2));
+        assert!(f.equation().starts_with("y = 2 − 0.5·(x − x₀) + 0.002·(x − x₀)²"), "{}", f.equation());
+    }
+
+    #[test]
+    fn linear_r_squared_drops_with_noise() {
+        let xs: Vec<f64> = (0..20).map(f64::from).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| 3.0 * x + 1.0 + if *x as i32 % 2 == 0 { 2.0 } else { -2.0 }).collect();
+        let f = fit(FitModel::parse("linear", 0).unwrap(), &xs, &ys, 0.0).unwrap();
+        assert!((f.coefficients[1] - 3.0).abs() < 0.05);
+        assert!(f.r_squared > 0.9 && f.r_squared < 1.0);
+        assert!(fit(FitModel::Polynomial(3), &xs[..3], &ys[..3], 0.0).is_err());
+        assert!(fit(FitModel::Polynomial(1), &[1.0, 1.0], &[1.0, 2.0], 0.0).is_err(), "one distinct X");
+    }
+
+    #[test]
+    fn exponential_and_power_law() {
+        let xs: Vec<f64> = (1..30).map(|i| i as f64 * 0.1).collect();
+        let e = fit(FitModel::Exponential, &xs, &xs.iter().map(|x| 2.5 * (0.7 * x).exp()).collect::<Vec<_>>(), 0.0).unwrap();
+        assert!(close(e.coefficients[0], 2.5) && close(e.coefficients[1], 0.7), "{:?}", e.coefficients);
+        let p = fit(FitModel::PowerLaw, &xs, &xs.iter().map(|x| 4.0 * x.powf(1.5)).collect::<Vec<_>>(), 0.0).unwrap();
+        assert!(close(p.coefficients[0], 4.0) && close(p.coefficients[1], 1.5), "{:?}", p.coefficients);
+        assert!(close(p.r_squared, 1.0));
+        assert!(p.eval(-1.0).is_nan());
+        assert!(fit(FitModel::Exponential, &[1.0, 2.0], &[-1.0, 0.0], 0.0).is_err());
+    }
+}
@@ -78,6 +78,51 @@ pub fn resample(xs: &[f64], ys: &[f64], n: usize, method: Method) -> (Vec<f64>,
     (grid, out)
 }
 
+/// The value showing at `x` under sample-and-hold (legend values): the last
+/// finite sample at or before it, `None` outside the series' X span. With
+/// `x = None`, the latest finite sample. `xs` ascending.
+pub fn held_value(xs: &[f64], ys: &[f64], x: Option<f64>) -> Option<f64> {
+    let end = match x {
+        None => ys.len(),
+        Some(x) => {
+            let (&first, &last) = (xs.first()?, xs.last()?);
+            if !(first <= x && x <= last) { return None; }
+            xs.partition_point(|&v| v <= x)
+        }
+    };
+    ys[..end.min(ys.len())].iter().rev().copied().find(|y| y.is_finite())
+}
+
+/// The value a line plot shows at `x` (cursor readouts): linear between the
+/// finite samples either side of it, `None` outside the series' X span or
+/// with no finite sample on one side. `xs` ascending.
+pub fn value_at(xs: &[f64], ys: &[f64], x: f64) -> Option<f64> {
+    let n = xs.len().min(ys.len());
+    let (&first, &last) = (xs.first()?, xs[..n].last()?);
+    if !(first <= x && x <= last) { return None; }
+    let i = xs[..n].partition_point(|&v| v <= x);
+    let lo = (0..i).rev().find(|&j| ys[j].is_finite())?;
+    if xs[lo] == x { return Some(ys[lo]); }
+    let hi = (i..n).find(|&j| ys[j].is_finite())?;
+    let (x0, x1, y0, y1) = (xs[lo], xs[hi], ys[lo], ys[hi]);
+    Some(if x1 == x0 { y0 } else { y0 + (y1 - y0) * (x - x0) / (x1 - x0) })
+}
+
+/// Index of the finite sample nearest `x` in X (snapping cursors to data);
+/// ties go to the earlier sample. `None` without a finite sample. `xs`
+/// ascending.
+pub fn nearest_sample(xs: &[f64], ys: &[f64], x: f64) -> Option<usize> {
+    let n = xs.len().min(ys.len());
+    let finite = |&j: &usize| xs[j].is_finite() && ys[j].is_finite();
+    let i = xs[..n].partition_point(|&v| v < x);
+    let before = (0..i).rev().find(finite);
+    let after = (i..n).find(finite);
+    match (before, after) {
+        (Some(b), Some(a)) => Some(if x - xs[b] <= xs[a] - x { b } else { a }),
+        (b, a) => b.or(a),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,6 +160,36 @@ mod tests {
         assert_eq!(*gx.last().unwrap(), 9.0);
     }
     #[test]
+    fn held_value_holds_the_previous_sample() {
+        let (xs, ys) = ([0.0, 1.0, 2.0, 3.0], [5.0, 6.0, f64::NAN, 8.0]);
+        assert_eq!(held_value(&xs, &ys, Some(1.5)), Some(6.0));
+        assert_eq!(held_value(&xs, &ys, Some(2.0)), Some(6.0)); // NaN sample: hold
+        assert_eq!(held_value(&xs, &ys, Some(3.0)), Some(8.0));
+        assert_eq!(held_value(&xs, &ys, Some(-1.0)), None);
+        assert_eq!(held_value(&xs, &ys, Some(4.0)), None);
+        assert_eq!(held_value(&xs, &ys, None), Some(8.0));
+    }
+    #[test]
+    fn value_at_interpolates_between_finite_samples() {
+        let (xs, ys) = ([0.0, 1.0, 2.0, 3.0], [0.0, 10.0, f64::NAN, 30.0]);
+        assert_eq!(value_at(&xs, &ys, 0.5), Some(5.0));
+        assert_eq!(value_at(&xs, &ys, 1.0), Some(10.0));
+        assert_eq!(value_at(&xs, &ys, 2.0), Some(20.0)); // bridges the NaN
+        assert_eq!(value_at(&xs, &ys, 3.0), Some(30.0));
+        assert_eq!(value_at(&xs, &ys, 3.5), None);
+        assert_eq!(value_at(&[], &[], 0.0), None);
+    }
+    #[test]
+    fn nearest_sample_skips_gaps() {
+        let (xs, ys) = ([0.0, 1.0, 2.0, 3.0], [5.0, f64::NAN, 7.0, 8.0]);
+        assert_eq!(nearest_sample(&xs, &ys, 0.9), Some(0)); // the NaN at 1 is skipped
+        assert_eq!(nearest_sample(&xs, &ys, 1.5), Some(2));
+        assert_eq!(nearest_sample(&xs, &ys, 1.0), Some(0)); // tie → earlier
+        assert_eq!(nearest_sample(&xs, &ys, 99.0), Some(3));
+        assert_eq!(nearest_sample(&xs, &ys, -5.0), Some(0));
+        assert_eq!(nearest_sample(&[], &[], 0.0), None);
+    }
+    #[test]
     fn degenerate_returns_input() {
         let (gx, gy) = resample(&[1.0], &[2.0], 50, Method::Linear);
         assert_eq!((gx, gy), (vec![1.0], vec![2.0]));
@@ -36,3 +36,62 @@ impl HoverTree {
         (idx, dist)
     }
 }
+
+/// Nearest sample to a screen position, searched in pixel space so X and Y
+/// units don't skew the distance. Only samples with X in `[x_lo, x_hi]` are
+/// candidates (`xs` ascending); past `max_points` candidates they are evenly
+/// strided. `to_px` maps a sample to pixels (`None` = not drawn, e.g. y ≤ 0 in
+/// Log scale). Returns `(sample index, distance in pixels)`.
+pub fn nearest_on_screen(
+    xs: &[f64],
+    ys: &[f64],
+    x_lo: f64,
+    x_hi: f64,
+    max_points: usize,
+    query_px: (f64, f64),
+    to_px: impl Fn(f64, f64) -> Option<(f64, f64)>,
+) -> Option<(usize, f64)> {
+    let n = xs.len().min(ys.len());
+    let start = xs[..n].partition_point(|&x| x < x_lo);
+    let end = xs[..n].partition_point(|&x| x <= x_hi);
+    if start >= end {
+        return None;
+    }
+    let stride = (end - start).div_ceil(max_points.max(1));
+    let mut px = Vec::new();
+    let mut py = Vec::new();
+    let mut orig = Vec::new();
+    for i in (start..end).step_by(stride) {
+        if let Some((sx, sy)) = to_px(xs[i], ys[i]) {
+            px.push(sx);
+            py.push(sy);
+            orig.push(i);
+        }
+    }
+    if orig.is_empty() {
+        return None;
+    }
+    let (k, dist) = HoverTree::build(&px, &py).nearest(query_px.0, query_px.1);
+    Some((orig[k], dist))
+}
+
+#[cfg(test)]
+mod kd_tree_tests {
+    use super::*;
+
+    #[test]
+    fn nearest_is_measured_in_pixels() {
+        // X spans 0..1000 over 100 px, Y spans 0..1 over 100 px.
+        let xs = [0.0, 500.0, 1000.0];
+        let ys = [0.0, 1.0, 0.0];
+        let to_px = |x: f64, y: f64| Some((x / 10.0, (1.0 - y) * 100.0));
+        // Near the middle-top of the plot: the peak sample wins even though
+        // it's 500 data units away in X.
+        let (i, d) = nearest_on_screen(&xs, &ys, 0.0, 1000.0, 1000, (52.0, 0.0), to_px).unwrap();
+        assert_eq!(i, 1);
+        assert!((d - 2.0).abs() < 1e-9);
+        assert!(nearest_on_screen(&xs, &ys, 2000.0, 3000.0, 1000, (0.0, 0.0), to_px).is_none());
+        let hidden = |_: f64, _: f64| None;
+        assert!(nearest_on_screen(&xs, &ys, 0.0, 1000.0, 1000, (0.0, 0.0), hidden).is_none());
+    }
+}
@@ -17,6 +17,33 @@ impl MathOp {
         }
     }
 
+    /// Parse an op name (`"add"`, `"sub"`, `"mul"`, `"div"`, or the symbol).
+    pub fn parse(s: &str) -> Option<MathOp> {
+        match s.trim() {
+            "add" | "+" => Some(MathOp::Add),
+            "sub" | "subtract" | "-" => Some(MathOp::Subtract),
+            "mul" | "multiply" | "*" | "\u{00d7}" => Some(MathOp::Multiply),
+            "div" | "divide" | "/" | "\u{00f7}" => Some(MathOp::Divide),
+            _ => None,
+        }
+    }
+
+    /// Apply the op to one pair of values (division by ~zero → NaN).
+    pub fn apply(&self, a: f64, b: f64) -> f64 {
+        match self {
+            MathOp::Add => a + b,
+            MathOp::Subtract => a - b,
+            MathOp::Multiply => a * b,
+            MathOp::Divide => {
+                if b.abs() < f64::EPSILON {
+                    f64::NAN
+                } else {
+                    a / b
+                }
+            }
+        }
+    }
+
     pub fn label(&self) -> &'static str {
         match self {
             MathOp::Add => "Add (+)",
@@ -75,18 +102,7 @@ pub fn perform_math(
 
         if let Some(j) = best_j {
             if best_diff <= tolerance {
-                let result = match op {
-                    MathOp::Add => y1[i] + y2[j],
-                    MathOp::Subtract => y1[i] - y2[j],
-                    MathOp::Multiply => y1[i] * y2[j],
-                    MathOp::Divide => {
-                        if y2[j].abs() < f64::EPSILON {
-                            f64::NAN
-                        } else {
-                            y1[i] / y2[j]
-                        }
-                    }
-                };
+                let result = op.apply(y1[i], y2[j]);
                 common_x.push(xv);
                 common_y.push(result);
                 matched += 1;
@@ -108,19 +124,6 @@ pub fn perform_math(
     })
 }
 
-/// Centered simple moving average, window >= 1, clamped at the edges.
-pub fn moving_average(ys: &[f64], window: usize) -> Vec<f64> {
-    let n = ys.len();
-    let w = window.max(1);
-    let half = w / 2;
-    (0..n).map(|i| {
-        let lo = i.saturating_sub(half);
-        let hi = (i + half + 1).min(n);
-        let slice = &ys[lo..hi];
-        slice.iter().sum::<f64>() / slice.len() as f64
-    }).collect()
-}
-
 /// Numerical dy/dx: central difference interior, forward/backward at the ends.
 pub fn derivative(xs: &[f64], ys: &[f64]) -> Vec<f64> {
     let n = ys.len().min(xs.len());
@@ -151,6 +154,57 @@ pub fn integral(xs: &[f64], ys: &[f64]) -> Vec<f64> {
     out
 }
 
+/// Trapezoidal area under the line from X `a` to X `b` (cursors), with the
+/// line interpolated at both ends so the area stops exactly there.
+/// Non-finite samples are bridged, as in `RegionStats::integral`. Negative
+/// when `b < a`; `None` unless both ends lie within the finite samples' X
+/// span. `xs` ascending.
+pub fn integral_between(xs: &[f64], ys: &[f64], a: f64, b: f64) -> Option<f64> {
+    let n = xs.len().min(ys.len());
+    let (lo, hi) = if a <= b { (a, b) } else { (b, a) };
+    let finite = |&i: &usize| xs[i].is_finite() && ys[i].is_finite();
+    let start = xs[..n].partition_point(|&x| x < lo);
+    let end = xs[..n].partition_point(|&x| x <= hi).max(start);
+    // The samples inside, plus the nearest finite one beyond each end.
+    let pts: Vec<(f64, f64)> = (0..start)
+        .rev()
+        .find(finite)
+        .into_iter()
+        .chain((start..end).filter(finite))
+        .chain((end..n).find(finite))
+        .map(|i| (xs[i], ys[i]))
+        .collect();
+    if !(pts.first()?.0 <= lo && pts.last()?.0 >= hi) {
+        return None;
+    }
+    let mut area = 0.0;
+    for w in pts.windows(2) {
+        let ((x0, y0), (x1, y1)) = (w[0], w[1]);
+        let (c0, c1) = (x0.max(lo), x1.min(hi));
+        if c1 <= c0 {
+            continue;
+        }
+        let at = |x: f64| y0 + (y1 - y0) * (x - x0) / (x1 - x0);
+        area += (c1 - c0) * (at(c0) + at(c1)) / 2.0;
+    }
+    Some(if a <= b { area } else { -area })
+}
+
+/// `unit` as one factor of a compound unit: parenthesised if compound itself.
+fn unit_factor(unit: &str) -> String {
+    if unit.contains(['/', '·', ' ']) { format!("({unit})") } else { unit.to_string() }
+}
+
+/// Unit of [`derivative`] for a `y_unit` series over `x_unit`: `"V/s"`.
+pub fn derivative_unit(y_unit: &str, x_unit: &str) -> String {
+    format!("{}/{}", unit_factor(y_unit), unit_factor(x_unit))
+}
+
+/// Unit of [`integral`] for a `y_unit` series over `x_unit`: `"A·s"`.
+pub fn integral_unit(y_unit: &str, x_unit: &str) -> String {
+    format!("{}·{}", unit_factor(y_unit), unit_factor(x_unit))
+}
+
 /// Normalize to [0,1] (min-max) or zero-mean/unit-std (z-score).
 pub fn normalize(ys: &[f64], zscore: bool) -> Vec<f64> {
     let finite: Vec<f64> = ys.iter().copied().filter(|v| v.is_finite()).collect();
@@ -173,23 +227,89 @@ pub fn normalize(ys: &[f64], zscore: bool) -> Vec<f64> {
 pub fn map_abs(ys: &[f64]) -> Vec<f64> { ys.iter().map(|v| v.abs()).collect() }
 pub fn map_ln(ys: &[f64]) -> Vec<f64> { ys.iter().map(|v| v.ln()).collect() }
 pub fn map_sqrt(ys: &[f64]) -> Vec<f64> { ys.iter().map(|v| v.sqrt()).collect() }
+pub fn map_log10(ys: &[f64]) -> Vec<f64> { ys.iter().map(|v| v.log10()).collect() }
+pub fn map_exp(ys: &[f64]) -> Vec<f64> { ys.iter().map(|v| v.exp()).collect() }
+
+/// Unwrap an angle series: wherever consecutive samples jump by more than half
+/// a `period` (360 for degrees, 2π for radians), shift that sample and all
+/// later ones by whole periods so the signal is continuous. NaN samples pass
+/// through without resetting the running offset.
+pub fn unwrap(ys: &[f64], period: f64) -> Vec<f64> {
+    let mut offset = 0.0;
+    let mut prev: Option<f64> = None;
+    ys.iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                return v;
+            }
+            if let Some(p) = prev {
+                offset -= period * ((v - p) / period).round();
+            }
+            prev = Some(v);
+            v + offset
+        })
+        .collect()
+}
+
+/// `xs` shifted so the first finite sample sits at zero (elapsed time from
+/// the start of a run).
+pub fn zero_start(xs: &[f64]) -> Vec<f64> {
+    let x0 = xs.iter().copied().find(|x| x.is_finite()).unwrap_or(0.0);
+    xs.iter().map(|x| x - x0).collect()
+}
+
+/// Insert a NaN break between consecutive samples of a modular signal (compass
+/// heading, wrapped phase) that jump by more than half a `period`, so the plot
+/// shows the wrap as a gap instead of a vertical line across the whole axis.
+pub fn break_wraps(xs: &[f64], ys: &[f64], period: f64) -> (Vec<f64>, Vec<f64>) {
+    let half = period.abs() * 0.5;
+    let mut out_x = Vec::with_capacity(xs.len());
+    let mut out_y = Vec::with_capacity(ys.len());
+    for (i, (&x, &y)) in xs.iter().zip(ys.iter()).enumerate() {
+        if i > 0 && (y - ys[i - 1]).abs() > half {
+            out_x.push(f64::NAN);
+            out_y.push(f64::NAN);
+        }
+        out_x.push(x);
+        out_y.push(y);
+    }
+    (out_x, out_y)
+}
+
+/// Extend a status/level channel's last value to `x_end` (the right edge of
+/// the view): append `(x_end, last finite y)` when the last sample ends
+/// before it, so the held state stays visible past the final change.
+pub fn hold_last(xs: &mut Vec<f64>, ys: &mut Vec<f64>, x_end: f64) {
+    let last = xs.iter().zip(ys.iter()).rev().find(|(x, y)| x.is_finite() && y.is_finite());
+    if let Some((&x, &y)) = last {
+        if x < x_end && x_end.is_finite() {
+            xs.push(x_end);
+            ys.push(y);
+        }
+    }
+}
+
+/// Sample range kept when cropping `xs` to `[x_min, x_max]`: from the first
+/// sample inside it to the last one, NaN breaks in between included. Empty
+/// when no sample falls inside.
+pub fn x_window(xs: &[f64], x_min: f64, x_max: f64) -> std::ops::Range<usize> {
+    let inside = |x: &f64| *x >= x_min && *x <= x_max;
+    match xs.iter().position(inside) {
+        Some(start) => start..xs.iter().rposition(inside).map_or(start, |end| end + 1),
+        None => 0..0,
+    }
+}
+
+/// Broadcast `op` between every value of `ys` and the constant `c`
+/// (`y op c`), e.g. a unit scale factor or a calibration offset.
+pub fn apply_constant(ys: &[f64], op: MathOp, c: f64) -> Vec<f64> {
+    ys.iter().map(|&v| op.apply(v, c)).collect()
+}
 
 #[cfg(test)]
 mod transform_tests {
     use super::*;
 
-    #[test]
-    fn moving_average_centered_window3() {
-        let ys = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-        // centered window 3 (half=1), clamped at edges:
-        // i0:{1,2}=1.5  i1:{1,2,3}=2  i2:{2,3,4}=3  i3:{3,4,5}=4  i4:{4,5}=4.5
-        assert_eq!(moving_average(&ys, 3), vec![1.5, 2.0, 3.0, 4.0, 4.5]);
-    }
-    #[test]
-    fn moving_average_window1_is_identity() {
-        let ys = vec![1.0, 9.0, 4.0];
-        assert_eq!(moving_average(&ys, 1), ys);
-    }
     #[test]
     fn derivative_of_linear_is_constant_slope() {
         let xs = vec![0.0, 1.0, 2.0, 3.0];
@@ -203,6 +323,22 @@ mod transform_tests {
         assert_eq!(integral(&xs, &ys), vec![0.0, 1.0, 2.0, 3.0]);
     }
     #[test]
+    fn integral_between_interpolates_its_ends() {
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = [0.0, 1.0, f64::NAN, 3.0, 4.0]; // y = x, one sample missing
+        // ∫ x dx from 0.5 to 3.5 = (3.5² − 0.5²) / 2 = 6.
+        assert!((integral_between(&xs, &ys, 0.5, 3.5).unwrap() - 6.0).abs() < 1e-12);
+        assert!((integral_between(&xs, &ys, 3.5, 0.5).unwrap() + 6.0).abs() < 1e-12);
+        assert_eq!(integral_between(&xs, &ys, 1.0, 1.0), Some(0.0));
+        assert_eq!(integral_between(&xs, &ys, -1.0, 2.0), None);
+    }
+    #[test]
+    fn derived_units_compose() {
+        assert_eq!(derivative_unit("V", "s"), "V/s");
+        assert_eq!(derivative_unit("m/s", "s"), "(m/s)/s");
+        assert_eq!(integral_unit("mA", "s"), "mA·s");
+    }
+    #[test]
     fn normalize_minmax_maps_to_unit() {
         assert_eq!(normalize(&[10.0, 20.0, 30.0], false), vec![0.0, 0.5, 1.0]);
     }
@@ -217,5 +353,66 @@ mod transform_tests {
         assert_eq!(map_abs(&[-1.0, 2.0]), vec![1.0, 2.0]);
         assert!(map_ln(&[-1.0])[0].is_nan());
         assert_eq!(map_sqrt(&[4.0, 9.0]), vec![2.0, 3.0]);
+        assert_eq!(map_log10(&[100.0]), vec![2.0]);
+        assert_eq!(map_exp(&[0.0]), vec![1.0]);
+    }
+    #[test]
+    fn unwrap_removes_period_jumps() {
+        let deg = unwrap(&[350.0, 355.0, 2.0, 8.0, f64::NAN, 15.0, 350.0], 360.0);
+        assert_eq!(deg[..4], [350.0, 355.0, 362.0, 368.0]);
+        assert!(deg[4].is_nan());
+        assert_eq!(deg[5], 375.0, "NaN keeps the running offset");
+        assert_eq!(deg[6], 350.0, "wrapping back down unwinds");
+        let tau = std::f64::consts::TAU;
+        let rad = unwrap(&[3.0, -3.0], tau);
+        assert!((rad[1] - (tau - 3.0)).abs() < 1e-12);
+    }
+    #[test]
+    fn wrap_breaks_split_heading_crossings() {
+        let (xs, ys) = break_wraps(&[0.0, 1.0, 2.0, 3.0], &[350.0, 359.0, 3.0, 10.0], 360.0);
+        assert_eq!(xs.len(), 5);
+        assert!(xs[2].is_nan() && ys[2].is_nan());
+        assert_eq!(ys[3], 3.0);
+        let (xs, _) = break_wraps(&[0.0, 1.0], &[10.0, 20.0], 360.0);
+        assert_eq!(xs, vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn hold_last_reaches_the_view_edge() {
+        let (mut xs, mut ys) = (vec![0.0, 1.0, f64::NAN], vec![3.0, 4.0, f64::NAN]);
+        hold_last(&mut xs, &mut ys, 5.0);
+        assert_eq!((xs[3], ys[3]), (5.0, 4.0));
+        // Data already past the edge: unchanged.
+        let (mut xs, mut ys) = (vec![0.0, 9.0], vec![1.0, 2.0]);
+        hold_last(&mut xs, &mut ys, 5.0);
+        assert_eq!(xs.len(), 2);
+    }
+
+    #[test]
+    fn x_window_keeps_the_samples_inside() {
+        let xs = [0.0, 1.0, 2.0, f64::NAN, 3.0, 4.0, 5.0];
+        assert_eq!(x_window(&xs, 1.0, 4.5), 1..6, "NaN break kept");
+        assert_eq!(x_window(&xs, -10.0, 10.0), 0..7);
+        assert_eq!(x_window(&xs, 2.2, 2.8), 0..0);
+    }
+
+    #[test]
+    fn constant_broadcasting() {
+        let ys = [1.0, -2.0, 4.0];
+        assert_eq!(apply_constant(&ys, MathOp::Add, 1.5), vec![2.5, -0.5, 5.5]);
+        assert_eq!(apply_constant(&ys, MathOp::Subtract, 1.0), vec![0.0, -3.0, 3.0]);
+        assert_eq!(apply_constant(&ys, MathOp::Multiply, -2.0), vec![-2.0, 4.0, -8.0]);
+        assert_eq!(apply_constant(&ys, MathOp::Divide, 4.0), vec![0.25, -0.5, 1.0]);
+        assert!(apply_constant(&ys, MathOp::Divide, 0.0).iter().all(|v| v.is_nan()));
+        assert_eq!(MathOp::parse("mul"), Some(MathOp::Multiply));
+        assert_eq!(MathOp::parse("pow"), None);
+    }
+
+    #[test]
+    fn zero_start_skips_leading_gaps() {
+        let xs = [f64::NAN, 10.0, 12.5];
+        let out = zero_start(&xs);
+        assert!(out[0].is_nan());
+        assert_eq!(&out[1..], &[0.0, 2.5]);
     }
 }
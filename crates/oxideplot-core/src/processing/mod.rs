@@ -1,5 +1,7 @@
 pub mod downsampling;
 pub mod expr;
+pub mod fft;
+pub mod filters;
 pub mod histogram;
 pub mod interpolation;
 pub mod math_ops;
@@ -7,3 +9,11 @@ pub mod qc;
 pub mod statistics;
 pub mod kd_tree;
 pub mod spectral;
+pub mod segments;
+pub mod uncertainty;
+pub mod stacking;
+pub mod cross_math;
+pub mod area_fill;
+pub mod sparkline;
+pub mod fitting;
+pub mod align;
@@ -1,4 +1,5 @@
-use crate::data::loader::{column_to_f64, column_to_timestamps, resolve_col, LoadedData};
+use crate::data::column::Column;
+use crate::data::loader::{resolve_col, LoadedData};
 use crate::processing::statistics::median_mad;
 use std::collections::HashMap;
 
@@ -143,17 +144,37 @@ pub(crate) fn shift_ratio_at(vals: &[f64], onset: usize, w: usize) -> Option<(f6
     Some((shift, shift.abs() / noise))
 }
 
-/// Longest run of consecutive identical raw cells (flags a frozen/stuck channel).
-pub fn longest_constant_run(cells: &[String]) -> usize {
+/// Longest run of consecutive identical cells (flags a frozen/stuck channel).
+/// Numeric cells compare by bit pattern, so runs of missing (NaN) cells count.
+pub fn longest_constant_run(col: &Column) -> usize {
+    match col {
+        Column::Text(cells) => longest_run(cells),
+        _ => longest_run(&value_bits(col)),
+    }
+}
+
+/// Number of distinct cells (numeric cells by bit pattern).
+pub fn distinct_count(col: &Column) -> usize {
+    match col {
+        Column::Text(cells) => cells.iter().collect::<std::collections::HashSet<_>>().len(),
+        _ => value_bits(col).into_iter().collect::<std::collections::HashSet<_>>().len(),
+    }
+}
+
+fn value_bits(col: &Column) -> Vec<u64> {
+    col.to_f64().iter().map(|v| v.to_bits()).collect()
+}
+
+fn longest_run<T: PartialEq>(items: &[T]) -> usize {
     let mut best = 0usize;
     let mut cur = 0usize;
-    let mut prev: Option<&str> = None;
-    for c in cells {
-        if prev == Some(c.as_str()) {
+    let mut prev: Option<&T> = None;
+    for c in items {
+        if prev == Some(c) {
             cur += 1;
         } else {
             cur = 1;
-            prev = Some(c.as_str());
+            prev = Some(c);
         }
         best = best.max(cur);
     }
@@ -174,8 +195,8 @@ pub fn health_check(
 
     // Dataset-level: time-index gaps (first datetime column).
     for c in 0..data.columns.len() {
-        if let Some((ts, frac)) = column_to_timestamps(&data.column_data[c]) {
-            if frac >= 0.5 && ts.len() >= 3 {
+        if let Some(ts) = data.column_data[c].timestamps() {
+            if data.column_data[c].valid_frac() >= 0.5 && ts.len() >= 3 {
                 let dts: Vec<f64> = ts.windows(2).map(|w| w[1] - w[0]).collect();
                 let mut sorted: Vec<f64> =
                     dts.iter().copied().filter(|d| d.is_finite() && *d > 0.0).collect();
@@ -210,6 +231,7 @@ pub fn health_check(
     }
 
     // Per numeric column checks.
+    #[allow(clippy::needless_range_loop)]
     for c in 0..data.columns.len() {
         if !numeric_cols[c] {
             continue;
@@ -217,17 +239,14 @@ pub fn health_check(
         let name = &data.columns[c];
         let cells = &data.column_data[c];
         // Skip a datetime index column (it's the time axis, not a data channel).
-        if column_to_timestamps(cells)
-            .map(|(_, f)| f >= 0.5)
-            .unwrap_or(false)
-        {
+        if cells.timestamps().is_some() && cells.valid_frac() >= 0.5 {
             continue;
         }
-        let (vals, _) = column_to_f64(cells);
+        let vals = cells.to_f64();
         let finite: Vec<f64> = vals.iter().copied().filter(|v| v.is_finite()).collect();
         let n_finite = finite.len();
         let n_missing = n_rows.saturating_sub(n_finite);
-        let distinct = cells.iter().collect::<std::collections::HashSet<_>>().len();
+        let distinct = distinct_count(cells);
         let const_run = longest_constant_run(cells);
 
         // Dead / constant.
@@ -418,9 +437,9 @@ pub fn health_check(
                 if cols_in.contains(&rc) {
                     continue;
                 }
-                let (rvals, _) = column_to_f64(&data.column_data[rc]);
+                let rvals = data.column_data[rc].to_f64();
                 if let Some((_, ratio)) = shift_ratio_at(&rvals, onset, w) {
-                    if ratio > 5.0 && best.map_or(true, |b| ratio > b.1) {
+                    if ratio > 5.0 && best.is_none_or(|b| ratio > b.1) {
                         best = Some((rc, ratio));
                     }
                 }
@@ -493,7 +512,7 @@ mod qc_tests {
             .map(|(_, v)| v.iter().map(|x| x.to_string()).collect())
             .collect();
         let numeric = vec![true; columns.len()];
-        (LoadedData { columns, column_data, row_count: n }, numeric)
+        (LoadedData::from_string_columns(columns, column_data, n), numeric)
     }
 
     #[test]
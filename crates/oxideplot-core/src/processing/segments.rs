@@ -0,0 +1,159 @@
+//! Splitting a series into segments at X gaps.
+//!
+//! Loggers that stop and restart leave several sessions concatenated in one
+//! file. With a gap threshold set, any X step larger than it separates two
+//! segments: the line is broken there instead of bridging the pause, and
+//! statistics and export can be taken per segment.
+
+use std::ops::Range;
+
+use crate::processing::statistics::SeriesStats;
+
+/// Multiple of the median X step suggested as the gap threshold.
+pub const SUGGESTED_GAP_STEPS: f64 = 10.0;
+
+/// The X gaps of one series for a threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GapSplit {
+    pub threshold: f64,
+    /// `(end, start)`: the last X of a segment and the first X of the next,
+    /// in ascending order.
+    pub gaps: Vec<(f64, f64)>,
+}
+
+impl GapSplit {
+    /// Find every step between consecutive finite X values (ascending)
+    /// larger than `threshold`.
+    pub fn detect(xs: &[f64], threshold: f64) -> Self {
+        let mut gaps = Vec::new();
+        let mut prev: Option<f64> = None;
+        for &x in xs.iter().filter(|x| x.is_finite()) {
+            if let Some(p) = prev {
+                if x - p > threshold {
+                    gaps.push((p, x));
+                }
+            }
+            prev = Some(x);
+        }
+        GapSplit { threshold, gaps }
+    }
+
+    /// No gaps: the whole series is one segment.
+    pub fn none() -> Self {
+        GapSplit { threshold: f64::INFINITY, gaps: Vec::new() }
+    }
+
+    /// Number of segments (gaps + 1).
+    pub fn segment_count(&self) -> usize {
+        self.gaps.len() + 1
+    }
+
+    /// Index ranges of the segments of `xs` (the series `detect` was run on).
+    pub fn segments(&self, xs: &[f64]) -> Vec<Range<usize>> {
+        let mut out = Vec::with_capacity(self.segment_count());
+        let mut start = 0;
+        for &(_, next) in &self.gaps {
+            let end = start + xs[start..].partition_point(|&x| x < next);
+            out.push(start..end);
+            start = end;
+        }
+        out.push(start..xs.len());
+        out
+    }
+
+    /// Insert a NaN break between consecutive samples (of a possibly
+    /// decimated copy of the series) lying on opposite sides of a gap.
+    pub fn break_lines(&self, xs: &[f64], ys: &[f64]) -> (Vec<f64>, Vec<f64>) {
+        let mut out_x = Vec::with_capacity(xs.len() + self.gaps.len());
+        let mut out_y = Vec::with_capacity(ys.len() + self.gaps.len());
+        let mut k = 0;
+        let mut prev = f64::NAN;
+        for (&x, &y) in xs.iter().zip(ys) {
+            if prev.is_finite() && x.is_finite() {
+                while k < self.gaps.len() && self.gaps[k].1 <= prev {
+                    k += 1;
+                }
+                if k < self.gaps.len() && prev <= self.gaps[k].0 && x >= self.gaps[k].1 {
+                    out_x.push(f64::NAN);
+                    out_y.push(f64::NAN);
+                }
+            }
+            if x.is_finite() {
+                prev = x;
+            }
+            out_x.push(x);
+            out_y.push(y);
+        }
+        (out_x, out_y)
+    }
+}
+
+/// Statistics of one segment.
+#[derive(Debug, Clone)]
+pub struct SegmentStats {
+    pub x_start: f64,
+    pub x_end: f64,
+    pub stats: SeriesStats,
+}
+
+/// Per-segment statistics of `ys` (segments without finite values skipped).
+pub fn segment_stats(split: &GapSplit, xs: &[f64], ys: &[f64]) -> Vec<SegmentStats> {
+    split
+        .segments(xs)
+        .into_iter()
+        .filter(|r| !r.is_empty())
+        .filter_map(|r| {
+            let stats = SeriesStats::compute(ys.get(r.clone())?)?;
+            Some(SegmentStats { x_start: xs[r.start], x_end: xs[r.end - 1], stats })
+        })
+        .collect()
+}
+
+/// Suggested threshold: [`SUGGESTED_GAP_STEPS`] × the median X step (`None`
+/// with fewer than two distinct X values).
+pub fn suggest_gap_threshold(xs: &[f64]) -> Option<f64> {
+    let mut steps: Vec<f64> = xs
+        .windows(2)
+        .map(|w| w[1] - w[0])
+        .filter(|d| d.is_finite() && *d > 0.0)
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    let mid = steps.len() / 2;
+    let (_, median, _) = steps.select_nth_unstable_by(mid, f64::total_cmp);
+    Some(*median * SUGGESTED_GAP_STEPS)
+}
+
+#[cfg(test)]
+mod segments_tests {
+    use super::*;
+
+    #[test]
+    fn splits_at_gaps() {
+        let xs = [0.0, 1.0, 2.0, 50.0, 51.0, 100.0];
+        let split = GapSplit::detect(&xs, 10.0);
+        assert_eq!(split.gaps, [(2.0, 50.0), (51.0, 100.0)]);
+        assert_eq!(split.segments(&xs), [0..3, 3..5, 5..6]);
+        assert_eq!(suggest_gap_threshold(&xs), Some(10.0));
+
+        let ys = [1.0, 2.0, 3.0, 10.0, 20.0, 7.0];
+        let stats = segment_stats(&split, &xs, &ys);
+        assert_eq!(stats.len(), 3);
+        assert_eq!((stats[1].x_start, stats[1].x_end, stats[1].stats.mean), (50.0, 51.0, 15.0));
+    }
+
+    #[test]
+    fn breaks_decimated_lines_across_gaps() {
+        let split = GapSplit { threshold: 10.0, gaps: vec![(2.0, 50.0)] };
+        // Decimated copy: a sample on each side of the gap gets a break;
+        // samples within a segment don't, however far apart.
+        let (xs, _) = split.break_lines(&[0.0, 2.0, 50.0, 90.0], &[0.0; 4]);
+        assert_eq!(xs.len(), 5);
+        assert!(xs[2].is_nan());
+        let (xs, _) = split.break_lines(&[0.0, 90.0], &[0.0; 2]);
+        assert!(xs[1].is_nan());
+        let (xs, _) = split.break_lines(&[50.0, 90.0], &[0.0; 2]);
+        assert_eq!(xs, [50.0, 90.0]);
+    }
+}
@@ -0,0 +1,83 @@
+//! Tiny per-column previews for the import overview.
+//!
+//! A wide log can have hundreds of channels and the names alone rarely say
+//! which ones moved. [`sparkline`] reduces a whole column to a fixed number
+//! of min/max buckets (so spikes survive) plus a few summary numbers, small
+//! enough to send every column of a file to the UI at once.
+
+use serde::Serialize;
+
+/// Default bucket count: about one per pixel of a grid cell.
+pub const SPARKLINE_BUCKETS: usize = 96;
+
+/// A column reduced to `lo.len()` equal-count buckets, in row order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Sparkline {
+    /// Smallest finite value of each bucket (NaN for an all-missing bucket).
+    pub lo: Vec<f64>,
+    /// Largest finite value of each bucket (NaN for an all-missing bucket).
+    pub hi: Vec<f64>,
+    /// Overall finite min/max/mean (NaN when the column has none).
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Number of finite values.
+    pub count: usize,
+}
+
+/// Reduce `values` to at most `buckets` min/max buckets, skipping
+/// non-finite values. Columns shorter than `buckets` get one per value.
+pub fn sparkline(values: &[f64], buckets: usize) -> Sparkline {
+    let n = buckets.max(1).min(values.len());
+    let mut lo = Vec::with_capacity(n);
+    let mut hi = Vec::with_capacity(n);
+    let (mut min, mut max, mut sum, mut count) = (f64::INFINITY, f64::NEG_INFINITY, 0.0, 0usize);
+    for b in 0..n {
+        let chunk = &values[b * values.len() / n..(b + 1) * values.len() / n];
+        let (mut l, mut h) = (f64::NAN, f64::NAN);
+        for &v in chunk.iter().filter(|v| v.is_finite()) {
+            l = if l.is_nan() { v } else { l.min(v) };
+            h = if h.is_nan() { v } else { h.max(v) };
+            sum += v;
+            count += 1;
+        }
+        if l.is_finite() {
+            min = min.min(l);
+            max = max.max(h);
+        }
+        lo.push(l);
+        hi.push(h);
+    }
+    if count == 0 {
+        (min, max) = (f64::NAN, f64::NAN);
+    }
+    let mean = if count > 0 { sum / count as f64 } else { f64::NAN };
+    Sparkline { lo, hi, min, max, mean, count }
+}
+
+#[cfg(test)]
+mod sparkline_tests {
+    use super::*;
+
+    #[test]
+    fn buckets_keep_spikes_and_skip_gaps() {
+        let mut values: Vec<f64> = (0..1000).map(|i| (i % 10) as f64).collect();
+        values[505] = 99.0;
+        values[10] = f64::NAN;
+        let s = sparkline(&values, 10);
+        assert_eq!(s.lo.len(), 10);
+        assert_eq!(s.hi[5], 99.0);
+        assert_eq!((s.min, s.max, s.count), (0.0, 99.0, 999));
+    }
+
+    #[test]
+    fn short_and_empty_columns() {
+        let s = sparkline(&[1.0, f64::NAN, 3.0], 96);
+        assert_eq!(s.lo.len(), 3);
+        assert!(s.lo[1].is_nan());
+        assert_eq!(s.mean, 2.0);
+        let empty = sparkline(&[f64::NAN; 4], 2);
+        assert_eq!(empty.count, 0);
+        assert!(empty.min.is_nan() && empty.mean.is_nan());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::data::loader::{column_to_timestamps, LoadedData};
+use crate::data::loader::LoadedData;
 use rustfft::num_complex::Complex;
 use rustfft::FftPlanner;
 use std::f64::consts::PI;
@@ -7,8 +7,8 @@ use std::f64::consts::PI;
 /// dt between rows). Falls back to 1.0 (freq then reads in cycles/sample).
 pub fn infer_sample_rate(data: &LoadedData) -> f64 {
     for c in 0..data.columns.len() {
-        if let Some((ts, frac)) = column_to_timestamps(&data.column_data[c]) {
-            if frac >= 0.5 && ts.len() >= 2 {
+        if let Some(ts) = data.column_data[c].timestamps() {
+            if data.column_data[c].valid_frac() >= 0.5 && ts.len() >= 2 {
                 let mut dts: Vec<f64> = ts
                     .windows(2)
                     .map(|w| w[1] - w[0])
@@ -46,9 +46,9 @@ pub fn compute_psd(vals: &[f64], fs: f64) -> (Vec<f64>, Vec<f64>) {
     let half = n / 2;
     let mut freqs = Vec::with_capacity(half);
     let mut power = Vec::with_capacity(half);
-    for k in 1..half {
+    for (k, c) in buf.iter().enumerate().take(half).skip(1) {
         freqs.push(k as f64 * fs / n as f64);
-        power.push(buf[k].norm_sqr() / n as f64);
+        power.push(c.norm_sqr() / n as f64);
     }
     (freqs, power)
 }
@@ -95,6 +95,86 @@ pub fn compute_spectrogram(vals: &[f64], window: usize) -> (Vec<Vec<f64>>, usize
     (frames, bins)
 }
 
+/// Centered rolling RMS over `window` samples (clamped at the edges, like
+/// `filters::moving_average`) — the usual vibration-severity trend.
+/// Non-finite samples are skipped within each window.
+pub fn rolling_rms(vals: &[f64], window: usize) -> Vec<f64> {
+    let n = vals.len();
+    let half = window.max(1) / 2;
+    // Prefix sums of squares and finite counts make each window O(1).
+    let mut sq = vec![0.0; n + 1];
+    let mut cnt = vec![0usize; n + 1];
+    for (i, &v) in vals.iter().enumerate() {
+        let ok = v.is_finite();
+        sq[i + 1] = sq[i] + if ok { v * v } else { 0.0 };
+        cnt[i + 1] = cnt[i] + ok as usize;
+    }
+    (0..n)
+        .map(|i| {
+            let lo = i.saturating_sub(half);
+            let hi = (i + half + 1).min(n);
+            let k = cnt[hi] - cnt[lo];
+            if k == 0 { f64::NAN } else { ((sq[hi] - sq[lo]) / k as f64).max(0.0).sqrt() }
+        })
+        .collect()
+}
+
+/// Amplitude envelope via the Hilbert transform: the magnitude of the analytic
+/// signal of the mean-removed input, optionally smoothed by a centered moving
+/// average of `smooth` samples (≤ 1 = none). Non-finite samples are treated
+/// as the mean and come back as NaN.
+pub fn hilbert_envelope(vals: &[f64], smooth: usize) -> Vec<f64> {
+    let n = vals.len();
+    let finite: Vec<f64> = vals.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.len() < 2 {
+        return vec![f64::NAN; n];
+    }
+    let mean = finite.iter().sum::<f64>() / finite.len() as f64;
+    let mut buf: Vec<Complex<f64>> = vals
+        .iter()
+        .map(|&v| Complex::new(if v.is_finite() { v - mean } else { 0.0 }, 0.0))
+        .collect();
+    let mut planner = FftPlanner::new();
+    planner.plan_fft_forward(n).process(&mut buf);
+    // Analytic signal: keep DC (and Nyquist for even n), double positive
+    // frequencies, zero negative ones.
+    for (k, c) in buf.iter_mut().enumerate() {
+        let keep = k == 0 || (n.is_multiple_of(2) && k == n / 2);
+        if !keep {
+            *c *= if k < n.div_ceil(2) { 2.0 } else { 0.0 };
+        }
+    }
+    planner.plan_fft_inverse(n).process(&mut buf);
+    let env: Vec<f64> = buf.iter().map(|c| c.norm() / n as f64).collect();
+    let env = if smooth > 1 { crate::processing::filters::moving_average(&env, smooth) } else { env };
+    env.into_iter()
+        .zip(vals)
+        .map(|(e, v)| if v.is_finite() { e } else { f64::NAN })
+        .collect()
+}
+
+/// Peak-follower envelope of the mean-removed input: jumps to each new |peak|
+/// immediately and decays exponentially with a time constant of `release`
+/// samples — cheap, causal, and robust to non-stationary signals.
+pub fn peak_envelope(vals: &[f64], release: usize) -> Vec<f64> {
+    let finite: Vec<f64> = vals.iter().copied().filter(|v| v.is_finite()).collect();
+    if finite.is_empty() {
+        return vec![f64::NAN; vals.len()];
+    }
+    let mean = finite.iter().sum::<f64>() / finite.len() as f64;
+    let decay = (-1.0 / release.max(1) as f64).exp();
+    let mut env = 0.0_f64;
+    vals.iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                return f64::NAN;
+            }
+            env = (v - mean).abs().max(env * decay);
+            env
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod spectral_tests {
     use super::*;
@@ -103,6 +183,29 @@ mod spectral_tests {
         v.iter().enumerate().max_by(|a, b| a.1.partial_cmp(b.1).unwrap()).map(|(i, _)| i).unwrap()
     }
 
+    #[test]
+    fn rolling_rms_of_sine_is_amplitude_over_root2() {
+        let sig: Vec<f64> = (0..2000).map(|i| 3.0 * (2.0 * PI * i as f64 / 50.0).sin()).collect();
+        let rms = rolling_rms(&sig, 200);
+        let want = 3.0 / 2f64.sqrt();
+        assert!((rms[1000] - want).abs() < 0.01, "got {}", rms[1000]);
+        assert!(rolling_rms(&[f64::NAN], 3)[0].is_nan());
+    }
+
+    #[test]
+    fn envelopes_track_amplitude_modulation() {
+        // 2 Hz-ish carrier whose amplitude ramps 1 → 3 across the signal.
+        let n = 4000;
+        let amp = |i: usize| 1.0 + 2.0 * i as f64 / n as f64;
+        let sig: Vec<f64> = (0..n).map(|i| amp(i) * (2.0 * PI * i as f64 / 40.0).sin()).collect();
+        let h = hilbert_envelope(&sig, 1);
+        let p = peak_envelope(&sig, 100);
+        for i in [1000, 2000, 3000] {
+            assert!((h[i] - amp(i)).abs() < 0.05 * amp(i), "hilbert {} vs {}", h[i], amp(i));
+            assert!(p[i] <= amp(i) + 1e-9 && p[i] > 0.8 * amp(i), "peak {} vs {}", p[i], amp(i));
+        }
+    }
+
     #[test]
     fn psd_peaks_at_signal_frequency() {
         let fs = 100.0;
@@ -142,11 +245,11 @@ mod spectral_tests {
         let stamps: Vec<String> = (0..20)
             .map(|i| format!("2026-07-02T16:00:{:02}Z", i * 2))
             .collect();
-        let d = LoadedData {
-            columns: vec!["t".into(), "v".into()],
-            column_data: vec![stamps, vec!["0".into(); 20]],
-            row_count: 20,
-        };
+        let d = LoadedData::from_string_columns(
+            vec!["t".into(), "v".into()],
+            vec![stamps, vec!["0".into(); 20]],
+            20,
+        );
         assert!((infer_sample_rate(&d) - 0.5).abs() < 0.05);
     }
 }
@@ -0,0 +1,147 @@
+//! Stacked-area composition: each series drawn on top of the ones before it.
+//!
+//! Series rarely share X samples, so they are first resampled onto one
+//! evenly spaced grid (linear interpolation; a series contributes 0 outside
+//! its own X range or at missing values). Layer *i* then spans from the sum
+//! of the series before it to that sum plus its own value. In
+//! [`StackMode::Percent`] every column of the stack is scaled to 100.
+
+/// Graph-level stacking of its visible series.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackMode {
+    Off,
+    /// Cumulative sums.
+    Stacked,
+    /// Cumulative shares of the total at each X, in percent.
+    Percent,
+}
+
+impl StackMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "off" => Some(StackMode::Off),
+            "stacked" => Some(StackMode::Stacked),
+            "percent" => Some(StackMode::Percent),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StackMode::Off => "off",
+            StackMode::Stacked => "stacked",
+            StackMode::Percent => "percent",
+        }
+    }
+}
+
+/// `n` evenly spaced X values from `lo` to `hi` inclusive (fewer for a
+/// degenerate range).
+pub fn stack_grid(lo: f64, hi: f64, n: usize) -> Vec<f64> {
+    if !(lo.is_finite() && hi.is_finite()) || hi < lo {
+        return Vec::new();
+    }
+    if hi == lo || n < 2 {
+        return vec![lo];
+    }
+    let step = (hi - lo) / (n - 1) as f64;
+    (0..n).map(|i| lo + step * i as f64).collect()
+}
+
+/// Value of the series `(xs, ys)` (X ascending) at `x` by linear
+/// interpolation; 0 outside its X range or where it is missing.
+fn value_at(xs: &[f64], ys: &[f64], x: f64) -> f64 {
+    let (Some(&first), Some(&last)) = (xs.first(), xs.last()) else {
+        return 0.0;
+    };
+    if x < first || x > last {
+        return 0.0;
+    }
+    let i = xs.partition_point(|&v| v < x);
+    let v = if xs[i] == x || i == 0 {
+        ys[i]
+    } else {
+        let t = (x - xs[i - 1]) / (xs[i] - xs[i - 1]);
+        ys[i - 1] + (ys[i] - ys[i - 1]) * t
+    };
+    if v.is_finite() { v } else { 0.0 }
+}
+
+/// `[lower, upper]` of each layer at each grid X, layers in `series` order
+/// (the first at the bottom).
+pub fn stack_layers(grid: &[f64], series: &[(&[f64], &[f64])], mode: StackMode) -> Vec<Vec<[f64; 2]>> {
+    let values: Vec<Vec<f64>> =
+        series.iter().map(|(xs, ys)| grid.iter().map(|&x| value_at(xs, ys, x)).collect()).collect();
+    let totals: Vec<f64> = (0..grid.len()).map(|j| values.iter().map(|v| v[j]).sum()).collect();
+    let mut base = vec![0.0; grid.len()];
+    values
+        .iter()
+        .map(|v| {
+            (0..grid.len())
+                .map(|j| {
+                    let scale = match mode {
+                        StackMode::Percent if totals[j] != 0.0 => 100.0 / totals[j],
+                        StackMode::Percent => 0.0,
+                        _ => 1.0,
+                    };
+                    let lower = base[j];
+                    base[j] += v[j] * scale;
+                    [lower, base[j]]
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// A computed stack: the shared grid and each layer's `[lower, upper]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stack {
+    pub grid: Vec<f64>,
+    pub layers: Vec<Vec<[f64; 2]>>,
+}
+
+/// Stack `series` (each `(xs, ys)`, X ascending) on `n` grid points over
+/// `[x_lo, x_hi]` clipped to their combined X extent; `None` when the window
+/// holds none of their data.
+pub fn build_stack(series: &[(&[f64], &[f64])], x_lo: f64, x_hi: f64, n: usize, mode: StackMode) -> Option<Stack> {
+    let first = |xs: &[f64]| xs.iter().copied().find(|x| x.is_finite());
+    let last = |xs: &[f64]| xs.iter().rev().copied().find(|x| x.is_finite());
+    let lo = series.iter().filter_map(|(xs, _)| first(xs)).fold(f64::INFINITY, f64::min);
+    let hi = series.iter().filter_map(|(xs, _)| last(xs)).fold(f64::NEG_INFINITY, f64::max);
+    let grid = stack_grid(lo.max(x_lo), hi.min(x_hi), n);
+    if grid.is_empty() {
+        return None;
+    }
+    let layers = stack_layers(&grid, series, mode);
+    Some(Stack { grid, layers })
+}
+
+#[cfg(test)]
+mod stacking_tests {
+    use super::*;
+
+    #[test]
+    fn layers_accumulate_on_a_shared_grid() {
+        let grid = stack_grid(0.0, 2.0, 3);
+        assert_eq!(grid, [0.0, 1.0, 2.0]);
+        let a = ([0.0, 2.0], [1.0, 3.0]); // 1, 2, 3 on the grid
+        let b = ([1.0, 2.0], [1.0, f64::NAN]); // absent at 0, missing at 2
+        let layers = stack_layers(&grid, &[(&a.0, &a.1), (&b.0, &b.1)], StackMode::Stacked);
+        assert_eq!(layers[0], [[0.0, 1.0], [0.0, 2.0], [0.0, 3.0]]);
+        assert_eq!(layers[1], [[1.0, 1.0], [2.0, 3.0], [3.0, 3.0]]);
+
+        let pct = stack_layers(&grid, &[(&a.0, &a.1), (&b.0, &b.1)], StackMode::Percent);
+        assert_eq!(pct[0][1], [0.0, 200.0 / 3.0]);
+        assert_eq!(pct[1][1][1], 100.0);
+        assert_eq!(pct[1][0], [100.0, 100.0]);
+        assert_eq!(StackMode::parse("percent"), Some(StackMode::Percent));
+    }
+
+    #[test]
+    fn stack_window_is_clipped_to_the_data() {
+        let (xs, ys) = ([2.0, 4.0], [1.0, 1.0]);
+        let stack = build_stack(&[(&xs, &ys)], 0.0, 10.0, 3, StackMode::Stacked).unwrap();
+        assert_eq!(stack.grid, [2.0, 3.0, 4.0]);
+        assert!(build_stack(&[(&xs, &ys)], 5.0, 10.0, 3, StackMode::Stacked).is_none());
+    }
+}
@@ -7,6 +7,9 @@ pub struct SeriesStats {
     pub peak_to_peak: f64,
     pub mean: f64,
     pub median: f64,
+    /// First and third quartiles (linear interpolation between ranks).
+    pub q1: f64,
+    pub q3: f64,
     pub std_dev: f64,
 }
 
@@ -25,11 +28,13 @@ impl SeriesStats {
         let mean = vals.iter().sum::<f64>() / count as f64;
 
         vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let median = if count % 2 == 0 {
+        let median = if count.is_multiple_of(2) {
             (vals[count / 2 - 1] + vals[count / 2]) / 2.0
         } else {
             vals[count / 2]
         };
+        let q1 = quantile(&vals, 0.25);
+        let q3 = quantile(&vals, 0.75);
 
         let variance = vals.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count as f64;
         let std_dev = variance.sqrt();
@@ -41,10 +46,29 @@ impl SeriesStats {
             peak_to_peak,
             mean,
             median,
+            q1,
+            q3,
             std_dev,
         })
     }
 
+    /// Interquartile range, `q3 - q1`.
+    pub fn iqr(&self) -> f64 {
+        self.q3 - self.q1
+    }
+
+    /// Statistics of the samples whose X lies in `[x_min, x_max]` (a
+    /// visible window); `None` if none do.
+    pub fn compute_window(xs: &[f64], ys: &[f64], x_min: f64, x_max: f64) -> Option<Self> {
+        let inside: Vec<f64> = xs
+            .iter()
+            .zip(ys)
+            .filter(|(&x, _)| x >= x_min && x <= x_max)
+            .map(|(_, &y)| y)
+            .collect();
+        Self::compute(&inside)
+    }
+
     /// Format as a multi-line report string.
     pub fn report(&self, label: &str) -> String {
         format!(
@@ -54,6 +78,75 @@ impl SeriesStats {
     }
 }
 
+/// Value at fraction `f` (0..1) of an ascending-sorted finite slice,
+/// interpolating linearly between neighbouring ranks. NaN when empty.
+pub fn quantile(sorted: &[f64], f: f64) -> f64 {
+    if sorted.is_empty() {
+        return f64::NAN;
+    }
+    let pos = (sorted.len() - 1) as f64 * f.clamp(0.0, 1.0);
+    let (lo, hi) = (pos.floor() as usize, pos.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (pos - lo as f64)
+}
+
+/// Samples in a [`BoxPlot`]'s violin outline.
+pub const VIOLIN_POINTS: usize = 48;
+
+/// Box-and-whisker summary of a series, with a density outline for violin
+/// plots.
+#[derive(Debug, Clone)]
+pub struct BoxPlot {
+    pub stats: SeriesStats,
+    /// Whisker ends: the most extreme samples within 1.5 IQR of the box.
+    pub whisker_lo: f64,
+    pub whisker_hi: f64,
+    /// Samples beyond the whiskers, ascending.
+    pub outliers: Vec<f64>,
+    /// Gaussian kernel density at [`VIOLIN_POINTS`] evenly spaced values
+    /// from `min` to `max`, scaled so the peak is 1.
+    pub density: Vec<f64>,
+}
+
+impl BoxPlot {
+    /// `None` when `y` has no finite samples.
+    pub fn compute(y: &[f64]) -> Option<Self> {
+        let stats = SeriesStats::compute(y)?;
+        let mut vals: Vec<f64> = y.iter().copied().filter(|v| v.is_finite()).collect();
+        vals.sort_by(|a, b| a.total_cmp(b));
+        let lo_fence = stats.q1 - 1.5 * stats.iqr();
+        let hi_fence = stats.q3 + 1.5 * stats.iqr();
+        let whisker_lo = vals.iter().copied().find(|&v| v >= lo_fence).unwrap_or(stats.min);
+        let whisker_hi = vals.iter().rev().copied().find(|&v| v <= hi_fence).unwrap_or(stats.max);
+        let outliers = vals.iter().copied().filter(|&v| v < lo_fence || v > hi_fence).collect();
+        let density = kernel_density(&vals, stats.min, stats.max, stats.std_dev, stats.iqr());
+        Some(BoxPlot { stats, whisker_lo, whisker_hi, outliers, density })
+    }
+}
+
+/// Gaussian KDE of sorted `vals` over `[min, max]` with Silverman's
+/// bandwidth, peak-normalised. Long series are thinned to about 4096
+/// evenly spaced ranks first, which keeps the shape.
+fn kernel_density(vals: &[f64], min: f64, max: f64, std_dev: f64, iqr: f64) -> Vec<f64> {
+    let stride = vals.len().div_ceil(4096).max(1);
+    let sample: Vec<f64> = vals.iter().step_by(stride).copied().collect();
+    let spread = if iqr > 0.0 { std_dev.min(iqr / 1.34) } else { std_dev };
+    let h = 0.9 * spread * (sample.len() as f64).powf(-0.2);
+    if h.is_nan() || h <= 0.0 || max <= min {
+        return vec![1.0; VIOLIN_POINTS];
+    }
+    let mut d: Vec<f64> = (0..VIOLIN_POINTS)
+        .map(|i| {
+            let x = min + (max - min) * i as f64 / (VIOLIN_POINTS - 1) as f64;
+            sample.iter().map(|v| (-0.5 * ((x - v) / h).powi(2)).exp()).sum::<f64>()
+        })
+        .collect();
+    let peak = d.iter().copied().fold(0.0, f64::max);
+    if peak > 0.0 {
+        d.iter_mut().for_each(|v| *v /= peak);
+    }
+    d
+}
+
 /// Pearson correlation over rows where both series are finite. None if < 2 pairs
 /// or a series has zero variance.
 pub fn pearson(a: &[f64], b: &[f64]) -> Option<f64> {
@@ -166,3 +259,92 @@ mod percentile_tests {
         assert!(percentile(&[], 0.5).is_nan());
     }
 }
+
+#[cfg(test)]
+mod box_plot_tests {
+    use super::*;
+
+    #[test]
+    fn quartiles_interpolate_between_ranks() {
+        let st = SeriesStats::compute(&[1.0, 2.0, 3.0, 4.0]).unwrap();
+        assert_eq!((st.q1, st.median, st.q3), (1.75, 2.5, 3.25));
+        assert_eq!(st.iqr(), 1.5);
+    }
+
+    #[test]
+    fn whiskers_stop_at_the_fences_and_the_rest_are_outliers() {
+        let mut y: Vec<f64> = (1..=9).map(f64::from).collect();
+        y.extend([40.0, -30.0, f64::NAN]);
+        let b = BoxPlot::compute(&y).unwrap();
+        assert_eq!((b.whisker_lo, b.whisker_hi), (1.0, 9.0));
+        assert_eq!(b.outliers, vec![-30.0, 40.0]);
+        assert_eq!(b.density.len(), VIOLIN_POINTS);
+        assert!(b.density.iter().all(|d| (0.0..=1.0).contains(d)));
+        assert!(BoxPlot::compute(&[f64::NAN]).is_none());
+    }
+}
+
+#[cfg(test)]
+mod window_stats_tests {
+    use super::*;
+
+    #[test]
+    fn window_stats_cover_only_the_window() {
+        let xs: Vec<f64> = (0..10).map(f64::from).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| x * 10.0).collect();
+        let st = SeriesStats::compute_window(&xs, &ys, 2.0, 4.5).unwrap();
+        assert_eq!((st.count, st.min, st.max, st.mean), (3, 20.0, 40.0, 30.0));
+        assert!(SeriesStats::compute_window(&xs, &ys, 20.0, 30.0).is_none());
+    }
+}
+
+/// Statistics of one series over a dragged X region: [`SeriesStats`] plus
+/// the RMS and the area under the curve.
+#[derive(Debug, Clone)]
+pub struct RegionStats {
+    pub stats: SeriesStats,
+    /// Root mean square of the finite samples.
+    pub rms: f64,
+    /// Trapezoidal integral over consecutive finite samples (Y unit × X
+    /// unit); gaps of non-finite samples are bridged.
+    pub integral: f64,
+}
+
+impl RegionStats {
+    /// Statistics of the samples with X in `[x_min, x_max]` (`xs` ascending);
+    /// `None` if none are finite.
+    pub fn compute(xs: &[f64], ys: &[f64], x_min: f64, x_max: f64) -> Option<Self> {
+        let n = xs.len().min(ys.len());
+        let start = xs[..n].partition_point(|&x| x < x_min);
+        let end = xs[..n].partition_point(|&x| x <= x_max).max(start);
+        let pts: Vec<(f64, f64)> = (start..end)
+            .map(|i| (xs[i], ys[i]))
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .collect();
+        let ys: Vec<f64> = pts.iter().map(|p| p.1).collect();
+        let stats = SeriesStats::compute(&ys)?;
+        let rms = (ys.iter().map(|y| y * y).sum::<f64>() / ys.len() as f64).sqrt();
+        let integral = pts.windows(2).map(|w| (w[1].0 - w[0].0) * (w[1].1 + w[0].1) / 2.0).sum();
+        Some(RegionStats { stats, rms, integral })
+    }
+}
+
+#[cfg(test)]
+mod region_stats_tests {
+    use super::*;
+
+    #[test]
+    fn region_stats_add_rms_and_area() {
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let ys = [9.0, 3.0, -3.0, f64::NAN, 3.0, 9.0];
+        let r = RegionStats::compute(&xs, &ys, 1.0, 4.0).unwrap();
+        assert_eq!(r.stats.count, 3);
+        assert_eq!((r.stats.min, r.stats.max, r.stats.peak_to_peak), (-3.0, 3.0, 6.0));
+        assert_eq!(r.rms, 3.0);
+        // 1→2: 0; 2→4 across the NaN: 2 · 0 = 0.
+        assert_eq!(r.integral, 0.0);
+        let r = RegionStats::compute(&xs, &[2.0; 6], 0.0, 5.0).unwrap();
+        assert_eq!(r.integral, 10.0);
+        assert!(RegionStats::compute(&xs, &ys, 6.0, 7.0).is_none());
+    }
+}
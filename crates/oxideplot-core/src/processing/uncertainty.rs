@@ -0,0 +1,153 @@
+//! ± uncertainty attached to a series, drawn as error bars or a band.
+//!
+//! The half-width is a constant or comes from an uncertainty column, one
+//! value per sample. Display works on the decimated samples a graph actually
+//! draws: [`Uncertainty::bounds`] looks each one up in the full series, and
+//! [`bar_segments`] / [`band_pairs`] turn the bounds into render geometry.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+/// How uncertainty is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorStyle {
+    /// A vertical bar with caps at each sample.
+    Bars,
+    /// A shaded band between the lower and upper bounds.
+    Band,
+}
+
+impl ErrorStyle {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "bars" => Some(ErrorStyle::Bars),
+            "band" => Some(ErrorStyle::Band),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorStyle::Bars => "bars",
+            ErrorStyle::Band => "band",
+        }
+    }
+}
+
+/// A series' ± uncertainty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Uncertainty {
+    pub style: ErrorStyle,
+    /// Constant half-width, used when there is no column.
+    pub value: f64,
+    /// Name of the uncertainty column, for display and saved projects.
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Per-sample half-widths from `column`, aligned with the series samples.
+    #[serde(default)]
+    pub values: Option<Arc<[f64]>>,
+}
+
+impl Uncertainty {
+    pub fn constant(style: ErrorStyle, value: f64) -> Self {
+        Uncertainty { style, value, column: None, values: None }
+    }
+
+    pub fn from_column(style: ErrorStyle, name: &str, values: Arc<[f64]>) -> Self {
+        Uncertainty { style, value: 0.0, column: Some(name.to_string()), values: Some(values) }
+    }
+
+    /// Half-width at sample `i` (NaN past the end of the column).
+    pub fn half_width(&self, i: usize) -> f64 {
+        match &self.values {
+            Some(v) => v.get(i).map_or(f64::NAN, |e| e.abs()),
+            None => self.value.abs(),
+        }
+    }
+
+    /// `[lower, upper]` for each drawn sample `(vis_x, vis_y)`, looking the
+    /// half-width up by X in the full series `xs` (ascending). Breaks and
+    /// samples without a finite half-width give NaN bounds.
+    pub fn bounds(&self, xs: &[f64], vis_x: &[f64], vis_y: &[f64]) -> Vec<[f64; 2]> {
+        vis_x
+            .iter()
+            .zip(vis_y)
+            .map(|(&x, &y)| {
+                if !x.is_finite() || !y.is_finite() {
+                    return [f64::NAN; 2];
+                }
+                let e = self.half_width(xs.partition_point(|&v| v < x));
+                if e.is_finite() { [y - e, y + e] } else { [f64::NAN; 2] }
+            })
+            .collect()
+    }
+
+    /// Short description for the series panel (`"± 0.5"`, `"± sigma_v"`).
+    pub fn describe(&self) -> String {
+        match &self.column {
+            Some(c) => format!("± {c}"),
+            None => format!("± {}", self.value),
+        }
+    }
+}
+
+/// Error bars as one polyline with NaN breaks: the vertical bar, then a
+/// cap of half-width `cap` (X units) at each end.
+pub fn bar_segments(vis_x: &[f64], bounds: &[[f64; 2]], cap: f64) -> Vec<[f64; 2]> {
+    let mut out = Vec::with_capacity(vis_x.len() * 9);
+    for (&x, &[lo, hi]) in vis_x.iter().zip(bounds) {
+        if !(x.is_finite() && lo.is_finite() && hi.is_finite()) {
+            continue;
+        }
+        out.extend_from_slice(&[[x, lo], [x, hi], [f64::NAN; 2]]);
+        if cap > 0.0 {
+            out.extend_from_slice(&[[x - cap, lo], [x + cap, lo], [f64::NAN; 2]]);
+            out.extend_from_slice(&[[x - cap, hi], [x + cap, hi], [f64::NAN; 2]]);
+        }
+    }
+    out
+}
+
+/// Band points as `[x, lower], [x, upper]` pairs (the `Band` draw mode's
+/// layout); NaN bounds become a NaN pair, which breaks the band.
+pub fn band_pairs(vis_x: &[f64], bounds: &[[f64; 2]]) -> Vec<[f64; 2]> {
+    vis_x.iter().zip(bounds).flat_map(|(&x, &[lo, hi])| [[x, lo], [x, hi]]).collect()
+}
+
+#[cfg(test)]
+mod uncertainty_tests {
+    use super::*;
+
+    #[test]
+    fn bounds_follow_the_column_by_x() {
+        let xs = [0.0, 1.0, 2.0, 3.0];
+        let err = Uncertainty::from_column(ErrorStyle::Bars, "sigma", vec![0.1, -0.2, f64::NAN, 0.4].into());
+        // Decimated copy: samples 1 and 3, plus a break.
+        let b = err.bounds(&xs, &[1.0, f64::NAN, 3.0, 2.0], &[5.0, f64::NAN, 6.0, 7.0]);
+        assert_eq!(b[0], [4.8, 5.2]);
+        assert!(b[1][0].is_nan() && b[3][0].is_nan());
+        assert_eq!(b[2], [5.6, 6.4]);
+        assert_eq!(err.describe(), "± sigma");
+
+        let c = Uncertainty::constant(ErrorStyle::Band, 0.5);
+        assert_eq!(c.bounds(&xs, &[2.0], &[1.0]), [[0.5, 1.5]]);
+    }
+
+    #[test]
+    fn geometry_for_bars_and_bands() {
+        let bounds = [[0.0, 2.0], [f64::NAN; 2]];
+        let bars = bar_segments(&[1.0, 2.0], &bounds, 0.25);
+        assert_eq!(bars.len(), 9);
+        assert_eq!(&bars[..2], &[[1.0, 0.0], [1.0, 2.0]]);
+        assert_eq!(&bars[6..8], &[[0.75, 2.0], [1.25, 2.0]]);
+        assert_eq!(bar_segments(&[1.0], &bounds[..1], 0.0).len(), 3);
+
+        let band = band_pairs(&[1.0, 2.0], &bounds);
+        assert_eq!(band.len(), 4);
+        assert_eq!(&band[..2], &[[1.0, 0.0], [1.0, 2.0]]);
+        assert!(band[3][1].is_nan());
+        assert_eq!(ErrorStyle::parse("band"), Some(ErrorStyle::Band));
+    }
+}
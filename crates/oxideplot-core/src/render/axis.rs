@@ -50,10 +50,36 @@ pub fn format_tick_value(val: f64) -> String {
     }
 }
 
+/// Format a value read off the plot (mouse readout) to the precision one
+/// pixel can resolve: `resolution` is the data span of a pixel. Very large or
+/// very small magnitudes use scientific notation, like tick labels.
+pub fn format_readout(val: f64, resolution: f64) -> String {
+    if !val.is_finite() {
+        return String::new();
+    }
+    if val.abs() >= 1e6 || (val != 0.0 && val.abs() < 1e-3) {
+        return format!("{val:.3e}");
+    }
+    let decimals = if resolution.is_finite() && resolution > 0.0 {
+        (-resolution.log10()).ceil().clamp(0.0, 9.0) as usize
+    } else {
+        3
+    };
+    format!("{val:.decimals$}")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn readout_precision_follows_pixel_resolution() {
+        assert_eq!(format_readout(12.345678, 0.01), "12.35");
+        assert_eq!(format_readout(12.345678, 0.5), "12.3");
+        assert_eq!(format_readout(1234.7, 20.0), "1235");
+        assert_eq!(format_readout(2.5e7, 1.0), "2.500e7");
+        assert_eq!(format_readout(f64::NAN, 1.0), "");
+    }
+    #[test]
     fn grid_lines_within_range() {
         let lines = compute_grid_lines(0.0, 100.0);
         assert!(!lines.is_empty());
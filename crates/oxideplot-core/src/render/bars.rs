@@ -0,0 +1,144 @@
+//! Bar chart geometry.
+//!
+//! A [`DrawMode::Bar`](super::gpu_types::DrawMode::Bar) series is drawn as
+//! one filled quad per sample, from a baseline up (or down) to the value.
+//! When several bar series share a graph they either sit side by side in
+//! each X slot ([`BarLayout::Grouped`]) or pile on top of each other
+//! ([`BarLayout::Stacked`]); [`layout_bars`] builds the quads for all of
+//! them at once so the slots line up.
+
+use std::collections::HashMap;
+
+use super::gpu_types::is_finite_point;
+
+/// Share of the X slot (the smallest spacing between samples) the bars of
+/// one X fill; the rest is the gap to the neighbouring slot.
+pub const BAR_FILL: f32 = 0.8;
+
+/// How bar series on one graph share an X slot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BarLayout {
+    /// Side by side, each series taking an equal share of the slot.
+    #[default]
+    Grouped,
+    /// One bar per slot, each series stacked on the ones before it
+    /// (positive values upward, negative values downward).
+    Stacked,
+}
+
+impl BarLayout {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "grouped" => Some(BarLayout::Grouped),
+            "stacked" => Some(BarLayout::Stacked),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BarLayout::Grouped => "grouped",
+            BarLayout::Stacked => "stacked",
+        }
+    }
+}
+
+/// Width of one X slot: the smallest positive spacing between consecutive
+/// finite samples of any series, or 1 when no series has two.
+pub fn slot_width(series: &[Vec<[f32; 2]>]) -> f32 {
+    series
+        .iter()
+        .flat_map(|pts| {
+            let xs: Vec<f32> = pts.iter().filter(|&&p| is_finite_point(p)).map(|p| p[0]).collect();
+            xs.windows(2).map(|w| (w[1] - w[0]).abs()).filter(|d| *d > 0.0).collect::<Vec<_>>()
+        })
+        .fold(None, |m: Option<f32>, d| Some(m.map_or(d, |m| m.min(d))))
+        .unwrap_or(1.0)
+}
+
+/// Bar quads for each of `series` (`[x, value]` points), rising from
+/// `baseline`. Each bar is `[x0, lo], [x0, hi], [x1, lo], [x1, hi]`, the
+/// corner order the fill pipeline draws. Non-finite points get no bar.
+pub fn layout_bars(series: &[Vec<[f32; 2]>], layout: BarLayout, baseline: f32) -> Vec<Vec<[f32; 2]>> {
+    let slot = slot_width(series) * BAR_FILL;
+    let quad = |x0: f32, x1: f32, lo: f32, hi: f32| [[x0, lo], [x0, hi], [x1, lo], [x1, hi]];
+    match layout {
+        BarLayout::Grouped => {
+            let w = slot / series.len().max(1) as f32;
+            series
+                .iter()
+                .enumerate()
+                .map(|(i, pts)| {
+                    let off = -slot / 2.0 + i as f32 * w;
+                    pts.iter()
+                        .filter(|&&p| is_finite_point(p))
+                        .flat_map(|&[x, y]| quad(x + off, x + off + w, baseline, y))
+                        .collect()
+                })
+                .collect()
+        }
+        BarLayout::Stacked => {
+            // Running (positive, negative) tops per X, keyed by its bits.
+            let mut tops: HashMap<u32, (f32, f32)> = HashMap::new();
+            series
+                .iter()
+                .map(|pts| {
+                    pts.iter()
+                        .filter(|&&p| is_finite_point(p))
+                        .flat_map(|&[x, y]| {
+                            let (up, down) = tops.entry(x.to_bits()).or_insert((baseline, baseline));
+                            let h = y - baseline;
+                            let (lo, hi) = if h >= 0.0 {
+                                let lo = *up;
+                                *up += h;
+                                (lo, *up)
+                            } else {
+                                let hi = *down;
+                                *down += h;
+                                (*down, hi)
+                            };
+                            quad(x - slot / 2.0, x + slot / 2.0, lo, hi)
+                        })
+                        .collect()
+                })
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod bars_tests {
+    use super::*;
+
+    #[test]
+    fn grouped_bars_split_the_slot() {
+        let a = vec![[0.0, 1.0], [1.0, 2.0]];
+        let b = vec![[0.0, 3.0], [1.0, 4.0]];
+        let out = layout_bars(&[a, b], BarLayout::Grouped, 0.0);
+        assert_eq!(out[0].len(), 8);
+        // Slot 0.8 wide, each series 0.4: a left of x, b right of it.
+        assert_eq!(out[0][..4], [[-0.4, 0.0], [-0.4, 1.0], [0.0, 0.0], [0.0, 1.0]]);
+        assert_eq!(out[1][..4], [[0.0, 0.0], [0.0, 3.0], [0.4, 0.0], [0.4, 3.0]]);
+    }
+
+    #[test]
+    fn stacked_bars_pile_up_and_down_separately() {
+        let a = vec![[0.0, 2.0]];
+        let b = vec![[0.0, -1.0]];
+        let c = vec![[0.0, 3.0]];
+        let out = layout_bars(&[a, b, c], BarLayout::Stacked, 0.0);
+        assert_eq!((out[0][0][1], out[0][1][1]), (0.0, 2.0));
+        assert_eq!((out[1][0][1], out[1][1][1]), (-1.0, 0.0));
+        assert_eq!((out[2][0][1], out[2][1][1]), (2.0, 5.0));
+    }
+
+    #[test]
+    fn gaps_get_no_bar_and_slot_defaults_to_one() {
+        let out = layout_bars(&[vec![[5.0, 1.0], [f32::NAN, f32::NAN]]], BarLayout::Grouped, 0.0);
+        assert_eq!(out[0].len(), 4);
+        assert_eq!(out[0][0][0], 5.0 - BAR_FILL / 2.0);
+        for l in [BarLayout::Grouped, BarLayout::Stacked] {
+            assert_eq!(BarLayout::parse(l.as_str()), Some(l));
+        }
+    }
+}
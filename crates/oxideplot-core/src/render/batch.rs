@@ -0,0 +1,72 @@
+//! Draw-call batching for graphs with many series.
+//!
+//! Every series is one draw call with its own buffers, which is fine for a
+//! handful of curves but costs a lot at a hundred or more. Series that share
+//! a style (colour, line width, point radius, draw mode) can share one
+//! buffer instead: [`batch_by_style`] concatenates them with a NaN break
+//! between members, which every renderer already treats as a gap.
+
+use crate::render::gpu_types::{DrawMode, SeriesGpuData};
+
+/// Below this many series, draw each separately so z-order stays exact.
+pub const BATCH_MIN_SERIES: usize = 32;
+
+const BREAK: [f32; 2] = [f32::NAN, f32::NAN];
+
+/// Merge series with identical style into one entry each, in order of each
+/// style's first appearance; empty series are dropped. Members of a batch
+/// draw at the z-position of the style's first series, so only call this
+/// when there are enough series for that to be an acceptable trade.
+pub fn batch_by_style(series: &[SeriesGpuData]) -> Vec<SeriesGpuData> {
+    let mut out: Vec<SeriesGpuData> = Vec::new();
+    for s in series.iter().filter(|s| !s.points.is_empty()) {
+        let same = out.iter_mut().find(|b| {
+            b.color == s.color
+                && b.line_width == s.line_width
+                && b.point_radius == s.point_radius
+                && b.draw_mode == s.draw_mode
+        });
+        match same {
+            Some(b) => {
+                // Bar quads stand alone; anything else needs a break.
+                if s.draw_mode != DrawMode::Bar {
+                    b.points.push(BREAK);
+                }
+                if s.draw_mode == DrawMode::Band {
+                    // Keep band points paired as [lower, upper].
+                    b.points.push(BREAK);
+                }
+                b.points.extend_from_slice(&s.points);
+            }
+            None => out.push(s.clone()),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod batch_tests {
+    use super::*;
+
+    fn series(color: [f32; 4], points: Vec<[f32; 2]>) -> SeriesGpuData {
+        SeriesGpuData { points, color, line_width: 2.0, point_radius: 3.0, draw_mode: DrawMode::Lines }
+    }
+
+    #[test]
+    fn same_style_series_share_one_buffer() {
+        let red = [1.0, 0.0, 0.0, 1.0];
+        let blue = [0.0, 0.0, 1.0, 1.0];
+        let input = vec![
+            series(red, vec![[0.0, 0.0], [1.0, 1.0]]),
+            series(blue, vec![[0.0, 2.0], [1.0, 2.0]]),
+            series(red, vec![]),
+            series(red, vec![[0.0, 5.0], [1.0, 6.0]]),
+        ];
+        let out = batch_by_style(&input);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].color, red);
+        assert_eq!(out[0].points.len(), 5, "two runs joined by one break");
+        assert!(out[0].points[2][0].is_nan());
+        assert_eq!(out[1].points, input[1].points);
+    }
+}
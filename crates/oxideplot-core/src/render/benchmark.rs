@@ -0,0 +1,103 @@
+//! Scripted pan/zoom benchmark.
+//!
+//! [`bench_script`] is a fixed sequence of interactive steps — zoom in,
+//! pan across, zoom back out — so frame times from one release can be
+//! compared with the next on the same synthetic data
+//! (`data::synthetic`). The caller times each step; [`frame_stats`]
+//! summarises the times.
+
+use serde::Serialize;
+
+/// Steps per phase of the script.
+const PHASE_STEPS: usize = 20;
+
+/// One scripted interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BenchStep {
+    /// Fit the view to all data.
+    Fit,
+    /// Pan by a fraction of the plot (`dx` right, `dy` up).
+    Pan { dx: f64, dy: f64 },
+    /// Zoom around the centre; `steps` > 0 zooms in.
+    Zoom { steps: f64 },
+}
+
+/// `frames` steps: a fit, then repeated phases of zooming in, panning right,
+/// panning left and up, and zooming out again.
+pub fn bench_script(frames: usize) -> Vec<BenchStep> {
+    let phases = [
+        BenchStep::Zoom { steps: 1.0 },
+        BenchStep::Pan { dx: 0.05, dy: 0.0 },
+        BenchStep::Pan { dx: -0.05, dy: 0.02 },
+        BenchStep::Pan { dx: 0.0, dy: -0.02 },
+        BenchStep::Zoom { steps: -1.0 },
+    ];
+    let mut steps = vec![BenchStep::Fit];
+    steps.extend((0..frames.saturating_sub(1)).map(|i| phases[(i / PHASE_STEPS) % phases.len()]));
+    steps.truncate(frames);
+    steps
+}
+
+/// Summary of measured frame times, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FrameStats {
+    pub frames: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    /// Frames per second at the mean frame time.
+    pub fps: f64,
+}
+
+/// Nearest-rank percentile of sorted `v`.
+fn percentile(v: &[f64], p: f64) -> f64 {
+    if v.is_empty() {
+        return f64::NAN;
+    }
+    let rank = ((p / 100.0) * v.len() as f64).ceil() as usize;
+    v[rank.clamp(1, v.len()) - 1]
+}
+
+/// Statistics of `times`, ignoring non-finite entries.
+pub fn frame_stats(times: &[f64]) -> FrameStats {
+    let mut v: Vec<f64> = times.iter().copied().filter(|t| t.is_finite()).collect();
+    v.sort_by(f64::total_cmp);
+    let mean = if v.is_empty() { f64::NAN } else { v.iter().sum::<f64>() / v.len() as f64 };
+    FrameStats {
+        frames: v.len(),
+        mean_ms: mean,
+        p50_ms: percentile(&v, 50.0),
+        p95_ms: percentile(&v, 95.0),
+        p99_ms: percentile(&v, 99.0),
+        max_ms: v.last().copied().unwrap_or(f64::NAN),
+        fps: 1000.0 / mean,
+    }
+}
+
+#[cfg(test)]
+mod benchmark_tests {
+    use super::*;
+
+    #[test]
+    fn script_starts_with_a_fit_and_cycles_phases() {
+        let s = bench_script(120);
+        assert_eq!(s.len(), 120);
+        assert_eq!(s[0], BenchStep::Fit);
+        assert_eq!(s[1], BenchStep::Zoom { steps: 1.0 });
+        assert_eq!(s[1 + PHASE_STEPS], BenchStep::Pan { dx: 0.05, dy: 0.0 });
+        assert_eq!(s[1 + 4 * PHASE_STEPS], BenchStep::Zoom { steps: -1.0 });
+        assert!(bench_script(0).is_empty());
+    }
+
+    #[test]
+    fn stats_use_nearest_rank_percentiles() {
+        let times: Vec<f64> = (1..=100).map(f64::from).chain([f64::NAN]).collect();
+        let s = frame_stats(&times);
+        assert_eq!((s.frames, s.p50_ms, s.p95_ms, s.max_ms), (100, 50.0, 95.0, 100.0));
+        assert_eq!(s.mean_ms, 50.5);
+        assert!(frame_stats(&[]).mean_ms.is_nan());
+    }
+}
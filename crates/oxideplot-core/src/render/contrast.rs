@@ -0,0 +1,76 @@
+//! Keeping series colours readable against the plot background.
+//!
+//! The palette is tuned for the dark theme: its yellow and lime all but
+//! vanish on the light background, as a navy picked on the light theme does
+//! on the dark one. [`readable_color`] darkens or lightens a colour just far
+//! enough to reach a WCAG contrast ratio against the background, keeping its
+//! hue, so switching themes never leaves a series invisible.
+
+/// WCAG 2.1's minimum contrast for graphical objects (lines, markers).
+pub const MIN_SERIES_CONTRAST: f32 = 3.0;
+
+/// WCAG relative luminance of an sRGB colour (components 0–1).
+pub fn relative_luminance(rgb: [f32; 3]) -> f32 {
+    let lin = |c: f32| if c <= 0.040_45 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+    0.2126 * lin(rgb[0]) + 0.7152 * lin(rgb[1]) + 0.0722 * lin(rgb[2])
+}
+
+/// WCAG contrast ratio between two colours: 1 (same) to 21 (black on white).
+pub fn contrast_ratio(a: [f32; 3], b: [f32; 3]) -> f32 {
+    let (la, lb) = (relative_luminance(a), relative_luminance(b));
+    (la.max(lb) + 0.05) / (la.min(lb) + 0.05)
+}
+
+/// `color` mixed towards black (light `bg`) or white (dark `bg`) by the
+/// least amount that gives `min_ratio` contrast against `bg`; unchanged when
+/// it already has it. Alpha is kept.
+pub fn readable_color(color: [f32; 4], bg: [f32; 3], min_ratio: f32) -> [f32; 4] {
+    let rgb = [color[0], color[1], color[2]];
+    if contrast_ratio(rgb, bg) >= min_ratio {
+        return color;
+    }
+    let target = if contrast_ratio([1.0; 3], bg) > contrast_ratio([0.0; 3], bg) { 1.0 } else { 0.0 };
+    let mix = |t: f32| [0, 1, 2].map(|i| rgb[i] + (target - rgb[i]) * t);
+    // Contrast grows monotonically with t: bisect for the smallest mix.
+    let (mut lo, mut hi) = (0.0_f32, 1.0_f32);
+    for _ in 0..16 {
+        let mid = 0.5 * (lo + hi);
+        if contrast_ratio(mix(mid), bg) >= min_ratio {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    let [r, g, b] = mix(hi);
+    [r, g, b, color[3]]
+}
+
+#[cfg(test)]
+mod contrast_tests {
+    use super::*;
+
+    const LIGHT_BG: [f32; 3] = [0.957, 0.957, 0.945];
+    const DARK_BG: [f32; 3] = [0.055, 0.059, 0.075];
+
+    #[test]
+    fn faint_colours_are_pushed_away_from_the_background() {
+        let yellow = [1.0, 0.9, 0.1, 1.0];
+        let fixed = readable_color(yellow, LIGHT_BG, MIN_SERIES_CONTRAST);
+        let rgb = [fixed[0], fixed[1], fixed[2]];
+        assert!(contrast_ratio(rgb, LIGHT_BG) >= MIN_SERIES_CONTRAST);
+        assert!(contrast_ratio(rgb, LIGHT_BG) < MIN_SERIES_CONTRAST + 0.1, "no darker than needed");
+        assert!(fixed[0] > fixed[2], "still a yellow hue");
+
+        let navy = [0.05, 0.1, 0.35, 0.5];
+        let fixed = readable_color(navy, DARK_BG, MIN_SERIES_CONTRAST);
+        assert!(contrast_ratio([fixed[0], fixed[1], fixed[2]], DARK_BG) >= MIN_SERIES_CONTRAST);
+        assert_eq!(fixed[3], 0.5, "alpha kept");
+    }
+
+    #[test]
+    fn readable_colours_are_left_alone() {
+        let cyan = [0.20, 0.85, 1.00, 1.0];
+        assert_eq!(readable_color(cyan, DARK_BG, MIN_SERIES_CONTRAST), cyan);
+        assert!((contrast_ratio([0.0; 3], [1.0; 3]) - 21.0).abs() < 1e-3);
+    }
+}
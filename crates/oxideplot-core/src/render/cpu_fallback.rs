@@ -0,0 +1,282 @@
+//! CPU fallback rendering for when the wgpu pipeline can't be initialised.
+//!
+//! Some drivers (old lab PCs, remote desktops, blocklisted GPUs) never hand
+//! out an adapter or device, so [`PlotRenderer`](super::renderer::PlotRenderer)
+//! can't be built and a graph would silently render nothing. This module turns
+//! the same [`SeriesGpuData`] + [`PlotUniforms`] the GPU path consumes into
+//! screen-space polylines a 2D painter (HTML canvas, egui painter, …) can
+//! stroke directly. Each series is aggressively decimated to a small point
+//! budget so CPU stroking stays interactive while panning.
+
+use serde::Serialize;
+
+use super::gpu_types::{is_finite_point, step_corner, DrawMode, PlotUniforms, SeriesGpuData};
+use super::spline::expand_smooth;
+
+/// Default per-series point budget for the CPU path. Far below the GPU path's
+/// ~1 point/pixel target — a 2D painter strokes every segment on the CPU.
+pub const CPU_POINT_BUDGET: usize = 600;
+
+/// One series ready for a 2D painter, in pixel coordinates (origin top-left,
+/// Y down), already step-expanded or smoothed and decimated.
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuPolyline {
+    pub points: Vec<[f32; 2]>,
+    pub color: [f32; 4],
+    pub line_width: f32,
+    pub point_radius: f32,
+    /// Draw each point as a dot instead of stroking a connected line.
+    pub points_only: bool,
+    /// Fill the closed polygon instead of stroking it (bands, bars).
+    pub filled: bool,
+}
+
+/// Build CPU polylines for `series` under the view/resolution in `uniforms`.
+///
+/// The data→pixel mapping mirrors the WGSL vertex shader, so the fallback
+/// frame lines up with the axis overlays exactly like the GPU frame does.
+/// Empty series are skipped, as in `build_draw_calls`, and a series broken
+/// by non-finite points becomes one polyline per unbroken run.
+pub fn build_cpu_polylines(
+    series: &[SeriesGpuData],
+    uniforms: &PlotUniforms,
+    budget: usize,
+) -> Vec<CpuPolyline> {
+    let [vx0, vy0] = uniforms.view_min;
+    let [vx1, vy1] = uniforms.view_max;
+    let [w, h] = uniforms.resolution;
+    let sx = if vx1 != vx0 { w / (vx1 - vx0) } else { 0.0 };
+    let sy = if vy1 != vy0 { h / (vy1 - vy0) } else { 0.0 };
+    let to_px = |p: [f32; 2]| [(p[0] - vx0) * sx, h - (p[1] - vy0) * sy];
+
+    let mut out = Vec::new();
+    for s in series.iter().filter(|s| !s.points.is_empty()) {
+        if s.draw_mode == DrawMode::Band {
+            for outline in band_outlines(&s.points) {
+                out.push(CpuPolyline {
+                    points: outline.into_iter().map(to_px).collect(),
+                    color: s.color,
+                    line_width: s.line_width,
+                    point_radius: s.point_radius,
+                    points_only: false,
+                    filled: true,
+                });
+            }
+            continue;
+        }
+        if s.draw_mode == DrawMode::Bar {
+            for q in s.points.chunks_exact(4).filter(|q| q.iter().all(|&p| is_finite_point(p))) {
+                out.push(CpuPolyline {
+                    points: [q[0], q[1], q[3], q[2]].into_iter().map(to_px).collect(),
+                    color: s.color,
+                    line_width: s.line_width,
+                    point_radius: s.point_radius,
+                    points_only: false,
+                    filled: true,
+                });
+            }
+            continue;
+        }
+        let total = s.points.len().max(1);
+        for run in s.points.split(|&p| !is_finite_point(p)).filter(|r| !r.is_empty()) {
+            // Split the budget across runs in proportion to their length.
+            let run_budget = (budget * run.len()).div_ceil(total);
+            let decimated = decimate_minmax(run, run_budget);
+            let pts: Vec<[f32; 2]> = match s.draw_mode {
+                DrawMode::Step => expand_step(&decimated, false),
+                DrawMode::StepPre => expand_step(&decimated, true),
+                DrawMode::Smooth => expand_smooth(&decimated),
+                _ => decimated,
+            };
+            out.push(CpuPolyline {
+                points: pts.into_iter().map(to_px).collect(),
+                color: s.color,
+                line_width: s.line_width,
+                point_radius: s.point_radius,
+                points_only: s.draw_mode == DrawMode::Points,
+                filled: false,
+            });
+        }
+    }
+    out
+}
+
+/// Reduce `points` to at most `budget` entries by keeping the min and max Y of
+/// each of `budget / 2` equal-count buckets, in X order, so spikes survive.
+/// The first and last points are always kept.
+fn decimate_minmax(points: &[[f32; 2]], budget: usize) -> Vec<[f32; 2]> {
+    let budget = budget.max(4);
+    if points.len() <= budget {
+        return points.to_vec();
+    }
+    let buckets = (budget - 2) / 2;
+    let inner = &points[1..points.len() - 1];
+    let mut out = Vec::with_capacity(budget);
+    out.push(points[0]);
+    for b in 0..buckets {
+        let lo = b * inner.len() / buckets;
+        let hi = ((b + 1) * inner.len() / buckets).max(lo + 1);
+        let chunk = &inner[lo..hi.min(inner.len())];
+        let (mut imin, mut imax) = (0, 0);
+        for (i, p) in chunk.iter().enumerate() {
+            if p[1] < chunk[imin][1] {
+                imin = i;
+            }
+            if p[1] > chunk[imax][1] {
+                imax = i;
+            }
+        }
+        if imin == imax {
+            out.push(chunk[imin]);
+        } else {
+            out.push(chunk[imin.min(imax)]);
+            out.push(chunk[imin.max(imax)]);
+        }
+    }
+    out.push(points[points.len() - 1]);
+    out
+}
+
+/// Expand a polyline into steps, horizontal-then-vertical or with `pre`
+/// vertical-then-horizontal (same shapes `build_draw_calls` produces for
+/// [`DrawMode::Step`] and [`DrawMode::StepPre`]).
+fn expand_step(points: &[[f32; 2]], pre: bool) -> Vec<[f32; 2]> {
+    let mut out = Vec::with_capacity(points.len() * 2);
+    for (i, &p) in points.iter().enumerate() {
+        if i > 0 {
+            out.push(step_corner(points[i - 1], p, pre));
+        }
+        out.push(p);
+    }
+    out
+}
+
+/// Closed outlines of a [`DrawMode::Band`] series: for each unbroken run of
+/// `[lower, upper]` pairs, the upper edge left to right, then the lower edge
+/// back. Bands are already about one pair per pixel, so no decimation.
+fn band_outlines(pairs: &[[f32; 2]]) -> Vec<Vec<[f32; 2]>> {
+    let mut out = Vec::new();
+    let mut run: Vec<&[[f32; 2]]> = Vec::new();
+    let mut flush = |run: &mut Vec<&[[f32; 2]]>| {
+        if run.len() >= 2 {
+            let upper = run.iter().map(|p| p[1]);
+            let lower = run.iter().rev().map(|p| p[0]);
+            out.push(upper.chain(lower).collect());
+        }
+        run.clear();
+    };
+    for pair in pairs.chunks_exact(2) {
+        if pair.iter().all(|&p| is_finite_point(p)) {
+            run.push(pair);
+        } else {
+            flush(&mut run);
+        }
+    }
+    flush(&mut run);
+    out
+}
+
+#[cfg(test)]
+mod cpu_fallback_tests {
+    use super::*;
+
+    fn uniforms(w: f32, h: f32) -> PlotUniforms {
+        PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [10.0, 1.0],
+            resolution: [w, h],
+            line_width: 2.0,
+            point_radius: 3.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        }
+    }
+
+    fn series(points: Vec<[f32; 2]>, draw_mode: DrawMode) -> SeriesGpuData {
+        SeriesGpuData {
+            points,
+            color: [1.0, 0.0, 0.0, 1.0],
+            line_width: 2.0,
+            point_radius: 3.0,
+            draw_mode,
+        }
+    }
+
+    #[test]
+    fn maps_view_corners_to_pixels() {
+        let s = series(vec![[0.0, 0.0], [10.0, 1.0]], DrawMode::Lines);
+        let out = build_cpu_polylines(&[s], &uniforms(200.0, 100.0), CPU_POINT_BUDGET);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].points, vec![[0.0, 100.0], [200.0, 0.0]]);
+        assert!(!out[0].points_only);
+    }
+
+    #[test]
+    fn skips_empty_series_and_flags_points() {
+        let empty = series(vec![], DrawMode::Lines);
+        let pts = series(vec![[5.0, 0.5]], DrawMode::Points);
+        let out = build_cpu_polylines(&[empty, pts], &uniforms(100.0, 100.0), CPU_POINT_BUDGET);
+        assert_eq!(out.len(), 1);
+        assert!(out[0].points_only);
+    }
+
+    #[test]
+    fn step_mode_inserts_corners() {
+        let s = series(vec![[0.0, 0.0], [5.0, 1.0], [10.0, 0.0]], DrawMode::Step);
+        let out = build_cpu_polylines(&[s], &uniforms(10.0, 1.0), CPU_POINT_BUDGET);
+        // 3 points → 3 + 2 corners.
+        assert_eq!(out[0].points.len(), 5);
+        // First corner holds the previous Y at the new X.
+        assert_eq!(out[0].points[1], [5.0, 1.0]);
+
+        // Step-pre takes the new Y at the previous X.
+        let s = series(vec![[0.0, 0.0], [5.0, 1.0], [10.0, 0.0]], DrawMode::StepPre);
+        let out = build_cpu_polylines(&[s], &uniforms(10.0, 1.0), CPU_POINT_BUDGET);
+        assert_eq!(out[0].points[1], [0.0, 0.0]);
+    }
+
+    #[test]
+    fn non_finite_points_split_into_runs() {
+        let s = series(
+            vec![[0.0, 0.0], [1.0, 0.5], [f32::NAN, f32::NAN], [2.0, 0.2], [3.0, 0.1]],
+            DrawMode::Lines,
+        );
+        let out = build_cpu_polylines(&[s], &uniforms(10.0, 1.0), CPU_POINT_BUDGET);
+        assert_eq!(out.len(), 2);
+        assert!(out.iter().all(|l| l.points.len() == 2));
+        assert!(out.iter().flat_map(|l| &l.points).all(|p| p[0].is_finite()));
+    }
+
+    #[test]
+    fn band_becomes_filled_outlines() {
+        let nan = [f32::NAN, f32::NAN];
+        let pairs = vec![[0.0, 0.0], [0.0, 1.0], [1.0, 0.25], [1.0, 0.75], nan, nan, [2.0, 0.0], [2.0, 1.0], [3.0, 0.0], [3.0, 1.0]];
+        let out = build_cpu_polylines(&[series(pairs, DrawMode::Band)], &uniforms(10.0, 1.0), CPU_POINT_BUDGET);
+        assert_eq!(out.len(), 2);
+        assert!(out.iter().all(|l| l.filled && l.points.len() == 4));
+        // Upper edge forward, then the lower edge back (pixel Y is flipped).
+        assert_eq!(out[0].points, vec![[0.0, 0.0], [1.0, 0.25], [1.0, 0.75], [0.0, 1.0]]);
+    }
+
+    #[test]
+    fn bars_become_filled_rectangles() {
+        let quad = vec![[1.0, 0.0], [1.0, 0.5], [2.0, 0.0], [2.0, 0.5]];
+        let out = build_cpu_polylines(&[series(quad, DrawMode::Bar)], &uniforms(10.0, 1.0), CPU_POINT_BUDGET);
+        assert_eq!(out.len(), 1);
+        assert!(out[0].filled);
+        assert_eq!(out[0].points, vec![[1.0, 1.0], [1.0, 0.5], [2.0, 0.5], [2.0, 1.0]]);
+    }
+
+    #[test]
+    fn decimation_respects_budget_and_keeps_spike() {
+        let mut pts: Vec<[f32; 2]> = (0..10_000).map(|i| [i as f32, 0.0]).collect();
+        pts[4321][1] = 50.0;
+        let out = decimate_minmax(&pts, 200);
+        assert!(out.len() <= 200);
+        assert_eq!(out[0], pts[0]);
+        assert_eq!(*out.last().unwrap(), pts[9_999]);
+        assert!(out.iter().any(|p| p[1] == 50.0), "spike lost in decimation");
+        assert!(out.windows(2).all(|w| w[0][0] <= w[1][0]), "X order broken");
+    }
+}
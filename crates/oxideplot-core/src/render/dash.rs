@@ -0,0 +1,173 @@
+//! Screen-space dash patterns for polylines.
+//!
+//! The line pipeline only draws solid segments, but it already treats a
+//! non-finite point as a line break (see [`is_finite_point`]). [`dash_polyline`]
+//! cuts a polyline into dashes of fixed *pixel* length separated by NaN
+//! breaks, so a dashed style needs no shader support. Dash lengths depend on
+//! the view scale, so callers re-dash whenever they rebuild for a new view.
+
+use crate::render::gpu_types::is_finite_point;
+
+const BREAK: [f32; 2] = [f32::NAN, f32::NAN];
+
+/// Split `points` into `dash_px`-long dashes with `gap_px` gaps, measured in
+/// pixels via `px_per_unit` (pixels per data unit on X and Y). The pattern
+/// restarts after each existing break in the input.
+pub fn dash_polyline(points: &[[f32; 2]], px_per_unit: [f64; 2], dash_px: f64, gap_px: f64) -> Vec<[f32; 2]> {
+    let period = dash_px + gap_px;
+    if points.len() < 2 || dash_px <= 0.0 || gap_px <= 0.0 || !px_per_unit.iter().all(|s| s.is_finite() && *s > 0.0) {
+        return points.to_vec();
+    }
+    let mut out = Vec::with_capacity(points.len() * 2);
+    // Distance travelled along the current pattern period, in pixels.
+    let mut phase = 0.0;
+    let mut drawing = false;
+    for w in points.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        if !is_finite_point(a) || !is_finite_point(b) {
+            if drawing {
+                out.push(BREAK);
+                drawing = false;
+            }
+            phase = 0.0;
+            continue;
+        }
+        let dx = (b[0] - a[0]) as f64 * px_per_unit[0];
+        let dy = (b[1] - a[1]) as f64 * px_per_unit[1];
+        let len = dx.hypot(dy);
+        if len == 0.0 {
+            continue;
+        }
+        let at = |d: f64| {
+            let t = (d / len) as f32;
+            [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t]
+        };
+        let mut d = 0.0;
+        while d < len {
+            let in_dash = phase < dash_px;
+            let boundary = if in_dash { dash_px } else { period };
+            let step = (boundary - phase).min(len - d);
+            if in_dash {
+                if !drawing {
+                    out.push(at(d));
+                    drawing = true;
+                }
+                out.push(at(d + step));
+            } else if drawing {
+                out.push(BREAK);
+                drawing = false;
+            }
+            d += step;
+            phase += step;
+            if phase >= period {
+                phase -= period;
+            }
+        }
+    }
+    out
+}
+
+/// How a break in a series (missing samples) is drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GapStyle {
+    /// Nothing: the line just stops and restarts.
+    Off,
+    /// A faint dashed line across the gap.
+    Dashed,
+    /// A faint dotted line across the gap.
+    Dotted,
+}
+
+impl GapStyle {
+    pub fn parse(s: &str) -> GapStyle {
+        match s {
+            "dashed" => GapStyle::Dashed,
+            "dotted" => GapStyle::Dotted,
+            _ => GapStyle::Off,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GapStyle::Off => "none",
+            GapStyle::Dashed => "dashed",
+            GapStyle::Dotted => "dotted",
+        }
+    }
+
+    /// Dash and gap lengths in pixels, or None when gaps aren't drawn.
+    pub fn pattern_px(self) -> Option<(f64, f64)> {
+        match self {
+            GapStyle::Off => None,
+            GapStyle::Dashed => Some((5.0, 4.0)),
+            GapStyle::Dotted => Some((1.5, 3.5)),
+        }
+    }
+}
+
+/// One straight segment per break in `points`, from the last finite point
+/// before it to the first after it, separated by breaks. Breaks at either
+/// end of the polyline join nothing and give no segment.
+pub fn gap_bridges(points: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    let mut out = Vec::new();
+    let mut last: Option<[f32; 2]> = None;
+    let mut in_gap = false;
+    for &p in points {
+        if !is_finite_point(p) {
+            in_gap = last.is_some();
+            continue;
+        }
+        if in_gap {
+            if !out.is_empty() {
+                out.push(BREAK);
+            }
+            out.extend([last.unwrap_or(p), p]);
+            in_gap = false;
+        }
+        last = Some(p);
+    }
+    out
+}
+
+#[cfg(test)]
+mod dash_tests {
+    use super::*;
+
+    fn runs(points: &[[f32; 2]]) -> Vec<Vec<[f32; 2]>> {
+        points
+            .split(|p| !is_finite_point(*p))
+            .filter(|r| !r.is_empty())
+            .map(|r| r.to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn straight_line_is_cut_into_pixel_dashes() {
+        // 0..10 data units at 10 px/unit = 100 px; 6 px dash + 4 px gap.
+        let out = dash_polyline(&[[0.0, 0.0], [10.0, 0.0]], [10.0, 1.0], 6.0, 4.0);
+        let r = runs(&out);
+        assert_eq!(r.len(), 10);
+        assert_eq!(r[0].len(), 2);
+        assert!((r[0][1][0] - 0.6).abs() < 1e-6);
+        assert!((r[1][0][0] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn existing_breaks_are_kept_and_restart_the_pattern() {
+        let pts = [[0.0, 0.0], [0.5, 0.0], [f32::NAN, f32::NAN], [2.0, 0.0], [2.5, 0.0]];
+        let r = runs(&dash_polyline(&pts, [10.0, 1.0], 6.0, 4.0));
+        assert_eq!(r.len(), 2);
+        assert_eq!(r[1][0], [2.0, 0.0]);
+    }
+
+    #[test]
+    fn gaps_are_bridged_between_their_finite_neighbours() {
+        let nan = [f32::NAN, f32::NAN];
+        let pts = [nan, [0.0, 0.0], [1.0, 1.0], nan, nan, [3.0, 2.0], nan, [4.0, 0.0], nan];
+        let r = runs(&gap_bridges(&pts));
+        assert_eq!(r, vec![vec![[1.0, 1.0], [3.0, 2.0]], vec![[3.0, 2.0], [4.0, 0.0]]]);
+        assert!(gap_bridges(&[[0.0, 0.0], [1.0, 1.0]]).is_empty());
+        assert_eq!(GapStyle::parse(GapStyle::Dotted.as_str()), GapStyle::Dotted);
+        assert_eq!(GapStyle::parse("bogus"), GapStyle::Off);
+    }
+}
@@ -0,0 +1,120 @@
+//! GPU adapter diagnostics and backend selection.
+//!
+//! [`GpuDiagnostics`] is a serialisable snapshot of the adapter/device a
+//! [`PlotRenderer`](super::renderer::PlotRenderer) ended up on, for the
+//! in-app diagnostics panel. [`BackendPreference`] is the user's persisted
+//! choice of graphics API, applied when the next renderer is created — the
+//! usual lever when a lab PC's driver misbehaves on WebGPU. The webview picks
+//! the native API (DX12/Vulkan/Metal) underneath, so that isn't offered.
+
+use serde::Serialize;
+
+/// Which graphics backend to request when creating a renderer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BackendPreference {
+    /// WebGPU.
+    #[default]
+    Auto,
+    /// WebGL2.
+    Gl,
+    /// Skip the GPU: the CPU painter.
+    Software,
+}
+
+impl BackendPreference {
+    /// Parse a preference string (`"auto"`, `"gl"`, `"software"`).
+    /// Unrecognised values (including the native API names older versions
+    /// stored) fall back to `Auto`.
+    pub fn parse(s: &str) -> BackendPreference {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "gl" | "opengl" | "webgl" => BackendPreference::Gl,
+            "software" | "cpu" => BackendPreference::Software,
+            _ => BackendPreference::Auto,
+        }
+    }
+
+    /// Stable string form, the inverse of [`parse`](Self::parse).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendPreference::Auto => "auto",
+            BackendPreference::Gl => "gl",
+            BackendPreference::Software => "software",
+        }
+    }
+}
+
+/// Adapter, device-limit and surface details for the diagnostics panel.
+#[derive(Debug, Clone, Serialize)]
+pub struct GpuDiagnostics {
+    pub adapter_name: String,
+    pub vendor: u32,
+    pub device: u32,
+    pub device_type: String,
+    pub backend: String,
+    pub driver: String,
+    pub driver_info: String,
+    pub surface_format: String,
+    pub max_texture_dimension_2d: u32,
+    pub max_buffer_size: u64,
+    pub max_storage_buffer_binding_size: u32,
+    pub max_bind_groups: u32,
+}
+
+impl GpuDiagnostics {
+    /// Build from the adapter info, the opened device's limits, and the
+    /// render-target format.
+    pub fn new(
+        info: &wgpu::AdapterInfo,
+        limits: &wgpu::Limits,
+        format: wgpu::TextureFormat,
+    ) -> Self {
+        Self {
+            adapter_name: info.name.clone(),
+            vendor: info.vendor,
+            device: info.device,
+            device_type: format!("{:?}", info.device_type),
+            backend: info.backend.to_str().to_string(),
+            driver: info.driver.clone(),
+            driver_info: info.driver_info.clone(),
+            surface_format: format!("{format:?}"),
+            max_texture_dimension_2d: limits.max_texture_dimension_2d,
+            max_buffer_size: limits.max_buffer_size,
+            max_storage_buffer_binding_size: limits.max_storage_buffer_binding_size,
+            max_bind_groups: limits.max_bind_groups,
+        }
+    }
+}
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use super::*;
+
+    #[test]
+    fn parse_round_trips_and_defaults() {
+        for p in [BackendPreference::Auto, BackendPreference::Gl, BackendPreference::Software] {
+            assert_eq!(BackendPreference::parse(p.as_str()), p);
+        }
+        assert_eq!(BackendPreference::parse(" OpenGL "), BackendPreference::Gl);
+        assert_eq!(BackendPreference::parse("vulkan"), BackendPreference::Auto);
+        assert_eq!(BackendPreference::parse("bogus"), BackendPreference::Auto);
+    }
+
+    #[test]
+    fn diagnostics_capture_limits_and_format() {
+        let info = wgpu::AdapterInfo {
+            name: "Test GPU".into(),
+            vendor: 0x10de,
+            device: 1,
+            device_type: wgpu::DeviceType::DiscreteGpu,
+            driver: "drv".into(),
+            driver_info: "1.2.3".into(),
+            backend: wgpu::Backend::Vulkan,
+        };
+        let d = GpuDiagnostics::new(&info, &wgpu::Limits::default(), wgpu::TextureFormat::Bgra8Unorm);
+        assert_eq!(d.adapter_name, "Test GPU");
+        assert_eq!(d.backend, "vulkan");
+        assert_eq!(d.device_type, "DiscreteGpu");
+        assert_eq!(d.surface_format, "Bgra8Unorm");
+        assert_eq!(d.max_texture_dimension_2d, wgpu::Limits::default().max_texture_dimension_2d);
+    }
+}
@@ -0,0 +1,107 @@
+//! Frame-budget guard for interactive pan/zoom on heavy graphs.
+//!
+//! Every pan/zoom event re-decimates each series for the new view. With many
+//! large series that prep can blow well past a 60 fps frame, so
+//! [`FrameBudget`] measures it and scales the per-series point target down
+//! when a frame runs over budget (and back up when there's headroom). Once
+//! interaction stops the caller restores full quality and refines series a
+//! few at a time across frames instead of in one long stall.
+
+/// Default prep budget per interactive frame, in milliseconds. Leaves headroom
+/// for the GPU submit and overlays so interaction stays above ~30 fps.
+pub const DEFAULT_FRAME_BUDGET_MS: f64 = 12.0;
+
+/// Lowest quality factor the guard will degrade to.
+pub const MIN_QUALITY: f64 = 0.1;
+
+/// Adaptive quality factor in `[MIN_QUALITY, 1.0]` driven by measured prep time.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameBudget {
+    budget_ms: f64,
+    quality: f64,
+}
+
+impl Default for FrameBudget {
+    fn default() -> Self {
+        Self::new(DEFAULT_FRAME_BUDGET_MS)
+    }
+}
+
+impl FrameBudget {
+    /// New guard at full quality with the given per-frame prep budget.
+    pub fn new(budget_ms: f64) -> Self {
+        Self { budget_ms: budget_ms.max(1.0), quality: 1.0 }
+    }
+
+    /// Current quality factor (1.0 = full point budget).
+    pub fn quality(&self) -> f64 {
+        self.quality
+    }
+
+    /// True when the last interactive frames were built below full quality.
+    pub fn is_degraded(&self) -> bool {
+        self.quality < 1.0
+    }
+
+    /// Feed the measured prep time of the frame just built. Over budget, the
+    /// quality drops proportionally (with 10% margin) so the next frame should
+    /// fit; under half the budget, it recovers by 25% per frame.
+    pub fn record(&mut self, prep_ms: f64) {
+        if !prep_ms.is_finite() {
+            return;
+        }
+        if prep_ms > self.budget_ms {
+            self.quality = (self.quality * self.budget_ms / prep_ms * 0.9).max(MIN_QUALITY);
+        } else if prep_ms < self.budget_ms * 0.5 {
+            self.quality = (self.quality * 1.25).min(1.0);
+        }
+    }
+
+    /// Back to full quality (call when interaction stops).
+    pub fn restore(&mut self) {
+        self.quality = 1.0;
+    }
+
+    /// Scale a full-quality point target by the current quality, never below
+    /// `floor` (nor above `full`).
+    pub fn scaled_target(&self, full: usize, floor: usize) -> usize {
+        ((full as f64 * self.quality) as usize).max(floor).min(full)
+    }
+}
+
+#[cfg(test)]
+mod frame_budget_tests {
+    use super::*;
+
+    #[test]
+    fn over_budget_degrades_and_headroom_recovers() {
+        let mut b = FrameBudget::new(12.0);
+        b.record(24.0);
+        assert!(b.is_degraded());
+        assert!((b.quality() - 0.45).abs() < 1e-9);
+        for _ in 0..10 {
+            b.record(1.0);
+        }
+        assert_eq!(b.quality(), 1.0);
+    }
+
+    #[test]
+    fn quality_is_floored_and_within_budget_holds() {
+        let mut b = FrameBudget::new(12.0);
+        b.record(10_000.0);
+        assert_eq!(b.quality(), MIN_QUALITY);
+        b.record(8.0); // between half and full budget: unchanged
+        assert_eq!(b.quality(), MIN_QUALITY);
+        b.restore();
+        assert!(!b.is_degraded());
+    }
+
+    #[test]
+    fn scaled_target_respects_floor() {
+        let mut b = FrameBudget::new(12.0);
+        assert_eq!(b.scaled_target(2000, 200), 2000);
+        b.record(120.0);
+        assert_eq!(b.scaled_target(2000, 200), 200);
+        assert_eq!(b.scaled_target(100, 200), 100);
+    }
+}
@@ -0,0 +1,157 @@
+//! GPU memory guardrails for the per-frame storage-buffer uploads.
+//!
+//! View decimation normally keeps uploads small, but a series drawn as dots
+//! with millions of samples (or many series batched into one draw) can ask
+//! for a storage buffer past the device's `max_storage_buffer_binding_size`,
+//! which wgpu reports as a validation error and the browser may answer by
+//! losing the device. [`GpuBudget`] caps every buffer and the total upload
+//! per frame; [`plan_points`] says how many points each series may keep and
+//! [`decimate`] thins a series to that count evenly.
+
+use serde::Serialize;
+
+use super::gpu_types::DrawMode;
+
+/// Default cap for one storage buffer, in bytes (64 MiB).
+pub const DEFAULT_MAX_BUFFER_BYTES: u64 = 64 << 20;
+
+/// Default cap for all storage buffers uploaded in one frame (256 MiB).
+pub const DEFAULT_FRAME_UPLOAD_BYTES: u64 = 256 << 20;
+
+/// Upload limits: the configured caps, never above what the device allows.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct GpuBudget {
+    /// Largest storage buffer the device can bind.
+    pub device_max_bytes: u64,
+    /// Cap for one series' storage buffer.
+    pub max_buffer_bytes: u64,
+    /// Cap for the sum of a frame's storage buffers.
+    pub frame_upload_bytes: u64,
+}
+
+impl GpuBudget {
+    /// Default caps, clamped to `limits`.
+    pub fn from_limits(limits: &wgpu::Limits) -> Self {
+        let device_max = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+        Self::with_device_max(device_max)
+    }
+
+    /// Default caps for a device whose largest bindable buffer is `device_max_bytes`.
+    pub fn with_device_max(device_max_bytes: u64) -> Self {
+        let mut budget = Self { device_max_bytes, max_buffer_bytes: 0, frame_upload_bytes: 0 };
+        budget.configure(DEFAULT_MAX_BUFFER_BYTES, DEFAULT_FRAME_UPLOAD_BYTES);
+        budget
+    }
+
+    /// Change the caps. The per-buffer cap is clamped to the device limit and
+    /// the frame cap to at least one buffer.
+    pub fn configure(&mut self, max_buffer_bytes: u64, frame_upload_bytes: u64) {
+        self.max_buffer_bytes = max_buffer_bytes.clamp(1024, self.device_max_bytes.max(1024));
+        self.frame_upload_bytes = frame_upload_bytes.max(self.max_buffer_bytes);
+    }
+}
+
+/// What the guardrails did to the last frame, for the diagnostics panel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+pub struct UploadReport {
+    /// Storage bytes uploaded.
+    pub uploaded_bytes: u64,
+    /// Storage bytes the frame would have needed without decimation.
+    pub requested_bytes: u64,
+    /// Series drawn with fewer points than they had.
+    pub decimated_series: usize,
+}
+
+impl UploadReport {
+    /// True when any series was thinned to stay within budget.
+    pub fn is_limited(&self) -> bool {
+        self.decimated_series > 0
+    }
+}
+
+/// Storage bytes a series uploads per input point in `mode` (line pairs,
+/// step corners, band or bar quads, or single points).
+pub fn bytes_per_point(mode: DrawMode) -> u64 {
+    match mode {
+        DrawMode::Points | DrawMode::Bar => 8,
+        DrawMode::Lines | DrawMode::Smooth | DrawMode::Band => 16,
+        DrawMode::Step | DrawMode::StepPre => 32,
+    }
+}
+
+/// Points each series may keep: `series` gives `(point count, mode)`. Every
+/// series fits one buffer, and when the frame as a whole is over budget all
+/// series are scaled down by the same factor. Also returns the bytes the
+/// frame would have needed.
+pub fn plan_points(series: &[(usize, DrawMode)], budget: &GpuBudget) -> (Vec<usize>, u64) {
+    let requested: u64 = series.iter().map(|&(n, mode)| n as u64 * bytes_per_point(mode)).sum();
+    let scale = if requested > budget.frame_upload_bytes {
+        budget.frame_upload_bytes as f64 / requested as f64
+    } else {
+        1.0
+    };
+    let caps = series
+        .iter()
+        .map(|&(n, mode)| {
+            let per_buffer = (budget.max_buffer_bytes / bytes_per_point(mode)) as usize;
+            ((n as f64 * scale) as usize).min(per_buffer).min(n)
+        })
+        .collect();
+    (caps, requested)
+}
+
+/// Keep at most `max` of `points`, evenly spaced, in groups of `group`
+/// (2 for band edge pairs) so groups are never split. The first and last
+/// groups are always kept.
+pub fn decimate(points: &[[f32; 2]], max: usize, group: usize) -> Vec<[f32; 2]> {
+    let group = group.max(1);
+    let groups = points.len() / group;
+    let keep = (max / group).max(2).min(groups);
+    if keep >= groups {
+        return points.to_vec();
+    }
+    let mut out = Vec::with_capacity(keep * group);
+    for k in 0..keep {
+        let g = k * (groups - 1) / (keep - 1);
+        out.extend_from_slice(&points[g * group..(g + 1) * group]);
+    }
+    out
+}
+
+#[cfg(test)]
+mod gpu_budget_tests {
+    use super::*;
+
+    #[test]
+    fn caps_fit_one_buffer_and_share_the_frame_budget() {
+        let mut budget = GpuBudget::with_device_max(128 << 20);
+        budget.configure(8 << 20, 16 << 20);
+        // 5M dots would need 40 MB in one buffer.
+        let (caps, requested) = plan_points(&[(5_000_000, DrawMode::Points), (1000, DrawMode::Lines)], &budget);
+        assert_eq!(requested, 40_016_000);
+        assert_eq!(caps[0], (8 << 20) / 8);
+        assert!(caps[1] < 1000 && caps[1] > 400, "frame budget scales every series");
+
+        let (caps, _) = plan_points(&[(1000, DrawMode::Step)], &budget);
+        assert_eq!(caps, [1000]);
+
+        // Configured caps never exceed the device.
+        budget.configure(1 << 40, 0);
+        assert_eq!(budget.max_buffer_bytes, 128 << 20);
+        assert_eq!(budget.frame_upload_bytes, 128 << 20);
+    }
+
+    #[test]
+    fn decimate_keeps_ends_and_groups() {
+        let pts: Vec<[f32; 2]> = (0..100).map(|i| [i as f32, 0.0]).collect();
+        let out = decimate(&pts, 10, 1);
+        assert_eq!(out.len(), 10);
+        assert_eq!(out[0][0], 0.0);
+        assert_eq!(out[9][0], 99.0);
+
+        let out = decimate(&pts, 10, 2);
+        assert_eq!(out.len(), 10);
+        assert!(out.chunks(2).all(|p| p[1][0] == p[0][0] + 1.0 && p[0][0] % 2.0 == 0.0));
+        assert_eq!(decimate(&pts, 500, 1).len(), 100);
+    }
+}
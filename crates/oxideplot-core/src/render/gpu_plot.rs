@@ -14,9 +14,9 @@ use super::gpu_types::PlotUniforms;
 // ---------------------------------------------------------------------------
 // WGSL shader source (embedded)
 //
-// NOTE: This string is byte-for-byte identical to the one in the legacy crate's
-// `render/gpu_plot.rs`. The duplication is deliberate (see module docs) and
-// disappears when legacy is deleted in Task 6.3.
+// NOTE: This started as a copy of the legacy crate's `render/gpu_plot.rs`
+// shader; it has since gained fills (`vs_fill`) and pixel snapping
+// (`pixel_snap`) that the legacy copy lacks.
 // ---------------------------------------------------------------------------
 
 pub const PLOT_SHADER_SRC: &str = r#"
@@ -27,7 +27,10 @@ struct Uniforms {
     line_width: f32,
     point_radius: f32,
     color: vec4<f32>,
-    _padding: vec4<f32>,
+    pixel_snap: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
 };
 
 @group(0) @binding(0) var<uniform> u: Uniforms;
@@ -52,6 +55,15 @@ fn px_to_ndc(px: vec2<f32>) -> vec2<f32> {
     return (px / u.resolution) * 2.0 - vec2<f32>(1.0, 1.0);
 }
 
+// Line centre across a `width`-pixel snapped line: odd widths sit on a pixel
+// centre, even widths on a pixel edge, so the quad covers whole pixels.
+fn snap_coord(v: f32, width: f32) -> f32 {
+    if width % 2.0 == 1.0 {
+        return floor(v) + 0.5;
+    }
+    return round(v);
+}
+
 @vertex
 fn vs_line(
     @builtin(instance_index) inst: u32,
@@ -61,16 +73,30 @@ fn vs_line(
     let p0 = data_to_ndc(points[inst * 2u]);
     let p1 = data_to_ndc(points[inst * 2u + 1u]);
 
-    let px0 = ndc_to_px(p0);
-    let px1 = ndc_to_px(p1);
+    var px0 = ndc_to_px(p0);
+    var px1 = ndc_to_px(p1);
+
+    // Pixel snapping: axis-aligned segments get a whole-pixel width and a
+    // snapped cross coordinate, so 1px grid lines don't straddle two pixels.
+    var width = u.line_width;
+    if u.pixel_snap > 0.5 {
+        width = max(round(width), 1.0);
+        if abs(px1.x - px0.x) < 0.5 {
+            px0.x = snap_coord(px0.x, width);
+            px1.x = px0.x;
+        } else if abs(px1.y - px0.y) < 0.5 {
+            px0.y = snap_coord(px0.y, width);
+            px1.y = px0.y;
+        }
+    }
 
     let dir = px1 - px0;
     let len = length(dir);
     var perp: vec2<f32>;
     if len > 0.001 {
-        perp = vec2<f32>(-dir.y, dir.x) / len * u.line_width * 0.5;
+        perp = vec2<f32>(-dir.y, dir.x) / len * width * 0.5;
     } else {
-        perp = vec2<f32>(0.0, u.line_width * 0.5);
+        perp = vec2<f32>(0.0, width * 0.5);
     }
 
     var base: vec2<f32>;
@@ -99,6 +125,58 @@ fn fs_solid(@location(0) color: vec4<f32>) -> @location(0) vec4<f32> {
     return color;
 }
 
+// Filled quads (bands): storage holds [a0, b0, a1, b1] per instance, the
+// lower/upper edge at two consecutive X positions; two triangles each.
+@vertex
+fn vs_fill(
+    @builtin(instance_index) inst: u32,
+    @builtin(vertex_index) vert: u32,
+) -> LineOutput {
+    var corner: u32;
+    switch vert {
+        case 0u: { corner = 0u; }
+        case 1u: { corner = 1u; }
+        case 2u: { corner = 2u; }
+        case 3u: { corner = 1u; }
+        case 4u: { corner = 3u; }
+        case 5u: { corner = 2u; }
+        default: { corner = 0u; }
+    }
+    var out: LineOutput;
+    out.pos = vec4<f32>(data_to_ndc(points[inst * 4u + corner]), 0.0, 1.0);
+    out.color = u.color;
+    return out;
+}
+
+// Heatmap cells (see `render::heatmap`): storage holds (cols, rows), the
+// lower and upper data corners, then two vec2s of RGBA per cell, row-major
+// from the bottom row. One instance per cell, two triangles each.
+@vertex
+fn vs_heat(
+    @builtin(instance_index) inst: u32,
+    @builtin(vertex_index) vert: u32,
+) -> LineOutput {
+    let dims = points[0];
+    let cols = u32(dims.x);
+    let cell = vec2<f32>(f32(inst % cols), f32(inst / cols));
+    var corner: vec2<f32>;
+    switch vert {
+        case 0u: { corner = vec2<f32>(0.0, 0.0); }
+        case 1u: { corner = vec2<f32>(1.0, 0.0); }
+        case 2u: { corner = vec2<f32>(0.0, 1.0); }
+        case 3u: { corner = vec2<f32>(1.0, 0.0); }
+        case 4u: { corner = vec2<f32>(1.0, 1.0); }
+        case 5u: { corner = vec2<f32>(0.0, 1.0); }
+        default: { corner = vec2<f32>(0.0, 0.0); }
+    }
+    let lo = points[1];
+    let size = (points[2] - lo) / dims;
+    var out: LineOutput;
+    out.pos = vec4<f32>(data_to_ndc(lo + (cell + corner) * size), 0.0, 1.0);
+    out.color = vec4<f32>(points[3u + inst * 2u], points[4u + inst * 2u]);
+    return out;
+}
+
 struct PointOutput {
     @builtin(position) pos: vec4<f32>,
     @location(0) color: vec4<f32>,
@@ -159,6 +237,10 @@ fn fs_point(
 pub enum PipelineType {
     Line,
     Point,
+    /// Filled quads, four storage points per instance (see `vs_fill`).
+    Fill,
+    /// Colormapped heatmap cells, one per instance (see `vs_heat`).
+    Heatmap,
 }
 
 /// A single GPU draw call: a bind group (uniform + storage buffer) plus the
@@ -173,8 +255,8 @@ pub struct DrawCall {
 // Pipeline creation
 // ---------------------------------------------------------------------------
 
-/// Create the 2D line and point render pipelines plus the shared bind-group
-/// layout, targeting `format`.
+/// Create the 2D line, point, fill and heatmap render pipelines plus the
+/// shared bind-group layout, targeting `format`.
 ///
 /// Adapted from legacy `init_gpu_resources`: identical pipeline/layout setup,
 /// but takes a plain `&wgpu::Device` + `wgpu::TextureFormat` instead of an
@@ -184,6 +266,8 @@ pub fn create_pipelines(
     device: &wgpu::Device,
     format: wgpu::TextureFormat,
 ) -> (
+    wgpu::RenderPipeline,
+    wgpu::RenderPipeline,
     wgpu::RenderPipeline,
     wgpu::RenderPipeline,
     wgpu::BindGroupLayout,
@@ -297,7 +381,59 @@ pub fn create_pipelines(
         cache: None,
     });
 
-    (line_pipeline, point_pipeline, bind_group_layout)
+    let fill_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("plot_fill_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_fill"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive,
+        depth_stencil: None,
+        multisample,
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_solid"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend_state),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview: None,
+        cache: None,
+    });
+
+    let heat_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("plot_heat_pipeline"),
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_heat"),
+            buffers: &[],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        },
+        primitive,
+        depth_stencil: None,
+        multisample,
+        fragment: Some(wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_solid"),
+            targets: &[Some(wgpu::ColorTargetState {
+                format,
+                blend: Some(blend_state),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+        }),
+        multiview: None,
+        cache: None,
+    });
+
+    (line_pipeline, point_pipeline, fill_pipeline, heat_pipeline, bind_group_layout)
 }
 
 // ---------------------------------------------------------------------------
@@ -1,5 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 
+use super::layers::GridLayer;
+
 /// GPU uniform buffer for the plot transform and rendering parameters.
 /// Layout matches the WGSL struct exactly (64 bytes, aligned to 16).
 #[repr(C)]
@@ -17,8 +19,11 @@ pub struct PlotUniforms {
     pub point_radius: f32,
     /// RGBA color for current draw call.
     pub color: [f32; 4],
+    /// 1.0 snaps horizontal/vertical line segments to whole device pixels
+    /// (crisp grid lines); 0.0 draws them where they fall.
+    pub pixel_snap: f32,
     /// Pad to 64 bytes (multiple of 16).
-    pub _padding: [f32; 4],
+    pub _padding: [f32; 3],
 }
 
 /// How a data series should be drawn.
@@ -26,10 +31,53 @@ pub struct PlotUniforms {
 pub enum DrawMode {
     /// Connected line segments (point[i] -> point[i+1]).
     Lines,
-    /// Step function (horizontal then vertical segments).
+    /// Step function (horizontal then vertical segments): each value holds
+    /// until the next point (step-post).
     Step,
+    /// Step function (vertical then horizontal segments): each value applies
+    /// from the previous point (step-pre).
+    StepPre,
     /// Individual scatter points.
     Points,
+    /// Smooth Catmull-Rom curve through the points (see [`super::spline`]).
+    Smooth,
+    /// Filled band: `points` holds `[lower, upper]` pairs, one per X. A
+    /// pair with a non-finite point breaks the band.
+    Band,
+    /// Bars: `points` holds one quad per bar, `[x0, lo], [x0, hi], [x1, lo],
+    /// [x1, hi]` (see [`super::bars::layout_bars`]). Before layout, a bar
+    /// source's samples are plain `[x, value]` points.
+    Bar,
+}
+
+/// False for a point with a NaN/∞ coordinate. Series use such points as line
+/// breaks: renderers skip every segment (or dot) that touches one.
+pub fn is_finite_point(p: [f32; 2]) -> bool {
+    p[0].is_finite() && p[1].is_finite()
+}
+
+/// Corner of the step between `p0` and `p1`: `[x1, y0]` for
+/// [`DrawMode::Step`], `[x0, y1]` for [`DrawMode::StepPre`].
+pub fn step_corner(p0: [f32; 2], p1: [f32; 2], pre: bool) -> [f32; 2] {
+    if pre {
+        [p0[0], p1[1]]
+    } else {
+        [p1[0], p0[1]]
+    }
+}
+
+/// Quads `[lower0, upper0, lower1, upper1]` between consecutive finite
+/// `[lower, upper]` pairs of a [`DrawMode::Band`] series.
+pub fn band_quads(pairs: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    let pairs: Vec<&[[f32; 2]]> = pairs.chunks_exact(2).collect();
+    let mut out = Vec::with_capacity(pairs.len().saturating_sub(1) * 4);
+    for w in pairs.windows(2) {
+        if w.iter().flat_map(|p| p.iter()).all(|&p| is_finite_point(p)) {
+            out.extend_from_slice(w[0]);
+            out.extend_from_slice(w[1]);
+        }
+    }
+    out
 }
 
 /// GPU-ready data for a single data series.
@@ -57,6 +105,11 @@ pub struct GridGpuData {
     pub color: [f32; 4],
     /// Line width in pixels.
     pub line_width: f32,
+    /// Snap the lines to the pixel grid so 1px lines stay sharp at any
+    /// scale (see [`PlotUniforms::pixel_snap`]).
+    pub pixel_snap: bool,
+    /// Beneath the series (default) or over them.
+    pub layer: GridLayer,
 }
 
 // ---------------------------------------------------------------------------
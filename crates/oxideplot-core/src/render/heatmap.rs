@@ -0,0 +1,179 @@
+//! Colormapped heatmaps (spectrograms) for the GPU.
+//!
+//! A [`HeatmapGpuData`] is a `cols` x `rows` grid of coloured cells over a
+//! data-space rectangle; `vs_heat` in [`gpu_plot`](super::gpu_plot) draws
+//! one quad per cell. Colours are looked up on the CPU with a [`Colormap`],
+//! so the GPU and the MCP server's software spectrograms share one palette.
+
+/// Magnitude → colour lookup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Colormap {
+    /// Black through purple and orange to pale yellow.
+    #[default]
+    Magma,
+    /// Dark blue through green to yellow.
+    Viridis,
+    /// Black to white.
+    Gray,
+}
+
+const MAGMA: [(f64, [f64; 3]); 5] = [
+    (0.0, [0.0, 0.0, 0.02]),
+    (0.25, [0.28, 0.05, 0.35]),
+    (0.5, [0.65, 0.18, 0.42]),
+    (0.75, [0.95, 0.45, 0.28]),
+    (1.0, [0.99, 0.87, 0.55]),
+];
+
+const VIRIDIS: [(f64, [f64; 3]); 5] = [
+    (0.0, [0.27, 0.00, 0.33]),
+    (0.25, [0.23, 0.32, 0.55]),
+    (0.5, [0.13, 0.57, 0.55]),
+    (0.75, [0.37, 0.79, 0.38]),
+    (1.0, [0.99, 0.91, 0.14]),
+];
+
+const GRAY: [(f64, [f64; 3]); 2] = [(0.0, [0.0, 0.0, 0.0]), (1.0, [1.0, 1.0, 1.0])];
+
+impl Colormap {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "magma" => Some(Colormap::Magma),
+            "viridis" => Some(Colormap::Viridis),
+            "gray" => Some(Colormap::Gray),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Colormap::Magma => "magma",
+            Colormap::Viridis => "viridis",
+            Colormap::Gray => "gray",
+        }
+    }
+
+    /// Colour at `t` (clamped to 0..1), linear between the map's stops.
+    pub fn color(self, t: f64) -> [f64; 3] {
+        let stops: &[(f64, [f64; 3])] = match self {
+            Colormap::Magma => &MAGMA,
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Gray => &GRAY,
+        };
+        let t = if t.is_finite() { t.clamp(0.0, 1.0) } else { 0.0 };
+        let i = stops.windows(2).position(|w| t <= w[1].0).unwrap_or(stops.len() - 2);
+        let ((t0, c0), (t1, c1)) = (stops[i], stops[i + 1]);
+        let f = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
+        [0, 1, 2].map(|k| c0[k] + (c1[k] - c0[k]) * f)
+    }
+}
+
+/// Robust colour range of a magnitude matrix: the 5th and 99.5th
+/// percentiles of `log10(m + 1e-12)`, so a few loud bins don't wash out
+/// the rest. `(0, 1)` when empty.
+pub fn log_color_range(frames: &[Vec<f64>]) -> (f64, f64) {
+    let mut logs: Vec<f64> = frames
+        .iter()
+        .flatten()
+        .map(|m| (m + 1e-12).log10())
+        .filter(|v| v.is_finite())
+        .collect();
+    if logs.is_empty() {
+        return (0.0, 1.0);
+    }
+    logs.sort_by(|a, b| a.total_cmp(b));
+    let at = |p: f64| logs[((p * logs.len() as f64) as usize).min(logs.len() - 1)];
+    let (lo, hi) = (at(0.05), at(0.995));
+    if hi > lo { (lo, hi) } else { (lo, lo + 1e-6) }
+}
+
+/// A grid of coloured cells covering `x_range` x `y_range` (data space).
+/// `cells` are row-major from the bottom row up: cell `(col, row)` is
+/// `cells[row * cols + col]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeatmapGpuData {
+    pub cols: u32,
+    pub rows: u32,
+    pub x_range: [f32; 2],
+    pub y_range: [f32; 2],
+    pub cells: Vec<[f32; 4]>,
+}
+
+impl HeatmapGpuData {
+    /// Colour a spectrogram (`frames[frame][bin]` magnitudes) with time on X
+    /// and frequency on Y, log-scaled over [`log_color_range`]. More than
+    /// `max_cols` frames are merged (loudest wins) so the upload stays about
+    /// one column per pixel.
+    pub fn from_frames(
+        frames: &[Vec<f64>],
+        colormap: Colormap,
+        max_cols: usize,
+        x_range: [f32; 2],
+        y_range: [f32; 2],
+    ) -> Self {
+        let rows = frames.iter().map(Vec::len).min().unwrap_or(0);
+        let n = frames.len();
+        let cols = n.min(max_cols.max(1));
+        let (lo, hi) = log_color_range(frames);
+        let mut cells = vec![[0.0; 4]; cols * rows];
+        for c in 0..cols {
+            let group = &frames[c * n / cols..((c + 1) * n / cols).max(c * n / cols + 1)];
+            for r in 0..rows {
+                let m = group.iter().map(|f| f[r]).fold(0.0, f64::max);
+                let [cr, cg, cb] = colormap.color(((m + 1e-12).log10() - lo) / (hi - lo));
+                cells[r * cols + c] = [cr as f32, cg as f32, cb as f32, 1.0];
+            }
+        }
+        Self { cols: cols as u32, rows: rows as u32, x_range, y_range, cells }
+    }
+
+    /// Storage-buffer layout read by `vs_heat`: `(cols, rows)`, the lower
+    /// and upper corners, then each cell's colour as two `vec2`s.
+    pub fn storage(&self) -> Vec<[f32; 2]> {
+        let mut out = Vec::with_capacity(3 + self.cells.len() * 2);
+        out.push([self.cols as f32, self.rows as f32]);
+        out.push([self.x_range[0], self.y_range[0]]);
+        out.push([self.x_range[1], self.y_range[1]]);
+        for c in &self.cells {
+            out.push([c[0], c[1]]);
+            out.push([c[2], c[3]]);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod heatmap_tests {
+    use super::*;
+
+    #[test]
+    fn colormaps_hit_their_end_stops() {
+        assert_eq!(Colormap::Magma.color(0.0), MAGMA[0].1);
+        let top = Colormap::Magma.color(2.0);
+        assert!(top.iter().zip(MAGMA[4].1).all(|(a, b)| (a - b).abs() < 1e-12));
+        assert_eq!(Colormap::Gray.color(0.5), [0.5, 0.5, 0.5]);
+        for m in [Colormap::Magma, Colormap::Viridis, Colormap::Gray] {
+            assert_eq!(Colormap::parse(m.as_str()), Some(m));
+        }
+    }
+
+    #[test]
+    fn frames_become_columns_with_frequency_rows() {
+        // Two frames of three bins; the loud bin is the top one in frame 1.
+        let frames = vec![vec![1.0, 1.0, 1.0], vec![1.0, 1.0, 1000.0]];
+        let h = HeatmapGpuData::from_frames(&frames, Colormap::Gray, 100, [0.0, 1.0], [0.0, 1.0]);
+        assert_eq!((h.cols, h.rows), (2, 3));
+        assert!(h.cells[2 * 2 + 1][0] > h.cells[0][0]);
+        let s = h.storage();
+        assert_eq!(s[0], [2.0, 3.0]);
+        assert_eq!(s.len(), 3 + 6 * 2);
+    }
+
+    #[test]
+    fn extra_frames_merge_keeping_the_loudest() {
+        let frames = vec![vec![1.0], vec![1000.0], vec![1.0], vec![1.0]];
+        let h = HeatmapGpuData::from_frames(&frames, Colormap::Gray, 2, [0.0, 1.0], [0.0, 1.0]);
+        assert_eq!(h.cols, 2);
+        assert!(h.cells[0][0] > h.cells[1][0]);
+    }
+}
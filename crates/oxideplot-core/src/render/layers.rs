@@ -0,0 +1,96 @@
+//! Explicit draw-order control.
+//!
+//! Series draw in list order by default, later ones on top. A series can be
+//! brought to the front or sent to the back without reordering the list:
+//! [`draw_order`] sorts by [`SeriesLayer`] and keeps list order within a
+//! layer. Where the grid goes relative to the data is [`GridLayer`].
+
+use serde::{Deserialize, Serialize};
+
+/// Which band of the draw order a series belongs to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SeriesLayer {
+    /// Under every normal series.
+    Back,
+    /// List order.
+    #[default]
+    Normal,
+    /// Over every normal series.
+    Front,
+}
+
+impl SeriesLayer {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "back" => Some(SeriesLayer::Back),
+            "normal" => Some(SeriesLayer::Normal),
+            "front" => Some(SeriesLayer::Front),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SeriesLayer::Back => "back",
+            SeriesLayer::Normal => "normal",
+            SeriesLayer::Front => "front",
+        }
+    }
+}
+
+/// Where grid lines draw relative to the data.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GridLayer {
+    /// Beneath every series.
+    #[default]
+    Below,
+    /// Over the series, for dense fills that would hide it.
+    Above,
+}
+
+impl GridLayer {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "below" => Some(GridLayer::Below),
+            "above" => Some(GridLayer::Above),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GridLayer::Below => "below",
+            GridLayer::Above => "above",
+        }
+    }
+}
+
+/// Indices of `layers` in draw order (first drawn first): back, normal,
+/// then front series, each in list order.
+pub fn draw_order(layers: &[SeriesLayer]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..layers.len()).collect();
+    order.sort_by_key(|&i| layers[i]);
+    order
+}
+
+#[cfg(test)]
+mod layers_tests {
+    use super::*;
+
+    #[test]
+    fn layers_sort_stably_back_to_front() {
+        use SeriesLayer::*;
+        assert_eq!(draw_order(&[Front, Normal, Back, Normal, Front]), vec![2, 1, 3, 0, 4]);
+        assert_eq!(draw_order(&[Normal; 3]), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn names_round_trip() {
+        for l in [SeriesLayer::Back, SeriesLayer::Normal, SeriesLayer::Front] {
+            assert_eq!(SeriesLayer::parse(l.as_str()), Some(l));
+        }
+        assert_eq!(GridLayer::parse("above"), Some(GridLayer::Above));
+        assert_eq!(GridLayer::parse("top"), None);
+    }
+}
@@ -1,4 +1,18 @@
 pub mod gpu_types;
 pub mod axis;
+pub mod bars;
+pub mod batch;
+pub mod benchmark;
+pub mod cpu_fallback;
+pub mod dash;
+pub mod diagnostics;
+pub mod frame_budget;
+pub mod gpu_budget;
 pub mod gpu_plot;
+pub mod heatmap;
+pub mod layers;
 pub mod renderer;
+pub mod spline;
+pub mod time_bands;
+pub mod unit_colors;
+pub mod contrast;
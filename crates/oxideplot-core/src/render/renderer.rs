@@ -10,6 +10,15 @@
 //! (`request_adapter` → `Option<Adapter>`, `request_device(&desc, None)`,
 //! `SurfaceConfiguration { desired_maximum_frame_latency, .. }`), so the same
 //! code path compiles for both native and `wasm32-unknown-unknown`.
+//!
+//! Uploads are held to a [`GpuBudget`] (oversized series are decimated), and
+//! the first wgpu error the device raises is kept for [`PlotRenderer::gpu_error`]
+//! instead of wgpu's default panic. A lost device (driver reset, sleep/resume)
+//! shows in [`PlotRenderer::device_lost`]; the renderer holds no plot state,
+//! so recovery is building a new one for the same target.
+
+use std::cell::Cell;
+use std::sync::{Arc, Mutex};
 
 use wgpu::util::DeviceExt;
 use wgpu::{
@@ -17,8 +26,13 @@ use wgpu::{
     SurfaceError, TextureFormat,
 };
 
+use super::diagnostics::GpuDiagnostics;
+use super::gpu_budget::{decimate, plan_points, GpuBudget, UploadReport};
 use super::gpu_plot::{create_pipelines, create_storage_buffer, DrawCall, PipelineType};
-use super::gpu_types::{DrawMode, GridGpuData, PlotUniforms, SeriesGpuData};
+use super::gpu_types::{band_quads, is_finite_point, step_corner, DrawMode, GridGpuData, PlotUniforms, SeriesGpuData};
+use super::heatmap::HeatmapGpuData;
+use super::layers::GridLayer;
+use super::spline::expand_smooth;
 
 /// Where a [`PlotRenderer`] draws its frames.
 pub enum RenderTarget {
@@ -36,14 +50,26 @@ pub enum RenderTarget {
     },
 }
 
+/// A message set from a wgpu callback (any thread), read by the renderer.
+type MessageSlot = Arc<Mutex<Option<String>>>;
+
 /// A self-contained 2D plot renderer that owns its GPU device/queue/target.
 pub struct PlotRenderer {
     pub device: Device,
     pub queue: Queue,
     pub target: RenderTarget,
     pub format: TextureFormat,
+    /// Adapter/device/limits snapshot for the diagnostics panel.
+    pub diagnostics: GpuDiagnostics,
+    /// Storage-buffer limits applied by [`build_draw_calls`](Self::build_draw_calls).
+    pub budget: GpuBudget,
+    last_upload: Cell<UploadReport>,
+    gpu_error: MessageSlot,
+    device_lost: MessageSlot,
     line_pipeline: RenderPipeline,
     point_pipeline: RenderPipeline,
+    fill_pipeline: RenderPipeline,
+    heat_pipeline: RenderPipeline,
     bind_group_layout: BindGroupLayout,
 }
 
@@ -53,12 +79,16 @@ impl PlotRenderer {
     /// Requests an adapter compatible with the surface, opens a device + queue,
     /// configures the surface at `width` x `height`, and builds the 2D
     /// pipelines. Async on both native and wasm.
+    ///
+    /// Returns an error (rather than panicking) when no adapter/device is
+    /// available, so the caller can fall back to CPU rendering (see
+    /// [`cpu_fallback`](super::cpu_fallback)).
     pub async fn new_for_surface(
         instance: &Instance,
         surface: Surface<'static>,
         width: u32,
         height: u32,
-    ) -> Self {
+    ) -> Result<Self, String> {
         let adapter = instance
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::default(),
@@ -66,15 +96,18 @@ impl PlotRenderer {
                 compatible_surface: Some(&surface),
             })
             .await
-            .expect("no compatible wgpu adapter found");
+            .ok_or_else(|| "no compatible wgpu adapter found".to_string())?;
 
         let (device, queue) = adapter
             .request_device(&wgpu::DeviceDescriptor::default(), None)
             .await
-            .expect("failed to request wgpu device");
+            .map_err(|e| format!("failed to request wgpu device: {e}"))?;
 
         let caps = surface.get_capabilities(&adapter);
-        let format = caps.formats[0];
+        let format = *caps
+            .formats
+            .first()
+            .ok_or_else(|| "surface reports no supported texture formats".to_string())?;
 
         let config = SurfaceConfiguration {
             usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
@@ -88,18 +121,28 @@ impl PlotRenderer {
         };
         surface.configure(&device, &config);
 
-        let (line_pipeline, point_pipeline, bind_group_layout) =
+        let (line_pipeline, point_pipeline, fill_pipeline, heat_pipeline, bind_group_layout) =
             create_pipelines(&device, format);
+        let diagnostics = GpuDiagnostics::new(&adapter.get_info(), &device.limits(), format);
+        let budget = GpuBudget::from_limits(&device.limits());
+        let (gpu_error, device_lost) = watch_device(&device);
 
-        Self {
+        Ok(Self {
             device,
             queue,
             target: RenderTarget::Surface { surface, config },
             format,
+            diagnostics,
+            budget,
+            last_upload: Cell::default(),
+            gpu_error,
+            device_lost,
             line_pipeline,
             point_pipeline,
+            fill_pipeline,
+            heat_pipeline,
             bind_group_layout,
-        }
+        })
     }
 
     /// Create a headless renderer that draws to an offscreen `width` x `height`
@@ -143,7 +186,11 @@ impl PlotRenderer {
             view_formats: &[],
         });
 
-        let (line_pipeline, point_pipeline, bind_group_layout) = create_pipelines(&device, format);
+        let (line_pipeline, point_pipeline, fill_pipeline, heat_pipeline, bind_group_layout) =
+            create_pipelines(&device, format);
+        let diagnostics = GpuDiagnostics::new(&adapter.get_info(), &device.limits(), format);
+        let budget = GpuBudget::from_limits(&device.limits());
+        let (gpu_error, device_lost) = watch_device(&device);
 
         Self {
             device,
@@ -154,8 +201,15 @@ impl PlotRenderer {
                 height: h,
             },
             format,
+            diagnostics,
+            budget,
+            last_upload: Cell::default(),
+            gpu_error,
+            device_lost,
             line_pipeline,
             point_pipeline,
+            fill_pipeline,
+            heat_pipeline,
             bind_group_layout,
         }
     }
@@ -177,13 +231,33 @@ impl PlotRenderer {
         }
     }
 
+    /// What the guardrails did to the last [`build_draw_calls`](Self::build_draw_calls).
+    pub fn last_upload(&self) -> UploadReport {
+        self.last_upload.get()
+    }
+
+    /// The first wgpu error the device raised (validation, out of memory),
+    /// if any. Later ones are dropped: the first is the cause.
+    pub fn gpu_error(&self) -> Option<String> {
+        self.gpu_error.lock().ok()?.clone()
+    }
+
+    /// Why the device was lost, once it has been (driver reset or update,
+    /// sleep/resume, GPU removed). Nothing drawn with this renderer shows
+    /// after that: replace it with a new one.
+    pub fn device_lost(&self) -> Option<String> {
+        self.device_lost.lock().ok()?.clone()
+    }
+
     /// Build the per-frame draw calls (uniform + storage buffers and their bind
-    /// groups) for the grid and each data series.
+    /// groups) for the grid and each data series, in `series` order with the
+    /// grid first or last per its [`GridLayer`].
     ///
     /// This is the egui-free port of the legacy `CallbackTrait::prepare` body;
     /// the per-series/grid buffer construction is identical, but uses
     /// `&self.device` and `self.bind_group_layout` instead of egui callback
-    /// resources.
+    /// resources. Series over [`budget`](Self::budget) are decimated first;
+    /// see [`last_upload`](Self::last_upload).
     pub fn build_draw_calls(
         &self,
         series: &[SeriesGpuData],
@@ -192,15 +266,21 @@ impl PlotRenderer {
     ) -> Vec<DrawCall> {
         let device = &self.device;
         let mut draw_calls: Vec<DrawCall> = Vec::new();
+        let sizes: Vec<(usize, DrawMode)> = series.iter().map(|s| (s.points.len(), s.draw_mode)).collect();
+        let (caps, requested_bytes) = plan_points(&sizes, &self.budget);
+        let mut report = UploadReport { requested_bytes, ..UploadReport::default() };
 
-        // -- Grid lines --------------------------------------------------
+        // -- Grid lines (issued after the series when above them) ---------
+        let mut grid_call = None;
         if grid.segments.len() >= 2 {
             let storage_data: &[u8] = bytemuck::cast_slice(&grid.segments);
+            report.uploaded_bytes += storage_data.len() as u64;
             let storage_buf = create_storage_buffer(device, "grid_storage", storage_data);
 
             let mut uniforms = uniforms_base;
             uniforms.color = grid.color;
             uniforms.line_width = grid.line_width;
+            uniforms.pixel_snap = if grid.pixel_snap { 1.0 } else { 0.0 };
 
             let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("grid_uniform"),
@@ -224,35 +304,63 @@ impl PlotRenderer {
             });
 
             let instance_count = (grid.segments.len() / 2) as u32;
-            draw_calls.push(DrawCall {
+            grid_call = Some(DrawCall {
                 bind_group,
                 instance_count,
                 pipeline_type: PipelineType::Line,
             });
         }
+        if grid.layer == GridLayer::Below {
+            draw_calls.extend(grid_call.take());
+        }
 
         // -- Data series -------------------------------------------------
-        for series in series {
+        for (series, &cap) in series.iter().zip(&caps) {
             if series.points.is_empty() {
                 continue;
             }
+            let thinned;
+            let series = if cap < series.points.len() {
+                report.decimated_series += 1;
+                let group = match series.draw_mode {
+                    DrawMode::Band => 2,
+                    DrawMode::Bar => 4,
+                    _ => 1,
+                };
+                thinned = SeriesGpuData { points: decimate(&series.points, cap, group), ..*series };
+                &thinned
+            } else {
+                series
+            };
             if series.points.len() < 2 && series.draw_mode != DrawMode::Points {
                 continue;
             }
 
             match series.draw_mode {
-                DrawMode::Lines => {
-                    let mut pairs: Vec<[f32; 2]> =
-                        Vec::with_capacity((series.points.len() - 1) * 2);
-                    for i in 0..series.points.len() - 1 {
-                        pairs.push(series.points[i]);
-                        pairs.push(series.points[i + 1]);
+                DrawMode::Lines | DrawMode::Smooth => {
+                    let smoothed;
+                    let points: &[[f32; 2]] = if series.draw_mode == DrawMode::Smooth {
+                        smoothed = expand_smooth(&series.points);
+                        &smoothed
+                    } else {
+                        &series.points
+                    };
+                    let mut pairs: Vec<[f32; 2]> = Vec::with_capacity((points.len() - 1) * 2);
+                    for i in 0..points.len() - 1 {
+                        // A non-finite point is a break: skip segments touching it.
+                        if !is_finite_point(points[i]) || !is_finite_point(points[i + 1]) {
+                            continue;
+                        }
+                        pairs.push(points[i]);
+                        pairs.push(points[i + 1]);
                     }
                     if pairs.is_empty() {
                         continue;
                     }
 
                     let storage_data: &[u8] = bytemuck::cast_slice(&pairs);
+
+                    report.uploaded_bytes += storage_data.len() as u64;
                     let storage_buf =
                         create_storage_buffer(device, "series_line_storage", storage_data);
 
@@ -289,12 +397,16 @@ impl PlotRenderer {
                     });
                 }
 
-                DrawMode::Step => {
+                DrawMode::Step | DrawMode::StepPre => {
+                    let pre = series.draw_mode == DrawMode::StepPre;
                     let mut step_points: Vec<[f32; 2]> = Vec::new();
                     for i in 0..series.points.len() - 1 {
                         let p0 = series.points[i];
                         let p1 = series.points[i + 1];
-                        let mid = [p1[0], p0[1]];
+                        if !is_finite_point(p0) || !is_finite_point(p1) {
+                            continue;
+                        }
+                        let mid = step_corner(p0, p1, pre);
                         step_points.push(p0);
                         step_points.push(mid);
                         step_points.push(mid);
@@ -305,6 +417,8 @@ impl PlotRenderer {
                     }
 
                     let storage_data: &[u8] = bytemuck::cast_slice(&step_points);
+
+                    report.uploaded_bytes += storage_data.len() as u64;
                     let storage_buf =
                         create_storage_buffer(device, "series_step_storage", storage_data);
 
@@ -342,7 +456,13 @@ impl PlotRenderer {
                 }
 
                 DrawMode::Points => {
-                    let storage_data: &[u8] = bytemuck::cast_slice(&series.points);
+                    let points: Vec<[f32; 2]> =
+                        series.points.iter().copied().filter(|&p| is_finite_point(p)).collect();
+                    if points.is_empty() {
+                        continue;
+                    }
+                    let storage_data: &[u8] = bytemuck::cast_slice(&points);
+                    report.uploaded_bytes += storage_data.len() as u64;
                     let storage_buf =
                         create_storage_buffer(device, "series_point_storage", storage_data);
 
@@ -374,16 +494,103 @@ impl PlotRenderer {
 
                     draw_calls.push(DrawCall {
                         bind_group,
-                        instance_count: series.points.len() as u32,
+                        instance_count: points.len() as u32,
                         pipeline_type: PipelineType::Point,
                     });
                 }
+
+                DrawMode::Band | DrawMode::Bar => {
+                    // Bars arrive as quads already.
+                    let banded;
+                    let quads: &[[f32; 2]] = if series.draw_mode == DrawMode::Band {
+                        banded = band_quads(&series.points);
+                        &banded
+                    } else {
+                        &series.points
+                    };
+                    if quads.is_empty() {
+                        continue;
+                    }
+                    let storage_data: &[u8] = bytemuck::cast_slice(quads);
+                    report.uploaded_bytes += storage_data.len() as u64;
+                    let storage_buf =
+                        create_storage_buffer(device, "series_band_storage", storage_data);
+
+                    let mut uniforms = uniforms_base;
+                    uniforms.color = series.color;
+
+                    let uniform_buf =
+                        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                            label: Some("series_band_uniform"),
+                            contents: bytemuck::bytes_of(&uniforms),
+                            usage: wgpu::BufferUsages::UNIFORM,
+                        });
+
+                    let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                        label: Some("series_band_bg"),
+                        layout: &self.bind_group_layout,
+                        entries: &[
+                            wgpu::BindGroupEntry {
+                                binding: 0,
+                                resource: uniform_buf.as_entire_binding(),
+                            },
+                            wgpu::BindGroupEntry {
+                                binding: 1,
+                                resource: storage_buf.as_entire_binding(),
+                            },
+                        ],
+                    });
+
+                    draw_calls.push(DrawCall {
+                        bind_group,
+                        instance_count: (quads.len() / 4) as u32,
+                        pipeline_type: PipelineType::Fill,
+                    });
+                }
             }
         }
 
+        draw_calls.extend(grid_call);
+        self.last_upload.set(report);
         draw_calls
     }
 
+    /// Build the draw call for a colormapped heatmap (one quad per cell), or
+    /// `None` when it has no cells. `uniforms_base` supplies the view and
+    /// resolution, as for [`build_draw_calls`](Self::build_draw_calls).
+    pub fn build_heatmap_call(&self, heat: &HeatmapGpuData, uniforms_base: PlotUniforms) -> Option<DrawCall> {
+        if heat.cells.is_empty() {
+            return None;
+        }
+        let device = &self.device;
+        let storage = heat.storage();
+        let storage_buf = create_storage_buffer(device, "heatmap_storage", bytemuck::cast_slice(&storage));
+        let uniform_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("heatmap_uniform"),
+            contents: bytemuck::bytes_of(&uniforms_base),
+            usage: wgpu::BufferUsages::UNIFORM,
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("heatmap_bind_group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buf.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: storage_buf.as_entire_binding(),
+                },
+            ],
+        });
+        Some(DrawCall {
+            bind_group,
+            instance_count: heat.cells.len() as u32,
+            pipeline_type: PipelineType::Heatmap,
+        })
+    }
+
     /// Render `draw_calls` to the target surface, clearing to `clear` (RGBA).
     ///
     /// This is the egui-free port of the legacy `CallbackTrait::paint` body: it
@@ -432,6 +639,8 @@ impl PlotRenderer {
                 match call.pipeline_type {
                     PipelineType::Line => pass.set_pipeline(&self.line_pipeline),
                     PipelineType::Point => pass.set_pipeline(&self.point_pipeline),
+                    PipelineType::Fill => pass.set_pipeline(&self.fill_pipeline),
+                    PipelineType::Heatmap => pass.set_pipeline(&self.heat_pipeline),
                 }
                 pass.set_bind_group(0, &call.bind_group, &[]);
                 pass.draw(0..6, 0..call.instance_count);
@@ -508,6 +717,8 @@ impl PlotRenderer {
                 match call.pipeline_type {
                     PipelineType::Line => pass.set_pipeline(&self.line_pipeline),
                     PipelineType::Point => pass.set_pipeline(&self.point_pipeline),
+                    PipelineType::Fill => pass.set_pipeline(&self.fill_pipeline),
+                    PipelineType::Heatmap => pass.set_pipeline(&self.heat_pipeline),
                 }
                 pass.set_bind_group(0, &call.bind_group, &[]);
                 pass.draw(0..6, 0..call.instance_count);
@@ -557,6 +768,31 @@ impl PlotRenderer {
     }
 }
 
+/// Route `device`'s uncaptured errors into a slot instead of wgpu's
+/// default handler, which panics, and record why it's lost if it is.
+/// Returns `(errors, lost)`.
+fn watch_device(device: &Device) -> (MessageSlot, MessageSlot) {
+    let errors = Arc::new(Mutex::new(None));
+    let sink = Arc::clone(&errors);
+    device.on_uncaptured_error(Box::new(move |e| {
+        if let Ok(mut s) = sink.lock() {
+            s.get_or_insert_with(|| e.to_string());
+        }
+    }));
+    let lost = Arc::new(Mutex::new(None));
+    let sink = Arc::clone(&lost);
+    device.set_device_lost_callback(move |reason, message| {
+        // Dropping the renderer destroys its device: not a loss.
+        if reason == wgpu::DeviceLostReason::Destroyed {
+            return;
+        }
+        if let Ok(mut s) = sink.lock() {
+            *s = Some(if message.is_empty() { "GPU device lost".to_string() } else { message });
+        }
+    });
+    (errors, lost)
+}
+
 #[cfg(test)]
 mod offscreen_tests {
     use super::*;
@@ -574,6 +810,8 @@ mod offscreen_tests {
             segments: vec![],
             color: [0.3, 0.3, 0.3, 1.0],
             line_width: 1.0,
+            pixel_snap: false,
+            layer: GridLayer::Below,
         };
         let uniforms = PlotUniforms {
             view_min: [0.0, -0.1],
@@ -582,7 +820,8 @@ mod offscreen_tests {
             line_width: 3.0,
             point_radius: 4.0,
             color: [0.0, 0.0, 0.0, 0.0],
-            _padding: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
         };
         r.build_draw_calls(&[series], &grid, uniforms)
     }
@@ -610,6 +849,186 @@ mod offscreen_tests {
         assert!(drew, "offscreen render produced a blank (clear-only) image");
     }
 
+    #[test]
+    fn band_fills_between_its_edges() {
+        let r = pollster::block_on(PlotRenderer::new_offscreen(100, 100));
+        let band = SeriesGpuData {
+            points: vec![[0.0, 0.25], [0.0, 0.75], [1.0, 0.25], [1.0, 0.75]],
+            color: [1.0, 1.0, 1.0, 1.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            draw_mode: DrawMode::Band,
+        };
+        let grid = GridGpuData { segments: vec![], color: [0.0; 4], line_width: 1.0, pixel_snap: false, layer: GridLayer::Below };
+        let uniforms = PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [1.0, 1.0],
+            resolution: [100.0, 100.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        };
+        let calls = r.build_draw_calls(&[band], &grid, uniforms);
+        let buf = r.render_to_rgba(&calls, [0.0, 0.0, 0.0, 1.0]);
+        let px = |x: usize, y: usize| buf[(y * 100 + x) * 4];
+        assert_eq!(px(50, 50), 255, "band interior not filled");
+        assert_eq!(px(50, 5), 0, "fill leaked above the upper edge");
+        assert_eq!(px(50, 95), 0, "fill leaked below the lower edge");
+    }
+
+    #[test]
+    fn heatmap_cells_fill_their_share_of_the_view() {
+        let r = pollster::block_on(PlotRenderer::new_offscreen(100, 100));
+        let heat = HeatmapGpuData {
+            cols: 2,
+            rows: 1,
+            x_range: [0.0, 1.0],
+            y_range: [0.0, 1.0],
+            cells: vec![[1.0, 0.0, 0.0, 1.0], [0.0, 0.0, 1.0, 1.0]],
+        };
+        let uniforms = PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [1.0, 1.0],
+            resolution: [100.0, 100.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        };
+        let call = r.build_heatmap_call(&heat, uniforms).expect("non-empty heatmap");
+        let buf = r.render_to_rgba(&[call], [0.0, 0.0, 0.0, 1.0]);
+        let px = |x: usize, y: usize| &buf[(y * 100 + x) * 4..(y * 100 + x) * 4 + 3];
+        assert_eq!(px(25, 50), &[255, 0, 0]);
+        assert_eq!(px(75, 50), &[0, 0, 255]);
+    }
+
+    #[test]
+    fn snapped_grid_line_covers_one_pixel_column() {
+        let r = pollster::block_on(PlotRenderer::new_offscreen(100, 100));
+        // x = 0.503 lands at 50.3px: unsnapped, the 1px quad straddles two columns.
+        let grid = GridGpuData {
+            segments: vec![[0.503, 0.0], [0.503, 1.0]],
+            color: [1.0; 4],
+            line_width: 1.0,
+            pixel_snap: true,
+            layer: GridLayer::Below,
+        };
+        let uniforms = PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [1.0, 1.0],
+            resolution: [100.0, 100.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        };
+        let calls = r.build_draw_calls(&[], &grid, uniforms);
+        let buf = r.render_to_rgba(&calls, [0.0, 0.0, 0.0, 1.0]);
+        let lit: Vec<usize> = (0..100).filter(|&x| buf[(50 * 100 + x) * 4] > 0).collect();
+        assert_eq!(lit, vec![50]);
+    }
+
+    #[test]
+    fn grid_layer_orders_it_against_the_data() {
+        let r = pollster::block_on(PlotRenderer::new_offscreen(100, 100));
+        let band = SeriesGpuData {
+            points: vec![[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]],
+            color: [1.0, 1.0, 1.0, 1.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            draw_mode: DrawMode::Band,
+        };
+        let uniforms = PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [1.0, 1.0],
+            resolution: [100.0, 100.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        };
+        let red_at_centre = |layer| {
+            let grid = GridGpuData {
+                segments: vec![[0.505, 0.0], [0.505, 1.0]],
+                color: [1.0, 0.0, 0.0, 1.0],
+                line_width: 1.0,
+                pixel_snap: true,
+                layer,
+            };
+            let calls = r.build_draw_calls(std::slice::from_ref(&band), &grid, uniforms);
+            let buf = r.render_to_rgba(&calls, [0.0, 0.0, 0.0, 1.0]);
+            buf[(50 * 100 + 50) * 4 + 1] == 0
+        };
+        assert!(!red_at_centre(GridLayer::Below), "fill should hide a grid beneath it");
+        assert!(red_at_centre(GridLayer::Above), "grid above should show over the fill");
+    }
+
+    #[test]
+    fn bar_quads_fill_from_base_to_value() {
+        let r = pollster::block_on(PlotRenderer::new_offscreen(100, 100));
+        let bars = SeriesGpuData {
+            points: vec![[0.25, 0.0], [0.25, 0.5], [0.75, 0.0], [0.75, 0.5]],
+            color: [1.0, 1.0, 1.0, 1.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            draw_mode: DrawMode::Bar,
+        };
+        let grid = GridGpuData { segments: vec![], color: [0.0; 4], line_width: 1.0, pixel_snap: false, layer: GridLayer::Below };
+        let uniforms = PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [1.0, 1.0],
+            resolution: [100.0, 100.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        };
+        let calls = r.build_draw_calls(&[bars], &grid, uniforms);
+        assert_eq!(calls[0].instance_count, 1);
+        let buf = r.render_to_rgba(&calls, [0.0, 0.0, 0.0, 1.0]);
+        let lit = |row: usize, col: usize| buf[(row * 100 + col) * 4] > 0;
+        assert!(lit(75, 50), "inside the bar");
+        assert!(!lit(25, 50), "above the bar");
+        assert!(!lit(75, 10), "beside the bar");
+    }
+
+    #[test]
+    fn oversized_series_are_decimated_to_the_budget() {
+        let mut r = pollster::block_on(PlotRenderer::new_offscreen(64, 64));
+        r.budget.configure(8 << 10, 8 << 10);
+        let dots = SeriesGpuData {
+            points: (0..100_000).map(|i| [i as f32, 0.5]).collect(),
+            color: [1.0; 4],
+            line_width: 1.0,
+            point_radius: 1.0,
+            draw_mode: DrawMode::Points,
+        };
+        let grid = GridGpuData { segments: vec![], color: [0.0; 4], line_width: 1.0, pixel_snap: false, layer: GridLayer::Below };
+        let uniforms = PlotUniforms {
+            view_min: [0.0, 0.0],
+            view_max: [100_000.0, 1.0],
+            resolution: [64.0, 64.0],
+            line_width: 1.0,
+            point_radius: 1.0,
+            color: [0.0; 4],
+            pixel_snap: 0.0,
+            _padding: [0.0; 3],
+        };
+        let calls = r.build_draw_calls(&[dots], &grid, uniforms);
+        assert_eq!(calls[0].instance_count, 1024);
+        let report = r.last_upload();
+        assert!(report.is_limited());
+        assert_eq!((report.uploaded_bytes, report.requested_bytes), (8 << 10, 800_000));
+        r.render_to_rgba(&calls, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(r.gpu_error(), None);
+    }
+
     #[test]
     fn offscreen_empty_is_solid_clear() {
         // 300*4 = 1200 bytes/row → NOT 256-aligned, so this exercises the
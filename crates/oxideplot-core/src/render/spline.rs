@@ -0,0 +1,86 @@
+//! Smooth-curve tessellation for the `Smooth` draw mode.
+//!
+//! A series is drawn as a uniform Catmull-Rom spline through its points,
+//! tessellated on the CPU into short line segments so both the GPU line
+//! pipeline and the CPU fallback can stroke it like any polyline. The curve
+//! passes through every data point; uniform parameterisation keeps the result
+//! independent of the X/Y scale ratio. Non-finite points still break the
+//! curve: each unbroken run is splined on its own.
+
+use super::gpu_types::is_finite_point;
+
+/// Maximum line segments tessellated per span between two data points.
+pub const MAX_SPAN_SEGMENTS: usize = 16;
+
+/// Soft cap on tessellated points per series: dense series get fewer
+/// subdivisions per span, down to plain lines.
+pub const SMOOTH_POINT_BUDGET: usize = 32_768;
+
+/// Tessellate `points` into a Catmull-Rom curve. Runs separated by
+/// non-finite points stay separated by a single NaN point.
+pub fn expand_smooth(points: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    let n = points.len().max(1);
+    let per_span = (SMOOTH_POINT_BUDGET / n).clamp(1, MAX_SPAN_SEGMENTS);
+    let mut out = Vec::with_capacity(points.len() * per_span);
+    for run in points.split(|&p| !is_finite_point(p)).filter(|r| !r.is_empty()) {
+        if !out.is_empty() {
+            out.push([f32::NAN; 2]);
+        }
+        catmull_rom(run, per_span, &mut out);
+    }
+    out
+}
+
+/// Append a uniform Catmull-Rom curve through `run` (all finite), with
+/// `per_span` segments between consecutive points. End tangents come from
+/// repeating the first and last points.
+fn catmull_rom(run: &[[f32; 2]], per_span: usize, out: &mut Vec<[f32; 2]>) {
+    out.push(run[0]);
+    if per_span <= 1 || run.len() < 3 {
+        out.extend_from_slice(&run[1..]);
+        return;
+    }
+    let at = |i: isize| run[i.clamp(0, run.len() as isize - 1) as usize];
+    for i in 0..run.len() - 1 {
+        let (p0, p1, p2, p3) = (at(i as isize - 1), run[i], run[i + 1], at(i as isize + 2));
+        for k in 1..per_span {
+            let t = k as f32 / per_span as f32;
+            let (t2, t3) = (t * t, t * t * t);
+            let blend = |a: f32, b: f32, c: f32, d: f32| {
+                0.5 * (2.0 * b
+                    + (c - a) * t
+                    + (2.0 * a - 5.0 * b + 4.0 * c - d) * t2
+                    + (3.0 * b - a - 3.0 * c + d) * t3)
+            };
+            out.push([blend(p0[0], p1[0], p2[0], p3[0]), blend(p0[1], p1[1], p2[1], p3[1])]);
+        }
+        out.push(p2);
+    }
+}
+
+#[cfg(test)]
+mod spline_tests {
+    use super::*;
+
+    #[test]
+    fn curve_passes_through_points_and_keeps_breaks() {
+        let pts = [[0.0, 0.0], [1.0, 1.0], [2.0, 0.0], [f32::NAN, f32::NAN], [5.0, 1.0], [6.0, 2.0]];
+        let out = expand_smooth(&pts);
+        // First run: 2 spans × 16 segments; then one break; the two-point
+        // run stays a straight segment.
+        assert_eq!(out.len(), 2 * MAX_SPAN_SEGMENTS + 1 + 1 + 2);
+        assert_eq!(out[0], [0.0, 0.0]);
+        assert_eq!(out[MAX_SPAN_SEGMENTS], [1.0, 1.0]);
+        assert_eq!(out[2 * MAX_SPAN_SEGMENTS], [2.0, 0.0]);
+        assert!(out[2 * MAX_SPAN_SEGMENTS + 1][0].is_nan());
+        // Approaching the peak the curve bulges above the chord y = x.
+        let mid = out[MAX_SPAN_SEGMENTS / 2];
+        assert!(mid[1] > mid[0]);
+    }
+
+    #[test]
+    fn dense_series_fall_back_to_lines() {
+        let pts: Vec<[f32; 2]> = (0..SMOOTH_POINT_BUDGET).map(|i| [i as f32, (i % 3) as f32]).collect();
+        assert_eq!(expand_smooth(&pts), pts);
+    }
+}
@@ -0,0 +1,107 @@
+//! Recurring background bands on datetime X axes.
+//!
+//! Long trends are easier to read against the clock: a [`TimeBand`] marks
+//! nights, weekends or a user-defined weekly interval (a shift, opening
+//! hours), and [`band_intervals`] lists where those fall inside a view, for
+//! the frontend to shade. Timestamps are epoch seconds read as UTC wall
+//! time, like the tick labels (`data::datetime::format_timestamp`).
+
+use serde::{Deserialize, Serialize};
+
+/// Seconds per day.
+const DAY: f64 = 86_400.0;
+
+/// Views spanning more days than this get no bands (they would blur into a
+/// flat tint).
+pub const MAX_BAND_DAYS: f64 = 3_660.0;
+
+/// One recurring band. Hours are `0..=24`; an end at or before the start
+/// runs past midnight into the next day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum TimeBand {
+    /// Every night, from `start_hour` to `end_hour` the next morning.
+    Night { start_hour: f64, end_hour: f64 },
+    /// Saturday and Sunday, midnight to midnight.
+    Weekend,
+    /// `start_hour` to `end_hour` on each weekday in `days` (0 = Monday).
+    Weekly { days: Vec<u8>, start_hour: f64, end_hour: f64 },
+}
+
+impl TimeBand {
+    /// Weekdays (0 = Monday) the band starts on, and its hours.
+    fn schedule(&self) -> (Vec<u8>, f64, f64) {
+        match self {
+            TimeBand::Night { start_hour, end_hour } => ((0..7).collect(), *start_hour, *end_hour),
+            TimeBand::Weekend => (vec![5, 6], 0.0, 24.0),
+            TimeBand::Weekly { days, start_hour, end_hour } => (days.clone(), *start_hour, *end_hour),
+        }
+    }
+}
+
+/// The stretches of `[x_min, x_max]` covered by `band`, clipped to the view
+/// and merged where they touch (a weekend is one interval). Empty for an
+/// invalid band or a view longer than [`MAX_BAND_DAYS`].
+pub fn band_intervals(band: &TimeBand, x_min: f64, x_max: f64) -> Vec<(f64, f64)> {
+    let (days, start, end) = band.schedule();
+    let valid_hour = |h: f64| (0.0..=24.0).contains(&h);
+    let span_days = (x_max - x_min) / DAY;
+    if !(span_days > 0.0 && span_days <= MAX_BAND_DAYS && valid_hour(start) && valid_hour(end)) {
+        return Vec::new();
+    }
+    let length = (if end > start { end - start } else { end + 24.0 - start }) * 3600.0;
+    let mut out: Vec<(f64, f64)> = Vec::new();
+    // Start a day early: last night's band reaches into this morning.
+    let first = (x_min / DAY).floor() as i64 - 1;
+    let last = (x_max / DAY).floor() as i64;
+    for day in first..=last {
+        // 1970-01-01 was a Thursday.
+        let weekday = (day + 3).rem_euclid(7) as u8;
+        if !days.contains(&weekday) {
+            continue;
+        }
+        let lo = day as f64 * DAY + start * 3600.0;
+        let (lo, hi) = (lo.max(x_min), (lo + length).min(x_max));
+        if lo >= hi {
+            continue;
+        }
+        match out.last_mut() {
+            Some(prev) if prev.1 >= lo => prev.1 = prev.1.max(hi),
+            _ => out.push((lo, hi)),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod time_bands_tests {
+    use super::*;
+
+    /// 2024-01-01 00:00 UTC, a Monday.
+    const MONDAY: f64 = 1_704_067_200.0;
+
+    #[test]
+    fn nights_wrap_past_midnight() {
+        let night = TimeBand::Night { start_hour: 20.0, end_hour: 6.0 };
+        let bands = band_intervals(&night, MONDAY, MONDAY + 2.0 * DAY);
+        let h = |x: f64| (x - MONDAY) / 3600.0;
+        let hours: Vec<(f64, f64)> = bands.iter().map(|&(a, b)| (h(a), h(b))).collect();
+        // Sunday night's tail, Monday night, Tuesday evening (clipped).
+        assert_eq!(hours, vec![(0.0, 6.0), (20.0, 30.0), (44.0, 48.0)]);
+    }
+
+    #[test]
+    fn weekends_merge_and_weekly_picks_days() {
+        let week = band_intervals(&TimeBand::Weekend, MONDAY, MONDAY + 7.0 * DAY);
+        assert_eq!(week, vec![(MONDAY + 5.0 * DAY, MONDAY + 7.0 * DAY)]);
+
+        let shift = TimeBand::Weekly { days: vec![0, 2], start_hour: 6.0, end_hour: 14.0 };
+        let bands = band_intervals(&shift, MONDAY, MONDAY + 7.0 * DAY);
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[1], (MONDAY + 2.0 * DAY + 6.0 * 3600.0, MONDAY + 2.0 * DAY + 14.0 * 3600.0));
+
+        assert!(band_intervals(&TimeBand::Weekend, 0.0, 4000.0 * DAY).is_empty(), "too long a view");
+        let bad = TimeBand::Night { start_hour: 25.0, end_hour: 6.0 };
+        assert!(band_intervals(&bad, MONDAY, MONDAY + DAY).is_empty());
+    }
+}
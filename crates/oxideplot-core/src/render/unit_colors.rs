@@ -0,0 +1,95 @@
+//! Colouring series by unit.
+//!
+//! Each unit gets a hue family — temperatures reds, pressures blues, voltages
+//! ambers, … — and the series sharing it step through lightness, so a
+//! multi-unit graph reads by colour alone. Units without a preset family take
+//! the free hues in order of first appearance.
+
+/// Preset hue (degrees) per unit, matched case-insensitively.
+const UNIT_HUES: &[(&[&str], f32)] = &[
+    (&["°c", "°f", "k", "degc", "degf"], 0.0),
+    (&["psi", "bar", "mbar", "pa", "kpa", "mpa", "hpa", "inhg", "mmhg"], 215.0),
+    (&["v", "mv", "kv"], 42.0),
+    (&["a", "ma", "ua"], 125.0),
+    (&["g", "m/s²", "m/s2"], 280.0),
+    (&["degrees", "deg", "°", "rad"], 185.0),
+    (&["gauss", "t", "ut", "nt"], 320.0),
+    (&["rpm", "hz", "khz"], 85.0),
+    (&["cps", "counts"], 25.0),
+];
+
+/// Hues handed out to units without a preset, in order.
+const FREE_HUES: [f32; 6] = [160.0, 250.0, 60.0, 300.0, 105.0, 345.0];
+
+const SATURATION: f32 = 0.85;
+/// Lightness range the members of one family are spread over.
+const LIGHTNESS: (f32, f32) = (0.42, 0.74);
+
+/// One RGBA colour per entry of `units` (the unit of each series, in order):
+/// series of the same unit share a hue and get distinct lightness.
+pub fn unit_colors(units: &[&str]) -> Vec<[f32; 4]> {
+    let mut families: Vec<(&str, f32)> = Vec::new();
+    let mut free = FREE_HUES.iter().copied().cycle();
+    for &unit in units {
+        if families.iter().any(|(u, _)| *u == unit) {
+            continue;
+        }
+        let hue = preset_hue(unit).unwrap_or_else(|| free.next().unwrap_or(0.0));
+        families.push((unit, hue));
+    }
+    let mut seen: Vec<usize> = vec![0; families.len()];
+    units
+        .iter()
+        .map(|&unit| {
+            let f = families.iter().position(|(u, _)| *u == unit).unwrap_or(0);
+            let members = units.iter().filter(|&&u| u == unit).count();
+            let k = seen[f];
+            seen[f] += 1;
+            let t = if members > 1 { k as f32 / (members - 1) as f32 } else { 0.5 };
+            let lightness = LIGHTNESS.0 + (LIGHTNESS.1 - LIGHTNESS.0) * t;
+            let [r, g, b] = hsl_to_rgb(families[f].1, SATURATION, lightness);
+            [r, g, b, 1.0]
+        })
+        .collect()
+}
+
+fn preset_hue(unit: &str) -> Option<f32> {
+    let unit = unit.trim().to_lowercase();
+    UNIT_HUES.iter().find(|(names, _)| names.contains(&unit.as_str())).map(|&(_, hue)| hue)
+}
+
+/// HSL (hue in degrees, saturation and lightness in 0..1) to RGB in 0..1.
+fn hsl_to_rgb(hue: f32, s: f32, l: f32) -> [f32; 3] {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = hue.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    [r + m, g + m, b + m]
+}
+
+#[cfg(test)]
+mod unit_colors_tests {
+    use super::*;
+
+    #[test]
+    fn units_share_a_hue_and_vary_in_lightness() {
+        let colors = unit_colors(&["°C", "psi", "°C", "flux", "°C"]);
+        let (t0, t1, t2) = (colors[0], colors[2], colors[4]);
+        // Temperatures are reds, darkest first.
+        assert!(t0[0] > t0[1] && t0[0] > t0[2]);
+        assert!(t0[0] + t0[1] + t0[2] < t1[0] + t1[1] + t1[2]);
+        assert!(t1[0] + t1[1] + t1[2] < t2[0] + t2[1] + t2[2]);
+        // Pressure is blue; an unknown unit takes the first free hue.
+        assert!(colors[1][2] > colors[1][0]);
+        let [r, g, b] = hsl_to_rgb(FREE_HUES[0], SATURATION, 0.58);
+        assert_eq!(colors[3], [r, g, b, 1.0]);
+    }
+}
@@ -0,0 +1,98 @@
+//! Workspace-wide series colours keyed by label.
+//!
+//! Each graph assigns palette colours by position, so the same channel gets
+//! a different colour in every graph it appears in. A [`ColorRegistry`]
+//! hands out one colour per label instead: the first time a label is seen
+//! it takes the next palette colour no other label holds, and every graph
+//! asking for that label afterwards gets the same one. A colour the user
+//! picks by hand is kept as an override and wins over the assignment.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Label → colour table shared by every graph. Serialised into the app's
+/// prefs so the assignment survives restarts.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ColorRegistry {
+    /// Colours handed out from the palette, by label.
+    #[serde(default)]
+    pub assigned: BTreeMap<String, [f32; 3]>,
+    /// Colours picked by hand, by label; these win over `assigned`.
+    #[serde(default)]
+    pub overrides: BTreeMap<String, [f32; 3]>,
+}
+
+/// Registry key for a series label: surrounding whitespace and case don't
+/// split a channel into two entries.
+pub fn label_key(label: &str) -> String {
+    label.trim().to_lowercase()
+}
+
+impl ColorRegistry {
+    /// The colour for `label`, assigning one from `palette` if the label is
+    /// new: the first palette entry no label holds yet, or (once the palette
+    /// is used up) the entry after the last one handed out, cycling.
+    pub fn color_for(&mut self, label: &str, palette: &[[f32; 3]]) -> [f32; 3] {
+        let key = label_key(label);
+        if let Some(c) = self.overrides.get(&key) {
+            return *c;
+        }
+        if let Some(c) = self.assigned.get(&key) {
+            return *c;
+        }
+        let color = palette
+            .iter()
+            .find(|p| !self.assigned.values().any(|c| c == *p))
+            .or_else(|| palette.get(self.assigned.len() % palette.len().max(1)))
+            .copied()
+            .unwrap_or([0.5, 0.5, 0.5]);
+        self.assigned.insert(key, color);
+        color
+    }
+
+    /// Pin `label` to `color` regardless of the palette assignment.
+    pub fn set_override(&mut self, label: &str, color: [f32; 3]) {
+        self.overrides.insert(label_key(label), color);
+    }
+
+    /// Drop every manual override; labels fall back to their assigned colour.
+    pub fn clear_overrides(&mut self) {
+        self.overrides.clear();
+    }
+}
+
+#[cfg(test)]
+mod color_registry_tests {
+    use super::*;
+
+    const PALETTE: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    #[test]
+    fn a_label_keeps_its_colour_whatever_order_it_is_asked_in() {
+        let mut reg = ColorRegistry::default();
+        let rpm = reg.color_for("Engine RPM", &PALETTE);
+        let speed = reg.color_for("Speed", &PALETTE);
+        assert_ne!(rpm, speed);
+        // Another graph plotting them the other way round.
+        assert_eq!(reg.color_for("Speed", &PALETTE), speed);
+        assert_eq!(reg.color_for(" engine rpm ", &PALETTE), rpm);
+    }
+
+    #[test]
+    fn new_labels_take_free_palette_colours_then_cycle() {
+        let mut reg = ColorRegistry::default();
+        let got: Vec<_> = ["a", "b", "c", "d"].iter().map(|l| reg.color_for(l, &PALETTE)).collect();
+        assert_eq!(&got[..3], &PALETTE[..]);
+        assert_eq!(got[3], PALETTE[0]);
+    }
+
+    #[test]
+    fn overrides_win_and_clear_back_to_the_assignment() {
+        let mut reg = ColorRegistry::default();
+        let auto = reg.color_for("Temp", &PALETTE);
+        reg.set_override("temp", [0.2, 0.3, 0.4]);
+        assert_eq!(reg.color_for("Temp", &PALETTE), [0.2, 0.3, 0.4]);
+        reg.clear_overrides();
+        assert_eq!(reg.color_for("Temp", &PALETTE), auto);
+    }
+}
@@ -20,19 +20,14 @@ pub fn color_for_index(index: usize) -> [u8; 4] {
     COLOR_PALETTE[index % COLOR_PALETTE.len()]
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum InterpolationMode {
+    #[default]
     Linear,
     Step,
     Points,
 }
 
-impl Default for InterpolationMode {
-    fn default() -> Self {
-        InterpolationMode::Linear
-    }
-}
-
 impl InterpolationMode {
     pub fn label(&self) -> &'static str {
         match self {
@@ -43,18 +38,13 @@ impl InterpolationMode {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum PlotMode {
+    #[default]
     Plot2D,
     Plot3D,
 }
 
-impl Default for PlotMode {
-    fn default() -> Self {
-        PlotMode::Plot2D
-    }
-}
-
 static NEXT_SERIES_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
 
 fn next_series_id() -> u64 {
@@ -66,12 +66,29 @@ fn next_graph_id() -> u64 {
     NEXT_GRAPH_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Plot edge a Y axis is drawn on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AxisSide {
+    #[default]
+    Left,
+    Right,
+}
+
+/// The Y axis of one unit: its title, range, and side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AxisState {
     pub label: String,
+    /// Fit the range to the data; otherwise `min..max` is used.
     pub auto_range: bool,
     pub min: f64,
     pub max: f64,
+    #[serde(default)]
+    pub side: AxisSide,
+    /// Place among the unit axes (0 first: outermost on its edge, top of
+    /// the settings list); `None` follows the order units were first used.
+    #[serde(default)]
+    pub order: Option<u32>,
 }
 
 impl Default for AxisState {
@@ -81,6 +98,134 @@ impl Default for AxisState {
             auto_range: true,
             min: 0.0,
             max: 1.0,
+            side: AxisSide::Left,
+            order: None,
+        }
+    }
+}
+
+impl AxisState {
+    /// A new auto-ranged axis titled by its unit.
+    pub fn for_unit(unit: &str) -> Self {
+        Self { label: unit.to_string(), ..Default::default() }
+    }
+
+    /// The range to draw: the manual `min..max` when auto-range is off (and
+    /// the range is valid), else `data` (the fitted range).
+    pub fn range(&self, data: (f64, f64)) -> (f64, f64) {
+        if !self.auto_range && self.min.is_finite() && self.max.is_finite() && self.min < self.max {
+            (self.min, self.max)
+        } else {
+            data
+        }
+    }
+
+    /// Zoom the axis now showing `shown` by `factor` (below 1 zooms in)
+    /// about the value at `pivot` (0 = `shown.0`, 1 = `shown.1`), which
+    /// stays where it is. The axis keeps the result as a manual range.
+    pub fn zoom(&mut self, shown: (f64, f64), pivot: f64, factor: f64) {
+        let at = shown.0 + pivot * (shown.1 - shown.0);
+        self.set_manual(at + (shown.0 - at) * factor, at + (shown.1 - at) * factor);
+    }
+
+    /// Shift the axis now showing `shown` by `frac` of its span (positive
+    /// moves the range up, so the data moves down), as a manual range.
+    pub fn pan(&mut self, shown: (f64, f64), frac: f64) {
+        let d = frac * (shown.1 - shown.0);
+        self.set_manual(shown.0 + d, shown.1 + d);
+    }
+
+    /// Switch to the manual range `min..max`, ignoring degenerate ones.
+    fn set_manual(&mut self, min: f64, max: f64) {
+        if min.is_finite() && max.is_finite() && min < max {
+            (self.auto_range, self.min, self.max) = (false, min, max);
+        }
+    }
+}
+
+/// Keep exactly one axis per unit in `units`: entries for units no longer
+/// plotted are dropped, new units get [`AxisState::for_unit`]. Edited
+/// entries of units still in use are kept.
+pub fn sync_unit_axes<'a>(axes: &mut HashMap<String, AxisState>, units: impl IntoIterator<Item = &'a str>) {
+    let units: Vec<&str> = units.into_iter().collect();
+    axes.retain(|unit, _| units.contains(&unit.as_str()));
+    for unit in units {
+        if !axes.contains_key(unit) {
+            axes.insert(unit.to_string(), AxisState::for_unit(unit));
+        }
+    }
+}
+
+/// Sort `units` (in order of first use) into axis order: units with an
+/// explicit [`AxisState::order`] first, by that order, then the rest as they
+/// were.
+pub fn sort_unit_axes(units: &mut [String], axes: &HashMap<String, AxisState>) {
+    units.sort_by_key(|u| axes.get(u).and_then(|a| a.order).unwrap_or(u32::MAX));
+}
+
+/// Give the axes of `units` the order they are listed in; axes of other
+/// units go back to first-use order.
+pub fn set_unit_axis_order(axes: &mut HashMap<String, AxisState>, units: &[String]) {
+    for (unit, axis) in axes.iter_mut() {
+        axis.order = units.iter().position(|u| u == unit).map(|i| i as u32);
+    }
+}
+
+/// An axis title as the user edits it: a name with an optional unit, written
+/// `"Temperature [°C]"` (parentheses are accepted too).
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct AxisTitle {
+    pub name: String,
+    pub unit: Option<String>,
+}
+
+impl AxisTitle {
+    /// Split `"name [unit]"` / `"name (unit)"`; text without a trailing
+    /// bracketed part is all name. Blank text gives `None`.
+    pub fn parse(text: &str) -> Option<AxisTitle> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        for (open, close) in [('[', ']'), ('(', ')')] {
+            if let (Some(body), Some(start)) = (text.strip_suffix(close), text.rfind(open)) {
+                let unit = body[start + 1..].trim();
+                let name = text[..start].trim();
+                if !unit.is_empty() {
+                    return Some(AxisTitle { name: name.to_string(), unit: Some(unit.to_string()) });
+                }
+            }
+        }
+        Some(AxisTitle { name: text.to_string(), unit: None })
+    }
+
+    /// From separately edited name and unit fields (trimmed; blank parts
+    /// unset). `None` when both are blank.
+    pub fn from_parts(name: &str, unit: &str) -> Option<AxisTitle> {
+        let (name, unit) = (name.trim(), unit.trim());
+        if name.is_empty() && unit.is_empty() {
+            return None;
+        }
+        Some(AxisTitle { name: name.to_string(), unit: Some(unit.to_string()).filter(|u| !u.is_empty()) })
+    }
+
+    /// Column header for exports and tables: the title, with `fallback`
+    /// (the data column's name) standing in for a missing name.
+    pub fn header(&self, fallback: &str) -> String {
+        let name = if self.name.is_empty() { fallback } else { &self.name };
+        match &self.unit {
+            Some(u) => format!("{name} [{u}]"),
+            None => name.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for AxisTitle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.unit, self.name.is_empty()) {
+            (Some(u), true) => write!(f, "[{u}]"),
+            (Some(u), false) => write!(f, "{} [{u}]", self.name),
+            (None, _) => f.write_str(&self.name),
         }
     }
 }
@@ -97,6 +242,7 @@ pub struct GraphState {
     pub y_axis_name: Option<String>,
     pub z_axis_name: Option<String>,
     pub auto_scale_y: bool,
+    /// Y axis per unit of the series, kept in step by add/remove.
     pub y_axes: HashMap<String, AxisState>,
     pub sync_partner_ids: Vec<u64>,
     /// Shared axis-link group identifier. All graphs in the same sync group
@@ -162,32 +308,45 @@ impl GraphState {
             series.color = color_for_index(self.series_counter);
         }
         self.series_counter += 1;
-
-        // Ensure y-axis entry exists for this unit
-        if !self.y_axes.contains_key(&series.unit) {
-            self.y_axes.insert(
-                series.unit.clone(),
-                AxisState {
-                    label: format!("Y Axis ({})", series.unit),
-                    ..Default::default()
-                },
-            );
-        }
-
         self.series.push(series);
+        self.sync_y_axes();
     }
 
     pub fn remove_series_by_id(&mut self, series_id: u64) {
         if let Some(pos) = self.series.iter().position(|s| s.id == series_id) {
-            let removed = self.series.remove(pos);
-            // Clean up y-axis if no more series use this unit
-            let unit_still_used = self.series.iter().any(|s| s.unit == removed.unit);
-            if !unit_still_used {
-                self.y_axes.remove(&removed.unit);
-            }
+            self.series.remove(pos);
+            self.sync_y_axes();
         }
     }
 
+    /// One Y axis per unit of the current series (see [`sync_unit_axes`]).
+    fn sync_y_axes(&mut self) {
+        sync_unit_axes(&mut self.y_axes, self.series.iter().map(|s| s.unit.as_str()));
+    }
+
+    /// The X axis title, when a name or unit is set.
+    pub fn x_title(&self) -> Option<AxisTitle> {
+        if self.x_axis_name.is_none() && self.x_axis_unit.is_none() {
+            return None;
+        }
+        Some(AxisTitle { name: self.x_axis_name.clone().unwrap_or_default(), unit: self.x_axis_unit.clone() })
+    }
+
+    /// Set (or with `None`, clear) the X axis name and unit.
+    pub fn set_x_title(&mut self, title: Option<AxisTitle>) {
+        let (name, unit) = match title {
+            Some(t) => (Some(t.name).filter(|n| !n.is_empty()), t.unit),
+            None => (None, None),
+        };
+        self.x_axis_name = name;
+        self.x_axis_unit = unit;
+    }
+
+    /// Relabel the Y axis for `unit` (created if missing).
+    pub fn set_y_axis_label(&mut self, unit: &str, label: &str) {
+        self.y_axes.entry(unit.to_string()).or_insert_with(|| AxisState::for_unit(unit)).label = label.to_string();
+    }
+
     pub fn series_labels(&self) -> Vec<String> {
         self.series.iter().map(|s| s.label.clone()).collect()
     }
@@ -214,8 +373,15 @@ impl GraphState {
         }
     }
 
-    /// Get the y range for a given unit within the given x range
+    /// Get the y range for a given unit within the given x range: the
+    /// axis' manual range, or the padded data range when auto-ranged.
     pub fn y_range_for_unit(&self, unit: &str, x_min: f64, x_max: f64) -> Option<(f64, f64)> {
+        if let Some(axis) = self.y_axes.get(unit).filter(|a| !a.auto_range) {
+            let manual = axis.range((f64::NAN, f64::NAN));
+            if manual.0.is_finite() {
+                return Some(manual);
+            }
+        }
         let mut y_min = f64::INFINITY;
         let mut y_max = f64::NEG_INFINITY;
         for s in &self.series {
@@ -243,3 +409,92 @@ impl Default for GraphState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod graph_state_tests {
+    use super::*;
+
+    #[test]
+    fn axis_title_parses_and_formats_units() {
+        let t = AxisTitle::parse(" Temperature [°C] ").unwrap();
+        assert_eq!(t, AxisTitle { name: "Temperature".into(), unit: Some("°C".into()) });
+        assert_eq!(t.to_string(), "Temperature [°C]");
+        assert_eq!(AxisTitle::parse("Depth (m)").unwrap().unit.as_deref(), Some("m"));
+        assert_eq!(AxisTitle::parse("Time").unwrap().to_string(), "Time");
+        assert_eq!(AxisTitle::parse("[V]").unwrap().to_string(), "[V]");
+        assert!(AxisTitle::parse("  ").is_none());
+
+        let t = AxisTitle::from_parts("", " s ").unwrap();
+        assert_eq!(t.header("t_rel"), "t_rel [s]");
+        assert_eq!(AxisTitle::from_parts("Elapsed", "").unwrap().header("t"), "Elapsed");
+        assert!(AxisTitle::from_parts(" ", "").is_none());
+    }
+
+    #[test]
+    fn titles_write_axis_fields() {
+        let mut g = GraphState::new();
+        assert!(g.x_title().is_none());
+        g.set_x_title(AxisTitle::parse("Elapsed [s]"));
+        assert_eq!(g.x_axis_name.as_deref(), Some("Elapsed"));
+        assert_eq!(g.x_axis_unit.as_deref(), Some("s"));
+        assert_eq!(g.x_title().unwrap().to_string(), "Elapsed [s]");
+        g.set_x_title(None);
+        assert!(g.x_axis_name.is_none() && g.x_axis_unit.is_none());
+        g.set_y_axis_label("V", "Bus voltage [V]");
+        assert_eq!(g.y_axes["V"].label, "Bus voltage [V]");
+    }
+
+    #[test]
+    fn unit_axes_follow_the_series() {
+        let mut g = GraphState::new();
+        let series = |label: &str, unit: &str| DataSeries::new(label.into(), vec![0.0, 1.0], vec![2.0, 4.0], [0; 4], unit.into());
+        g.add_series(series("bus", "V"));
+        g.add_series(series("load", "A"));
+        assert_eq!(g.y_axes["A"].label, "A");
+        let a = g.y_axes.get_mut("A").unwrap();
+        a.auto_range = false;
+        (a.min, a.max) = (-1.0, 10.0);
+        assert_eq!(g.y_range_for_unit("A", 0.0, 1.0), Some((-1.0, 10.0)));
+        assert_eq!(g.y_range_for_unit("V", 0.0, 1.0), Some((1.9, 4.1)));
+
+        let id = g.series[0].id;
+        g.remove_series_by_id(id);
+        assert!(!g.y_axes.contains_key("V"));
+        assert!(!g.y_axes["A"].auto_range, "edits of units still plotted survive");
+
+        // An inverted manual range falls back to the data range.
+        let axis = AxisState { auto_range: false, min: 5.0, max: 1.0, ..AxisState::for_unit("V") };
+        assert_eq!(axis.range((0.0, 2.0)), (0.0, 2.0));
+    }
+
+    #[test]
+    fn explicit_axis_order_comes_first() {
+        let mut axes: HashMap<String, AxisState> =
+            ["°C", "bar", "V"].into_iter().map(|u| (u.to_string(), AxisState::for_unit(u))).collect();
+        let mut units: Vec<String> = vec!["°C".into(), "bar".into(), "V".into()];
+        sort_unit_axes(&mut units, &axes);
+        assert_eq!(units, ["°C", "bar", "V"], "first use without an order");
+
+        set_unit_axis_order(&mut axes, &["V".into(), "°C".into()]);
+        assert_eq!((axes["V"].order, axes["°C"].order, axes["bar"].order), (Some(0), Some(1), None));
+        sort_unit_axes(&mut units, &axes);
+        assert_eq!(units, ["V", "°C", "bar"]);
+    }
+
+    #[test]
+    fn axis_zoom_and_pan_keep_a_manual_range() {
+        let mut axis = AxisState::for_unit("V");
+        // Zoom in 2x about the middle of 0..10.
+        axis.zoom((0.0, 10.0), 0.5, 0.5);
+        assert!(!axis.auto_range);
+        assert_eq!((axis.min, axis.max), (2.5, 7.5));
+        // About the bottom: the bottom stays put.
+        axis.zoom((2.5, 7.5), 0.0, 2.0);
+        assert_eq!((axis.min, axis.max), (2.5, 12.5));
+        axis.pan((2.5, 12.5), -0.1);
+        assert_eq!((axis.min, axis.max), (1.5, 11.5));
+        // A degenerate zoom is ignored.
+        axis.zoom((1.5, 11.5), 0.5, 0.0);
+        assert_eq!((axis.min, axis.max), (1.5, 11.5));
+    }
+}
@@ -1,4 +1,8 @@
+pub mod color_registry;
 pub mod data_series;
 pub mod graph_state;
 pub mod orbital_camera;
 pub mod plot_view;
+pub mod project;
+pub mod project_archive;
+pub mod release_notes;
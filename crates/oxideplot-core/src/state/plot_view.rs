@@ -183,6 +183,22 @@ impl PlotViewState {
         self.auto_fit = false;
     }
 
+    /// Box zoom: the view becomes the data range under the screen rectangle
+    /// with corners `a` and `b` (either order). A rectangle with no width or
+    /// height leaves the view unchanged and returns false.
+    pub fn zoom_to_rect(&mut self, a: crate::geom::Pos2, b: crate::geom::Pos2, rect: crate::geom::Rect) -> bool {
+        let (ax, ay) = self.screen_to_data(a, rect);
+        let (bx, by) = self.screen_to_data(b, rect);
+        let (x_min, x_max) = (ax.min(bx), ax.max(bx));
+        let (y_min, y_max) = (ay.min(by), ay.max(by));
+        if !(x_min < x_max && y_min < y_max) {
+            return false;
+        }
+        (self.x_min, self.x_max, self.y_min, self.y_max) = (x_min, x_max, y_min, y_max);
+        self.auto_fit = false;
+        true
+    }
+
     /// Screen pixel position within `rect` -> data coordinates.
     pub fn screen_to_data(&self, pos: crate::geom::Pos2, rect: crate::geom::Rect) -> (f64, f64) {
         let t_x = (pos.x - rect.left) as f64 / rect.width as f64;
@@ -201,7 +217,7 @@ impl PlotViewState {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::geom::Rect;
+    use crate::geom::{Pos2, Rect};
 
     #[test]
     fn pan_shifts_view_left() {
@@ -210,6 +226,16 @@ mod tests {
         assert!(v.x_min < 0.0 && v.x_max < 10.0, "drag-right should move the view left");
     }
 
+    #[test]
+    fn box_zoom_takes_the_rectangle_in_any_order() {
+        let mut v = PlotViewState { x_min: 0.0, x_max: 10.0, y_min: 0.0, y_max: 10.0, ..Default::default() };
+        let r = Rect { left: 0.0, top: 0.0, width: 100.0, height: 100.0 };
+        assert!(v.zoom_to_rect(Pos2 { x: 60.0, y: 20.0 }, Pos2 { x: 20.0, y: 60.0 }, r));
+        assert_eq!((v.x_min, v.x_max, v.y_min, v.y_max), (2.0, 6.0, 4.0, 8.0));
+        assert!(!v.zoom_to_rect(Pos2 { x: 30.0, y: 0.0 }, Pos2 { x: 30.0, y: 100.0 }, r));
+        assert_eq!(v.x_min, 2.0);
+    }
+
     #[test]
     fn screen_data_roundtrip() {
         let v = PlotViewState { x_min: 0.0, x_max: 10.0, y_min: 0.0, y_max: 10.0, ..Default::default() };
@@ -0,0 +1,325 @@
+//! Saved-project model: every graph's series data plus, for derived series,
+//! the operation that produced them.
+//!
+//! A project stores each series' samples so it reopens without the original
+//! files, and each derived series also records its [`Derivation`]: the
+//! transform kind or formula, the series it was computed from, and its
+//! parameters. The series panel shows [`Derivation::formula`], so a reviewer
+//! can see how every curve was produced.
+//!
+//! A graph may instead link the file it was plotting ([`SourceFile`]): its
+//! file-column series are then saved without samples and re-read from the
+//! file on open. [`project_archive`](super::project_archive) stores projects
+//! as a zip of a JSON manifest plus binary sample blobs.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data::chunked::DecimationSpec;
+use crate::data::events::EventMarker;
+use crate::data::unit_convert::UnitConversion;
+use crate::render::time_bands::TimeBand;
+use crate::processing::align::SeriesAlign;
+use crate::processing::area_fill::AreaFill;
+use crate::processing::uncertainty::Uncertainty;
+use crate::render::layers::SeriesLayer;
+use crate::state::graph_state::AxisState;
+
+/// Format version written to new project files.
+pub const PROJECT_VERSION: u32 = 1;
+
+/// File extension for saved projects.
+pub const PROJECT_EXTENSION: &str = "oxproj";
+
+/// How a derived series was computed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Derivation {
+    /// Transform kind (`"moving_average"`, `"savgol"`, …) or `"formula"`.
+    pub op: String,
+    /// Name of the series the transform was applied to (`None` for formulas,
+    /// which reference columns by name in their expression).
+    pub source: Option<String>,
+    /// Operation parameters as text, in key order.
+    pub params: BTreeMap<String, String>,
+}
+
+impl Derivation {
+    pub fn new(op: &str, source: Option<&str>) -> Self {
+        Self { op: op.to_string(), source: source.map(str::to_string), params: BTreeMap::new() }
+    }
+
+    /// Add a parameter (builder style).
+    pub fn param(mut self, key: &str, value: impl ToString) -> Self {
+        self.params.insert(key.to_string(), value.to_string());
+        self
+    }
+
+    /// Human-readable definition: the expression itself for formulas, else
+    /// `op("source"; key=value, …)`.
+    pub fn formula(&self) -> String {
+        if self.op == "formula" {
+            if let Some(expr) = self.params.get("expr") {
+                return expr.clone();
+            }
+        }
+        let mut args: Vec<String> = Vec::new();
+        if let Some(src) = &self.source {
+            args.push(format!("\"{src}\""));
+        }
+        let params: Vec<String> = self.params.iter().map(|(k, v)| format!("{k}={v}")).collect();
+        if !params.is_empty() {
+            args.push(params.join(", "));
+        }
+        format!("{}({})", self.op, args.join("; "))
+    }
+}
+
+/// One saved series.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProjectSeries {
+    pub name: String,
+    pub x_name: String,
+    pub color: [f32; 4],
+    /// `"lines"`, `"step"`, `"step-pre"`, `"points"` or `"smooth"`.
+    pub draw_mode: String,
+    pub visible: bool,
+    #[serde(default)]
+    pub wrap_period: Option<f64>,
+    /// `None` for series plotted straight from a file column.
+    #[serde(default)]
+    pub derivation: Option<Derivation>,
+    /// Reference (baseline) curve, drawn dashed.
+    #[serde(default)]
+    pub reference: bool,
+    /// Value→label map in `LabelMap::to_spec` form.
+    #[serde(default)]
+    pub labels: Option<String>,
+    /// X-gap threshold splitting the series into segments.
+    #[serde(default)]
+    pub gap_threshold: Option<f64>,
+    /// ± uncertainty display, with the column's per-sample values.
+    #[serde(default)]
+    pub error: Option<Uncertainty>,
+    /// Last value extended to the right edge of the view.
+    #[serde(default)]
+    pub hold_last: bool,
+    /// Left out of the nearest-point search (dense or reference curves).
+    #[serde(default)]
+    pub no_hover: bool,
+    /// Brought to front / sent to back of the draw order.
+    #[serde(default)]
+    pub layer: SeriesLayer,
+    /// Area filled under the series or between it and another.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fill: Option<AreaFill>,
+    /// File column the series was read from, when a unit conversion has
+    /// renamed it (see `GraphProject::unit_conversions`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// X offset and Y scale/offset already applied to the samples; repeated
+    /// when a linked series is re-read from its file.
+    #[serde(default, skip_serializing_if = "SeriesAlign::is_identity")]
+    pub align: SeriesAlign,
+    /// Samples; empty for a file column saved as a link (see [`SourceFile`]).
+    #[serde(default)]
+    pub xs: Vec<f64>,
+    #[serde(default)]
+    pub ys: Vec<f64>,
+}
+
+impl ProjectSeries {
+    /// Plotted straight from a file column (not derived, not a reference),
+    /// so it can be re-read from the graph's source file.
+    pub fn is_file_column(&self) -> bool {
+        self.derivation.is_none() && !self.reference
+    }
+
+    /// Name of the file column to re-read this series from.
+    pub fn column_name(&self) -> &str {
+        self.column.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// The file a graph's columns were read from, for linked projects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceFile {
+    /// Path relative to the project file, `/`-separated (absolute when the
+    /// file is on another drive).
+    pub path: String,
+    /// Query that produced the columns, for SQLite databases.
+    #[serde(default)]
+    pub sql: Option<String>,
+    /// CSV import options the file was read with (`delimiter`, `decimal`,
+    /// `thousands`), when not auto-detected.
+    #[serde(default)]
+    pub csv: BTreeMap<String, String>,
+    /// Row thinning of a chunked import, re-applied when the link is read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decimate: Option<DecimationSpec>,
+}
+
+/// A text note pinned to a plot position, in view coordinates (the space
+/// `view_state` reports: log10 in Log scale, 0–1 when normalized).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub x: f64,
+    pub y: f64,
+    pub text: String,
+}
+
+/// One saved graph.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphProject {
+    /// Name of the file the graph was plotting, for display.
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Where that file is, when known; see [`Project::link_sources`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceFile>,
+    pub x_is_time: bool,
+    /// Y-axis value→label map in `LabelMap::to_spec` form.
+    #[serde(default)]
+    pub y_labels: Option<String>,
+    /// Edited axis titles in `"name [unit]"` form (`None` = derived).
+    #[serde(default)]
+    pub x_title: Option<String>,
+    #[serde(default)]
+    pub y_title: Option<String>,
+    /// Stacked-area mode (`"stacked"` / `"percent"`); `None` when off.
+    #[serde(default)]
+    pub stack_mode: Option<String>,
+    /// How bar series share an X slot (`"stacked"`); `None` when grouped.
+    #[serde(default)]
+    pub bar_layout: Option<String>,
+    /// Edited per-unit Y axes (normalized mode), by unit.
+    #[serde(default)]
+    pub unit_axes: BTreeMap<String, AxisState>,
+    /// Unit conversions applied to the graph's file columns, in order; a
+    /// re-read column whose detected unit matches repeats them.
+    #[serde(default)]
+    pub unit_conversions: Vec<UnitConversion>,
+    pub x_min: f64,
+    pub x_max: f64,
+    pub y_min: f64,
+    pub y_max: f64,
+    pub series: Vec<ProjectSeries>,
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// Night/weekend/weekly shading of a datetime X axis.
+    #[serde(default)]
+    pub time_bands: Vec<TimeBand>,
+    /// Imported event lines (`data::events`).
+    #[serde(default)]
+    pub events: Vec<EventMarker>,
+}
+
+/// A saved project: the graphs of the workspace, in order.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    pub version: u32,
+    pub graphs: Vec<GraphProject>,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self { version: PROJECT_VERSION, graphs: Vec::new() }
+    }
+}
+
+impl Project {
+    /// Reject projects written by a newer version of the format.
+    pub fn check_version(&self) -> Result<(), String> {
+        if self.version > PROJECT_VERSION {
+            return Err(format!(
+                "project format version {} is newer than supported version {PROJECT_VERSION}",
+                self.version
+            ));
+        }
+        Ok(())
+    }
+
+    /// Drop the samples of every file-column series whose graph has a
+    /// source file, leaving them to be re-read from it on open. Graphs
+    /// without one (streams, fetched URLs) keep their data.
+    pub fn link_sources(&mut self) {
+        for graph in self.graphs.iter_mut().filter(|g| g.source.is_some()) {
+            for s in graph.series.iter_mut().filter(|s| s.is_file_column()) {
+                s.xs = Vec::new();
+                s.ys = Vec::new();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod project_tests {
+    use super::*;
+    use crate::processing::uncertainty::ErrorStyle;
+    use crate::state::graph_state::AxisSide;
+
+    #[test]
+    fn formula_text_describes_the_operation() {
+        let d = Derivation::new("savgol", Some("temp")).param("window", 11).param("order", 3);
+        assert_eq!(d.formula(), "savgol(\"temp\"; order=3, window=11)");
+        assert_eq!(Derivation::new("abs", Some("v")).formula(), "abs(\"v\")");
+        let f = Derivation::new("formula", None).param("expr", "\"a\" + 2*\"b\"");
+        assert_eq!(f.formula(), "\"a\" + 2*\"b\"");
+    }
+
+    #[test]
+    fn project_round_trips_through_json() {
+        let project = Project {
+            version: PROJECT_VERSION,
+            graphs: vec![GraphProject {
+                file: Some("run.csv".into()),
+                source: None,
+                x_is_time: false,
+                y_labels: None,
+                x_title: Some("Elapsed [s]".into()),
+                y_title: None,
+                stack_mode: Some("percent".into()),
+                bar_layout: None,
+                unit_axes: BTreeMap::from([(
+                    "V".into(),
+                    AxisState { auto_range: false, min: 0.0, max: 5.0, side: AxisSide::Right, ..AxisState::for_unit("V") },
+                )]),
+                unit_conversions: vec![UnitConversion::new("\u{00B0}F", "\u{00B0}C").unwrap()],
+                x_min: 0.0,
+                x_max: 2.0,
+                y_min: -1.0,
+                y_max: 1.0,
+                series: vec![ProjectSeries {
+                    name: "v · MA(5)".into(),
+                    x_name: "t".into(),
+                    color: [1.0, 0.5, 0.0, 1.0],
+                    draw_mode: "lines".into(),
+                    visible: true,
+                    wrap_period: None,
+                    derivation: Some(Derivation::new("moving_average", Some("v")).param("window", 5)),
+                    reference: false,
+                    labels: Some("0=OFF, 1=ON".into()),
+                    gap_threshold: Some(30.0),
+                    error: Some(Uncertainty::constant(ErrorStyle::Band, 0.05)),
+                    hold_last: true,
+                    no_hover: false,
+                    layer: SeriesLayer::Front,
+                    fill: Some(AreaFill::Between("v".into())),
+                    column: None,
+                    align: SeriesAlign::new(1.5, 0.0, 2.0).unwrap(),
+                    xs: vec![0.0, 1.0, 2.0],
+                    ys: vec![0.5, 0.25, 0.0],
+                }],
+                annotations: vec![Annotation { x: 1.0, y: 0.25, text: "valve opens".into() }],
+                time_bands: vec![TimeBand::Weekend, TimeBand::Night { start_hour: 22.0, end_hour: 6.0 }],
+                events: vec![EventMarker { x: 0.5, label: "DTC P0301".into(), color: Some([1.0, 0.0, 0.0]) }],
+            }],
+        };
+        let json = serde_json::to_string(&project).unwrap();
+        let back: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, project);
+        assert!(back.check_version().is_ok());
+        let newer = Project { version: PROJECT_VERSION + 1, graphs: vec![] };
+        assert!(newer.check_version().is_err());
+    }
+}
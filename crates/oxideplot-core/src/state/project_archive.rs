@@ -0,0 +1,284 @@
+//! Binary project files (`.oxideplot`): a zip holding the [`Project`] as a
+//! JSON manifest with the samples left out, plus one blob per series of its
+//! X then Y values as little-endian `f64`. Saving and opening a
+//! 10M-point project is a memcpy instead of printing and parsing 20M
+//! decimal numbers, and the file is a fraction of the JSON's size.
+//!
+//! Bundles ([`write_bundle`]) are the same archive, deflated, with the
+//! source files and preview images alongside: one self-contained file to
+//! attach to a report or hand over, which [`read_archive`] still opens.
+//!
+//! Also the path helpers for linked projects ([`SourceFile`]), whose source
+//! files are stored relative to the project so a folder can be moved whole.
+//!
+//! [`SourceFile`]: super::project::SourceFile
+
+use std::io::{Cursor, Read, Write};
+
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::state::project::Project;
+
+/// File extension for binary projects.
+pub const ARCHIVE_EXTENSION: &str = "oxideplot";
+
+const MANIFEST: &str = "project.json";
+
+/// Zip local-file-header magic: how an archive is told from a JSON project.
+pub fn is_archive(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"PK\x03\x04")
+}
+
+fn blob_name(graph: usize, series: usize) -> String {
+    format!("data/g{graph}/s{series}.f64")
+}
+
+/// Encode `project` as a binary project archive. Series without samples
+/// (linked file columns) get no blob.
+pub fn write_archive(project: &Project) -> Result<Vec<u8>, String> {
+    write_zip(project, CompressionMethod::Stored, &[])
+}
+
+/// Encode `project` as a self-contained bundle: the archive's manifest and
+/// blobs, deflated, plus `files` (e.g. `sources/run.csv`,
+/// `previews/graph-1.png`) under their names. Names must be relative,
+/// unique, and clear of the manifest and `data/`.
+pub fn write_bundle(project: &Project, files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    for (i, (name, _)) in files.iter().enumerate() {
+        let bad = name.is_empty()
+            || name.starts_with(['/', '\\'])
+            || name.split(['/', '\\']).any(|c| c == "..")
+            || name == MANIFEST
+            || name.starts_with("data/");
+        if bad {
+            return Err(format!("bundle: invalid file name \"{name}\""));
+        }
+        if files[..i].iter().any(|(other, _)| other == name) {
+            return Err(format!("bundle: duplicate file \"{name}\""));
+        }
+    }
+    write_zip(project, CompressionMethod::Deflated, files)
+}
+
+fn write_zip(project: &Project, blob_method: CompressionMethod, files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, String> {
+    let mut manifest = project.clone();
+    let mut blobs: Vec<(String, Vec<u8>)> = Vec::new();
+    for (gi, graph) in manifest.graphs.iter_mut().enumerate() {
+        for (si, s) in graph.series.iter_mut().enumerate() {
+            if s.xs.is_empty() && s.ys.is_empty() {
+                continue;
+            }
+            if s.xs.len() != s.ys.len() {
+                return Err(format!("series \"{}\" has {} X and {} Y values", s.name, s.xs.len(), s.ys.len()));
+            }
+            let mut blob = Vec::with_capacity((s.xs.len() + s.ys.len()) * 8);
+            for v in s.xs.iter().chain(&s.ys) {
+                blob.extend_from_slice(&v.to_le_bytes());
+            }
+            blobs.push((blob_name(gi, si), blob));
+            s.xs = Vec::new();
+            s.ys = Vec::new();
+        }
+    }
+    let json = serde_json::to_vec(&manifest).map_err(|e| e.to_string())?;
+
+    let err = |e: zip::result::ZipError| e.to_string();
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    zip.start_file(MANIFEST, SimpleFileOptions::default()).map_err(err)?;
+    zip.write_all(&json).map_err(|e| e.to_string())?;
+    // Raw floats barely deflate; storing keeps saves I/O-bound. Bundles
+    // trade that for size.
+    let blobs = blobs.iter().map(|(name, blob)| (name, blob, blob_method));
+    let extras = files.iter().map(|(name, bytes)| (name, bytes, CompressionMethod::Deflated));
+    for (name, bytes, method) in blobs.chain(extras) {
+        let options = SimpleFileOptions::default()
+            .compression_method(method)
+            .large_file(bytes.len() as u64 >= u32::MAX as u64);
+        zip.start_file(name.as_str(), options).map_err(err)?;
+        zip.write_all(bytes).map_err(|e| e.to_string())?;
+    }
+    Ok(zip.finish().map_err(err)?.into_inner())
+}
+
+/// Decode a binary project archive, restoring every series' samples from
+/// its blob. Rejects projects from a newer format version.
+pub fn read_archive(bytes: &[u8]) -> Result<Project, String> {
+    let mut zip = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("not an OxidePlot project: {e}"))?;
+    let mut json = Vec::new();
+    zip.by_name(MANIFEST)
+        .map_err(|_| "not an OxidePlot project: no manifest".to_string())?
+        .read_to_end(&mut json)
+        .map_err(|e| e.to_string())?;
+    let mut project: Project = serde_json::from_slice(&json).map_err(|e| format!("bad project manifest: {e}"))?;
+    project.check_version()?;
+    for (gi, graph) in project.graphs.iter_mut().enumerate() {
+        for (si, s) in graph.series.iter_mut().enumerate() {
+            let Ok(mut file) = zip.by_name(&blob_name(gi, si)) else { continue };
+            let mut blob = Vec::with_capacity(file.size() as usize);
+            file.read_to_end(&mut blob).map_err(|e| e.to_string())?;
+            if blob.len() % 16 != 0 {
+                return Err(format!("series \"{}\": truncated sample data", s.name));
+            }
+            let mut values = blob.chunks_exact(8).map(|b| f64::from_le_bytes(b.try_into().unwrap()));
+            let n = blob.len() / 16;
+            s.xs = values.by_ref().take(n).collect();
+            s.ys = values.collect();
+        }
+    }
+    Ok(project)
+}
+
+fn components(path: &str) -> Vec<&str> {
+    path.split(['/', '\\']).filter(|c| !c.is_empty() && *c != ".").collect()
+}
+
+/// Leading drive (`C:`) or `/` root, `""` for relative paths.
+fn root(path: &str) -> &str {
+    match path.as_bytes() {
+        [d, b':', ..] if d.is_ascii_alphabetic() => &path[..2],
+        [b'/' | b'\\', ..] => "/",
+        _ => "",
+    }
+}
+
+/// `file` relative to the folder holding `project`, `/`-separated. Falls
+/// back to `file` itself when the two are on different drives.
+pub fn relative_path(project: &str, file: &str) -> String {
+    if !root(project).eq_ignore_ascii_case(root(file)) {
+        return file.to_string();
+    }
+    let skip = usize::from(!root(file).is_empty() && root(file) != "/");
+    let base = components(project);
+    let base = &base[skip..base.len().saturating_sub(1)];
+    let target = &components(file)[skip..];
+    let common = base.iter().zip(target).take_while(|(a, b)| a == b).count();
+    let mut parts: Vec<&str> = vec![".."; base.len() - common];
+    parts.extend(&target[common..]);
+    parts.join("/")
+}
+
+/// Inverse of [`relative_path`]: where `rel` points from `project`'s folder
+/// (`rel` as is when already absolute). Uses `project`'s separator style.
+pub fn resolve_path(project: &str, rel: &str) -> String {
+    if !root(rel).is_empty() {
+        return rel.to_string();
+    }
+    let sep = if project.contains('\\') && !project.contains('/') { "\\" } else { "/" };
+    let mut parts = components(project);
+    parts.pop();
+    for c in components(rel) {
+        if c == ".." {
+            parts.pop();
+        } else {
+            parts.push(c);
+        }
+    }
+    let lead = if root(project) == "/" { sep } else { "" };
+    format!("{lead}{}", parts.join(sep))
+}
+
+#[cfg(test)]
+mod project_archive_tests {
+    use super::*;
+    use crate::state::project::{Derivation, GraphProject, ProjectSeries, SourceFile, PROJECT_VERSION};
+
+    fn series(name: &str, derivation: Option<Derivation>, n: usize) -> ProjectSeries {
+        ProjectSeries {
+            name: name.into(),
+            x_name: "t".into(),
+            color: [1.0, 0.0, 0.0, 1.0],
+            draw_mode: "lines".into(),
+            visible: true,
+            wrap_period: None,
+            derivation,
+            reference: false,
+            labels: None,
+            gap_threshold: None,
+            error: None,
+            hold_last: false,
+            no_hover: false,
+            layer: Default::default(),
+            fill: None,
+            column: None,
+            align: Default::default(),
+            xs: (0..n).map(|i| i as f64 * 0.1).collect(),
+            ys: (0..n).map(|i| (i as f64).sin()).collect(),
+        }
+    }
+
+    fn graph(source: Option<SourceFile>) -> GraphProject {
+        GraphProject {
+            file: Some("run.csv".into()),
+            source,
+            x_is_time: false,
+            y_labels: None,
+            x_title: None,
+            y_title: None,
+            stack_mode: None,
+            bar_layout: None,
+            unit_axes: Default::default(),
+            unit_conversions: Vec::new(),
+            x_min: 0.0,
+            x_max: 1.0,
+            y_min: -1.0,
+            y_max: 1.0,
+            series: vec![
+                series("v", None, 1000),
+                series("d/dx(v)", Some(Derivation::new("derivative", Some("v"))), 1000),
+            ],
+            annotations: Vec::new(),
+            time_bands: Vec::new(),
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn archive_round_trips_and_links_sources() {
+        let source = SourceFile { path: "data/run.csv".into(), sql: None, csv: Default::default(), decimate: None };
+        let project = Project { version: PROJECT_VERSION, graphs: vec![graph(None), graph(Some(source))] };
+        let bytes = write_archive(&project).unwrap();
+        assert!(is_archive(&bytes) && !is_archive(b"{\"version\":1}"));
+        assert_eq!(read_archive(&bytes).unwrap(), project);
+        // Much smaller than the JSON text of the same samples.
+        assert!(bytes.len() < serde_json::to_vec(&project).unwrap().len());
+
+        let mut linked = project.clone();
+        linked.link_sources();
+        let back = read_archive(&write_archive(&linked).unwrap()).unwrap();
+        assert_eq!(back.graphs[0].series[0].xs.len(), 1000, "graph without a source keeps its data");
+        assert!(back.graphs[1].series[0].xs.is_empty(), "file column re-read on open");
+        assert_eq!(back.graphs[1].series[1], project.graphs[1].series[1], "derived series kept");
+        assert!(read_archive(b"PK\x03\x04 nope").is_err());
+    }
+
+    #[test]
+    fn bundles_reopen_and_carry_extra_files() {
+        let project = Project { version: PROJECT_VERSION, graphs: vec![graph(None)] };
+        let files = vec![
+            ("sources/run.csv".to_string(), b"t,v\n0,1\n".to_vec()),
+            ("previews/graph-1.png".to_string(), vec![0x89, b'P', b'N', b'G']),
+        ];
+        let bytes = write_bundle(&project, &files).unwrap();
+        assert_eq!(read_archive(&bytes).unwrap(), project);
+        let mut zip = ZipArchive::new(Cursor::new(&bytes[..])).unwrap();
+        let mut csv = String::new();
+        zip.by_name("sources/run.csv").unwrap().read_to_string(&mut csv).unwrap();
+        assert_eq!(csv, "t,v\n0,1\n");
+        for bad in ["project.json", "data/g0/s0.f64", "../run.csv", "/etc/passwd", ""] {
+            assert!(write_bundle(&project, &[(bad.to_string(), vec![])]).is_err(), "{bad}");
+        }
+        assert!(write_bundle(&project, &[files[0].clone(), files[0].clone()]).is_err());
+    }
+
+    #[test]
+    fn source_paths_are_relative_to_the_project() {
+        assert_eq!(relative_path("/home/a/proj/p.oxideplot", "/home/a/proj/data/run.csv"), "data/run.csv");
+        assert_eq!(relative_path("/home/a/proj/p.oxideplot", "/home/a/logs/run.csv"), "../logs/run.csv");
+        assert_eq!(relative_path(r"C:\work\p.oxideplot", r"C:\work\run.csv"), "run.csv");
+        assert_eq!(relative_path(r"C:\work\p.oxideplot", r"D:\run.csv"), r"D:\run.csv");
+        assert_eq!(resolve_path("/home/a/proj/p.oxideplot", "../logs/run.csv"), "/home/a/logs/run.csv");
+        assert_eq!(resolve_path(r"C:\work\p.oxideplot", "data/run.csv"), r"C:\work\data\run.csv");
+        assert_eq!(resolve_path(r"C:\work\p.oxideplot", r"D:\run.csv"), r"D:\run.csv");
+    }
+}
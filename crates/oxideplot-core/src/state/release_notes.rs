@@ -0,0 +1,153 @@
+//! In-app release notes and feature tips.
+//!
+//! Most of OxidePlot's tools live behind a dialog or a modifier key, so a
+//! changelog in the docs is easy to miss. [`RELEASES`] is the same list
+//! compiled into the app: the "What's new" window shows it newest first,
+//! and notes with a [`TipTrigger`] also pop up once, the first time the
+//! situation they help with comes up ([`tip_for`]).
+
+use serde::Serialize;
+
+/// A situation the frontend reports, worth a one-time hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TipTrigger {
+    /// The first series were plotted.
+    DataLoaded,
+    /// A graph shows two or more series.
+    TwoSeries,
+    /// The workspace has two or more graphs.
+    TwoGraphs,
+    /// A graph's X axis is a date-time.
+    TimeX,
+    /// A file large enough for chunked import was opened.
+    LargeFile,
+}
+
+impl TipTrigger {
+    /// Parse the kebab-case name the frontend sends.
+    pub fn parse(name: &str) -> Option<TipTrigger> {
+        Some(match name {
+            "data-loaded" => TipTrigger::DataLoaded,
+            "two-series" => TipTrigger::TwoSeries,
+            "two-graphs" => TipTrigger::TwoGraphs,
+            "time-x" => TipTrigger::TimeX,
+            "large-file" => TipTrigger::LargeFile,
+            _ => return None,
+        })
+    }
+}
+
+/// One release-note entry. `id` is stable, so "seen" survives reordering.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Note {
+    pub id: &'static str,
+    pub title: &'static str,
+    pub body: &'static str,
+    /// Where the feature lives ("Toolbar → Box", "Project menu").
+    #[serde(rename = "where")]
+    pub location: &'static str,
+    pub trigger: Option<TipTrigger>,
+}
+
+/// The notes of one released version.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Release {
+    pub version: &'static str,
+    pub notes: &'static [Note],
+}
+
+/// Every release, newest first.
+pub const RELEASES: &[Release] = &[Release {
+    version: "0.1.1",
+    notes: &[
+        Note {
+            id: "box-zoom",
+            title: "Box zoom",
+            body: "Shift+drag on the plot (or the Box toolbar mode) zooms to any rectangle, in X and Y at once.",
+            location: "Toolbar → Box",
+            trigger: Some(TipTrigger::DataLoaded),
+        },
+        Note {
+            id: "series-math",
+            title: "Math between series",
+            body: "Derive a new series from others with a formula, or combine series from several graphs aligned on X.",
+            location: "Series list → +ƒ Formula, toolbar → Cross Math",
+            trigger: Some(TipTrigger::TwoSeries),
+        },
+        Note {
+            id: "window-stats",
+            title: "Statistics across graphs",
+            body: "With X-synced graphs, min/mean/max of every series over the shared X window in one table, exportable as CSV.",
+            location: "Toolbar → Window Stats",
+            trigger: Some(TipTrigger::TwoGraphs),
+        },
+        Note {
+            id: "x-transforms",
+            title: "Relative time and X offsets",
+            body: "Start a time axis at zero, shift or scale X, or convert its unit.",
+            location: "Series list → fx → X from zero / X ∘ constant / X unit",
+            trigger: Some(TipTrigger::TimeX),
+        },
+        Note {
+            id: "import-decimation",
+            title: "Thin or window huge files on import",
+            body: "Keep every Nth row, average into X buckets, or read only a time range; re-read any view at full resolution later.",
+            location: "Opening a large file, toolbar → Range",
+            trigger: Some(TipTrigger::LargeFile),
+        },
+        Note {
+            id: "project-bundle",
+            title: "Project bundles",
+            body: "Export one compressed zip with the project, every series' data, a preview of each graph and optionally the source files.",
+            location: "Project menu → Export bundle",
+            trigger: None,
+        },
+        Note {
+            id: "keyboard-shortcuts",
+            title: "Keyboard shortcuts",
+            body: "Arrows pan (Shift: a screen), +/- zoom, F fits, T toggles the table, Del removes the selected series, Ctrl+O opens, Ctrl+S saves.",
+            location: "Focused graph",
+            trigger: None,
+        },
+        Note {
+            id: "unit-axis-zoom",
+            title: "Per-unit axis zoom",
+            body: "With one Y axis per unit, scroll or right-drag over an axis gutter to zoom or pan just that axis.",
+            location: "Axis gutters",
+            trigger: None,
+        },
+    ],
+}];
+
+/// The first note for `trigger` whose id isn't in `seen`, oldest release
+/// first so long-standing tools are introduced before newer variations.
+pub fn tip_for(trigger: TipTrigger, seen: &[String]) -> Option<&'static Note> {
+    RELEASES
+        .iter()
+        .rev()
+        .flat_map(|r| r.notes.iter())
+        .find(|n| n.trigger == Some(trigger) && !seen.iter().any(|s| s == n.id))
+}
+
+#[cfg(test)]
+mod release_notes_tests {
+    use super::*;
+
+    #[test]
+    fn ids_are_unique_and_triggers_parse() {
+        let ids: Vec<&str> = RELEASES.iter().flat_map(|r| r.notes.iter().map(|n| n.id)).collect();
+        for (i, id) in ids.iter().enumerate() {
+            assert!(!ids[..i].contains(id), "duplicate note id {id}");
+        }
+        assert_eq!(TipTrigger::parse("two-series"), Some(TipTrigger::TwoSeries));
+        assert_eq!(TipTrigger::parse("nope"), None);
+    }
+
+    #[test]
+    fn each_tip_shows_once() {
+        let tip = tip_for(TipTrigger::TwoSeries, &[]).unwrap();
+        assert_eq!(tip.id, "series-math");
+        assert!(tip_for(TipTrigger::TwoSeries, &[tip.id.to_string()]).is_none());
+    }
+}
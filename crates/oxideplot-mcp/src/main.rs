@@ -16,21 +16,26 @@ use serde_json::json;
 use tokio::sync::Mutex;
 
 use oxideplot_core::data::datetime::format_timestamp;
-use oxideplot_core::data::loader::{
-    column_to_f64, column_to_timestamps, load_from_bytes, FileMeta, LoadedData,
-};
+use oxideplot_core::data::column::Column;
+use oxideplot_core::data::loader::{load_from_bytes, FileMeta, LoadedData};
 use oxideplot_core::data::table::{compute_view_index, window_rows, TableQuery};
 use oxideplot_core::processing::downsampling::lttb_downsample;
+use oxideplot_core::processing::filters;
 use oxideplot_core::processing::math_ops;
 use oxideplot_core::processing::statistics::SeriesStats;
 use oxideplot_core::render::axis::{compute_grid_lines, format_tick_value};
 use oxideplot_core::render::gpu_types::{DrawMode, GridGpuData, PlotUniforms, SeriesGpuData};
+use oxideplot_core::render::bars::{layout_bars, BarLayout};
+use oxideplot_core::render::heatmap::{log_color_range, Colormap};
+use oxideplot_core::render::layers::GridLayer;
 use oxideplot_core::render::renderer::PlotRenderer;
 use oxideplot_core::data::loader::resolve_col;
 use oxideplot_core::processing::downsampling::minmax_envelope;
 use oxideplot_core::processing::expr::{apply_filter, collect_expr_cols, eval_expr, parse_expr, rolling_compute};
 use oxideplot_core::processing::histogram::histogram as core_histogram;
-use oxideplot_core::processing::qc::{health_check as core_health_check, longest_constant_run, Finding, Severity};
+use oxideplot_core::processing::qc::{
+    distinct_count, health_check as core_health_check, longest_constant_run, Finding, Severity,
+};
 use oxideplot_core::processing::spectral::{compute_psd, compute_spectrogram, infer_sample_rate};
 use oxideplot_core::processing::statistics::pearson;
 
@@ -48,28 +53,10 @@ const PALETTE: [[f32; 4]; 8] = [
     [1.00, 0.55, 0.80, 1.0],
 ];
 
-/// Magma-like colormap: t in 0..1 -> RGB. For spectrogram intensity.
+/// Magma colormap: t in 0..1 -> RGB. For spectrogram intensity; the same
+/// stops the GPU spectrogram uses.
 fn heat_color(t: f64) -> [u8; 3] {
-    const STOPS: [(f64, [f64; 3]); 5] = [
-        (0.0, [0.0, 0.0, 0.02]),
-        (0.25, [0.28, 0.05, 0.35]),
-        (0.5, [0.65, 0.18, 0.42]),
-        (0.75, [0.95, 0.45, 0.28]),
-        (1.0, [0.99, 0.87, 0.55]),
-    ];
-    let t = t.clamp(0.0, 1.0);
-    let mut i = 0;
-    while i + 1 < STOPS.len() && t > STOPS[i + 1].0 {
-        i += 1;
-    }
-    let (t0, c0) = STOPS[i];
-    let (t1, c1) = STOPS[(i + 1).min(STOPS.len() - 1)];
-    let f = if t1 > t0 { (t - t0) / (t1 - t0) } else { 0.0 };
-    [
-        ((c0[0] + (c1[0] - c0[0]) * f) * 255.0) as u8,
-        ((c0[1] + (c1[1] - c0[1]) * f) * 255.0) as u8,
-        ((c0[2] + (c1[2] - c0[2]) * f) * 255.0) as u8,
-    ]
+    Colormap::Magma.color(t).map(|c| (c * 255.0) as u8)
 }
 
 /// Escape a CSV field (quote if it contains a comma, quote, or newline).
@@ -152,7 +139,7 @@ impl Transform {
     fn apply(self, xs: &[f64], ys: &[f64]) -> Vec<f64> {
         match self {
             Transform::None => ys.to_vec(),
-            Transform::MovingAverage(w) => math_ops::moving_average(ys, w),
+            Transform::MovingAverage(w) => filters::moving_average(ys, w),
             Transform::Derivative => math_ops::derivative(xs, ys),
             Transform::Integral => math_ops::integral(xs, ys),
         }
@@ -258,7 +245,7 @@ struct DeriveParams {
     offset: Option<f64>,
     /// For op "expr": a formula over column names, e.g.
     /// "deg(acos(calibrated_az / total_gravity))" or "raw_ax2 - raw_ax1". Supports
-    /// + - * / ^, parentheses, and sqrt/abs/sin/cos/tan/asin/acos/atan/atan2/hypot/
+    /// `+ - * / ^`, parentheses, and sqrt/abs/sin/cos/tan/asin/acos/atan/atan2/hypot/
     /// pow/exp/ln/log10/floor/ceil/round/sign/deg/rad/min/max.
     #[serde(default)]
     expression: Option<String>,
@@ -364,7 +351,9 @@ struct CreateGraphParams {
     x_col: String,
     /// Y-axis columns: names or numeric indices (one or more).
     y_cols: Vec<String>,
-    /// Draw mode: "lines" (default), "step", or "points".
+    /// Draw mode: "lines" (default), "step" (hold until the next point),
+    /// "step-pre" (step at the previous point), "points", "smooth" (spline),
+    /// or "bar" (grouped bars from zero).
     #[serde(default)]
     draw_mode: Option<String>,
     /// Vertical layout for multiple Y series: "overlay" (default, shared Y axis),
@@ -500,12 +489,7 @@ impl OxidePlot {
         let data = load_from_bytes(&bytes, &fname)
             .map_err(|e| McpError::internal_error(format!("parse failed: {e}"), None))?;
         let meta = FileMeta::from_loaded(&data);
-        let numeric_cols: Vec<bool> = (0..data.columns.len())
-            .map(|c| {
-                let col = &data.column_data[c];
-                column_to_f64(col).1 >= 0.5 || column_to_timestamps(col).is_some()
-            })
-            .collect();
+        let numeric_cols = meta.numeric_mask();
 
         let mut s = self.session.lock().await;
         let id = s.new_id("ds");
@@ -554,9 +538,9 @@ impl OxidePlot {
             let cells = &ds.data.column_data[c];
             let n_total = cells.len();
             // QC stats (computed for every column, numeric or not).
-            let distinct = cells.iter().collect::<std::collections::HashSet<_>>().len();
+            let distinct = distinct_count(cells);
             let const_run = longest_constant_run(cells);
-            let (vals, _) = column_to_f64(cells);
+            let vals = cells.to_f64();
             let n_finite = vals.iter().filter(|v| v.is_finite()).count();
             let n_missing = n_total.saturating_sub(n_finite);
             let n_zero = vals.iter().filter(|v| v.is_finite() && **v == 0.0).count();
@@ -628,7 +612,7 @@ impl OxidePlot {
             .map(|&c| {
                 (
                     ds.data.columns[c].clone(),
-                    column_to_f64(&ds.data.column_data[c]).0,
+                    ds.data.column_data[c].to_f64().into_owned(),
                 )
             })
             .collect();
@@ -699,7 +683,7 @@ impl OxidePlot {
 
         let mut out = Vec::new();
         for &c in &idxs {
-            let (vals, _) = column_to_f64(&ds.data.column_data[c]);
+            let vals = ds.data.column_data[c].to_f64();
             let mut segs = Vec::new();
             for seg in 0..n_seg {
                 let lo = seg * n_rows / n_seg;
@@ -787,7 +771,7 @@ impl OxidePlot {
             collect_expr_cols(&ast, &mut refs);
             let colvals: std::collections::HashMap<usize, Vec<f64>> = refs
                 .iter()
-                .map(|&ci| (ci, column_to_f64(&ds.data.column_data[ci]).0))
+                .map(|&ci| (ci, ds.data.column_data[ci].to_f64().into_owned()))
                 .collect();
             (0..n_rows).map(|r| eval_expr(&ast, &colvals, r)).collect()
         } else {
@@ -828,7 +812,7 @@ impl OxidePlot {
             }
             let cols: Vec<Vec<f64>> = idxs
                 .iter()
-                .map(|&c| column_to_f64(&ds.data.column_data[c]).0)
+                .map(|&c| ds.data.column_data[c].to_f64().into_owned())
                 .collect();
 
             if op.starts_with("rolling_") {
@@ -867,15 +851,11 @@ impl OxidePlot {
                 format!("{op}_{}", columns.join("_"))
             }
         });
-        let cells: Vec<String> = vals
-            .iter()
-            .map(|v| if v.is_finite() { format!("{v}") } else { String::new() })
-            .collect();
+        let st = SeriesStats::compute(&vals);
         ds.data.columns.push(name.clone());
-        ds.data.column_data.push(cells);
+        ds.data.column_data.push(Column::numeric(vals).into());
         ds.numeric_cols.push(true);
         let new_index = ds.data.columns.len() - 1;
-        let st = SeriesStats::compute(&vals);
 
         Ok(Self::text_result(json!({
             "dataset_id": dataset_id,
@@ -916,7 +896,7 @@ impl OxidePlot {
                 ));
             }
             let fs = sample_rate.unwrap_or_else(|| infer_sample_rate(&ds.data));
-            let (vals, _) = column_to_f64(&ds.data.column_data[ci]);
+            let vals = ds.data.column_data[ci].to_f64();
             let (freqs, power) = compute_psd(&vals, fs);
             (freqs, power, fs, ds.data.columns[ci].clone())
         };
@@ -942,11 +922,9 @@ impl OxidePlot {
         let n = freqs.len();
         let ld = LoadedData {
             columns: vec!["frequency".to_string(), "power".to_string()],
-            column_data: vec![
-                freqs.iter().map(|v| format!("{v}")).collect(),
-                power.iter().map(|v| format!("{v}")).collect(),
-            ],
+            column_data: vec![Column::numeric(freqs.clone()).into(), Column::numeric(power.clone()).into()],
             row_count: n,
+            lazy: None,
         };
         let id = s.new_id("ds");
         s.datasets.insert(
@@ -997,7 +975,7 @@ impl OxidePlot {
             }
             let fs = sample_rate.unwrap_or_else(|| infer_sample_rate(&ds.data));
             (
-                column_to_f64(&ds.data.column_data[ci]).0,
+                ds.data.column_data[ci].to_f64().into_owned(),
                 fs,
                 ds.data.columns[ci].clone(),
             )
@@ -1020,13 +998,7 @@ impl OxidePlot {
         let n_frames = frames.len();
 
         // Colour range from the 5th–99.5th percentile of log-magnitude (contrast).
-        let mut logs: Vec<f64> = frames
-            .iter()
-            .flat_map(|f| f.iter().map(|&m| (m + 1e-12).log10()))
-            .collect();
-        logs.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-        let lo = logs[(logs.len() as f64 * 0.05) as usize];
-        let hi = logs[((logs.len() - 1) as f64 * 0.995) as usize];
+        let (lo, hi) = log_color_range(&frames);
         let span = (hi - lo).max(1e-9);
 
         let mut buf = vec![0u8; (w * h * 4) as usize];
@@ -1119,7 +1091,7 @@ impl OxidePlot {
                 ));
             }
             (
-                column_to_f64(&ds.data.column_data[ci]).0,
+                ds.data.column_data[ci].to_f64().into_owned(),
                 ds.data.columns[ci].clone(),
             )
         };
@@ -1152,8 +1124,8 @@ impl OxidePlot {
         let (ml, mr, mt, mb) = (46u32, 10u32, 12u32, 22u32);
         let plot_w = w.saturating_sub(ml + mr).max(1);
         let plot_h = h.saturating_sub(mt + mb).max(1);
-        for bi in 0..nbins {
-            let bar_h = (counts[bi] as f64 / maxc as f64 * plot_h as f64) as u32;
+        for (bi, &count) in counts.iter().enumerate().take(nbins) {
+            let bar_h = (count as f64 / maxc as f64 * plot_h as f64) as u32;
             let x0 = ml + (bi as u32 * plot_w / nbins as u32);
             let x1 = ml + ((bi as u32 + 1) * plot_w / nbins as u32);
             let ytop = mt + plot_h - bar_h;
@@ -1331,7 +1303,7 @@ impl OxidePlot {
         for &r in &rows {
             let line: Vec<String> = cidx
                 .iter()
-                .map(|&c| csv_escape(ds.data.column_data[c].get(r).map(|s| s.as_str()).unwrap_or("")))
+                .map(|&c| csv_escape(&ds.data.column_data[c].cell(r)))
                 .collect();
             out.push_str(&line.join(","));
             out.push('\n');
@@ -1407,7 +1379,10 @@ impl OxidePlot {
         };
         let dm = match draw_mode.as_deref() {
             Some("step") => DrawMode::Step,
+            Some("step-pre") => DrawMode::StepPre,
             Some("points") => DrawMode::Points,
+            Some("smooth") => DrawMode::Smooth,
+            Some("bar") => DrawMode::Bar,
             _ => DrawMode::Lines,
         };
         let lay = Layout::parse(layout.as_deref());
@@ -1613,12 +1588,11 @@ impl OxidePlot {
             let n_cols = ds.data.columns.len();
             let n_numeric = ds.numeric_cols.iter().filter(|&&b| b).count();
             let dt_col = (0..ds.data.columns.len()).find(|&c| {
-                column_to_timestamps(&ds.data.column_data[c])
-                    .map(|(_, f)| f >= 0.5)
-                    .unwrap_or(false)
+                let col = &ds.data.column_data[c];
+                col.timestamps().is_some() && col.valid_frac() >= 0.5
             });
             let time_range = dt_col.and_then(|c| {
-                let (ts, _) = column_to_timestamps(&ds.data.column_data[c])?;
+                let ts = ds.data.column_data[c].timestamps()?;
                 let finite: Vec<f64> = ts.iter().copied().filter(|v| v.is_finite()).collect();
                 if finite.is_empty() {
                     return None;
@@ -1769,7 +1743,7 @@ impl OxidePlot {
                 .map(|&r| {
                     let vals = display_cols
                         .iter()
-                        .map(|&c| ds.data.column_data[c].get(r).cloned().unwrap_or_default())
+                        .map(|&c| ds.data.column_data[c].cell(r).into_owned())
                         .collect();
                     (r, vals)
                 })
@@ -1882,6 +1856,7 @@ struct PanelSeries {
 /// Build the (series, grid, uniforms) for one panel over the shared `x_view`.
 /// When `normalize`, each series' y is rescaled to 0..1 (its own range) and the
 /// y-view is fixed to [-0.05, 1.05]; otherwise the y-view fits the panel's data.
+#[allow(clippy::too_many_arguments)]
 fn build_panel(
     series: &[PanelSeries],
     x_view: (f64, f64),
@@ -1962,6 +1937,15 @@ fn build_panel(
             .collect();
         ((ymn - ypad, ymx + ypad), s)
     };
+    // Bars: grouped quads rising from zero, with zero kept in view.
+    let (y_view, gpu_series) = if draw_mode == DrawMode::Bar {
+        let samples: Vec<Vec<[f32; 2]>> = gpu_series.iter().map(|s| s.points.clone()).collect();
+        let quads = layout_bars(&samples, BarLayout::Grouped, 0.0);
+        let bars = gpu_series.into_iter().zip(quads).map(|(s, points)| SeriesGpuData { points, ..s }).collect();
+        ((y_view.0.min(0.0), y_view.1.max(0.0)), bars)
+    } else {
+        (y_view, gpu_series)
+    };
 
     let x_ticks = compute_grid_lines(x_view.0, x_view.1);
     let y_ticks = compute_grid_lines(y_view.0, y_view.1);
@@ -1980,6 +1964,8 @@ fn build_panel(
         segments: segs,
         color: [0.45, 0.47, 0.55, 0.22],
         line_width: 1.0,
+        pixel_snap: true,
+        layer: GridLayer::Below,
     };
     let uniforms = PlotUniforms {
         view_min: [(x_view.0 - x_origin) as f32, y_view.0 as f32],
@@ -1988,7 +1974,8 @@ fn build_panel(
         line_width: 2.0,
         point_radius: 3.0,
         color: [0.0, 0.0, 0.0, 0.0],
-        _padding: [0.0; 4],
+        pixel_snap: 0.0,
+        _padding: [0.0; 3],
     };
     (gpu_series, grid, uniforms)
 }
@@ -2051,10 +2038,7 @@ fn prepare_render(
 
     // X values: datetime → epoch-second timestamps, else numeric.
     let xcol = &data.column_data[spec.x_col];
-    let (xs, x_is_time): (Vec<f64>, bool) = match column_to_timestamps(xcol) {
-        Some((v, _)) => (v, true),
-        None => (column_to_f64(xcol).0, false),
-    };
+    let (xs, x_is_time) = (xcol.to_f64(), xcol.timestamps().is_some());
     // Large-coordinate render offset: datetime X is epoch seconds (~1.8e9),
     // beyond f32 precision, so every X vertex (and the view bounds / grid) is
     // shifted by a stable in-window origin before the f32 cast. The origin
@@ -2075,8 +2059,7 @@ fn prepare_render(
     let mut max_raw_points = 0usize;
     let mut any_downsampled = false;
     for (k, &yc) in spec.y_cols.iter().enumerate() {
-        let (ysv, _) = column_to_f64(&data.column_data[yc]);
-        let ysv = tf.apply(&xs, &ysv); // optional transform (smooth/derivative/integral)
+        let ysv = tf.apply(&xs, &data.column_data[yc].to_f64()); // optional transform (smooth/derivative/integral)
 
         // Finite (x, y) pairs + y-range over the FULL series.
         let mut fx: Vec<f64> = Vec::new();
@@ -2387,6 +2370,7 @@ fn glyph5x7(c: char) -> [u8; 7] {
 
 /// Draw `text` onto an RGBA buffer at top-left (x, y), scaled `scale`×, clipped
 /// to the image bounds. Used for baked numeric tick labels.
+#[allow(clippy::too_many_arguments)]
 fn draw_text(buf: &mut [u8], w: u32, h: u32, text: &str, x: i32, y: i32, color: [u8; 4], scale: i32) {
     let mut cx = x;
     for ch in text.chars() {
@@ -20,7 +20,65 @@
 //
 // Task 4.3 — adds `x_is_time` field and `axis_ticks()` method for SVG tick
 // label overlay.  `set_series` now detects datetime X columns.
+//
+// GPU fallback — `create` no longer panics when wgpu can't get an adapter or
+// device; the renderer is left unset, `gpu_fallback_reason()` reports why, and
+// `cpu_polylines()` hands the frontend decimated screen-space polylines to
+// stroke on a 2D canvas instead.
+//
+// GPU diagnostics — `create` takes the persisted backend preference (WebGPU,
+// WebGL2, or forced software/CPU) and `gpu_diagnostics()` reports the adapter,
+// backend, limits and surface format for the frontend's diagnostics panel.
+//
+// Crash reports — the panic hook still logs to the console, then hands the
+// message and the JS stack to `globalThis.oxideplotPanic` (installed by the
+// frontend), which saves a crash report before the module is unusable.
+//
+// Hibernation — `hibernate()` packs the samples of a graph that has sat idle
+// off-screen (`data::compressed`) and `wake()` restores them; while packed,
+// `export_project` unpacks on the fly.
+//
+// Culling — `set_culled(true)` for a graph scrolled out of view skips its
+// downsampling and drawing until it scrolls back.
+//
+// Time bands — static `time_band_intervals` lists where night / weekend /
+// weekly bands (`render::time_bands`) fall in a datetime view; the frontend
+// shades them behind the plot.
+//
+// Event markers — static `parse_events` reads an events file (time, label,
+// colour) for the frontend to draw as vertical lines, and `events_csv`
+// writes markers and placed notes back out.
+//
+// Split view — `table_row_at_x` maps a plot hover to the nearest table row
+// under the current filter/sort/decimation, and `table_row_x` maps a hovered
+// row back to its X, so plot and table highlight each other side by side.
+//
+// Series alignment — `set_series_align` shifts a series' X (logger clock
+// skew) and scales/offsets its Y (`processing::align`); saved in projects.
+//
+// Cropping — `crop_series` deletes samples outside an X range
+// (`math_ops::x_window`); `undo_sample_edit` restores the last few edits.
+//
+// Theme contrast — with `set_auto_contrast` on, series too faint against the
+// background are drawn darker or lighter (`render::contrast`); the series
+// keep their own colours.
+//
+// Despiking — `preview_despike` marks the outliers a Hampel or z-score test
+// finds (`processing::filters`); `despike_series` deletes or bridges them,
+// undoable like a crop.
+//
+// Delta cursor — `point_delta` measures a hovered sample from a clicked
+// reference sample (ΔX, ΔY, %), quicker than placing two cursors.
+//
+// Gaps — missing Y values are kept as NaN line breaks (`gapped_pairs`) and
+// survive decimation, so a hole in the data is drawn as one; `set_gap_style`
+// can bridge each break with a faint dashed line (`render::dash`).
+//
+// Cursor area — with two cursors placed, `cursor_table` adds each series'
+// area under the curve between them (`math_ops::integral_between`), in
+// Y·X units (kW·h on a datetime axis).
 
+#[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
 // ─── WASM-only implementation ────────────────────────────────────────────────
@@ -28,21 +86,73 @@ use wasm_bindgen::prelude::*;
 #[cfg(target_arch = "wasm32")]
 mod wasm_impl {
     use super::*;
-    use std::collections::{BTreeSet, HashMap, HashSet};
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
     use serde::{Deserialize, Serialize};
     use oxideplot_core::render::gpu_types::{DrawMode, GridGpuData, PlotUniforms, SeriesGpuData};
+    use oxideplot_core::render::bars::{layout_bars, BarLayout};
     use oxideplot_core::render::renderer::PlotRenderer;
-    use oxideplot_core::data::loader::{LoadedData, FileMeta, load_from_bytes, column_to_f64, column_to_timestamps};
-    use oxideplot_core::data::table::{ColFilter, TableQuery, compute_view_index, window_rows};
+    use oxideplot_core::render::heatmap::{Colormap, HeatmapGpuData};
+    use oxideplot_core::render::cpu_fallback::{build_cpu_polylines, CPU_POINT_BUDGET};
+    use oxideplot_core::render::dash::{dash_polyline, gap_bridges, GapStyle};
+    use oxideplot_core::render::batch::{batch_by_style, BATCH_MIN_SERIES};
+    use oxideplot_core::render::layers::{draw_order, GridLayer, SeriesLayer};
+    use oxideplot_core::render::contrast::{contrast_ratio, readable_color, MIN_SERIES_CONTRAST};
+    use oxideplot_core::state::color_registry::ColorRegistry;
+    use oxideplot_core::data::reference::{load_reference, ReferenceCurve};
+    use oxideplot_core::data::events::{events_to_csv, load_events, EventMarker};
+    use oxideplot_core::data::label_map::LabelMap;
+    use oxideplot_core::render::diagnostics::{BackendPreference, GpuDiagnostics};
+    use oxideplot_core::render::frame_budget::FrameBudget;
+    use oxideplot_core::render::gpu_budget::{GpuBudget, UploadReport};
+    use oxideplot_core::data::loader::{
+        CsvMeta, CsvOptions, LoadedData, FileMeta, SyntheticX, is_delimited_text, load_from_bytes_lazy,
+    };
+    use oxideplot_core::data::chunked::{ChunkedCsv, DecimationSpec, ImportDecimation};
+    use oxideplot_core::data::streaming::{Delimiter, LineStream, StreamConfig, DEFAULT_MAX_POINTS};
+    use oxideplot_core::data::column::Column;
+    use oxideplot_core::data::store::{fingerprint, gapped_pairs, series_arrays, share_columns, ColumnStore, SharedStore};
+    use std::borrow::Cow;
+    use std::cell::RefCell;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::sync::Arc;
+    use oxideplot_core::data::table::{
+        compute_view_index, decimated_columns, decimated_window, nearest_table_row, table_row_x, ColFilter,
+        Decimation, TableQuery,
+    };
     use oxideplot_core::processing::downsampling::{DownsampleMode, downsample_for_view_mode};
-    use oxideplot_core::processing::statistics::percentile;
+    use oxideplot_core::processing::align::SeriesAlign;
+    use oxideplot_core::processing::statistics::{percentile, BoxPlot, RegionStats, SeriesStats};
     use oxideplot_core::processing::expr::{parse_expr, collect_expr_cols, eval_expr};
     use oxideplot_core::state::plot_view::PlotViewState;
+    use oxideplot_core::state::project::{Derivation, GraphProject, Project, ProjectSeries};
+    use oxideplot_core::state::project_archive;
+    use oxideplot_core::state::release_notes::{self, TipTrigger};
+    use oxideplot_core::data::compressed::CompressedSeries;
+    use oxideplot_core::data::synthetic::{synthetic, synthetic_xs, SyntheticKind, MAX_SYNTHETIC_POINTS};
+    use oxideplot_core::render::benchmark;
+    use oxideplot_core::render::time_bands::{band_intervals, TimeBand};
+    use oxideplot_core::state::graph_state::{set_unit_axis_order, sort_unit_axes, sync_unit_axes, AxisSide, AxisState, AxisTitle};
     use oxideplot_core::geom::{Pos2, Rect};
-    use oxideplot_core::render::axis::{compute_grid_lines, format_tick_value};
+    use oxideplot_core::render::axis::{compute_grid_lines, format_readout, format_tick_value};
+    use oxideplot_core::render::unit_colors::unit_colors;
+    use oxideplot_core::data::unit_inference::{infer_unit, UNKNOWN_UNIT};
+    use oxideplot_core::data::split::{pivot_name, split_by_category, split_name};
+    use oxideplot_core::data::unit_convert::{self, apply_rules, UnitConversion};
     use oxideplot_core::data::datetime::format_timestamp;
+    use oxideplot_core::processing::filters::{self, find_spikes, interpolate_spikes, SpikeTest};
     use oxideplot_core::processing::math_ops;
+    use oxideplot_core::processing::cross_math;
+    use oxideplot_core::processing::segments::{segment_stats, suggest_gap_threshold, GapSplit};
+    use oxideplot_core::processing::stacking::{build_stack, Stack, StackMode};
+    use oxideplot_core::processing::uncertainty::{band_pairs, bar_segments, ErrorStyle, Uncertainty};
+    use oxideplot_core::processing::area_fill::{area_pairs, AreaFill};
+    use oxideplot_core::processing::sparkline::{sparkline, Sparkline, SPARKLINE_BUCKETS};
+    use oxideplot_core::processing::kd_tree::nearest_on_screen;
+    use oxideplot_core::processing::spectral;
+    use oxideplot_core::processing::fft;
     use oxideplot_core::processing::interpolation;
+    use oxideplot_core::processing::fitting::{fit, Fit, FitModel};
 
     /// Autoscale strategy for the non-normalized Y bounds in `auto_fit`.
     /// `MinMax` uses the raw data extremes; `Robust` clips to the 1st/99th
@@ -64,20 +174,213 @@ mod wasm_impl {
     /// Minimum target point count when width is very small.
     const MIN_TARGET_POINTS: usize = 800;
 
+    /// Floor for the per-series point target while the frame-budget guard is
+    /// degrading interactive frames.
+    const MIN_INTERACTIVE_POINTS: usize = 200;
+
+    /// Dash pattern for reference curves, in pixels.
+    const REFERENCE_DASH_PX: f64 = 8.0;
+    const REFERENCE_GAP_PX: f64 = 5.0;
+
+    /// Default cap on simultaneously drawn series; visible series past it
+    /// are skipped (the frontend shows how many) until the cap is raised.
+    const MAX_DRAWN_SERIES: usize = 64;
+
+    /// Frozen ("ghost") traces: how far their colour is pulled toward grey,
+    /// and their opacity.
+    const GHOST_GREY_MIX: f32 = 0.75;
+    const GHOST_ALPHA: f32 = 0.45;
+
+    /// Error-bar cap half-width in pixels, and uncertainty-band opacity
+    /// relative to the series colour.
+    const ERROR_CAP_PX: f64 = 3.0;
+    const ERROR_BAND_ALPHA: f32 = 0.25;
+
+    /// Opacity of stacked-area layer fills relative to the series colour.
+    const STACK_FILL_ALPHA: f32 = 0.55;
+
+    /// Opacity of a series' filled area relative to its line colour.
+    const AREA_FILL_ALPHA: f32 = 0.35;
+
+    /// Grid points per stack when fitting the Y view to stacked totals.
+    const STACK_FIT_POINTS: usize = 1024;
+
+    /// Newest samples kept in view while a live stream auto-scrolls.
+    const STREAM_WINDOW_SAMPLES: usize = 1000;
+
+    /// Grid line colour on dark and light backgrounds (the themes'
+    /// `--grid-line`), picked by `set_background`.
+    const GRID_COLOR_DARK: [f32; 4] = [1.0, 1.0, 1.0, 0.05];
+    const GRID_COLOR_LIGHT: [f32; 4] = [0.0, 0.0, 0.0, 0.06];
+
+    /// High-resolution timestamp in ms (0 if `performance` is unavailable).
+    fn now_ms() -> f64 {
+        web_sys::window()
+            .and_then(|w| w.performance())
+            .map(|p| p.now())
+            .unwrap_or(0.0)
+    }
+
+    #[wasm_bindgen]
+    extern "C" {
+        /// Frontend crash reporter; absent (and the call an error) outside
+        /// the app shell.
+        #[wasm_bindgen(catch, js_namespace = globalThis, js_name = oxideplotPanic)]
+        fn report_panic(message: &str, stack: &str) -> Result<(), JsValue>;
+
+        type Error;
+        #[wasm_bindgen(constructor)]
+        fn new() -> Error;
+        #[wasm_bindgen(structural, method, getter)]
+        fn stack(error: &Error) -> String;
+    }
+
+    /// Panic hook: console output as before, plus a crash report through
+    /// `report_panic`. Installed once, by the first `create`.
+    fn install_panic_hook() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            std::panic::set_hook(Box::new(|info| {
+                console_error_panic_hook::hook(info);
+                let _ = report_panic(&info.to_string(), &Error::new().stack());
+            }));
+        });
+    }
+
     /// Full source data for one series, stored before any downsampling.
     /// xs must be in ascending order (standard time-series assumption).
+    #[derive(Clone)]
     struct SourceSeries {
         name: String,
         x_name: String,
         visible: bool,
-        xs: Vec<f64>,
-        ys: Vec<f64>,
+        /// Shared with any other graph plotting the same file columns (and
+        /// with transforms derived from this series), see `shared_pair`.
+        xs: Arc<[f64]>,
+        ys: Arc<[f64]>,
         color: [f32; 4],
         draw_mode: DrawMode,
         /// Global Y min/max over the FULL ys array, computed once in set_series.
         /// Used for per-source normalization in rebuild_visible when normalized mode is on.
         y_min: f64,
         y_max: f64,
+        /// Modular display (compass headings, wrapped phase): the wrap period
+        /// in Y units. Jumps over half a period are drawn as gaps.
+        wrap_period: Option<f64>,
+        /// How a derived series was computed (`None` for file columns); saved
+        /// in projects and shown in the series panel.
+        derivation: Option<Derivation>,
+        /// Reference (baseline) curve overlaid from the app's reference
+        /// list: always drawn as a dashed line.
+        reference: bool,
+        /// State names for an enumerated channel (ticks, readouts, table).
+        labels: Option<LabelMap>,
+        /// Segments split at X gaps (see `set_series_gap`); `None` draws
+        /// straight across gaps.
+        gaps: Option<GapSplit>,
+        /// ± uncertainty drawn as error bars or a band (`set_series_error`).
+        error: Option<Uncertainty>,
+        /// Status/level channel: the last value is drawn on to the right
+        /// edge of the view (`set_series_hold_last`).
+        hold_last: bool,
+        /// Left out of the nearest-point search, so a dense background or
+        /// reference curve doesn't grab the point menu (`set_series_hover`).
+        no_hover: bool,
+        /// Draw-order band: brought to front / sent to back without moving
+        /// in the list (`set_series_layer`).
+        layer: SeriesLayer,
+        /// Area filled under the line or between it and another series
+        /// (`set_series_fill`).
+        fill: Option<AreaFill>,
+        /// Colour last applied from the workspace colour registry
+        /// (`apply_color_registry`); a different current colour means the
+        /// user picked one by hand since.
+        locked_color: Option<[f32; 3]>,
+        /// File column the series was read from, once a unit conversion has
+        /// renamed it (`convert_unit`); `None` while `name` is the column.
+        column: Option<String>,
+        /// Compressed `xs`/`ys` while the graph hibernates; both arrays are
+        /// empty until `wake` (see `full_xs`).
+        packed: Option<(Arc<CompressedSeries>, Arc<CompressedSeries>)>,
+        /// X offset and Y scale/offset baked into `xs`/`ys`
+        /// (`set_series_align`).
+        align: SeriesAlign,
+    }
+
+    impl SourceSeries {
+        /// The file column this series reads from.
+        fn column(&self) -> &str {
+            self.column.as_deref().unwrap_or(&self.name)
+        }
+
+        /// Full X samples, unpacked on the fly while hibernating.
+        fn full_xs(&self) -> Vec<f64> {
+            match &self.packed {
+                Some((xs, _)) => xs.decompress(),
+                None => self.xs.to_vec(),
+            }
+        }
+
+        /// Full Y samples, unpacked on the fly while hibernating.
+        fn full_ys(&self) -> Vec<f64> {
+            match &self.packed {
+                Some((_, ys)) => ys.decompress(),
+                None => self.ys.to_vec(),
+            }
+        }
+
+        /// Swap in edited samples (`crop_series`, `despike_series`) with
+        /// their per-sample ± half-widths, recomputing the Y bounds and
+        /// gaps. Returns the record that undoes it.
+        fn replace_samples(&mut self, xs: Vec<f64>, ys: Vec<f64>, error_values: Option<Arc<[f64]>>) -> SampleUndo {
+            let (xs, ys): (Arc<[f64]>, Arc<[f64]>) = (xs.into(), ys.into());
+            let (y_min, y_max) = compute_y_bounds(&ys);
+            let gaps = self.gaps.as_ref().map(|g| GapSplit::detect(&xs, g.threshold));
+            let error = self.error.clone().map(|e| Uncertainty { values: error_values, ..e });
+            SampleUndo {
+                name: self.name.clone(),
+                edited: (xs.clone(), ys.clone()),
+                xs: std::mem::replace(&mut self.xs, xs),
+                ys: std::mem::replace(&mut self.ys, ys),
+                y_min: std::mem::replace(&mut self.y_min, y_min),
+                y_max: std::mem::replace(&mut self.y_max, y_max),
+                gaps: std::mem::replace(&mut self.gaps, gaps),
+                error: std::mem::replace(&mut self.error, error),
+            }
+        }
+    }
+
+    /// Most sample edits (`crop_series`, `despike_series`) that
+    /// `undo_sample_edit` can take back.
+    const MAX_SAMPLE_UNDO: usize = 10;
+
+    /// Despike preview markers: a warning red, drawn over the series.
+    const DESPIKE_PREVIEW_COLOR: [f32; 4] = [1.0, 0.25, 0.2, 1.0];
+
+    /// Opacity of gap bridges (`set_gap_style`) relative to their series.
+    const GAP_BRIDGE_ALPHA: f32 = 0.45;
+
+    /// One series as it was before a sample edit. `edited` is the samples
+    /// the edit left, so an undo can tell whether the series has been
+    /// replaced (re-read, realigned) since; compared by value, as `wake`
+    /// hands back fresh arrays.
+    struct SampleUndo {
+        name: String,
+        edited: (Arc<[f64]>, Arc<[f64]>),
+        xs: Arc<[f64]>,
+        ys: Arc<[f64]>,
+        y_min: f64,
+        y_max: f64,
+        gaps: Option<GapSplit>,
+        error: Option<Uncertainty>,
+    }
+
+    impl SampleUndo {
+        /// Whether `src` still holds the samples the edit left.
+        fn matches(&self, src: &SourceSeries) -> bool {
+            let same = |a: &[f64], b: &[f64]| a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.to_bits() == b.to_bits());
+            src.name == self.name && same(&src.xs, &self.edited.0) && same(&src.ys, &self.edited.1)
+        }
     }
 
     /// Parameters for `add_transform`, deserialised from a JS object.
@@ -88,8 +391,73 @@ mod wasm_impl {
         mode: Option<String>,
         method: Option<String>,
         points: Option<usize>,
+        /// `"savgol"`: polynomial order (window length comes from `window`).
+        order: Option<usize>,
+        /// `"constant"`: the op (`"add"`/`"sub"`/`"mul"`/`"div"`) and operand.
+        op: Option<String>,
+        value: Option<f64>,
+    }
+
+    /// Parameters for `set_series_error`, deserialised from a JS object.
+    #[derive(serde::Deserialize)]
+    struct ErrorParams {
+        /// `"bars"` or `"band"`.
+        style: String,
+        /// Loaded-file column holding per-sample ± values.
+        column: Option<usize>,
+        /// Constant ± value (used without `column`).
+        value: Option<f64>,
+    }
+
+    /// Parameters for `stream_start`, deserialised from a JS object.
+    #[derive(serde::Deserialize, Default)]
+    struct StreamParams {
+        /// `"auto"`, `"comma"`, `"semicolon"`, `"tab"`, `"space"` or a
+        /// single character.
+        delimiter: Option<String>,
+        /// Field holding each sample's X (default: the sample index).
+        x_column: Option<usize>,
+        /// Samples kept per channel.
+        max_points: Option<usize>,
     }
 
+    /// Import options accepted by `load_file_bytes` for delimited text.
+    #[derive(serde::Deserialize, Default)]
+    #[serde(default)]
+    struct CsvParams {
+        /// `"auto"`, `"comma"`, `"semicolon"`, `"tab"`, `"space"` or a
+        /// single character.
+        delimiter: Option<String>,
+        /// `"auto"`, `"."` or `","`.
+        decimal: Option<String>,
+        /// A single character; absent or `""` for none.
+        thousands: Option<String>,
+    }
+
+    /// Return payload for `series_box_plot`: box-and-whisker summary and
+    /// violin outline of a source series' finite Y values.
+    #[derive(serde::Serialize)]
+    struct BoxPlotData {
+        n: usize,
+        min: f64,
+        q1: f64,
+        median: f64,
+        q3: f64,
+        max: f64,
+        mean: f64,
+        whisker_lo: f64,
+        whisker_hi: f64,
+        /// Up to `MAX_OUTLIERS` samples beyond the whiskers (evenly thinned).
+        outliers: Vec<f64>,
+        /// How many samples lie beyond the whiskers in all.
+        n_outliers: usize,
+        /// Peak-normalised density at evenly spaced values from min to max.
+        density: Vec<f64>,
+    }
+
+    /// Cap on outlier dots sent per box plot.
+    const MAX_OUTLIERS: usize = 500;
+
     /// Return payload for `series_histogram`: the binned distribution of a
     /// source series' finite Y values.
     #[derive(serde::Serialize)]
@@ -110,6 +478,16 @@ mod wasm_impl {
         sample_rate: f64,
     }
 
+    /// Return payload for `series_amplitude_spectrum`: the windowed one-sided
+    /// FFT amplitude of a source series' Y values.
+    #[derive(serde::Serialize)]
+    struct AmplitudeSpectrumData {
+        freqs: Vec<f64>,
+        amplitude: Vec<f64>,
+        window: &'static str,
+        sample_rate: f64,
+    }
+
     /// Return payload for `series_spectrogram`: a short-time FFT magnitude
     /// spectrogram of a source series' Y values.
     #[derive(serde::Serialize)]
@@ -131,6 +509,43 @@ mod wasm_impl {
         n: usize,
     }
 
+    /// One entry of `column_overview`: a numeric column's sparkline.
+    #[derive(serde::Serialize)]
+    struct ColumnSparkline {
+        col: usize,
+        name: String,
+        spark: Sparkline,
+    }
+
+    /// One cross-graph math operand, deserialised from `{ name, x_name, xs, ys }`
+    /// (as returned by another graph's `series_curve`).
+    #[derive(serde::Deserialize)]
+    struct CrossOperand {
+        name: String,
+        #[serde(default)]
+        x_name: String,
+        xs: Vec<f64>,
+        ys: Vec<f64>,
+    }
+
+    /// Return payload for `series_curve`: a plotted series' full data.
+    #[derive(Serialize)]
+    struct SeriesCurve<'a> {
+        name: &'a str,
+        x_name: &'a str,
+        xs: &'a [f64],
+        ys: &'a [f64],
+    }
+
+    /// One entry of `detected_units`: a convertible unit, how many series
+    /// have it, and what it converts to.
+    #[derive(Serialize)]
+    struct DetectedUnit {
+        unit: String,
+        count: usize,
+        targets: Vec<&'static str>,
+    }
+
     /// The colour palette used by `ColumnDialog` on the JS side.
     /// `add_transform` picks from this palette by series count so derived
     /// series blend visually with the source series.
@@ -145,6 +560,68 @@ mod wasm_impl {
         [1.00, 0.55, 0.80, 1.0], // pink
     ];
 
+    /// Greyed, translucent version of a series colour for frozen traces.
+    fn ghost_color(c: [f32; 4]) -> [f32; 4] {
+        let grey = 0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2];
+        let mix = |v: f32| v + (grey - v) * GHOST_GREY_MIX;
+        [mix(c[0]), mix(c[1]), mix(c[2]), GHOST_ALPHA]
+    }
+
+    /// Parse a label-map spec from JS; blank means "no map".
+    fn parse_label_spec(spec: &str) -> Result<Option<LabelMap>, JsValue> {
+        if spec.trim().is_empty() {
+            return Ok(None);
+        }
+        LabelMap::parse(spec).map(Some).map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Parse `load_file_bytes` import options; null/undefined means all auto.
+    fn csv_options(options: JsValue) -> Result<CsvOptions, JsValue> {
+        if options.is_null() || options.is_undefined() {
+            return Ok(CsvOptions::default());
+        }
+        let p: CsvParams =
+            serde_wasm_bindgen::from_value(options).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        let delimiter = match p.delimiter.as_deref() {
+            Some(d) => match Delimiter::parse(d) {
+                Some(Delimiter::Auto) => None,
+                Some(Delimiter::Char(c)) if c.is_ascii() => Some(c as u8),
+                _ => return Err(JsValue::from_str(&format!("Unknown delimiter {d:?}"))),
+            },
+            None => None,
+        };
+        let single = |what: &str, s: Option<&str>| -> Result<Option<char>, JsValue> {
+            match s {
+                None | Some("") | Some("auto") => Ok(None),
+                Some("space") => Ok(Some(' ')),
+                Some(s) => {
+                    let mut chars = s.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(c), None) => Ok(Some(c)),
+                        _ => Err(JsValue::from_str(&format!("Unknown {what} separator {s:?}"))),
+                    }
+                }
+            }
+        };
+        let csv = CsvOptions {
+            delimiter,
+            decimal: single("decimal", p.decimal.as_deref())?,
+            thousands: single("thousands", p.thousands.as_deref())?,
+        };
+        csv.validate().map_err(|e| JsValue::from_str(&e))?;
+        Ok(csv)
+    }
+
+    /// Parse `chunked_begin` row thinning; null/undefined means every row.
+    fn decimation_options(params: JsValue) -> Result<ImportDecimation, JsValue> {
+        if params.is_null() || params.is_undefined() {
+            return Ok(ImportDecimation::Off);
+        }
+        let spec: DecimationSpec =
+            serde_wasm_bindgen::from_value(params).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        spec.parse().map_err(|e| JsValue::from_str(&e))
+    }
+
     /// Pick a palette colour by cycling over `PALETTE` at `index`.
     fn palette_color(index: usize) -> [f32; 4] {
         PALETTE[index % PALETTE.len()]
@@ -169,6 +646,44 @@ mod wasm_impl {
         }
     }
 
+    type PairKey = (u64, usize, usize);
+
+    thread_local! {
+        /// File columns shared by every graph on the page, keyed by
+        /// (file fingerprint, column index).
+        static COLUMNS: RefCell<ColumnStore> = RefCell::new(ColumnStore::new());
+        /// Finite-pair-filtered series X/Y arrays, keyed by
+        /// (file fingerprint, x column, y column).
+        static SERIES_XS: RefCell<SharedStore<PairKey, [f64]>> = RefCell::new(SharedStore::new());
+        static SERIES_YS: RefCell<SharedStore<PairKey, [f64]>> = RefCell::new(SharedStore::new());
+    }
+
+    /// The shared X/Y arrays for a file-column pair: another graph's copy if
+    /// one is still alive, else `build()`'s result, stored for later graphs.
+    fn shared_pair(
+        key: PairKey,
+        build: impl FnOnce() -> (Arc<[f64]>, Arc<[f64]>),
+    ) -> (Arc<[f64]>, Arc<[f64]>) {
+        let existing = SERIES_XS
+            .with(|s| s.borrow().get(&key))
+            .zip(SERIES_YS.with(|s| s.borrow().get(&key)));
+        if let Some(pair) = existing {
+            return pair;
+        }
+        let (xs, ys) = build();
+        let xs = SERIES_XS.with(|s| {
+            let mut s = s.borrow_mut();
+            s.prune();
+            s.intern(key, xs)
+        });
+        let ys = SERIES_YS.with(|s| {
+            let mut s = s.borrow_mut();
+            s.prune();
+            s.intern(key, ys)
+        });
+        (xs, ys)
+    }
+
     /// Compute the global Y min/max over a slice of finite values.
     /// Matches the rule used in `set_series`: if the range is degenerate
     /// (empty, non-finite, or < 1e-15), return `(center−1, center+1)`.
@@ -189,14 +704,246 @@ mod wasm_impl {
         }
     }
 
+    /// Return payload for `gpu_diagnostics`: what was requested, what the
+    /// renderer ended up on (None in CPU fallback), and why it fell back.
+    #[derive(Serialize)]
+    struct GpuReport {
+        requested_backend: &'static str,
+        fallback_reason: Option<String>,
+        adapter: Option<GpuDiagnostics>,
+    }
+
+    /// Return payload for `gpu_upload`: the upload limits, what the last
+    /// frame did under them, and the first device error.
+    #[derive(Serialize)]
+    struct GpuUploadStatus {
+        budget: GpuBudget,
+        upload: UploadReport,
+        error: Option<String>,
+    }
+
     /// Serialisable info about one series, returned by `series_info`.
     #[derive(Serialize)]
     struct SeriesInfo {
         name: String,
+        /// As drawn: after any `auto_contrast` adjustment.
         color: [f32; 4],
         visible: bool,
+        wrap_period: Option<f64>,
+        /// Defining operation of a derived series, as readable text.
+        formula: Option<String>,
+        reference: bool,
+        /// Value→label map in `LabelMap::to_spec` form, if set.
+        labels: Option<String>,
+        /// Gap-split threshold in X units, or null when off.
+        gap_threshold: Option<f64>,
+        /// Segment count (1 unless split at gaps).
+        segments: usize,
+        /// Uncertainty display: `"bars"`/`"band"`, or null when off.
+        error_style: Option<&'static str>,
+        /// Uncertainty column name, or null for a constant.
+        error_column: Option<String>,
+        /// Constant ± value (meaningful without `error_column`).
+        error_value: Option<f64>,
+        /// Last value extended to the view's right edge.
+        hold_last: bool,
+        /// Skipped by the nearest-point search.
+        no_hover: bool,
+        /// Draw-order band: `"back"`, `"normal"` or `"front"`.
+        layer: &'static str,
+        /// Area fill: `"baseline"`/`"between"`, or null when off.
+        fill: Option<&'static str>,
+        /// The other series' name for a `"between"` fill.
+        fill_to: Option<String>,
+        /// How a file series' rows were thinned at import, or null at full
+        /// resolution.
+        decimated: Option<String>,
+        /// `{ x_offset, y_offset, y_scale }` applied to the samples.
+        align: SeriesAlign,
+    }
+
+    /// Statistics of one series over a view window, returned by `window_stats`.
+    #[derive(Serialize)]
+    struct WindowStatsRow {
+        name: String,
+        count: usize,
+        min: f64,
+        max: f64,
+        peak_to_peak: f64,
+        mean: f64,
+        median: f64,
+        std_dev: f64,
+    }
+
+    /// Return payload for `window_stats`.
+    #[derive(Serialize)]
+    struct WindowStats {
+        /// Window bounds formatted like the X axis.
+        start_text: String,
+        end_text: String,
+        rows: Vec<WindowStatsRow>,
+    }
+
+    /// Statistics of one series over a dragged X region (`region_stats`).
+    #[derive(Serialize)]
+    struct RegionStatsRow {
+        /// Index into the series list.
+        series: usize,
+        name: String,
+        /// The series' unit, or `null` when it can't be inferred.
+        unit: Option<String>,
+        count: usize,
+        mean: f64,
+        min: f64,
+        max: f64,
+        std_dev: f64,
+        rms: f64,
+        peak_to_peak: f64,
+        integral: f64,
+        /// Unit of `integral` (`"A·s"`), or `null`.
+        integral_unit: Option<String>,
+    }
+
+    /// Return payload for `region_stats`.
+    #[derive(Serialize)]
+    struct RegionStatsReport {
+        /// Region bounds formatted like the X axis.
+        start_text: String,
+        end_text: String,
+        rows: Vec<RegionStatsRow>,
+    }
+
+    /// Return payload for `series_fit`.
+    #[derive(Serialize)]
+    struct FitReport {
+        /// `"linear"`, `"poly3"`, `"exp"` or `"power"`.
+        model: String,
+        /// Polynomial `c₀ … c_d`, or `[a, b]` for exponential / power law.
+        coefficients: Vec<f64>,
+        /// Origin of the polynomial / exponential variable `x − x₀`.
+        x0: f64,
+        /// `x0` formatted like the X axis.
+        x0_text: String,
+        r_squared: f64,
+        count: usize,
+        equation: String,
+    }
+
+    /// One shaded stretch of a time band (`time_band_intervals`).
+    #[derive(Serialize)]
+    struct BandInterval {
+        band: usize,
+        x0: f64,
+        x1: f64,
+    }
+
+    /// Statistics of one segment, returned by `series_segments`.
+    #[derive(Serialize)]
+    struct SegmentInfo {
+        x_start: f64,
+        x_end: f64,
+        /// Formatted like the X axis (timestamps on datetime axes).
+        start_text: String,
+        end_text: String,
+        count: usize,
+        min: f64,
+        max: f64,
+        mean: f64,
+        std_dev: f64,
+    }
+
+    /// Formatted mouse position, returned by `readout`.
+    #[derive(Serialize)]
+    struct Readout {
+        x: String,
+        y: String,
+    }
+
+    /// Axis titles drawn on the plot, returned by `axis_titles`. `*_custom`
+    /// is set when the title was edited rather than derived from the data.
+    #[derive(Serialize)]
+    struct AxisTitles {
+        x: String,
+        y: String,
+        x_custom: bool,
+        y_custom: bool,
+        /// Edited X display name ("" when derived) and unit, for the
+        /// settings fields and cursor readouts.
+        x_name: String,
+        x_unit: Option<String>,
+    }
+
+    /// Sample nearest the mouse, returned by `nearest_point`. `x_text` /
+    /// `y_text` are full-precision copies (timestamps on datetime axes).
+    #[derive(Serialize)]
+    struct NearestPoint {
+        series: usize,
+        index: usize,
+        name: String,
+        x: f64,
+        y: f64,
+        x_text: String,
+        y_text: String,
+        /// Y in view space (where the sample is drawn), for annotations.
+        view_y: f64,
+        dist_px: f64,
+    }
+
+    /// A sample measured from a reference sample (`point_delta`).
+    #[derive(Serialize)]
+    struct PointDelta {
+        dx: f64,
+        dy: f64,
+        /// `dy` as a percentage of the reference Y; `None` when that is zero.
+        percent: Option<f64>,
+        /// `dx` with the X unit (seconds on a datetime axis).
+        dx_text: String,
+        /// `dy` with the sample's series unit.
+        dy_text: String,
+    }
+
+    /// One series' entry in the legend value column (`legend_values`).
+    #[derive(Serialize)]
+    struct LegendValue {
+        value: f64,
+        /// Formatted with the series' unit, or its state name.
+        text: String,
     }
 
+    /// One visible series' line in the cursor readout table (`cursor_table`).
+    #[derive(Serialize)]
+    struct CursorRow {
+        /// Index into the series list.
+        series: usize,
+        name: String,
+        /// Value at each cursor; `None` off the series' X span.
+        values: Vec<Option<f64>>,
+        /// `values` formatted with the series' unit or state name ('' for none).
+        texts: Vec<String>,
+        /// Second cursor's value minus the first's.
+        delta: Option<f64>,
+        delta_text: String,
+        /// Area under the series from the first cursor to the second
+        /// (`math_ops::integral_between`), per hour on a datetime axis once
+        /// the cursors are an hour or more apart.
+        area: Option<f64>,
+        /// `area` with its unit (`"kW·h"`) when the series' unit is known.
+        area_text: String,
+    }
+
+    /// `value` formatted for a readout, with the series' unit. A thousandth
+    /// of the series' span: enough digits to see it move.
+    fn series_value_text(src: &SourceSeries, value: f64) -> String {
+        let text = format_readout(value, (src.y_max - src.y_min) / 1000.0);
+        match infer_unit(&src.name) {
+            u if u == "units" => text,
+            u => format!("{text} {u}"),
+        }
+    }
+
+    /// Most candidate samples searched per series by `nearest_point`.
+    const NEAREST_MAX_CANDIDATES: usize = 20_000;
+
     /// JSON spec for one series passed in from JS via `set_series`.
     #[derive(Deserialize)]
     struct SeriesSpec {
@@ -204,6 +951,21 @@ mod wasm_impl {
         y_col: usize,
         color: [f32; 4],
         draw_mode: String,
+        /// Generated X replacing `x_col` (start + row × interval).
+        #[serde(default)]
+        x_synth: Option<SyntheticX>,
+        /// Category column: one series per distinct value, each with only
+        /// that value's rows (see `split_by_category`).
+        #[serde(default)]
+        split_col: Option<usize>,
+        /// With `split_col`: a long-format pivot, naming each series after
+        /// its category value (the channel) instead of `y (by=value)`.
+        #[serde(default)]
+        pivot: bool,
+        /// ± uncertainty for the series, as `set_series_error` takes it (a
+        /// column applies to unsplit series only).
+        #[serde(default)]
+        error: Option<ErrorParams>,
     }
 
     /// Serialisable snapshot of the current view bounds, returned by `view_state`.
@@ -222,6 +984,15 @@ mod wasm_impl {
         numeric: bool,
     }
 
+    /// Table decimation state, returned by `table_decimation`.
+    #[derive(Serialize)]
+    struct TableDecimationInfo {
+        mode: &'static str,
+        n: usize,
+        /// True while drilled down to raw rows (see `table_drill`).
+        drilled: bool,
+    }
+
     /// Deserialised filter spec from JS for `table_set_column_filter`.
     #[derive(Deserialize)]
     struct FilterSpec {
@@ -241,6 +1012,34 @@ mod wasm_impl {
     struct AxisTicks {
         x: Vec<TickEntry>,
         y: Vec<TickEntry>,
+        /// Normalized mode: one axis per unit of the visible series, tick
+        /// values in view space but labelled in the unit.
+        units: Vec<UnitAxisTicks>,
+    }
+
+    #[derive(Serialize)]
+    struct UnitAxisTicks {
+        unit: String,
+        label: String,
+        side: AxisSide,
+        /// Colour of the unit's first visible series, to tell axes apart.
+        color: [f32; 4],
+        ticks: Vec<TickEntry>,
+    }
+
+    /// A per-unit Y axis, returned by `unit_axes`.
+    #[derive(Serialize)]
+    struct UnitAxisInfo {
+        unit: String,
+        label: String,
+        auto_range: bool,
+        min: f64,
+        max: f64,
+        side: AxisSide,
+        order: Option<u32>,
+        /// Range currently drawn (manual or fitted); `None` while no series
+        /// of the unit is visible.
+        range: Option<[f64; 2]>,
     }
 
     /// Format an f64 for CSV output: up to 15 significant-digit precision,
@@ -271,6 +1070,120 @@ mod wasm_impl {
         }
     }
 
+    /// Build the GPU renderer for `canvas` per `backend_pref` (WebGL2 for
+    /// `"gl"`, WebGPU otherwise; no GPU at all for `"software"`).
+    async fn build_renderer(
+        canvas: web_sys::HtmlCanvasElement,
+        backend_pref: BackendPreference,
+    ) -> Result<PlotRenderer, String> {
+        if backend_pref == BackendPreference::Software {
+            return Err("software rendering selected in preferences".to_string());
+        }
+        let (width, height) = (canvas.width(), canvas.height());
+        let backends = if backend_pref == BackendPreference::Gl {
+            wgpu::Backends::GL
+        } else {
+            wgpu::Backends::BROWSER_WEBGPU
+        };
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends,
+            ..Default::default()
+        });
+
+        // Build surface from the canvas element, then the core renderer.
+        let surface_target = wgpu::SurfaceTarget::Canvas(canvas);
+        match instance.create_surface(surface_target) {
+            Ok(surface) => {
+                PlotRenderer::new_for_surface(&instance, surface, width.max(1), height.max(1)).await
+            }
+            Err(e) => Err(format!("failed to create wgpu surface from canvas: {e}")),
+        }
+    }
+
+    /// A freshly built GPU renderer on its way to `OxidePlot::attach_gpu`
+    /// (device-lost recovery; see `OxidePlot::rebuild_gpu`).
+    #[wasm_bindgen]
+    pub struct GpuRenderer {
+        inner: PlotRenderer,
+    }
+
+    /// GPU spectrogram heatmap on its own canvas (the Spectrogram view). Takes
+    /// the frames `OxidePlot.series_spectrogram` returns and draws them as one
+    /// colormapped quad per time/frequency cell inside the given plot rect;
+    /// the frontend bakes axis labels over it on a 2D canvas.
+    ///
+    /// Call as: `const heat = await HeatmapPlot.create(canvas, 'auto')`
+    #[wasm_bindgen]
+    pub struct HeatmapPlot {
+        renderer: PlotRenderer,
+        width: u32,
+        height: u32,
+        bg_color: [f64; 4],
+    }
+
+    #[wasm_bindgen]
+    impl HeatmapPlot {
+        /// Errs when no GPU renderer can be built for `canvas`; the caller
+        /// keeps painting the heatmap on the CPU.
+        #[wasm_bindgen(js_name = "create")]
+        pub async fn create(canvas: web_sys::HtmlCanvasElement, backend: Option<String>) -> Result<HeatmapPlot, JsValue> {
+            install_panic_hook();
+            let (width, height) = (canvas.width(), canvas.height());
+            let backend_pref = BackendPreference::parse(backend.as_deref().unwrap_or("auto"));
+            let renderer = build_renderer(canvas, backend_pref).await.map_err(|e| JsValue::from_str(&e))?;
+            Ok(HeatmapPlot { renderer, width, height, bg_color: [0.10, 0.10, 0.12, 1.0] })
+        }
+
+        pub fn resize(&mut self, w: u32, h: u32) {
+            self.width = w;
+            self.height = h;
+            self.renderer.resize(w, h);
+        }
+
+        /// Clear colour behind the heatmap (0..1 components).
+        pub fn set_background(&mut self, r: f64, g: f64, b: f64, a: f64) {
+            self.bg_color = [r, g, b, a];
+        }
+
+        /// Draw `frames` (`frames[frame][bin]` magnitudes) with `colormap`
+        /// (`"magma"`, `"viridis"`, `"gray"`) into the `plot_w` x `plot_h`
+        /// rect at (`left`, `top`) canvas pixels: time left to right,
+        /// frequency bottom to top. Empty frames just clear the canvas.
+        pub fn draw(
+            &self,
+            frames: JsValue,
+            colormap: String,
+            left: f64,
+            top: f64,
+            plot_w: f64,
+            plot_h: f64,
+        ) -> Result<(), JsValue> {
+            let frames: Vec<Vec<f64>> =
+                serde_wasm_bindgen::from_value(frames).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            let colormap = Colormap::parse(&colormap)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown colormap \"{colormap}\"")))?;
+            let (w, h) = (self.width as f64, self.height as f64);
+            let (pw, ph) = (plot_w.max(1.0), plot_h.max(1.0));
+            // The heatmap spans [0, 1]^2; widen the view so that square lands
+            // on the plot rect (canvas Y runs down, data Y up).
+            let uniforms = PlotUniforms {
+                view_min: [(-left / pw) as f32, (-(h - top - ph) / ph) as f32],
+                view_max: [((w - left) / pw) as f32, ((top + ph) / ph) as f32],
+                resolution: [self.width as f32, self.height as f32],
+                line_width: 1.0,
+                point_radius: 1.0,
+                color: [0.0; 4],
+                pixel_snap: 0.0,
+                _padding: [0.0; 3],
+            };
+            let heat = HeatmapGpuData::from_frames(&frames, colormap, pw as usize, [0.0, 1.0], [0.0, 1.0]);
+            let calls: Vec<_> = self.renderer.build_heatmap_call(&heat, uniforms).into_iter().collect();
+            self.renderer
+                .render(&calls, self.bg_color)
+                .map_err(|e| JsValue::from_str(&format!("heatmap render error: {e:?}")))
+        }
+    }
+
     /// A GPU-accelerated 2D plot bound to an HTML canvas.
     ///
     /// Usage from JavaScript/TypeScript:
@@ -281,16 +1194,31 @@ mod wasm_impl {
     /// ```
     #[wasm_bindgen]
     pub struct OxidePlot {
-        renderer: PlotRenderer,
+        /// GPU renderer; `None` when wgpu init failed and the frontend is
+        /// drawing via the CPU fallback (`cpu_polylines`).
+        renderer: Option<PlotRenderer>,
+        /// Why the GPU renderer couldn't be created (None = GPU path live).
+        gpu_error: Option<String>,
+        /// Backend preference this plot was created with.
+        backend_pref: BackendPreference,
         /// Full source data per series (stored un-downsampled).
         sources: Vec<SourceSeries>,
         /// Viewport-downsampled GPU series, rebuilt by rebuild_visible().
         series: Vec<SeriesGpuData>,
+        /// Grid style (colour, snapping, layer); its segments stay empty and
+        /// each frame's come from the major ticks (`frame_grid`).
         grid: GridGpuData,
+        /// Draw the grid at all (`set_grid`).
+        show_grid: bool,
         width: u32,
         height: u32,
         /// Parsed data stored here for set_series / series building.
         loaded: Option<LoadedData>,
+        /// Fingerprint of the loaded file and its column count: columns below
+        /// that count are read from the file and shared across graphs by key;
+        /// derived columns appended after them are private to this graph.
+        file_key: u64,
+        file_cols: usize,
         /// Current view state (bounds + pan/zoom logic).
         view: PlotViewState,
         /// True when the X axis contains datetime (Unix timestamp) data.
@@ -301,9 +1229,41 @@ mod wasm_impl {
         point_radius: f32,
         /// Background clear color [r, g, b, a]. Default: dark (#1a1a1f).
         bg_color: [f64; 4],
-        /// When true, each series' Y is rescaled to [0, 1] (per-source global range)
-        /// before building GPU points, and the Y view is set to [-0.05, 1.05].
+        /// When true, each series' Y is rescaled to [0, 1] (its unit's range,
+        /// see `unit_ranges`) before building GPU points, and the Y view is
+        /// set to [-0.05, 1.05].
         normalized: bool,
+        /// Y axis per inferred unit of the sources (label, manual range,
+        /// side); kept in step with the sources by `refresh_unit_axes`.
+        y_axes: HashMap<String, AxisState>,
+        /// Raw Y range each unit maps onto [0, 1] in normalized mode: the
+        /// axis' manual range, else the extremes of its visible series.
+        unit_ranges: HashMap<String, (f64, f64)>,
+        /// Colour series by unit (hue family per unit, see `set_color_by_unit`),
+        /// re-applied as series come and go.
+        color_by_unit: bool,
+        /// Series drawn lightened or darkened as needed to stand out from
+        /// the background (`set_auto_contrast`); their own colours are kept.
+        auto_contrast: bool,
+        /// Unit conversions applied to this graph's series, in order;
+        /// repeated on file columns read in later (`convert_unit`).
+        unit_conversions: Vec<UnitConversion>,
+        /// Sample edits `undo_sample_edit` can take back, most recent last.
+        sample_undo: Vec<Vec<SampleUndo>>,
+        /// Spikes `preview_despike` found, drawn as markers until applied
+        /// or cleared.
+        despike_preview: Option<SourceSeries>,
+        /// GPU data for `despike_preview`, rebuilt with `series`.
+        preview_series: Vec<SeriesGpuData>,
+        /// How breaks in line series are marked (`set_gap_style`).
+        gap_style: GapStyle,
+        /// Bridges over the breaks of the drawn series, per `gap_style`.
+        gap_series: Vec<SeriesGpuData>,
+        /// Stacked-area display of the drawn series (exclusive with
+        /// `normalized`); see `set_stack_mode`.
+        stack_mode: StackMode,
+        /// How bar series share an X slot; see `set_bar_layout`.
+        bar_layout: BarLayout,
         /// Autoscale strategy for non-normalized Y bounds (MinMax | Robust).
         autoscale_mode: AutoscaleMode,
         /// Y-axis scale (Linear | Log). Log maps points to log10(y) and drops y<=0.
@@ -319,6 +1279,11 @@ mod wasm_impl {
         table_query: TableQuery,
         /// Filtered + sorted row indices for the current table_query.
         table_index: Vec<usize>,
+        /// Aggregation of `table_index` into table rows (off by default).
+        table_decimation: Decimation,
+        /// Positions in `table_index` the table is drilled down to (raw rows,
+        /// no decimation); cleared whenever the index is rebuilt.
+        table_drill: Option<std::ops::Range<usize>>,
         /// Original file-column indices to show in the Table view, in file
         /// order: the union of each plotted source's `x_name` and `name`,
         /// resolved to column indices and deduped. Empty = fallback to
@@ -326,6 +1291,47 @@ mod wasm_impl {
         /// plotted names matched a file column). Recomputed by
         /// `recompute_plotted_cols` whenever `sources` changes.
         plotted_cols: Vec<usize>,
+        /// Frame-budget guard for pan/zoom: scales the point target down when
+        /// a frame's prep runs over budget.
+        frame_budget: FrameBudget,
+        /// Source indices whose current `series` entry was built below full
+        /// quality; drained a few per frame by `refine_step`.
+        refine_queue: Vec<usize>,
+        /// Value→label map attached to the Y axis; overrides series maps.
+        y_labels: Option<LabelMap>,
+        /// User-edited axis titles; `None` shows the derived title.
+        x_title: Option<AxisTitle>,
+        y_title: Option<AxisTitle>,
+        /// Frozen copies of earlier traces (`freeze_traces`), drawn greyed
+        /// beneath the live series. They survive loading new data, so a
+        /// reloaded run can be compared against the frozen one.
+        ghosts: Vec<SourceSeries>,
+        /// GPU data for `ghosts`, rebuilt with `series`.
+        ghost_series: Vec<SeriesGpuData>,
+        /// Error bars / bands of drawn series with uncertainty, beneath the
+        /// lines; rebuilt with `series`.
+        error_series: Vec<SeriesGpuData>,
+        /// Area fills of drawn series that have one (`set_series_fill`),
+        /// drawn under the lines.
+        fill_series: Vec<SeriesGpuData>,
+        /// Stacked-area layer fills, beneath the lines (stacked mode only).
+        stack_series: Vec<SeriesGpuData>,
+        /// Visible series past this many (in list order) are not drawn.
+        max_drawn_series: usize,
+        /// Live line stream feeding `sources` (`stream_start`).
+        stream: Option<LineStream>,
+        /// Auto-scroll to the newest streamed samples; pan/zoom turns it off.
+        stream_follow: bool,
+        /// Huge file arriving in pieces (`chunked_begin`), with a running
+        /// hash of its bytes for the column-store key and its row thinning.
+        chunked: Option<(ChunkedCsv, DefaultHasher, ImportDecimation)>,
+        /// How the loaded file's rows were thinned at import, shown on the
+        /// series read from it (`None` at full resolution).
+        import_note: Option<String>,
+        /// Scrolled out of view (`set_culled`): downsampling and drawing
+        /// are skipped; `cull_stale` records that a rebuild was skipped.
+        culled: bool,
+        cull_stale: bool,
     }
 
     #[wasm_bindgen]
@@ -336,59 +1342,103 @@ mod wasm_impl {
         /// then initialises the core PlotRenderer.  No data is plotted until
         /// `set_series` is called after `load_file_bytes`.
         ///
-        /// Call as: `const plot = await OxidePlot.create(canvas)`
+        /// If the surface, adapter, or device can't be created the plot is
+        /// still returned — data loading, pan/zoom and every analysis view keep
+        /// working — but `render()` becomes a no-op and the frontend should
+        /// draw `cpu_polylines()` itself (see `gpu_fallback_reason`).
+        ///
+        /// `backend` is the persisted preference (`"auto"`, `"gl"` or
+        /// `"software"`; see `BackendPreference::parse`): `"gl"` selects
+        /// WebGL2, `"software"` skips the GPU entirely (CPU painter), and
+        /// anything else uses WebGPU — the webview picks the native API
+        /// (DX12/Vulkan/Metal) underneath.
+        ///
+        /// Call as: `const plot = await OxidePlot.create(canvas, 'auto')`
         #[wasm_bindgen(js_name = "create")]
-        pub async fn create(canvas: web_sys::HtmlCanvasElement) -> OxidePlot {
-            console_error_panic_hook::set_once();
+        pub async fn create(canvas: web_sys::HtmlCanvasElement, backend: Option<String>) -> OxidePlot {
+            install_panic_hook();
 
             let width = canvas.width();
             let height = canvas.height();
+            let backend_pref = BackendPreference::parse(backend.as_deref().unwrap_or("auto"));
+
+            let renderer = build_renderer(canvas, backend_pref).await;
+            let (renderer, gpu_error) = match renderer {
+                Ok(r) => (Some(r), None),
+                Err(e) => {
+                    web_sys::console::warn_1(
+                        &format!("OxidePlot: GPU unavailable, using CPU fallback — {e}").into(),
+                    );
+                    (None, Some(e))
+                }
+            };
 
-            // Create wgpu instance with WebGPU backend.
-            let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-                backends: wgpu::Backends::BROWSER_WEBGPU,
-                ..Default::default()
-            });
-
-            // Build surface from the canvas element.
-            let surface_target = wgpu::SurfaceTarget::Canvas(canvas);
-            let surface = instance
-                .create_surface(surface_target)
-                .expect("failed to create wgpu surface from canvas");
-
-            // Initialise the core renderer.
-            let renderer =
-                PlotRenderer::new_for_surface(&instance, surface, width.max(1), height.max(1))
-                    .await;
-
-            // Empty grid (no segments).
+            // Grid style only; segments are built per frame.
             let grid = GridGpuData {
                 segments: vec![],
-                color: [0.3, 0.3, 0.3, 1.0],
+                color: GRID_COLOR_DARK,
                 line_width: 1.0,
+                pixel_snap: true,
+                layer: GridLayer::Below,
             };
 
             OxidePlot {
                 renderer,
+                gpu_error,
+                backend_pref,
                 sources: vec![],
                 series: vec![],
                 grid,
+                show_grid: true,
                 width,
                 height,
                 loaded: None,
+                file_key: 0,
+                file_cols: 0,
                 view: PlotViewState::default(),
                 x_is_time: false,
                 line_width: 2.0,
                 point_radius: 3.0,
                 bg_color: [0.10_f64, 0.10, 0.12, 1.0],
                 normalized: false,
+                y_axes: HashMap::new(),
+                unit_ranges: HashMap::new(),
+                color_by_unit: false,
+                auto_contrast: false,
+                unit_conversions: vec![],
+                sample_undo: vec![],
+                despike_preview: None,
+                preview_series: Vec::new(),
+                gap_style: GapStyle::Off,
+                gap_series: Vec::new(),
+                stack_mode: StackMode::Off,
+                bar_layout: BarLayout::Grouped,
                 autoscale_mode: AutoscaleMode::MinMax,
                 y_scale: YScale::Linear,
                 downsample_mode: DownsampleMode::MinMax,
                 x_origin: 0.0,
                 table_query: TableQuery::default(),
                 table_index: vec![],
+                table_decimation: Decimation::Off,
+                table_drill: None,
                 plotted_cols: vec![],
+                frame_budget: FrameBudget::default(),
+                refine_queue: vec![],
+                y_labels: None,
+                x_title: None,
+                y_title: None,
+                ghosts: Vec::new(),
+                ghost_series: Vec::new(),
+                error_series: Vec::new(),
+                fill_series: Vec::new(),
+                stack_series: Vec::new(),
+                max_drawn_series: MAX_DRAWN_SERIES,
+                stream: None,
+                stream_follow: false,
+                chunked: None,
+                import_note: None,
+                culled: false,
+                cull_stale: false,
             }
         }
 
@@ -402,46 +1452,161 @@ mod wasm_impl {
         ///
         /// The parsed data is stored internally in `self.loaded` so that
         /// `set_series` can build GPU series from the chosen column indices.
+        /// Wide files load lazily (header + sample only); each column's full
+        /// data is read from the retained bytes the first time it's used.
         #[wasm_bindgen]
         pub fn load_file_bytes(
             &mut self,
             bytes: Vec<u8>,
             filename: String,
+            options: JsValue,
         ) -> Result<JsValue, JsValue> {
-            let data = load_from_bytes(&bytes, &filename)
+            let csv = csv_options(options)?;
+            // Same bytes read with other separators are a different table.
+            let file_key = if csv == CsvOptions::default() {
+                fingerprint(&filename, &bytes)
+            } else {
+                fingerprint(&format!("{filename}\n{csv:?}"), &bytes)
+            };
+            let csv_meta = is_delimited_text(&filename).then(|| CsvMeta::resolved(&csv, &bytes));
+            let data = load_from_bytes_lazy(bytes, &filename, csv)
                 .map_err(|e| JsValue::from_str(&e))?;
+            self.install_loaded(file_key, data, csv_meta, None)
+        }
 
-            let meta = FileMeta::from_loaded(&data);
+        /// Start a chunked import of a delimited-text file too big to pass
+        /// whole: `chunked_push` the file in order, then `chunked_finish`.
+        /// `options` are as for `load_file_bytes`; `decimation`
+        /// (`{ mode, n?, width?, min?, max? }`, null for every row) thins
+        /// the rows as they're parsed.
+        #[wasm_bindgen]
+        pub fn chunked_begin(&mut self, options: JsValue, decimation: JsValue) -> Result<(), JsValue> {
+            let csv = csv_options(options)?;
+            let decimation = decimation_options(decimation)?;
+            let mut hash = DefaultHasher::new();
+            // A thinned copy is a different table from the full file.
+            if decimation != ImportDecimation::Off {
+                format!("{decimation:?}").hash(&mut hash);
+            }
+            self.chunked = Some((ChunkedCsv::new(csv).decimated(decimation), hash, decimation));
+            Ok(())
+        }
 
-            // Store parsed data for series construction.
-            self.loaded = Some(data);
-            // Clear any previous series until the user picks new columns.
-            self.sources.clear();
-            self.series.clear();
-            // No series plotted yet — Table view falls back to all columns.
-            self.plotted_cols.clear();
+        /// Parse the next piece of the file (any split point). Returns false
+        /// once an X-window import has read past the window in ascending X:
+        /// the rest of the file can be skipped (`ChunkedCsv::past_window`).
+        #[wasm_bindgen]
+        pub fn chunked_push(&mut self, chunk: &[u8]) -> Result<bool, JsValue> {
+            let (parser, hash, _) = self.chunked.as_mut().ok_or_else(|| JsValue::from_str("No chunked import in progress"))?;
+            hash.write(chunk);
+            if let Err(e) = parser.push(chunk) {
+                self.chunked = None;
+                return Err(JsValue::from_str(&e));
+            }
+            Ok(!parser.past_window())
+        }
 
-            // Initialise numeric_cols: a column is numeric if it parses as f64
-            // (≥ 50% success rate) OR if it parses as timestamps.
-            // This matches the rule used for ColumnMeta.kind (numeric/datetime → numeric; text → false).
-            {
-                let d = self.loaded.as_ref().unwrap();
-                let num_cols = d.column_data.len();
-                let mut numeric_cols = vec![false; num_cols];
-                for (i, col) in d.column_data.iter().enumerate() {
-                    let is_num = column_to_f64(col).1 >= 0.5 || column_to_timestamps(col).is_some();
-                    numeric_cols[i] = is_num;
+        /// Parse the rest and load the columns exactly like `load_file_bytes`;
+        /// returns the same metadata.
+        #[wasm_bindgen]
+        pub fn chunked_finish(&mut self, filename: String) -> Result<JsValue, JsValue> {
+            let (parser, mut hash, decimation) =
+                self.chunked.take().ok_or_else(|| JsValue::from_str("No chunked import in progress"))?;
+            filename.hash(&mut hash);
+            let data = parser.finish().map_err(|e| JsValue::from_str(&e))?;
+            let time_x = data.column_data.iter().find(|c| c.kind() != "text").is_some_and(|c| c.kind() == "datetime");
+            self.install_loaded(hash.finish(), data, None, decimation.label(time_x))
+        }
+
+        /// Drop a chunked import (the graph keeps whatever it showed).
+        #[wasm_bindgen]
+        pub fn chunked_cancel(&mut self) {
+            self.chunked = None;
+        }
+
+        /// Start plotting a live line stream (serial port, socket): clears the
+        /// graph, then `stream_push` appends text as it arrives.
+        /// `params` is `{ delimiter?, x_column?, max_points? }`.
+        #[wasm_bindgen]
+        pub fn stream_start(&mut self, params: JsValue) -> Result<(), JsValue> {
+            let p: StreamParams = if params.is_null() || params.is_undefined() {
+                StreamParams::default()
+            } else {
+                serde_wasm_bindgen::from_value(params).map_err(|e| JsValue::from_str(&e.to_string()))?
+            };
+            let delimiter = match p.delimiter.as_deref() {
+                Some(d) => Delimiter::parse(d).ok_or_else(|| JsValue::from_str(&format!("Unknown delimiter {d:?}")))?,
+                None => Delimiter::Auto,
+            };
+            self.stream = Some(LineStream::new(StreamConfig {
+                delimiter,
+                x_column: p.x_column,
+                max_points: p.max_points.unwrap_or(DEFAULT_MAX_POINTS),
+            }));
+            self.stream_follow = true;
+            self.sources.clear();
+            self.sample_undo.clear();
+            self.despike_preview = None;
+            self.loaded = None;
+            self.import_note = None;
+            self.file_key = 0;
+            self.file_cols = 0;
+            self.table_query = TableQuery::default();
+            self.table_index.clear();
+            self.table_drill = None;
+            self.recompute_plotted_cols();
+            self.x_is_time = false;
+            self.rebuild_visible();
+            self.render();
+            Ok(())
+        }
+
+        /// Append a chunk of streamed text (split anywhere: a partial line
+        /// waits for its newline) and re-render, auto-scrolling while
+        /// following. Returns the stream's channel count, so callers can
+        /// refresh series lists when a new channel appears.
+        #[wasm_bindgen]
+        pub fn stream_push(&mut self, text: &str) -> usize {
+            let Some(stream) = &mut self.stream else {
+                return 0;
+            };
+            if stream.push(text) > 0 {
+                self.sync_stream_sources();
+                if self.stream_follow {
+                    self.follow_stream();
+                } else {
+                    self.rebuild_visible();
                 }
-                // Reset table_query to defaults but preserve the new numeric_cols.
-                self.table_query = TableQuery {
-                    numeric_cols,
-                    ..TableQuery::default()
-                };
-                self.rebuild_table_index();
+                self.render();
             }
+            self.stream.as_ref().map_or(0, |s| s.channels().len())
+        }
 
-            serde_wasm_bindgen::to_value(&meta)
-                .map_err(|e| JsValue::from_str(&e.to_string()))
+        /// Stop the live stream; what was received stays plotted.
+        #[wasm_bindgen]
+        pub fn stream_stop(&mut self) {
+            self.stream = None;
+            self.stream_follow = false;
+        }
+
+        #[wasm_bindgen]
+        pub fn is_streaming(&self) -> bool {
+            self.stream.is_some()
+        }
+
+        /// Resume (or stop) auto-scrolling to the newest streamed samples.
+        #[wasm_bindgen]
+        pub fn set_stream_follow(&mut self, on: bool) {
+            self.stream_follow = on && self.stream.is_some();
+            if self.stream_follow {
+                self.follow_stream();
+                self.render();
+            }
+        }
+
+        #[wasm_bindgen]
+        pub fn stream_follow(&self) -> bool {
+            self.stream_follow
         }
 
         /// Build GPU series from column specs and render.
@@ -450,21 +1615,28 @@ mod wasm_impl {
         /// ```json
         /// [{ "x_col": 0, "y_col": 1, "color": [r, g, b, a], "draw_mode": "lines" }]
         /// ```
-        /// `draw_mode` is one of `"lines"`, `"step"`, or `"points"`.
+        /// `draw_mode` is one of `"lines"`, `"step"`, `"step-pre"`, `"points"`,
+        /// `"smooth"` or `"bar"`.
         ///
         /// After building all series, `auto_fit` is called (which renders).
         #[wasm_bindgen]
         pub fn set_series(&mut self, specs_json: String) -> Result<(), JsValue> {
-            let data = self
-                .loaded
-                .as_ref()
-                .ok_or_else(|| JsValue::from_str("No file loaded — call load_file_bytes first"))?;
+            if self.loaded.is_none() {
+                return Err(JsValue::from_str("No file loaded — call load_file_bytes first"));
+            }
 
             let specs: Vec<SeriesSpec> = serde_json::from_str(&specs_json)
                 .map_err(|e| JsValue::from_str(&format!("Invalid series spec JSON: {e}")))?;
 
+            // Read in the chosen columns of a lazily-loaded file (one pass).
+            let wanted: Vec<usize> = specs.iter().flat_map(|s| [s.x_col, s.y_col].into_iter().chain(s.split_col)).collect();
+            self.materialize_cols(&wanted)?;
+            let data = self.loaded.as_ref().unwrap();
+
             let num_cols = data.columns.len();
             let mut new_sources: Vec<SourceSeries> = Vec::with_capacity(specs.len());
+            // Uncertainty to attach once the sources are in place, by index.
+            let mut errors: Vec<(usize, &ErrorParams)> = Vec::new();
             let mut x_is_time_any = false;
 
             for spec in &specs {
@@ -475,37 +1647,125 @@ mod wasm_impl {
                     )));
                 }
 
-                let x_col_data = &data.column_data[spec.x_col];
-                let y_col_data = &data.column_data[spec.y_col];
-
-                // Convert X: try datetime first, fall back to f64.
-                let x_vals: Vec<f64> = if let Some((ts, _)) = column_to_timestamps(x_col_data) {
+                // Columns were typed at import: datetime X is already epoch
+                // seconds, numeric columns already f64 — no re-parsing here.
+                let x_col = &data.column_data[spec.x_col];
+                let synth_x = match &spec.x_synth {
+                    Some(sx) => Some(sx.values(data.row_count).map_err(|e| JsValue::from_str(&e))?),
+                    None => None,
+                };
+                if synth_x.is_none() && x_col.timestamps().is_some() {
                     x_is_time_any = true;
-                    ts
-                } else {
-                    let (vals, _) = column_to_f64(x_col_data);
-                    vals
+                }
+                let y_col = &data.column_data[spec.y_col];
+                let draw_mode = match spec.draw_mode.as_str() {
+                    "step" => DrawMode::Step,
+                    "step-pre" => DrawMode::StepPre,
+                    "points" => DrawMode::Points,
+                    "smooth" => DrawMode::Smooth,
+                    "bar" => DrawMode::Bar,
+                    _ => DrawMode::Lines,
+                };
+                let x_name = match &spec.x_synth {
+                    Some(sx) => sx.name().to_string(),
+                    None => data.columns[spec.x_col].clone(),
                 };
 
-                // Convert Y: always f64.
-                let (y_vals, _) = column_to_f64(y_col_data);
+                // Split by category (or pivot a long-format log): one derived
+                // series per value, so they are saved with their samples
+                // rather than re-read by name.
+                if let Some(split_col) = spec.split_col {
+                    let by = data
+                        .columns
+                        .get(split_col)
+                        .ok_or_else(|| JsValue::from_str(&format!("split column {split_col} out of range")))?;
+                    let x_vals: Cow<[f64]> = match &synth_x {
+                        Some(sx) => Cow::Borrowed(sx),
+                        None => x_col.to_f64(),
+                    };
+                    let column = &data.columns[spec.y_col];
+                    let y_vals = y_col.to_f64();
+                    let converted = if spec.pivot { None } else { apply_rules(&self.unit_conversions, column, &y_vals) };
+                    let (y_name, y_vals) = match converted {
+                        Some((name, converted)) => (name, Cow::Owned(converted)),
+                        None => (column.clone(), y_vals),
+                    };
+                    let parts = split_by_category(&data.column_data[split_col], &x_vals, &y_vals)
+                        .map_err(|e| JsValue::from_str(&e))?;
+                    for (k, part) in parts.into_iter().enumerate() {
+                        if let Some(e) = &spec.error {
+                            errors.push((new_sources.len(), e));
+                        }
+                        let op = if spec.pivot { "pivot" } else { "split" };
+                        let derivation = Derivation::new(op, Some(column.as_str()))
+                            .param("by", by)
+                            .param("value", &part.value);
+                        // A pivoted channel carries its own unit in its name,
+                        // so conversions are matched per channel.
+                        let (name, ys) = if spec.pivot {
+                            let name = pivot_name(&part.value);
+                            match apply_rules(&self.unit_conversions, &name, &part.ys) {
+                                Some(converted) => converted,
+                                None => (name, part.ys),
+                            }
+                        } else {
+                            (split_name(&y_name, by, &part.value), part.ys)
+                        };
+                        let (y_min, y_max) = compute_y_bounds(&ys);
+                        new_sources.push(SourceSeries {
+                            name,
+                            x_name: x_name.clone(),
+                            visible: true,
+                            xs: part.xs.into(),
+                            ys: ys.into(),
+                            color: if k == 0 { spec.color } else { palette_color(new_sources.len()) },
+                            draw_mode,
+                            y_min,
+                            y_max,
+                            wrap_period: None,
+                            derivation: Some(derivation),
+                            reference: false,
+                            labels: None,
+                            gaps: None,
+                            error: None,
+                            hold_last: false,
+                            no_hover: false,
+                            layer: SeriesLayer::Normal,
+                            fill: None,
+                            locked_color: None,
+                            packed: None,
+                            align: SeriesAlign::default(),
+                            column: None,
+                        });
+                    }
+                    continue;
+                }
 
-                // Zip and filter: keep only finite pairs.
-                let (xs, ys): (Vec<f64>, Vec<f64>) = x_vals
-                    .iter()
-                    .zip(y_vals.iter())
-                    .filter(|(&x, &y)| x.is_finite() && y.is_finite())
-                    .map(|(&x, &y)| (x, y))
-                    .unzip();
+                // Missing Y values stay in as line breaks (see gapped_pairs);
+                // gap-free columns are plotted from their own arrays, without
+                // a copy.
+                let build = || -> (Arc<[f64]>, Arc<[f64]>) {
+                    let Some(sx) = &synth_x else {
+                        return series_arrays(x_col, y_col);
+                    };
+                    let (xs, ys) = gapped_pairs(sx, &y_col.to_f64());
+                    (xs.into(), ys.into())
+                };
+                let (xs, ys) = if synth_x.is_none() && spec.x_col < self.file_cols && spec.y_col < self.file_cols {
+                    shared_pair((self.file_key, spec.x_col, spec.y_col), build)
+                } else {
+                    build()
+                };
 
                 if xs.is_empty() {
                     continue;
                 }
 
-                let draw_mode = match spec.draw_mode.as_str() {
-                    "step" => DrawMode::Step,
-                    "points" => DrawMode::Points,
-                    _ => DrawMode::Lines,
+                // Repeat this graph's unit conversions on a matching column.
+                let column = data.columns[spec.y_col].clone();
+                let (name, ys, column) = match apply_rules(&self.unit_conversions, &column, &ys) {
+                    Some((name, converted)) => (name, converted.into(), Some(column)),
+                    None => (column, ys, None),
                 };
 
                 // Compute global Y min/max from the FULL ys array for normalization.
@@ -513,10 +1773,12 @@ mod wasm_impl {
                 // set a safe range so normalization maps to ~0.5 without div-by-zero.
                 let (src_y_min, src_y_max) = compute_y_bounds(&ys);
 
+                if let Some(e) = &spec.error {
+                    errors.push((new_sources.len(), e));
+                }
+
                 // Store FULL source data — no downsampling here.
                 // rebuild_visible() will LTTB-downsample to the visible range.
-                let name = data.columns[spec.y_col].clone();
-                let x_name = data.columns[spec.x_col].clone();
                 new_sources.push(SourceSeries {
                     name,
                     x_name,
@@ -527,11 +1789,30 @@ mod wasm_impl {
                     draw_mode,
                     y_min: src_y_min,
                     y_max: src_y_max,
+                    wrap_period: None,
+                    derivation: None,
+                    reference: false,
+                    labels: None,
+                    gaps: None,
+                    error: None,
+                    hold_last: false,
+                    no_hover: false,
+                    layer: SeriesLayer::Normal,
+                    fill: None,
+                    locked_color: None,
+                    packed: None,
+                    align: SeriesAlign::default(),
+                    column,
                 });
             }
 
             self.sources = new_sources;
+            self.sample_undo.clear();
+            self.despike_preview = None;
             self.x_is_time = x_is_time_any;
+            for (index, params) in errors {
+                self.sources[index].error = Some(self.uncertainty(index, params)?);
+            }
             // Table view should now show only the columns backing these series.
             self.recompute_plotted_cols();
             // auto_fit computes bounds from source data, calls rebuild_visible + render.
@@ -581,64 +1862,8 @@ mod wasm_impl {
             self.view.x_min = x_min - x_pad;
             self.view.x_max = x_max + x_pad;
 
-            if !self.normalized {
-                // Normal mode: fit Y from raw data values, honoring the Y-scale.
-                // In Log mode each y>0 contributes log10(y) and non-positive y is
-                // dropped; in Linear mode every finite y contributes as-is.
-                let log = self.y_scale == YScale::Log;
-                let robust = self.autoscale_mode == AutoscaleMode::Robust;
-
-                let mut v_min = f64::INFINITY;
-                let mut v_max = f64::NEG_INFINITY;
-                // Only the Robust path needs the full stream for percentiles.
-                let mut vals: Vec<f64> = Vec::new();
-
-                for s in &self.sources {
-                    if !s.visible {
-                        continue; // fit to what's shown, not hidden series
-                    }
-                    for &y in s.ys.iter() {
-                        if !y.is_finite() {
-                            continue;
-                        }
-                        let v = if log {
-                            if y > 0.0 { y.log10() } else { continue }
-                        } else {
-                            y
-                        };
-                        v_min = v_min.min(v);
-                        v_max = v_max.max(v);
-                        if robust {
-                            vals.push(v);
-                        }
-                    }
-                }
-
-                let (y_min, y_max) = if robust {
-                    // Clip to the 1st/99th percentiles so outliers don't dominate.
-                    // Fall back to the min/max of the stream, then to (0, 1) if the
-                    // percentile range is degenerate (non-finite or y_max <= y_min).
-                    vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
-                    let lo = percentile(&vals, 0.01);
-                    let hi = percentile(&vals, 0.99);
-                    if lo.is_finite() && hi.is_finite() && hi > lo {
-                        (lo, hi)
-                    } else if v_min.is_finite() && v_max.is_finite() && v_max > v_min {
-                        (v_min, v_max)
-                    } else {
-                        (0.0, 1.0)
-                    }
-                } else {
-                    // MinMax: raw extremes of the stream (existing behavior for linear).
-                    if !v_min.is_finite() || !v_max.is_finite() {
-                        return;
-                    }
-                    (v_min, v_max)
-                };
-
-                let y_pad = ((y_max - y_min) * 0.05).max(1e-9);
-                self.view.y_min = y_min - y_pad;
-                self.view.y_max = y_max + y_pad;
+            if !self.normalized && !self.fit_y(f64::NEG_INFINITY, f64::INFINITY) {
+                return;
             }
 
             self.view.auto_fit = false;
@@ -651,9 +1876,10 @@ mod wasm_impl {
         /// Pan the view by a pixel drag delta (backing-store pixels) and re-render.
         #[wasm_bindgen]
         pub fn pan(&mut self, dx_px: f32, dy_px: f32) {
+            self.stream_follow = false;
             let rect = self.canvas_rect();
             self.view.pan(dx_px, dy_px, rect);
-            self.rebuild_visible();
+            self.rebuild_interactive();
             self.render();
         }
 
@@ -663,11 +1889,77 @@ mod wasm_impl {
         /// Pass `-event.deltaY` from the browser `wheel` event.
         #[wasm_bindgen]
         pub fn zoom(&mut self, scroll_y: f32, anchor_x: f32, anchor_y: f32) {
+            self.stream_follow = false;
             let anchor = Pos2 { x: anchor_x, y: anchor_y };
             let rect = self.canvas_rect();
             self.view.zoom(scroll_y, anchor, rect);
+            self.rebuild_interactive();
+            self.render();
+        }
+
+        /// Box zoom: show the data under the screen rectangle with corners
+        /// (`x0`, `y0`) and (`x1`, `y1`) in backing-store pixels (either
+        /// order). Returns false, leaving the view as it was, for a
+        /// rectangle with no width or height.
+        #[wasm_bindgen]
+        pub fn zoom_to_rect(&mut self, x0: f32, y0: f32, x1: f32, y1: f32) -> bool {
+            let rect = self.canvas_rect();
+            if !self.view.zoom_to_rect(Pos2 { x: x0, y: y0 }, Pos2 { x: x1, y: y1 }, rect) {
+                return false;
+            }
+            self.stream_follow = false;
             self.rebuild_visible();
             self.render();
+            true
+        }
+
+        /// Progressive refinement after interaction stops. Restores the frame
+        /// budget to full quality, rebuilds up to `max_series` series that
+        /// pan/zoom built degraded, and re-renders. Returns true while more
+        /// remain — call once per animation frame until it returns false.
+        #[wasm_bindgen]
+        pub fn refine_step(&mut self, max_series: usize) -> bool {
+            self.frame_budget.restore();
+            if self.culled && !self.refine_queue.is_empty() {
+                // Rebuilt in full when the graph scrolls back into view.
+                self.refine_queue.clear();
+                self.cull_stale = true;
+            }
+            if self.refine_queue.is_empty() {
+                return false;
+            }
+            let target = self.full_target();
+            let n = max_series.max(1).min(self.refine_queue.len());
+            let batch: Vec<usize> = self.refine_queue.drain(..n).collect();
+            for i in batch {
+                if let (Some(src), true) = (self.sources.get(i), i < self.series.len()) {
+                    self.series[i] = self.build_visible_series(src, target);
+                }
+            }
+            if self.refine_queue.is_empty() && !self.ghosts.is_empty() {
+                self.ghost_series = self.ghosts.iter().map(|g| self.build_visible_series(g, target)).collect();
+            }
+            if self.refine_queue.is_empty() && !self.error_series.is_empty() {
+                self.error_series = self.build_error_layer(&self.drawn_mask(), target);
+            }
+            if self.refine_queue.is_empty() && !self.fill_series.is_empty() {
+                self.fill_series = self.build_fill_layer(&self.drawn_mask(), target);
+            }
+            if self.refine_queue.is_empty() && !self.gap_series.is_empty() {
+                self.gap_series = self.build_gap_layer(&self.drawn_mask());
+            }
+            // The loop above rebuilt stacked series as plain lines and bars
+            // as points.
+            self.apply_stack(&self.drawn_mask(), target);
+            self.apply_bars(&self.drawn_mask(), target);
+            self.render();
+            !self.refine_queue.is_empty()
+        }
+
+        /// Current interactive quality factor in (0, 1] (1 = full point budget).
+        #[wasm_bindgen]
+        pub fn render_quality(&self) -> f64 {
+            self.frame_budget.quality()
         }
 
         /// True when the plotted X axis is datetime (Unix-timestamp) data. The
@@ -691,7 +1983,8 @@ mod wasm_impl {
         }
 
         /// Return tick data for both axes as a JS object:
-        /// `{ x: [{value, label, major}], y: [{value, label, major}] }`
+        /// `{ x: [{value, label, major}], y: [{value, label, major}], units }`
+        /// (`units`: per-unit axes in normalized mode, see `unit_axes`).
         /// X labels use datetime format when x_is_time is true.
         #[wasm_bindgen]
         pub fn axis_ticks(&self) -> JsValue {
@@ -726,29 +2019,47 @@ mod wasm_impl {
                 .collect();
 
             let y_log = self.y_scale == YScale::Log;
-            let y_ticks: Vec<TickEntry> = y_lines
-                .into_iter()
-                .map(|(val, major)| {
-                    // In Log mode the view (and thus `val`) is in log10-space, so
-                    // keep `value` as-is for correct positioning but label with the
-                    // de-logged magnitude 10^val.
-                    let label = if y_log {
-                        format_tick_value(10f64.powf(val))
-                    } else {
-                        format_tick_value(val)
-                    };
-                    TickEntry { value: val, label, major }
-                })
-                .collect();
+            // Enumerated channel: one tick per state in view, labelled by name.
+            let y_ticks: Vec<TickEntry> = if let Some(map) = self.active_y_labels() {
+                map.entries()
+                    .iter()
+                    .filter(|(v, _)| *v >= self.view.y_min && *v <= self.view.y_max)
+                    .map(|(v, l)| TickEntry { value: *v, label: l.clone(), major: true })
+                    .collect()
+            } else {
+                y_lines
+                    .into_iter()
+                    .map(|(val, major)| {
+                        // In Log mode the view (and thus `val`) is in log10-space, so
+                        // keep `value` as-is for correct positioning but label with the
+                        // de-logged magnitude 10^val.
+                        let label = if y_log {
+                            format_tick_value(10f64.powf(val))
+                        } else {
+                            format_tick_value(val)
+                        };
+                        TickEntry { value: val, label, major }
+                    })
+                    .collect()
+            };
 
-            let ticks = AxisTicks { x: x_ticks, y: y_ticks };
+            let units = if self.normalized { self.unit_axis_ticks() } else { Vec::new() };
+            let ticks = AxisTicks { x: x_ticks, y: y_ticks, units };
             serde_wasm_bindgen::to_value(&ticks).unwrap_or(JsValue::NULL)
         }
 
         /// Render one frame: build draw calls from stored series, then present.
+        /// No-op in CPU fallback mode — the frontend draws `cpu_polylines()`.
         pub fn render(&self) {
+            let Some(renderer) = &self.renderer else {
+                return;
+            };
+            if self.culled {
+                return;
+            }
+
             // If no series yet, draw a blank dark frame.
-            if self.series.is_empty() {
+            if self.series.is_empty() && self.ghost_series.is_empty() {
                 // Attempt a blank render — just clear to background.
                 let uniforms = PlotUniforms {
                     view_min: [0.0, 0.0],
@@ -757,35 +2068,121 @@ mod wasm_impl {
                     line_width: 2.0,
                     point_radius: 4.0,
                     color: [0.0, 0.0, 0.0, 0.0],
-                    _padding: [0.0; 4],
+                    pixel_snap: 0.0,
+                    _padding: [0.0; 3],
                 };
-                let calls = self.renderer.build_draw_calls(&[], &self.grid, uniforms);
-                if let Err(e) = self.renderer.render(&calls, self.bg_color) {
+                let calls = renderer.build_draw_calls(&[], &self.grid, uniforms);
+                if let Err(e) = renderer.render(&calls, self.bg_color) {
                     web_sys::console::error_1(&format!("OxidePlot render error: {e:?}").into());
                 }
                 return;
             }
 
-            // Shift the X view bounds by the same origin used for series vertices
-            // (rebuild_visible) so epoch-second X survives the f32 cast; the origin
-            // cancels in the shader's (p - view_min)/(view_max - view_min).
-            let uniforms = PlotUniforms {
-                view_min: [(self.view.x_min - self.x_origin) as f32, self.view.y_min as f32],
-                view_max: [(self.view.x_max - self.x_origin) as f32, self.view.y_max as f32],
-                resolution: [self.width as f32, self.height as f32],
-                line_width: self.line_width,
-                point_radius: self.point_radius,
-                color: [0.0, 0.0, 0.0, 0.0], // overridden per-series inside build_draw_calls
-                _padding: [0.0; 4],
-            };
-
-            let calls = self.renderer.build_draw_calls(&self.series, &self.grid, uniforms);
+            let calls = renderer.build_draw_calls(&self.drawn_series(), &self.frame_grid(), self.frame_uniforms());
 
-            if let Err(e) = self.renderer.render(&calls, self.bg_color) {
+            if let Err(e) = renderer.render(&calls, self.bg_color) {
                 web_sys::console::error_1(&format!("OxidePlot render error: {e:?}").into());
             }
         }
 
+        /// Why the GPU device was lost (driver reset or update, sleep/resume),
+        /// or `null` while it's alive or in CPU fallback. Once set the canvas
+        /// shows nothing new until `attach_gpu` replaces the renderer.
+        #[wasm_bindgen]
+        pub fn gpu_device_lost(&self) -> Option<String> {
+            self.renderer.as_ref()?.device_lost()
+        }
+
+        /// Build a new GPU renderer for `canvas` with `backend` (the same
+        /// preference string `create` takes), for `attach_gpu`. Static, so the
+        /// plot isn't borrowed across the await:
+        /// `plot.attach_gpu(await OxidePlot.rebuild_gpu(canvas, 'auto'))`.
+        #[wasm_bindgen]
+        pub async fn rebuild_gpu(canvas: web_sys::HtmlCanvasElement, backend: Option<String>) -> Result<GpuRenderer, JsValue> {
+            let backend_pref = BackendPreference::parse(backend.as_deref().unwrap_or("auto"));
+            build_renderer(canvas, backend_pref)
+                .await
+                .map(|inner| GpuRenderer { inner })
+                .map_err(|e| JsValue::from_str(&e))
+        }
+
+        /// Swap in `gpu` (from `rebuild_gpu`) for a lost renderer: the GPU
+        /// limits carry over, every series is re-uploaded at the current
+        /// size and view, and a frame is drawn. Data, view and settings are
+        /// untouched — only GPU objects are rebuilt.
+        #[wasm_bindgen]
+        pub fn attach_gpu(&mut self, gpu: GpuRenderer) {
+            let mut renderer = gpu.inner;
+            if let Some(old) = &self.renderer {
+                renderer.budget.configure(old.budget.max_buffer_bytes, old.budget.frame_upload_bytes);
+            }
+            renderer.resize(self.width.max(1), self.height.max(1));
+            self.renderer = Some(renderer);
+            self.gpu_error = None;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Why the GPU renderer couldn't be created, or `null` when the GPU
+        /// path is live. The frontend shows a warning badge and switches to
+        /// drawing `cpu_polylines()` when this is set.
+        #[wasm_bindgen]
+        pub fn gpu_fallback_reason(&self) -> Option<String> {
+            self.gpu_error.clone()
+        }
+
+        /// Return `{ requested_backend, fallback_reason, adapter }` for the GPU
+        /// diagnostics panel. `adapter` is
+        /// `{ adapter_name, vendor, device, device_type, backend, driver,
+        /// driver_info, surface_format, max_texture_dimension_2d,
+        /// max_buffer_size, max_storage_buffer_binding_size, max_bind_groups }`,
+        /// or `null` in CPU fallback mode.
+        #[wasm_bindgen]
+        pub fn gpu_diagnostics(&self) -> JsValue {
+            let report = GpuReport {
+                requested_backend: self.backend_pref.as_str(),
+                fallback_reason: self.gpu_error.clone(),
+                adapter: self.renderer.as_ref().map(|r| r.diagnostics.clone()),
+            };
+            serde_wasm_bindgen::to_value(&report).unwrap_or(JsValue::NULL)
+        }
+
+        /// Cap GPU storage uploads: `max_buffer_mb` per series buffer and
+        /// `frame_upload_mb` per frame (MiB, clamped to the device limit).
+        /// Series over the cap are drawn decimated. No-op in CPU fallback.
+        #[wasm_bindgen]
+        pub fn set_gpu_limits(&mut self, max_buffer_mb: f64, frame_upload_mb: f64) {
+            let Some(renderer) = &mut self.renderer else { return };
+            let mib = |v: f64| (v.max(0.0) * (1u64 << 20) as f64) as u64;
+            renderer.budget.configure(mib(max_buffer_mb), mib(frame_upload_mb));
+        }
+
+        /// `{ budget: { device_max_bytes, max_buffer_bytes, frame_upload_bytes },
+        /// upload: { uploaded_bytes, requested_bytes, decimated_series }, error }`
+        /// for the last frame, or `null` in CPU fallback mode. `error` is the
+        /// first wgpu error the device raised; after one, the frontend
+        /// reports it instead of trying to draw.
+        #[wasm_bindgen]
+        pub fn gpu_upload(&self) -> JsValue {
+            let Some(renderer) = &self.renderer else { return JsValue::NULL };
+            let status = GpuUploadStatus {
+                budget: renderer.budget,
+                upload: renderer.last_upload(),
+                error: renderer.gpu_error(),
+            };
+            serde_wasm_bindgen::to_value(&status).unwrap_or(JsValue::NULL)
+        }
+
+        /// Return the visible series as decimated screen-space polylines
+        /// `[{ points: [[x, y]], color, line_width, point_radius, points_only }]`
+        /// (canvas pixels, origin top-left) for the CPU fallback painter.
+        /// Works whether or not the GPU path is live.
+        #[wasm_bindgen]
+        pub fn cpu_polylines(&self) -> JsValue {
+            let lines = build_cpu_polylines(&self.drawn_series(), &self.frame_uniforms(), CPU_POINT_BUDGET);
+            serde_wasm_bindgen::to_value(&lines).unwrap_or(JsValue::NULL)
+        }
+
         /// Set the plot background clear colour and re-render.
         ///
         /// Call this whenever the theme changes.  Values are linear RGB in [0, 1].
@@ -793,11 +2190,77 @@ mod wasm_impl {
         #[wasm_bindgen]
         pub fn set_background(&mut self, r: f32, g: f32, b: f32, a: f32) {
             self.bg_color = [r as f64, g as f64, b as f64, a as f64];
+            let luma = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+            self.grid.color = if luma < 0.5 { GRID_COLOR_DARK } else { GRID_COLOR_LIGHT };
+            if self.auto_contrast {
+                self.rebuild_visible();
+            }
+        }
+
+        /// Draw series whose colour is too close to the background (yellow
+        /// on the light theme, navy on the dark one) darkened or lightened
+        /// just enough to stand out, re-checked whenever `set_background`
+        /// changes the theme. The series keep their own colours: turning it
+        /// off, or a theme they suit, draws them unchanged. Re-renders.
+        #[wasm_bindgen]
+        pub fn set_auto_contrast(&mut self, on: bool) {
+            self.auto_contrast = on;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        #[wasm_bindgen]
+        pub fn auto_contrast(&self) -> bool {
+            self.auto_contrast
+        }
+
+        /// How a break in a line series — missing samples, or the gap
+        /// between logger sessions — is shown: `"none"` leaves it blank,
+        /// `"dashed"` or `"dotted"` bridge it with a faint line in the
+        /// series colour, so a gap reads as missing data rather than the
+        /// series ending. Re-renders.
+        #[wasm_bindgen]
+        pub fn set_gap_style(&mut self, style: String) {
+            self.gap_style = GapStyle::parse(&style);
+            self.rebuild_visible();
+            self.render();
+        }
+
+        #[wasm_bindgen]
+        pub fn gap_style(&self) -> String {
+            self.gap_style.as_str().to_string()
+        }
+
+        /// Indices of the series too close to the background colour to read
+        /// (below WCAG's 3:1 for graphics) as drawn now: none while
+        /// `auto_contrast` is on.
+        #[wasm_bindgen]
+        pub fn low_contrast_series(&self) -> Vec<usize> {
+            let bg = self.bg_rgb();
+            (0..self.sources.len())
+                .filter(|&i| {
+                    let c = self.shown_color(self.sources[i].color);
+                    contrast_ratio([c[0], c[1], c[2]], bg) < MIN_SERIES_CONTRAST
+                })
+                .collect()
+        }
+
+        /// Grid display: shown at all, `layer` `"below"` the data or
+        /// `"above"` it (for dense fills), and `crisp` snapping to whole
+        /// pixels. Does not render.
+        #[wasm_bindgen]
+        pub fn set_grid(&mut self, show: bool, layer: String, crisp: bool) -> Result<(), JsValue> {
+            self.grid.layer = GridLayer::parse(&layer)
+                .ok_or_else(|| JsValue::from_str(&format!("Unknown grid layer {layer:?}")))?;
+            self.show_grid = show;
+            self.grid.pixel_snap = crisp;
+            Ok(())
         }
 
         /// Set the draw mode for all existing series and re-render.
         ///
-        /// `mode` is one of `"lines"`, `"step"`, or `"points"`.
+        /// `mode` is one of `"lines"`, `"step"`, `"step-pre"`, `"points"`,
+        /// `"smooth"` or `"bar"`.
         /// Unrecognised values fall back to `"lines"`.
         ///
         /// After updating every `SourceSeries`, `rebuild_visible()` is called
@@ -806,7 +2269,10 @@ mod wasm_impl {
         pub fn set_draw_mode(&mut self, mode: String) {
             let draw_mode = match mode.as_str() {
                 "step" => DrawMode::Step,
+                "step-pre" => DrawMode::StepPre,
                 "points" => DrawMode::Points,
+                "smooth" => DrawMode::Smooth,
+                "bar" => DrawMode::Bar,
                 _ => DrawMode::Lines,
             };
             for src in &mut self.sources {
@@ -820,7 +2286,9 @@ mod wasm_impl {
         pub fn resize(&mut self, w: u32, h: u32) {
             self.width = w;
             self.height = h;
-            self.renderer.resize(w, h);
+            if let Some(renderer) = &mut self.renderer {
+                renderer.resize(w, h);
+            }
             self.rebuild_visible();
             self.render();
         }
@@ -836,8 +2304,26 @@ mod wasm_impl {
                 .iter()
                 .map(|src| SeriesInfo {
                     name: src.name.clone(),
-                    color: src.color,
+                    color: self.shown_color(src.color),
                     visible: src.visible,
+                    wrap_period: src.wrap_period,
+                    formula: src.derivation.as_ref().map(Derivation::formula),
+                    reference: src.reference,
+                    labels: src.labels.as_ref().map(LabelMap::to_spec),
+                    gap_threshold: src.gaps.as_ref().map(|g| g.threshold),
+                    segments: src.gaps.as_ref().map_or(1, GapSplit::segment_count),
+                    error_style: src.error.as_ref().map(|e| e.style.as_str()),
+                    error_column: src.error.as_ref().and_then(|e| e.column.clone()),
+                    error_value: src.error.as_ref().map(|e| e.value),
+                    hold_last: src.hold_last,
+                    no_hover: src.no_hover,
+                    layer: src.layer.as_str(),
+                    fill: src.fill.as_ref().map(AreaFill::kind),
+                    fill_to: src.fill.as_ref().and_then(|f| f.other().map(str::to_string)),
+                    decimated: (src.derivation.is_none() && !src.reference)
+                        .then(|| self.import_note.clone())
+                        .flatten(),
+                    align: src.align,
                 })
                 .collect();
             serde_wasm_bindgen::to_value(&info).unwrap_or(JsValue::NULL)
@@ -854,6 +2340,31 @@ mod wasm_impl {
             self.render();
         }
 
+        /// Solo the series at `index`: show it alone, hiding the rest. Soloing
+        /// the series that is already the only one shown unmutes all.
+        #[wasm_bindgen]
+        pub fn solo_series(&mut self, index: usize) {
+            if index >= self.sources.len() {
+                return;
+            }
+            let already_solo = self.sources.iter().enumerate().all(|(i, s)| s.visible == (i == index));
+            for (i, s) in self.sources.iter_mut().enumerate() {
+                s.visible = already_solo || i == index;
+            }
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Make every series visible again and re-render.
+        #[wasm_bindgen]
+        pub fn unmute_all(&mut self) {
+            for s in &mut self.sources {
+                s.visible = true;
+            }
+            self.rebuild_visible();
+            self.render();
+        }
+
         /// Set the RGB color of the series at `index` (components in 0..1;
         /// alpha kept at 1) and re-render.
         #[wasm_bindgen]
@@ -862,800 +2373,3764 @@ mod wasm_impl {
                 return;
             }
             self.sources[index].color = [r, g, b, 1.0];
+            self.color_by_unit = false; // a hand-picked colour ends automatic colouring
             self.rebuild_visible();
             self.render();
         }
 
-        /// Remove the series at `index` and re-render.
+        /// Set (or clear, with `None`) the modular-display wrap period of the
+        /// series at `index` — e.g. 360 for compass headings — and re-render.
         #[wasm_bindgen]
-        pub fn remove_series(&mut self, index: usize) {
+        pub fn set_series_wrap(&mut self, index: usize, period: Option<f64>) {
             if index >= self.sources.len() {
                 return;
             }
-            self.sources.remove(index);
-            self.recompute_plotted_cols();
+            self.sources[index].wrap_period = period.filter(|p| p.is_finite() && *p > 0.0);
             self.rebuild_visible();
             self.render();
         }
 
-        /// Remove ALL series from this graph and re-render (returns to empty state).
+        /// Split the series at `index` into segments wherever X jumps by more
+        /// than `threshold` (`None` or a non-positive value: off). Segments
+        /// are drawn unconnected and get their own stats and export.
         #[wasm_bindgen]
-        pub fn clear_series(&mut self) {
-            self.sources.clear();
-            self.recompute_plotted_cols();
+        pub fn set_series_gap(&mut self, index: usize, threshold: Option<f64>) {
+            let Some(src) = self.sources.get_mut(index) else {
+                return;
+            };
+            src.gaps = threshold
+                .filter(|t| t.is_finite() && *t > 0.0)
+                .map(|t| GapSplit::detect(&src.xs, t));
             self.rebuild_visible();
             self.render();
         }
 
-        /// Move the series at `from` to position `to`, shifting others, and re-render.
-        /// This changes z-order: later indices render on top.
+        /// Extend the last value of the series at `index` to the right edge of
+        /// the view (status/level channels whose final state still holds).
         #[wasm_bindgen]
-        pub fn move_series(&mut self, from: usize, to: usize) {
-            let n = self.sources.len();
-            if from >= n || to >= n {
+        pub fn set_series_hold_last(&mut self, index: usize, on: bool) {
+            let Some(src) = self.sources.get_mut(index) else {
                 return;
+            };
+            src.hold_last = on;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Align the series at `index` with recordings from another logger:
+        /// shift X by `x_offset` (clock skew) and map Y to
+        /// `y * y_scale + y_offset`, replacing any earlier alignment. The
+        /// samples themselves move, so readouts and exports agree with the plot.
+        #[wasm_bindgen]
+        pub fn set_series_align(&mut self, index: usize, x_offset: f64, y_offset: f64, y_scale: f64) -> Result<(), JsValue> {
+            let align = SeriesAlign::new(x_offset, y_offset, y_scale).map_err(|e| JsValue::from_str(&e))?;
+            let src = self.sources.get_mut(index).ok_or_else(|| JsValue::from_str("no such series"))?;
+            if let Some(xs) = src.align.realign_x(&align, &src.xs) {
+                src.gaps = src.gaps.as_ref().map(|g| GapSplit::detect(&xs, g.threshold));
+                src.xs = xs.into();
             }
-            let src = self.sources.remove(from);
-            // After remove(from), indices >= from shift down by one.
-            // When to > from we must subtract 1 so the element lands at the
-            // intended logical position (non-adjacent moves are also correct).
-            let adjusted_to = if to > from { to - 1 } else { to };
-            self.sources.insert(adjusted_to, src);
+            if let Some(ys) = src.align.realign_y(&align, &src.ys) {
+                (src.y_min, src.y_max) = compute_y_bounds(&ys);
+                src.ys = ys.into();
+            }
+            src.align = align;
             self.rebuild_visible();
             self.render();
+            Ok(())
         }
 
-        /// Return the histogram of the source series at `source_index` as
-        /// `{ counts, bin_centers, min, max, n }`.
-        ///
-        /// `nbins` is clamped to `[2, 200]` by the core `histogram` function.
-        /// Throws (JS exception) if `source_index` is out of range or the
-        /// series has fewer than 2 finite values.
+        /// Permanently crop the series at `index` (every visible series when
+        /// `None`) to `x_min..=x_max`, deleting the samples outside — idle
+        /// stretches before and after a test that would skew statistics and
+        /// exports. Returns how many samples were removed; throws, leaving
+        /// every series untouched, when a series would be left empty.
+        /// `undo_sample_edit` takes the step back.
         #[wasm_bindgen]
-        pub fn series_histogram(&self, source_index: usize, nbins: usize) -> Result<JsValue, JsValue> {
-            let src = self
-                .sources
-                .get(source_index)
-                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
-            let h = oxideplot_core::processing::histogram::histogram(&src.ys, nbins)
-                .ok_or_else(|| JsValue::from_str("not enough finite values for a histogram"))?;
-            let data = HistogramData {
-                counts: h.counts,
-                bin_centers: h.bin_centers,
-                min: h.min,
-                max: h.max,
-                n: h.n,
+        pub fn crop_series(&mut self, index: Option<usize>, x_min: f64, x_max: f64) -> Result<usize, JsValue> {
+            if !(x_min.is_finite() && x_max.is_finite() && x_min < x_max) {
+                return Err(JsValue::from_str("crop range must be two finite X values, low to high"));
+            }
+            let targets: Vec<usize> = match index {
+                Some(i) if i < self.sources.len() => vec![i],
+                Some(_) => return Err(JsValue::from_str("no such series")),
+                None => (0..self.sources.len()).filter(|&i| self.sources[i].visible && !self.sources[i].reference).collect(),
             };
-            serde_wasm_bindgen::to_value(&data).map_err(|e| JsValue::from_str(&e.to_string()))
+            self.wake();
+            let windows: Vec<_> = targets.iter().map(|&i| math_ops::x_window(&self.sources[i].xs, x_min, x_max)).collect();
+            if let Some(k) = windows.iter().position(|w| w.is_empty()) {
+                return Err(JsValue::from_str(&format!("no samples of {} in the crop range", self.sources[targets[k]].name)));
+            }
+            let mut removed = 0;
+            let mut step = Vec::new();
+            for (&i, w) in targets.iter().zip(windows) {
+                let src = &mut self.sources[i];
+                if w.len() == src.xs.len() {
+                    continue;
+                }
+                removed += src.xs.len() - w.len();
+                let clip = |v: &[f64]| v[w.start.min(v.len())..w.end.min(v.len())].to_vec();
+                let (xs, ys) = (clip(&src.xs), clip(&src.ys));
+                let error_values = src.error.as_ref().and_then(|e| e.values.as_deref()).map(|v| clip(v).into());
+                step.push(src.replace_samples(xs, ys, error_values));
+            }
+            self.push_sample_undo(step);
+            Ok(removed)
         }
 
-        /// Return the power spectral density of the source series at
-        /// `source_index` as `{ freqs, power, sample_rate }`.
-        ///
-        /// `sample_rate` (Hz) is used if given; otherwise it is inferred from
-        /// the series' `xs` (1 / median positive dt, 1.0 fallback).
-        /// Throws (JS exception) if `source_index` is out of range or the
-        /// series has too few samples for a spectrum.
+        /// Mark the spikes `method` (`"hampel"`: further than `threshold`
+        /// robust σ from the median of a `window`-sample neighbourhood;
+        /// `"zscore"`: further than `threshold` σ from the series mean)
+        /// finds in the series at `index`, drawn over the plot until
+        /// `despike_series` or `clear_despike_preview`. Returns how many.
         #[wasm_bindgen]
-        pub fn series_spectrum(&self, source_index: usize, sample_rate: Option<f64>) -> Result<JsValue, JsValue> {
-            let src = self
-                .sources
-                .get(source_index)
-                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
-            let fs = sample_rate.unwrap_or_else(|| sample_rate_from_xs(&src.xs));
-            let (freqs, power) = oxideplot_core::processing::spectral::compute_psd(&src.ys, fs);
-            if freqs.is_empty() {
-                return Err(JsValue::from_str("not enough samples for a spectrum"));
-            }
-            serde_wasm_bindgen::to_value(&SpectrumData { freqs, power, sample_rate: fs })
-                .map_err(|e| JsValue::from_str(&e.to_string()))
+        pub fn preview_despike(&mut self, index: usize, method: String, window: usize, threshold: f64) -> Result<usize, JsValue> {
+            let test = SpikeTest::parse(&method, window, threshold).map_err(|e| JsValue::from_str(&e))?;
+            self.wake();
+            let src = self.sources.get(index).ok_or_else(|| JsValue::from_str("no such series"))?;
+            let spikes = find_spikes(&src.ys, test);
+            let n = spikes.len();
+            self.despike_preview = Some(SourceSeries {
+                xs: spikes.iter().map(|&i| src.xs[i]).collect(),
+                ys: spikes.iter().map(|&i| src.ys[i]).collect(),
+                color: DESPIKE_PREVIEW_COLOR,
+                draw_mode: DrawMode::Points,
+                visible: true,
+                gaps: None,
+                error: None,
+                fill: None,
+                hold_last: false,
+                packed: None,
+                ..src.clone()
+            });
+            self.rebuild_visible();
+            self.render();
+            Ok(n)
         }
 
-        /// Return a short-time FFT magnitude spectrogram of the source series
-        /// at `source_index` as
-        /// `{ frames, bins, n_frames, sample_rate, nyquist, duration_s }`,
-        /// where `frames[frame][bin]` is the magnitude at that frame/bin.
-        ///
-        /// `window` is clamped to `[16, 4096]` by the core `compute_spectrogram`
-        /// call path. `sample_rate` (Hz) is used if given; otherwise it is
-        /// inferred from the series' `xs` (1 / median positive dt, 1.0
-        /// fallback). Throws (JS exception) if `source_index` is out of range
-        /// or the series has too few samples for a spectrogram.
+        /// Drop the `preview_despike` markers and re-render.
         #[wasm_bindgen]
-        pub fn series_spectrogram(&self, source_index: usize, window: usize, sample_rate: Option<f64>) -> Result<JsValue, JsValue> {
-            let src = self
-                .sources
-                .get(source_index)
-                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
-            let fs = sample_rate.unwrap_or_else(|| sample_rate_from_xs(&src.xs));
-            let win = window.clamp(16, 4096);
-            let (frames, bins) = oxideplot_core::processing::spectral::compute_spectrogram(&src.ys, win);
-            if frames.is_empty() || bins == 0 {
-                return Err(JsValue::from_str("not enough samples for a spectrogram"));
+        pub fn clear_despike_preview(&mut self) {
+            if self.despike_preview.take().is_some() {
+                self.rebuild_visible();
+                self.render();
             }
-            let n_frames = frames.len();
-            let duration_s = n_frames as f64 * (win as f64 / 2.0) / fs;
-            serde_wasm_bindgen::to_value(&SpectrogramData {
-                frames, bins, n_frames, sample_rate: fs, nyquist: fs / 2.0, duration_s,
-            })
-            .map_err(|e| JsValue::from_str(&e.to_string()))
         }
 
-        // ── Appearance settings ───────────────────────────────────────────────
+        /// Despike the series at `index` (spikes found as `preview_despike`
+        /// does): `interpolate` bridges each with a straight line between
+        /// its neighbours, otherwise the spikes are deleted. Clears the
+        /// preview; `undo_sample_edit` takes it back. Returns how many
+        /// spikes were fixed.
+        #[wasm_bindgen]
+        pub fn despike_series(
+            &mut self,
+            index: usize,
+            method: String,
+            window: usize,
+            threshold: f64,
+            interpolate: bool,
+        ) -> Result<usize, JsValue> {
+            let test = SpikeTest::parse(&method, window, threshold).map_err(|e| JsValue::from_str(&e))?;
+            self.wake();
+            self.despike_preview = None;
+            let src = self.sources.get_mut(index).ok_or_else(|| JsValue::from_str("no such series"))?;
+            let spikes = find_spikes(&src.ys, test);
+            if spikes.is_empty() {
+                self.rebuild_visible();
+                self.render();
+                return Ok(0);
+            }
+            let error_values = src.error.as_ref().and_then(|e| e.values.clone());
+            let undo = if interpolate {
+                let ys = interpolate_spikes(&src.xs, &src.ys, &spikes);
+                src.replace_samples(src.xs.to_vec(), ys, error_values)
+            } else {
+                let keep = |v: &[f64]| -> Vec<f64> {
+                    v.iter().enumerate().filter(|(i, _)| spikes.binary_search(i).is_err()).map(|(_, &y)| y).collect()
+                };
+                let (xs, ys) = (keep(&src.xs), keep(&src.ys));
+                src.replace_samples(xs, ys, error_values.map(|v| keep(&v).into()))
+            };
+            self.push_sample_undo(vec![undo]);
+            Ok(spikes.len())
+        }
 
-        /// Set the line width for all series and re-render.
+        /// Restore the samples before the most recent `crop_series` or
+        /// `despike_series`. Series replaced or realigned since are left as
+        /// they are. Returns false when there is nothing to undo.
         #[wasm_bindgen]
-        pub fn set_line_width(&mut self, w: f32) {
-            self.line_width = w;
+        pub fn undo_sample_edit(&mut self) -> bool {
+            let Some(step) = self.sample_undo.pop() else { return false };
+            self.wake();
+            for u in step {
+                let Some(src) = self.sources.iter_mut().find(|s| u.matches(s)) else {
+                    continue;
+                };
+                src.xs = u.xs;
+                src.ys = u.ys;
+                src.y_min = u.y_min;
+                src.y_max = u.y_max;
+                src.gaps = u.gaps;
+                src.error = u.error;
+            }
             self.rebuild_visible();
             self.render();
+            true
         }
 
-        /// Set the point radius for all series and re-render.
+        /// How many sample edits `undo_sample_edit` can take back.
         #[wasm_bindgen]
-        pub fn set_point_radius(&mut self, r: f32) {
-            self.point_radius = r;
-            self.rebuild_visible();
-            self.render();
+        pub fn sample_undo_depth(&self) -> usize {
+            self.sample_undo.len()
         }
 
-        /// Enable or disable normalized multi-unit overlay mode.
-        ///
-        /// When ON, each series' Y values are mapped to [0, 1] using that
-        /// series' own global min/max (computed once in `set_series`), so
-        /// series with very different units/scales overlay comparably.  The Y
-        /// view is set to [-0.05, 1.05] (unitless).
-        ///
-        /// When OFF (default), raw Y values are used and the Y axis shows real
-        /// engineering units.  Turning off calls `auto_fit()` to restore the
-        /// original Y range.
-        ///
-        /// The raw `ys` data in each `SourceSeries` is NEVER mutated; the
-        /// normalization is applied only when building GPU points in
-        /// `rebuild_visible`.  CSV export always returns the original values.
+        /// Include the series at `index` in the nearest-point search (the
+        /// point menu) or leave it out. Reference curves start out left out.
         #[wasm_bindgen]
-        pub fn set_normalized(&mut self, on: bool) {
-            self.normalized = on;
-            self.auto_fit();
+        pub fn set_series_hover(&mut self, index: usize, on: bool) {
+            if let Some(src) = self.sources.get_mut(index) {
+                src.no_hover = !on;
+            }
         }
 
-        /// Set the autoscale strategy for the (non-normalized) Y bounds.
-        ///
-        /// `mode` is `"robust"` (clip to 1st/99th percentiles) or `"minmax"`
-        /// (raw data extremes; the default). Unrecognised values fall back to
-        /// MinMax. Re-fits the view via `auto_fit()` (which re-renders).
+        /// Bring the series at `index` to the front (`"front"`), send it to
+        /// the back (`"back"`) or return it to list order (`"normal"`). Its
+        /// place in the list is unchanged.
         #[wasm_bindgen]
-        pub fn set_autoscale_mode(&mut self, mode: String) {
-            self.autoscale_mode = if mode == "robust" {
-                AutoscaleMode::Robust
-            } else {
-                AutoscaleMode::MinMax
-            };
-            self.auto_fit();
+        pub fn set_series_layer(&mut self, index: usize, layer: String) -> Result<(), JsValue> {
+            let layer = SeriesLayer::parse(&layer)
+                .ok_or_else(|| JsValue::from_str(&format!("Unknown layer {layer:?}")))?;
+            let src = self
+                .sources
+                .get_mut(index)
+                .ok_or_else(|| JsValue::from_str("Series index out of range"))?;
+            src.layer = layer;
+            self.render();
+            Ok(())
         }
 
-        /// Set the Y-axis scale.
-        ///
-        /// `mode` is `"log"` (plot log10(y), dropping y<=0) or `"linear"` (the
-        /// default). Unrecognised values fall back to Linear. Re-fits the view
-        /// via `auto_fit()` (which re-renders).
+        /// Show ± uncertainty on the series at `index` as error bars or a
+        /// shaded band. `params` is `{ style: "bars" | "band", column?, value? }`:
+        /// per-sample values from a loaded-file column, else the constant
+        /// `value`. `null` turns it off.
         #[wasm_bindgen]
-        pub fn set_y_scale(&mut self, mode: String) {
-            self.y_scale = if mode == "log" {
-                YScale::Log
+        pub fn set_series_error(&mut self, index: usize, params: JsValue) -> Result<(), JsValue> {
+            if index >= self.sources.len() {
+                return Err(JsValue::from_str("Series index out of range"));
+            }
+            let error = if params.is_null() || params.is_undefined() {
+                None
             } else {
-                YScale::Linear
+                let p: ErrorParams = serde_wasm_bindgen::from_value(params)
+                    .map_err(|e| JsValue::from_str(&e.to_string()))?;
+                Some(self.uncertainty(index, &p)?)
             };
-            self.auto_fit();
+            self.sources[index].error = error;
+            self.rebuild_visible();
+            self.render();
+            Ok(())
         }
 
-        /// Set the downsampling (decimation) strategy for the visible render series.
-        ///
-        /// `mode` is one of `"minmax"` (default), `"lttb"`, or `"none"`.
-        /// Unrecognised values fall back to MinMax. Rebuilds the visible series
-        /// at the current view and re-renders (bounds are unchanged, so no
-        /// `auto_fit()`).
+        /// Fill the area of the series at `index`: `"baseline"` down (or up)
+        /// to zero, `"between"` it and the series at `other` (an envelope),
+        /// or `"off"`. The fill is drawn in the series' colour under the
+        /// lines, and not while stacking.
         #[wasm_bindgen]
-        pub fn set_downsample_mode(&mut self, mode: String) {
-            self.downsample_mode = DownsampleMode::parse(&mode);
+        pub fn set_series_fill(&mut self, index: usize, mode: String, other: Option<usize>) -> Result<(), JsValue> {
+            if index >= self.sources.len() {
+                return Err(JsValue::from_str("Series index out of range"));
+            }
+            let fill = match mode.as_str() {
+                "off" => None,
+                "baseline" => Some(AreaFill::Baseline),
+                "between" => {
+                    let other = other
+                        .filter(|&o| o != index)
+                        .and_then(|o| self.sources.get(o))
+                        .ok_or_else(|| JsValue::from_str("Pick another series to fill to"))?;
+                    Some(AreaFill::Between(other.name.clone()))
+                }
+                _ => return Err(JsValue::from_str(&format!("Unknown fill mode {mode:?}"))),
+            };
+            self.sources[index].fill = fill;
             self.rebuild_visible();
             self.render();
+            Ok(())
         }
 
-        // ── Export ────────────────────────────────────────────────────────────
-
-        /// Export all source series as a CSV string.
-        ///
-        /// The header row is the X column name (from the first series, or `"x"` if
-        /// no sources are loaded) followed by each series' Y name.  All sources are
-        /// assumed to share the same X axis; for row `i` the X value from each
-        /// source's own `xs[i]` is used (they are identical in practice).
-        ///
-        /// Data rows contain the full, un-downsampled values.  For row `i`:
-        /// - column 0: `sources[0].xs[i]` (or the per-series xs[i] if you track
-        ///   independent X axes — currently all sources share one X).
-        /// - column k+1: `sources[k].ys[i]`, or an empty cell if that series is
-        ///   shorter than the maximum length.
-        ///
-        /// f64 values are formatted with up to 15 significant digits, dropping
-        /// trailing zeros (`{:.15}` then trimmed).
+        /// Suggested gap threshold for the series at `index`: 10× its median
+        /// X step (`None` if it has fewer than two distinct X values).
         #[wasm_bindgen]
-        pub fn export_csv(&self) -> String {
-            if self.sources.is_empty() {
-                return String::new();
-            }
+        pub fn suggest_series_gap(&self, index: usize) -> Option<f64> {
+            suggest_gap_threshold(&self.sources.get(index)?.xs)
+        }
 
-            // Header row
-            let mut rows: Vec<String> = Vec::new();
-            let header = std::iter::once(self.sources[0].x_name.as_str())
-                .chain(self.sources.iter().map(|s| s.name.as_str()))
-                .map(csv_escape)
-                .collect::<Vec<_>>()
-                .join(",");
-            rows.push(header);
+        /// Statistics of each segment of the series at `index` (the whole
+        /// series when it isn't split):
+        /// `[{ x_start, x_end, start_text, end_text, count, min, max, mean, std_dev }]`.
+        #[wasm_bindgen]
+        pub fn series_segments(&self, index: usize) -> JsValue {
+            let Some(src) = self.sources.get(index) else {
+                return JsValue::NULL;
+            };
+            let whole = GapSplit::none();
+            let split = src.gaps.as_ref().unwrap_or(&whole);
+            let x_text = |x: f64| if self.x_is_time { format_timestamp(x) } else { format_f64(x) };
+            let segments: Vec<SegmentInfo> = segment_stats(split, &src.xs, &src.ys)
+                .into_iter()
+                .map(|s| SegmentInfo {
+                    x_start: s.x_start,
+                    x_end: s.x_end,
+                    start_text: x_text(s.x_start),
+                    end_text: x_text(s.x_end),
+                    count: s.stats.count,
+                    min: s.stats.min,
+                    max: s.stats.max,
+                    mean: s.stats.mean,
+                    std_dev: s.stats.std_dev,
+                })
+                .collect();
+            serde_wasm_bindgen::to_value(&segments).unwrap_or(JsValue::NULL)
+        }
 
-            // Determine the maximum row count across all series.
-            let max_len = self.sources.iter().map(|s| s.xs.len()).max().unwrap_or(0);
+        /// Least-squares fit of the series at `index`: `kind` is `"linear"`,
+        /// `"polynomial"` (of `degree`), `"exponential"` or `"power"`.
+        /// Returns `{ model, coefficients, x0, x0_text, r_squared, count,
+        /// equation }`; `add_transform(index, "fit", { mode, order })` plots
+        /// the curve.
+        #[wasm_bindgen]
+        pub fn series_fit(&self, index: usize, kind: String, degree: usize) -> Result<JsValue, JsValue> {
+            let src = self.sources.get(index).ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            let f = self.fit_source(src, &kind, degree)?;
+            let report = FitReport {
+                model: f.model.label(),
+                x0_text: if self.x_is_time { format_timestamp(f.x0) } else { format_f64(f.x0) },
+                equation: f.equation(),
+                coefficients: f.coefficients,
+                x0: f.x0,
+                r_squared: f.r_squared,
+                count: f.count,
+            };
+            serde_wasm_bindgen::to_value(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
 
-            // Use the first source's xs as the shared X axis.
-            let ref_xs = &self.sources[0].xs;
+        /// Statistics of every visible series over `[x_min, x_max]` (the
+        /// common window of X-synced graphs), for the cross-graph window
+        /// report: `{ start_text, end_text, rows: [{ name, count, min, max,
+        /// peak_to_peak, mean, median, std_dev }] }`. Series with no samples
+        /// in the window are left out.
+        #[wasm_bindgen]
+        pub fn window_stats(&self, x_min: f64, x_max: f64) -> Result<JsValue, JsValue> {
+            let x_text = |x: f64| if self.x_is_time { format_timestamp(x) } else { format_f64(x) };
+            let rows = self
+                .sources
+                .iter()
+                .filter(|s| s.visible)
+                .filter_map(|s| {
+                    let st = SeriesStats::compute_window(&s.xs, &s.ys, x_min, x_max)?;
+                    Some(WindowStatsRow {
+                        name: s.name.clone(),
+                        count: st.count,
+                        min: st.min,
+                        max: st.max,
+                        peak_to_peak: st.peak_to_peak,
+                        mean: st.mean,
+                        median: st.median,
+                        std_dev: st.std_dev,
+                    })
+                })
+                .collect();
+            let report = WindowStats { start_text: x_text(x_min), end_text: x_text(x_max), rows };
+            serde_wasm_bindgen::to_value(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
 
-            for i in 0..max_len {
-                let x_val = if i < ref_xs.len() {
-                    format_f64(ref_xs[i])
-                } else {
-                    String::new()
-                };
+        /// Statistics of every visible series over the X region between
+        /// `x0` and `x1` (either order), for the region statistics tool:
+        /// mean, min, max, std, RMS, peak-to-peak and integral. Series with
+        /// no samples in the region are left out.
+        #[wasm_bindgen]
+        pub fn region_stats(&self, x0: f64, x1: f64) -> Result<JsValue, JsValue> {
+            let (x_min, x_max) = (x0.min(x1), x0.max(x1));
+            let x_text = |x: f64| if self.x_is_time { format_timestamp(x) } else { format_f64(x) };
+            let rows = self
+                .sources
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| s.visible)
+                .filter_map(|(series, s)| {
+                    let r = RegionStats::compute(&s.xs, &s.ys, x_min, x_max)?;
+                    Some(RegionStatsRow {
+                        series,
+                        name: s.name.clone(),
+                        unit: Some(infer_unit(&s.name)).filter(|u| u != UNKNOWN_UNIT),
+                        count: r.stats.count,
+                        mean: r.stats.mean,
+                        min: r.stats.min,
+                        max: r.stats.max,
+                        std_dev: r.stats.std_dev,
+                        rms: r.rms,
+                        peak_to_peak: r.stats.peak_to_peak,
+                        integral: r.integral,
+                        integral_unit: self.transform_unit("integral", s),
+                    })
+                })
+                .collect();
+            let report = RegionStatsReport { start_text: x_text(x_min), end_text: x_text(x_max), rows };
+            serde_wasm_bindgen::to_value(&report).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
 
-                let mut cols = vec![x_val];
-                for src in &self.sources {
-                    let y_val = if i < src.ys.len() {
-                        format_f64(src.ys[i])
-                    } else {
-                        String::new()
-                    };
-                    cols.push(y_val);
+        /// The series at `index` as CSV with a leading 1-based `segment`
+        /// column (`segment,<x name>,<y name>`), full resolution.
+        #[wasm_bindgen]
+        pub fn export_segments_csv(&self, index: usize) -> String {
+            let Some(src) = self.sources.get(index) else {
+                return String::new();
+            };
+            let x_header = self.x_header(&src.x_name);
+            let mut rows = vec![["segment", x_header.as_str(), src.name.as_str()].map(csv_escape).join(",")];
+            let whole = GapSplit::none();
+            let split = src.gaps.as_ref().unwrap_or(&whole);
+            for (k, r) in split.segments(&src.xs).into_iter().enumerate() {
+                for i in r {
+                    rows.push(format!("{},{},{}", k + 1, format_f64(src.xs[i]), format_f64(src.ys[i])));
                 }
-                rows.push(cols.join(","));
             }
-
             rows.join("\n")
         }
 
-        // ── Table API ─────────────────────────────────────────────────────────
-
-        /// Return column metadata as `[{ name: string, numeric: boolean }]`.
-        ///
-        /// Restricted to the plotted columns (`self.plotted_cols`) when any
-        /// series are plotted and at least one of their column names matched
-        /// the file; otherwise every file column is returned. Indices here
-        /// are "display indices" — positions in this returned array — which
-        /// `table_set_sort` / `table_set_column_filter` / `table_window`
-        /// accept and internally translate back to real file-column indices.
+        /// Remove the series at `index` and re-render.
+        /// Attach a value→label map (`"0=OFF, 1=IDLE, 2=RUN"`, see
+        /// `LabelMap::parse`) to the series at `index`; a blank `spec` removes
+        /// it. Y ticks, cursor readouts and the table then show the labels.
         #[wasm_bindgen]
-        pub fn table_columns(&self) -> Result<JsValue, JsValue> {
-            let cols: Vec<TableColumnInfo> = match &self.loaded {
-                None => vec![],
-                Some(d) => self
-                    .display_indices(d)
-                    .into_iter()
-                    .map(|i| TableColumnInfo {
-                        name: d.columns[i].clone(),
-                        numeric: self.table_query.numeric_cols.get(i).copied().unwrap_or(false),
-                    })
-                    .collect(),
-            };
-            serde_wasm_bindgen::to_value(&cols).map_err(|e| JsValue::from_str(&e.to_string()))
+        pub fn set_series_labels(&mut self, index: usize, spec: String) -> Result<(), JsValue> {
+            let labels = parse_label_spec(&spec)?;
+            if let Some(src) = self.sources.get_mut(index) {
+                src.labels = labels;
+                self.render();
+            }
+            Ok(())
         }
 
-        /// Set sort: DISPLAY column index (position in `table_columns()`'s
-        /// output) + direction; rebuilds the view index. Translated to the
-        /// real file-column index before being stored.
+        /// Attach a value→label map to the Y axis (overrides series maps), or
+        /// remove it with a blank `spec`.
         #[wasm_bindgen]
-        pub fn table_set_sort(&mut self, col: usize, ascending: bool) {
-            let orig = self.to_orig_col(col);
-            self.table_query.sort = Some((orig, ascending));
-            self.rebuild_table_index();
+        pub fn set_y_labels(&mut self, spec: String) -> Result<(), JsValue> {
+            self.y_labels = parse_label_spec(&spec)?;
+            self.render();
+            Ok(())
         }
 
-        /// Clear sort (restore original row order); rebuilds the view index.
+        /// Current Y-axis label map in text form (`None` when unset).
         #[wasm_bindgen]
-        pub fn table_clear_sort(&mut self) {
-            self.table_query.sort = None;
-            self.rebuild_table_index();
+        pub fn y_labels(&self) -> Option<String> {
+            self.y_labels.as_ref().map(LabelMap::to_spec)
         }
 
-        /// Set global search term; rebuilds the view index.
+        /// State name for a Y value under the active label map (nearest
+        /// state within half a step), for cursor readouts.
         #[wasm_bindgen]
-        pub fn table_set_search(&mut self, term: String) {
-            self.table_query.search = term;
-            self.rebuild_table_index();
+        pub fn y_label(&self, value: f64) -> Option<String> {
+            self.active_y_labels().and_then(|m| m.nearest(value)).map(str::to_string)
         }
 
-        /// Set or clear a per-column filter. `col` is a DISPLAY column index
-        /// (see `table_columns`), translated to the real file-column index
-        /// before being stored.
-        ///
-        /// `spec` is a JS value of shape:
-        /// - `null` / `undefined` → remove the filter for `col`
-        /// - `{ text: string }` → `ColFilter::Text`
-        /// - `{ min?: number, max?: number }` → `ColFilter::Range`
+        /// Titles for the X and Y axes: the edited title when set, else
+        /// derived — the X column's name, and for Y the single visible
+        /// series' name or the unit all visible series share.
         #[wasm_bindgen]
-        pub fn table_set_column_filter(&mut self, col: usize, spec: JsValue) -> Result<(), JsValue> {
-            let orig = self.to_orig_col(col);
-            if spec.is_null() || spec.is_undefined() {
-                self.table_query.col_filters.remove(&orig);
-            } else {
-                let fs: FilterSpec = serde_wasm_bindgen::from_value(spec)
-                    .map_err(|e| JsValue::from_str(&e.to_string()))?;
-                if let Some(text) = fs.text {
-                    self.table_query.col_filters.insert(orig, ColFilter::Text(text));
-                } else if fs.min.is_some() || fs.max.is_some() {
-                    self.table_query.col_filters.insert(orig, ColFilter::Range { min: fs.min, max: fs.max });
-                } else {
-                    // All None → clear the filter.
-                    self.table_query.col_filters.remove(&orig);
+        pub fn axis_titles(&self) -> JsValue {
+            let x = match &self.x_title {
+                Some(t) => t.to_string(),
+                None => self.sources.first().map(|s| s.x_name.clone()).unwrap_or_default(),
+            };
+            let y = match &self.y_title {
+                Some(t) => t.to_string(),
+                None if self.normalized => "Normalized".to_string(),
+                None if self.stack_mode == StackMode::Percent => "Share [%]".to_string(),
+                None => {
+                    let mut visible = self.sources.iter().filter(|s| s.visible);
+                    match (visible.next(), visible.next()) {
+                        (Some(only), None) => only.name.clone(),
+                        _ => self.inferred_y_unit().map(|u| format!("[{u}]")).unwrap_or_default(),
+                    }
                 }
+            };
+            let titles = AxisTitles {
+                x,
+                y,
+                x_custom: self.x_title.is_some(),
+                y_custom: self.y_title.is_some(),
+                x_name: self.x_title.as_ref().map(|t| t.name.clone()).unwrap_or_default(),
+                x_unit: self.x_title.as_ref().and_then(|t| t.unit.clone()),
+            };
+            serde_wasm_bindgen::to_value(&titles).unwrap_or(JsValue::NULL)
+        }
+
+        /// Set the title of axis `"x"` or `"y"` from `"name [unit]"` text; a
+        /// blank `text` restores the derived title. A Y unit set here also
+        /// replaces the inferred unit in the mouse readout.
+        #[wasm_bindgen]
+        pub fn set_axis_title(&mut self, axis: String, text: String) -> Result<(), JsValue> {
+            let title = AxisTitle::parse(&text);
+            match axis.as_str() {
+                "x" => self.x_title = title,
+                "y" => self.y_title = title,
+                other => return Err(JsValue::from_str(&format!("unknown axis {other:?}"))),
             }
-            self.rebuild_table_index();
             Ok(())
         }
 
-        /// Return the number of rows visible under the current filters/sort.
+        /// Set the X axis display name and unit (either may be blank; both
+        /// blank restores the derived title). Used for the axis title, export
+        /// and table headers, and readouts.
         #[wasm_bindgen]
-        pub fn table_row_count(&self) -> usize {
-            self.table_index.len()
+        pub fn set_x_axis(&mut self, name: String, unit: String) {
+            self.x_title = AxisTitle::from_parts(&name, &unit);
         }
 
-        /// Return a window of rows `[start, start+count)` from the current view
-        /// as `string[][]` — each inner array is one row, each string is one
-        /// cell, projected onto the same (plotted-columns-only, or all-columns
-        /// fallback) column set and order as `table_columns()`.
+        /// The Y axis of each unit inferred from the series names, in axis
+        /// order (`set_unit_axis_order`, else first use):
+        /// `[{ unit, label, auto_range, min, max, side, order, range }]`.
+        /// Normalized mode draws one axis per unit with these settings.
         #[wasm_bindgen]
-        pub fn table_window(&self, start: usize, count: usize) -> Result<JsValue, JsValue> {
-            let rows = self
-                .loaded
-                .as_ref()
-                .map(|d| {
-                    let cols = self.display_indices(d);
-                    window_rows(d, &self.table_index, start, count, Some(&cols))
+        pub fn unit_axes(&self) -> JsValue {
+            let out: Vec<UnitAxisInfo> = self
+                .axis_units(false)
+                .into_iter()
+                .map(|unit| {
+                    let axis = self.y_axes.get(&unit).cloned().unwrap_or_else(|| AxisState::for_unit(&unit));
+                    UnitAxisInfo {
+                        label: axis.label,
+                        auto_range: axis.auto_range,
+                        min: axis.min,
+                        max: axis.max,
+                        side: axis.side,
+                        order: axis.order,
+                        range: self.unit_ranges.get(&unit).map(|&(lo, hi)| [lo, hi]),
+                        unit,
+                    }
                 })
-                .unwrap_or_default();
-            serde_wasm_bindgen::to_value(&rows).map_err(|e| JsValue::from_str(&e.to_string()))
+                .collect();
+            serde_wasm_bindgen::to_value(&out).unwrap_or(JsValue::NULL)
         }
 
-        // ── Transform API ─────────────────────────────────────────────────────
-
-        /// Append a derived series built from a math transform of an existing source.
-        ///
-        /// `source_index` — index into `self.sources` (bounds-checked).
-        /// `kind`         — one of `"moving_average"`, `"derivative"`, `"integral"`,
-        ///                  `"normalize"`, `"abs"`, `"log"`, `"sqrt"`.
-        /// `params`       — optional JS object `{ window?: number, mode?: string }`;
-        ///                  pass `null` / `undefined` to use defaults.
-        ///
-        /// The new series inherits the source's X data and `x_name`, gets a label
-        /// that describes the transform, is assigned the next palette colour, and is
-        /// immediately visible.  `auto_fit()` is called so the new series renders.
+        /// Order the unit axes as `units` lists them (first = outermost on
+        /// its edge); units left out follow in order of first use. Does not
+        /// re-render (the axes are drawn by the frontend).
         #[wasm_bindgen]
-        pub fn add_transform(
-            &mut self,
-            source_index: usize,
-            kind: String,
-            params: JsValue,
-        ) -> Result<(), JsValue> {
-            let src = self.sources.get(source_index)
-                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
-
-            let p: TransformParams = if params.is_null() || params.is_undefined() {
-                TransformParams::default()
-            } else {
-                serde_wasm_bindgen::from_value(params)
-                    .map_err(|e| JsValue::from_str(&e.to_string()))?
-            };
+        pub fn set_unit_axis_order(&mut self, units: Vec<String>) {
+            set_unit_axis_order(&mut self.y_axes, &units);
+        }
 
-            let xs   = src.xs.clone();
-            let base = src.name.clone();
-            let x_name = src.x_name.clone();
+        /// Edit the Y axis of `unit` from `{ label, auto_range, min, max,
+        /// side }` (`side` `"left"` or `"right"`). A manual range needs
+        /// `min < max`.
+        #[wasm_bindgen]
+        pub fn set_unit_axis(&mut self, unit: String, axis: JsValue) -> Result<(), JsValue> {
+            let axis: AxisState = serde_wasm_bindgen::from_value(axis).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            if !self.sources.iter().any(|s| infer_unit(&s.name) == unit) {
+                return Err(JsValue::from_str(&format!("no series has unit {unit:?}")));
+            }
+            // `range` only falls back to the data range for an invalid manual one.
+            if !axis.auto_range && axis.range((f64::NAN, f64::NAN)).0.is_nan() {
+                return Err(JsValue::from_str("axis min must be below max"));
+            }
+            self.y_axes.insert(unit, axis);
+            self.rebuild_visible();
+            self.render();
+            Ok(())
+        }
 
-            let (new_xs, new_ys, label) = match kind.as_str() {
-                "moving_average" => {
-                    let w = p.window.unwrap_or(5).max(1);
-                    (xs.clone(), math_ops::moving_average(&src.ys, w), format!("{base} · MA({w})"))
-                }
-                "derivative" => (xs.clone(), math_ops::derivative(&src.xs, &src.ys), format!("d/dx({base})")),
-                "integral"   => (xs.clone(), math_ops::integral(&src.xs, &src.ys), format!("∫({base})")),
-                "normalize"  => {
-                    let zscore = p.mode.as_deref() == Some("zscore");
-                    let label = if zscore {
-                        format!("z({base})")
-                    } else {
-                        format!("norm({base})")
-                    };
-                    (xs.clone(), math_ops::normalize(&src.ys, zscore), label)
-                }
-                "abs"  => (xs.clone(), math_ops::map_abs(&src.ys),  format!("|{base}|")),
-                "log"  => (xs.clone(), math_ops::map_ln(&src.ys),   format!("log({base})")),
-                "sqrt" => (xs.clone(), math_ops::map_sqrt(&src.ys), format!("√({base})")),
-                "resample" => {
-                    let method = match p.method.as_deref() {
-                        Some("nearest") => interpolation::Method::Nearest,
-                        Some("cubic")   => interpolation::Method::Cubic,
-                        _ => interpolation::Method::Linear,
-                    };
-                    let n = p.points.unwrap_or(500).max(2);
-                    let (grid_xs, grid_ys) = interpolation::resample(&src.xs, &src.ys, n, method);
-                    let mlabel = p.method.as_deref().unwrap_or("linear");
-                    (grid_xs, grid_ys, format!("{base} · resample({mlabel}, {n})"))
-                }
-                other  => return Err(JsValue::from_str(&format!("unknown transform: {other}"))),
-            };
+        /// Zoom only the Y axis of `unit` (normalized mode) about canvas row
+        /// `anchor_y` (backing-store pixels), with `zoom`'s wheel step: the
+        /// wheel over that axis' gutter. The axis keeps a manual range.
+        #[wasm_bindgen]
+        pub fn zoom_unit_axis(&mut self, unit: String, scroll_y: f32, anchor_y: f32) -> Result<(), JsValue> {
+            let shown = self.shown_unit_range(&unit)?;
+            let factor = (1.0 - (scroll_y as f64) * 0.001).clamp(0.5, 2.0);
+            let (_, pivot) = self.view.screen_to_data(Pos2 { x: 0.0, y: anchor_y }, self.canvas_rect());
+            self.y_axes.entry(unit.clone()).or_insert_with(|| AxisState::for_unit(&unit)).zoom(shown, pivot, factor);
+            self.rebuild_visible();
+            self.render();
+            Ok(())
+        }
 
-            // Color: cycle the shared palette by current series count (same palette
-            // ColumnDialog.svelte uses on the JS side).
-            let color = palette_color(self.sources.len());
+        /// Pan only the Y axis of `unit` (normalized mode) by a vertical
+        /// drag of `dy_px` backing-store pixels, like `pan` does the view.
+        #[wasm_bindgen]
+        pub fn pan_unit_axis(&mut self, unit: String, dy_px: f32) -> Result<(), JsValue> {
+            let shown = self.shown_unit_range(&unit)?;
+            let frac = dy_px as f64 * (self.view.y_max - self.view.y_min) / self.height.max(1) as f64;
+            self.y_axes.entry(unit.clone()).or_insert_with(|| AxisState::for_unit(&unit)).pan(shown, frac);
+            self.rebuild_visible();
+            self.render();
+            Ok(())
+        }
 
-            // Y bounds: same degenerate-safe rule as set_series.
-            let (y_min, y_max) = compute_y_bounds(&new_ys);
+        #[wasm_bindgen]
+        pub fn remove_series(&mut self, index: usize) {
+            if index >= self.sources.len() {
+                return;
+            }
+            self.sources.remove(index);
+            self.recompute_plotted_cols();
+            self.rebuild_visible();
+            self.render();
+        }
 
-            self.sources.push(SourceSeries {
-                name: label,
-                x_name,
-                visible: true,
-                xs: new_xs,
-                ys: new_ys,
-                color,
-                draw_mode: DrawMode::Lines,
-                y_min,
-                y_max,
-            });
+        /// Remove ALL series from this graph and re-render (returns to empty state).
+        #[wasm_bindgen]
+        pub fn clear_series(&mut self) {
+            self.sources.clear();
+            self.sample_undo.clear();
+            self.despike_preview = None;
+            self.recompute_plotted_cols();
+            self.rebuild_visible();
+            self.render();
+        }
 
-            // Transform output rarely matches a file column name, so it's
-            // typically omitted from the Table view (acceptable per spec) —
-            // but the source's x column should still count if it's the
-            // first plotted series to reference it.
+        /// Benchmark data: replace this graph's series with `series` synthetic
+        /// series of `kind` (`"sine-sweep"`, `"noise"`, `"step-train"`),
+        /// `points` samples each on a shared 1 kHz X. Errs past
+        /// `MAX_SYNTHETIC_POINTS` in total.
+        #[wasm_bindgen]
+        pub fn load_synthetic(&mut self, kind: &str, points: usize, series: usize) -> Result<(), JsValue> {
+            let kind = SyntheticKind::parse(kind)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown synthetic dataset: {kind}")))?;
+            let total = points.saturating_mul(series);
+            if points == 0 || series == 0 || total > MAX_SYNTHETIC_POINTS {
+                return Err(JsValue::from_str(&format!(
+                    "synthetic data must have 1 to {MAX_SYNTHETIC_POINTS} points in total"
+                )));
+            }
+            let xs: Arc<[f64]> = synthetic_xs(points).into();
+            self.sources.clear();
+            self.sample_undo.clear();
+            self.despike_preview = None;
+            self.x_is_time = false;
+            for i in 0..series {
+                let ys = synthetic(kind, points, i as u64);
+                let (y_min, y_max) = compute_y_bounds(&ys);
+                self.sources.push(SourceSeries {
+                    name: format!("{} {}", kind.label(), i + 1),
+                    x_name: "t [s]".to_string(),
+                    visible: true,
+                    xs: xs.clone(),
+                    ys: ys.into(),
+                    color: palette_color(i),
+                    draw_mode: DrawMode::Lines,
+                    y_min,
+                    y_max,
+                    wrap_period: None,
+                    derivation: None,
+                    reference: false,
+                    labels: None,
+                    gaps: None,
+                    error: None,
+                    hold_last: false,
+                    no_hover: false,
+                    layer: SeriesLayer::Normal,
+                    fill: None,
+                    locked_color: None,
+                    packed: None,
+                    align: SeriesAlign::default(),
+                    column: None,
+                });
+            }
             self.recompute_plotted_cols();
-            // auto_fit calls rebuild_visible + render — new series is immediately visible.
             self.auto_fit();
             Ok(())
         }
 
-        // ── Formula columns ───────────────────────────────────────────────────
+        /// The benchmark's pan/zoom script, `frames` steps long
+        /// (`oxideplot_core::render::benchmark::BenchStep`s).
+        #[wasm_bindgen]
+        pub fn bench_script(frames: usize) -> Result<JsValue, JsValue> {
+            serde_wasm_bindgen::to_value(&benchmark::bench_script(frames)).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
 
-        /// Create a new derived column from an arithmetic/logical expression
-        /// over existing columns (see `oxideplot_core::processing::expr` for
-        /// grammar — e.g. `sqrt("raw_ax"^2 + "raw_ay"^2 + "raw_az"^2)`),
-        /// append it to the loaded dataset, and plot it against the current
-        /// X axis (the `x_name` of `self.sources[0]`).
-        ///
-        /// `name` — the new column's name; falls back to `expr` if blank.
-        /// `expr` — the formula string, parsed by `parse_expr`.
-        ///
-        /// Returns the updated `FileMeta` (dataset now includes the new
-        /// column), serialized the same way `load_file_bytes` returns its.
+        /// Mean/percentile summary of measured frame times (ms).
         #[wasm_bindgen]
-        pub fn derive_column(&mut self, name: String, expr: String) -> Result<JsValue, JsValue> {
-            // A formula column plots against the current X — need an existing
-            // series to know what that X axis is.
-            let x_name = self.sources.first().map(|s| s.x_name.clone()).ok_or_else(|| {
-                JsValue::from_str(
-                    "Plot at least one series first — a formula column needs an X axis.",
-                )
-            })?;
+        pub fn bench_frame_stats(times: Vec<f64>) -> Result<JsValue, JsValue> {
+            serde_wasm_bindgen::to_value(&benchmark::frame_stats(&times)).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
 
-            // ── Immutable phase: evaluate the expression + read X, producing
-            // only owned Vecs, so the borrow of `self.loaded` ends here and we
-            // can re-borrow mutably below to append the new column/series.
-            // `values` is one entry per data row (row-aligned, for the
-            // appended column_data); `xs`/`ys` are the finite-pair-filtered
-            // subset used for the plotted series.
-            let (values, xs, ys, col_name) = {
-                let data = self
-                    .loaded
-                    .as_ref()
-                    .ok_or_else(|| JsValue::from_str("No file loaded."))?;
+        /// Version of the plotting engine, for benchmark reports.
+        #[wasm_bindgen]
+        pub fn engine_version() -> String {
+            env!("CARGO_PKG_VERSION").to_string()
+        }
 
-                let ast = parse_expr(data, &expr).map_err(|e| JsValue::from_str(&e))?;
-                let mut refs = HashSet::new();
-                collect_expr_cols(&ast, &mut refs);
-                let colvals: HashMap<usize, Vec<f64>> = refs
-                    .iter()
-                    .filter(|&&ci| ci < data.column_data.len())
-                    .map(|&ci| (ci, column_to_f64(&data.column_data[ci]).0))
-                    .collect();
-                let values: Vec<f64> = (0..data.row_count)
-                    .map(|r| eval_expr(&ast, &colvals, r))
-                    .collect();
+        /// Pack every source series whose arrays no other graph or ghost
+        /// holds, freeing the raw samples until `wake`. Called for graphs
+        /// that have been idle off-screen; a streaming graph is left alone.
+        /// Returns the bytes saved.
+        #[wasm_bindgen]
+        pub fn hibernate(&mut self) -> f64 {
+            if self.stream.is_some() || self.sources.iter().any(|s| s.packed.is_some()) {
+                return 0.0;
+            }
+            // A shared array is only freed once every holder lets go, so
+            // skip series whose arrays are referenced from elsewhere.
+            let mut holders: HashMap<*const f64, usize> = HashMap::new();
+            for src in &self.sources {
+                *holders.entry(src.xs.as_ptr()).or_default() += 1;
+                *holders.entry(src.ys.as_ptr()).or_default() += 1;
+            }
+            let only_here = |a: &Arc<[f64]>| Arc::strong_count(a) == holders[&a.as_ptr()];
+            let mut packed: HashMap<*const f64, Arc<CompressedSeries>> = HashMap::new();
+            let mut saved = 0usize;
+            for src in &mut self.sources {
+                if src.xs.is_empty() || !only_here(&src.xs) || !only_here(&src.ys) {
+                    continue;
+                }
+                let mut pack = |a: &Arc<[f64]>| {
+                    packed
+                        .entry(a.as_ptr())
+                        .or_insert_with(|| {
+                            let c = Arc::new(CompressedSeries::compress(a));
+                            saved += (a.len() * 8).saturating_sub(c.packed_bytes());
+                            c
+                        })
+                        .clone()
+                };
+                let pair = (pack(&src.xs), pack(&src.ys));
+                src.packed = Some(pair);
+                src.xs = Arc::from(Vec::new());
+                src.ys = Arc::from(Vec::new());
+            }
+            if saved > 0 {
+                self.rebuild_visible();
+            }
+            saved as f64
+        }
 
-                // Resolve the current X column name to its file-column index,
-                // then read it as X exactly like set_series: datetime first,
-                // f64 fallback.
-                let x_col_idx = data
-                    .columns
-                    .iter()
-                    .position(|c| c == &x_name)
-                    .ok_or_else(|| JsValue::from_str("current X column not found in loaded data"))?;
-                let x_col_data = &data.column_data[x_col_idx];
-                let x_vals: Vec<f64> = if let Some((ts, _)) = column_to_timestamps(x_col_data) {
-                    ts
-                } else {
-                    column_to_f64(x_col_data).0
+        /// Skip (or resume) per-frame work while the graph is scrolled out
+        /// of view: view changes still apply, but the downsampled series
+        /// are only rebuilt, and the frame drawn, once it is back.
+        #[wasm_bindgen]
+        pub fn set_culled(&mut self, culled: bool) {
+            if self.culled == culled {
+                return;
+            }
+            self.culled = culled;
+            if !culled && std::mem::take(&mut self.cull_stale) {
+                self.rebuild_visible();
+            }
+            if !culled {
+                self.render();
+            }
+        }
+
+        /// Whether `hibernate` has packed any series.
+        #[wasm_bindgen]
+        pub fn is_hibernating(&self) -> bool {
+            self.sources.iter().any(|s| s.packed.is_some())
+        }
+
+        /// Unpack series packed by `hibernate` and redraw. A no-op when
+        /// nothing is packed.
+        #[wasm_bindgen]
+        pub fn wake(&mut self) {
+            if !self.is_hibernating() {
+                return;
+            }
+            let mut unpacked: HashMap<*const CompressedSeries, Arc<[f64]>> = HashMap::new();
+            for src in &mut self.sources {
+                let Some((xs, ys)) = src.packed.take() else { continue };
+                let mut unpack = |c: &Arc<CompressedSeries>| {
+                    unpacked.entry(Arc::as_ptr(c)).or_insert_with(|| c.decompress().into()).clone()
                 };
+                src.xs = unpack(&xs);
+                src.ys = unpack(&ys);
+            }
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Freeze the visible series as ghost traces: greyed copies drawn
+        /// beneath whatever is plotted next (before/after comparisons while
+        /// tuning). Adds to any existing ghosts; returns how many were frozen.
+        #[wasm_bindgen]
+        pub fn freeze_traces(&mut self) -> usize {
+            let frozen: Vec<SourceSeries> = self
+                .sources
+                .iter()
+                .filter(|s| s.visible)
+                .map(|s| SourceSeries { color: ghost_color(s.color), ..s.clone() })
+                .collect();
+            let n = frozen.len();
+            self.ghosts.extend(frozen);
+            self.rebuild_visible();
+            self.render();
+            n
+        }
+
+        /// Drop every ghost trace and re-render.
+        #[wasm_bindgen]
+        pub fn clear_frozen(&mut self) {
+            self.ghosts.clear();
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Cap the number of simultaneously drawn series (at least 1) and
+        /// re-render. Visible series past the cap, in list order, are skipped.
+        #[wasm_bindgen]
+        pub fn set_max_drawn_series(&mut self, n: usize) {
+            self.max_drawn_series = n.max(1);
+            self.rebuild_visible();
+            self.render();
+        }
+
+        #[wasm_bindgen]
+        pub fn max_drawn_series(&self) -> usize {
+            self.max_drawn_series
+        }
+
+        /// How many visible series are not drawn because of the cap.
+        #[wasm_bindgen]
+        pub fn series_overflow(&self) -> usize {
+            self.sources.iter().filter(|s| s.visible).count().saturating_sub(self.max_drawn_series)
+        }
+
+        /// Number of ghost traces.
+        #[wasm_bindgen]
+        pub fn frozen_count(&self) -> usize {
+            self.ghosts.len()
+        }
+
+        /// Move the series at `from` to position `to`, shifting others, and re-render.
+        /// This changes z-order: later indices render on top.
+        #[wasm_bindgen]
+        pub fn move_series(&mut self, from: usize, to: usize) {
+            let n = self.sources.len();
+            if from >= n || to >= n {
+                return;
+            }
+            let src = self.sources.remove(from);
+            // After remove(from), indices >= from shift down by one.
+            // When to > from we must subtract 1 so the element lands at the
+            // intended logical position (non-adjacent moves are also correct).
+            let adjusted_to = if to > from { to - 1 } else { to };
+            self.sources.insert(adjusted_to, src);
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Return the histogram of the source series at `source_index` as
+        /// `{ counts, bin_centers, min, max, n }`.
+        ///
+        /// `nbins` is clamped to `[2, 200]` by the core `histogram` function.
+        /// Throws (JS exception) if `source_index` is out of range or the
+        /// series has fewer than 2 finite values.
+        #[wasm_bindgen]
+        pub fn series_histogram(&self, source_index: usize, nbins: usize) -> Result<JsValue, JsValue> {
+            let src = self
+                .sources
+                .get(source_index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            let h = oxideplot_core::processing::histogram::histogram(&src.ys, nbins)
+                .ok_or_else(|| JsValue::from_str("not enough finite values for a histogram"))?;
+            let data = HistogramData {
+                counts: h.counts,
+                bin_centers: h.bin_centers,
+                min: h.min,
+                max: h.max,
+                n: h.n,
+            };
+            serde_wasm_bindgen::to_value(&data).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Return the box-plot summary of the source series at `source_index`:
+        /// `{ n, min, q1, median, q3, max, mean, whisker_lo, whisker_hi,
+        /// outliers, n_outliers, density }`. Whiskers reach the furthest
+        /// samples within 1.5 IQR of the box. Throws if `source_index` is out
+        /// of range or the series has no finite values.
+        #[wasm_bindgen]
+        pub fn series_box_plot(&self, source_index: usize) -> Result<JsValue, JsValue> {
+            let src = self
+                .sources
+                .get(source_index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            let b = BoxPlot::compute(&src.ys).ok_or_else(|| JsValue::from_str("no finite values"))?;
+            let n_outliers = b.outliers.len();
+            let stride = n_outliers.div_ceil(MAX_OUTLIERS).max(1);
+            let data = BoxPlotData {
+                n: b.stats.count,
+                min: b.stats.min,
+                q1: b.stats.q1,
+                median: b.stats.median,
+                q3: b.stats.q3,
+                max: b.stats.max,
+                mean: b.stats.mean,
+                whisker_lo: b.whisker_lo,
+                whisker_hi: b.whisker_hi,
+                outliers: b.outliers.into_iter().step_by(stride).collect(),
+                n_outliers,
+                density: b.density,
+            };
+            serde_wasm_bindgen::to_value(&data).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Return the power spectral density of the source series at
+        /// `source_index` as `{ freqs, power, sample_rate }`.
+        ///
+        /// `sample_rate` (Hz) is used if given; otherwise it is inferred from
+        /// the series' `xs` (1 / median positive dt, 1.0 fallback).
+        /// Throws (JS exception) if `source_index` is out of range or the
+        /// series has too few samples for a spectrum.
+        #[wasm_bindgen]
+        pub fn series_spectrum(&self, source_index: usize, sample_rate: Option<f64>) -> Result<JsValue, JsValue> {
+            let src = self
+                .sources
+                .get(source_index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            let fs = sample_rate.unwrap_or_else(|| sample_rate_from_xs(&src.xs));
+            let (freqs, power) = spectral::compute_psd(&src.ys, fs);
+            if freqs.is_empty() {
+                return Err(JsValue::from_str("not enough samples for a spectrum"));
+            }
+            serde_wasm_bindgen::to_value(&SpectrumData { freqs, power, sample_rate: fs })
+                .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Return the one-sided amplitude spectrum of the source series at
+        /// `source_index` as `{ freqs, amplitude, window, sample_rate }`.
+        ///
+        /// `window` is `"hann"`, `"hamming"` or `"rectangular"`; peaks read in
+        /// the series' Y units whatever the window. `sample_rate` is handled as
+        /// in `series_spectrum`. Throws (JS exception) for an unknown window,
+        /// an out-of-range index or too few samples.
+        #[wasm_bindgen]
+        pub fn series_amplitude_spectrum(&self, source_index: usize, window: String, sample_rate: Option<f64>) -> Result<JsValue, JsValue> {
+            let window = fft::Window::parse(&window)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown FFT window '{window}'")))?;
+            let src = self
+                .sources
+                .get(source_index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            let fs = sample_rate.unwrap_or_else(|| sample_rate_from_xs(&src.xs));
+            let (freqs, amplitude) = fft::magnitude_spectrum(&src.ys, fs, window);
+            if freqs.is_empty() {
+                return Err(JsValue::from_str("not enough samples for a spectrum"));
+            }
+            serde_wasm_bindgen::to_value(&AmplitudeSpectrumData { freqs, amplitude, window: window.as_str(), sample_rate: fs })
+                .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Return a short-time FFT magnitude spectrogram of the source series
+        /// at `source_index` as
+        /// `{ frames, bins, n_frames, sample_rate, nyquist, duration_s }`,
+        /// where `frames[frame][bin]` is the magnitude at that frame/bin.
+        ///
+        /// `window` is clamped to `[16, 4096]` by the core `compute_spectrogram`
+        /// call path. `sample_rate` (Hz) is used if given; otherwise it is
+        /// inferred from the series' `xs` (1 / median positive dt, 1.0
+        /// fallback). Throws (JS exception) if `source_index` is out of range
+        /// or the series has too few samples for a spectrogram.
+        #[wasm_bindgen]
+        pub fn series_spectrogram(&self, source_index: usize, window: usize, sample_rate: Option<f64>) -> Result<JsValue, JsValue> {
+            let src = self
+                .sources
+                .get(source_index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            let fs = sample_rate.unwrap_or_else(|| sample_rate_from_xs(&src.xs));
+            let win = window.clamp(16, 4096);
+            let (frames, bins) = spectral::compute_spectrogram(&src.ys, win);
+            if frames.is_empty() || bins == 0 {
+                return Err(JsValue::from_str("not enough samples for a spectrogram"));
+            }
+            let n_frames = frames.len();
+            let duration_s = n_frames as f64 * (win as f64 / 2.0) / fs;
+            serde_wasm_bindgen::to_value(&SpectrogramData {
+                frames, bins, n_frames, sample_rate: fs, nyquist: fs / 2.0, duration_s,
+            })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        // ── Appearance settings ───────────────────────────────────────────────
+
+        /// Set the line width for all series and re-render.
+        #[wasm_bindgen]
+        pub fn set_line_width(&mut self, w: f32) {
+            self.line_width = w;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Set the point radius for all series and re-render.
+        #[wasm_bindgen]
+        pub fn set_point_radius(&mut self, r: f32) {
+            self.point_radius = r;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Enable or disable normalized multi-unit overlay mode.
+        ///
+        /// When ON, each series' Y values are mapped to [0, 1] using the
+        /// range of its unit's axis (manual, or the global min/max of the
+        /// unit's visible series), so series with very different
+        /// units/scales overlay comparably, and `axis_ticks` reports one
+        /// labelled axis per unit.  The Y view is set to [-0.05, 1.05].
+        ///
+        /// When OFF (default), raw Y values are used and the Y axis shows real
+        /// engineering units.  Turning off calls `auto_fit()` to restore the
+        /// original Y range.
+        ///
+        /// The raw `ys` data in each `SourceSeries` is NEVER mutated; the
+        /// normalization is applied only when building GPU points in
+        /// `rebuild_visible`.  CSV export always returns the original values.
+        #[wasm_bindgen]
+        pub fn set_normalized(&mut self, on: bool) {
+            self.normalized = on;
+            if on {
+                self.stack_mode = StackMode::Off;
+            }
+            self.auto_fit();
+        }
+
+        /// Stack the drawn series as filled areas: `"stacked"` (cumulative
+        /// sums), `"percent"` (each X scaled to 100 %) or `"off"`.
+        ///
+        /// Series stack per inferred unit, in list order, after resampling
+        /// onto one X grid per stack (see `oxideplot_core::processing::stacking`).
+        /// Stacking turns normalized mode off and hides uncertainty display;
+        /// the Y view is re-fitted to the stacked totals.
+        #[wasm_bindgen]
+        pub fn set_stack_mode(&mut self, mode: String) -> Result<(), JsValue> {
+            self.stack_mode = StackMode::parse(&mode)
+                .ok_or_else(|| JsValue::from_str(&format!("Unknown stack mode {mode:?}")))?;
+            if self.stack_mode != StackMode::Off {
+                self.normalized = false;
+            }
+            self.auto_fit();
+            Ok(())
+        }
+
+        /// Current stack mode (`"off"`, `"stacked"` or `"percent"`).
+        #[wasm_bindgen]
+        pub fn stack_mode(&self) -> String {
+            self.stack_mode.as_str().to_string()
+        }
+
+        /// Lay out the graph's bar series (draw mode `"bar"`): `"grouped"`
+        /// side by side in each X slot, or `"stacked"` on top of each other
+        /// (positive values up, negative down). The Y view is re-fitted.
+        #[wasm_bindgen]
+        pub fn set_bar_layout(&mut self, layout: String) -> Result<(), JsValue> {
+            self.bar_layout = BarLayout::parse(&layout)
+                .ok_or_else(|| JsValue::from_str(&format!("Unknown bar layout {layout:?}")))?;
+            self.auto_fit();
+            Ok(())
+        }
+
+        /// Current bar layout (`"grouped"` or `"stacked"`).
+        #[wasm_bindgen]
+        pub fn bar_layout(&self) -> String {
+            self.bar_layout.as_str().to_string()
+        }
+
+        /// Colour series by their inferred unit: one hue family per unit
+        /// (temperatures reds, pressures blues, …) with lightness stepping
+        /// through the unit's series; kept up as series are added. Turning
+        /// it off restores the palette colours. Picking a series colour also
+        /// turns it off. Reference curves keep their colour.
+        #[wasm_bindgen]
+        pub fn set_color_by_unit(&mut self, on: bool) {
+            self.color_by_unit = on;
+            if !on {
+                for (i, src) in self.sources.iter_mut().enumerate().filter(|(_, s)| !s.reference) {
+                    src.color = palette_color(i);
+                }
+            }
+            self.rebuild_visible();
+            self.render();
+        }
+
+        #[wasm_bindgen]
+        pub fn color_by_unit(&self) -> bool {
+            self.color_by_unit
+        }
+
+        /// Colour series from the workspace colour registry (a serialised
+        /// `ColorRegistry`, shared by every graph) so a label gets the same
+        /// colour in every graph. A series recoloured by hand since the last
+        /// call becomes an override for its label first. Returns the updated
+        /// registry for the app to pass on to the next graph and persist.
+        /// Turns colour-by-unit off; reference curves keep their colour.
+        #[wasm_bindgen]
+        pub fn apply_color_registry(&mut self, registry: JsValue) -> Result<JsValue, JsValue> {
+            let mut reg: ColorRegistry = if registry.is_undefined() || registry.is_null() {
+                ColorRegistry::default()
+            } else {
+                serde_wasm_bindgen::from_value(registry).map_err(|e| JsValue::from_str(&e.to_string()))?
+            };
+            let palette: Vec<[f32; 3]> = PALETTE.iter().map(|c| [c[0], c[1], c[2]]).collect();
+            for src in self.sources.iter_mut().filter(|s| !s.reference) {
+                let current = [src.color[0], src.color[1], src.color[2]];
+                if src.locked_color.is_some_and(|c| c != current) {
+                    reg.set_override(&src.name, current);
+                }
+                let c = reg.color_for(&src.name, &palette);
+                src.color = [c[0], c[1], c[2], src.color[3]];
+                src.locked_color = Some(c);
+            }
+            self.color_by_unit = false;
+            self.rebuild_visible();
+            self.render();
+            serde_wasm_bindgen::to_value(&reg).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Convert every series whose detected unit is `from` to `to` (e.g.
+        /// all °F channels to °C) and record the conversion, so file columns
+        /// read into this graph later, and linked projects reopened, repeat
+        /// it. Converted series are renamed `"name [to]"`. Returns how many
+        /// series were converted; throws for unknown or incompatible units.
+        #[wasm_bindgen]
+        pub fn convert_unit(&mut self, from: String, to: String) -> Result<usize, JsValue> {
+            let conv = UnitConversion::new(&from, &to).map_err(|e| JsValue::from_str(&e))?;
+            let mut n = 0;
+            let mut renamed: Vec<(String, String)> = Vec::new();
+            for src in self.sources.iter_mut().filter(|s| !s.reference && infer_unit(&s.name) == from) {
+                let ys = conv.apply(&src.ys);
+                let (y_min, y_max) = compute_y_bounds(&ys);
+                src.ys = ys.into();
+                src.y_min = y_min;
+                src.y_max = y_max;
+                if src.derivation.is_none() && src.column.is_none() {
+                    src.column = Some(src.name.clone());
+                }
+                let name = conv.rename(&src.name);
+                renamed.push((std::mem::replace(&mut src.name, name.clone()), name));
+                n += 1;
+            }
+            // Keep fills between series pointing at their renamed partners.
+            for src in &mut self.sources {
+                if let Some(AreaFill::Between(other)) = &mut src.fill {
+                    if let Some((_, new)) = renamed.iter().find(|(old, _)| *old == *other) {
+                        *other = new.clone();
+                    }
+                }
+            }
+            if n > 0 {
+                if !self.unit_conversions.contains(&conv) {
+                    self.unit_conversions.push(conv);
+                }
+                self.auto_fit();
+            }
+            Ok(n)
+        }
+
+        /// Units of the plotted series that can be converted, as
+        /// `[{ unit, count, targets }]` sorted by unit.
+        #[wasm_bindgen]
+        pub fn detected_units(&self) -> Result<JsValue, JsValue> {
+            let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+            for src in self.sources.iter().filter(|s| !s.reference) {
+                *counts.entry(infer_unit(&src.name)).or_default() += 1;
+            }
+            let units: Vec<DetectedUnit> = counts
+                .into_iter()
+                .map(|(unit, count)| DetectedUnit { targets: unit_convert::targets(&unit), unit, count })
+                .filter(|u| !u.targets.is_empty())
+                .collect();
+            serde_wasm_bindgen::to_value(&units).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// The recorded unit conversions, `[{ from, to }]` in order.
+        #[wasm_bindgen]
+        pub fn unit_conversions(&self) -> Result<JsValue, JsValue> {
+            serde_wasm_bindgen::to_value(&self.unit_conversions).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Stop repeating the recorded conversions on future imports (series
+        /// already converted stay as they are).
+        #[wasm_bindgen]
+        pub fn clear_unit_conversions(&mut self) {
+            self.unit_conversions.clear();
+        }
+
+        /// Forget the registry colours applied to this graph, so its series
+        /// are no longer tracked for hand-picked overrides (colour lock off).
+        #[wasm_bindgen]
+        pub fn release_color_registry(&mut self) {
+            for src in &mut self.sources {
+                src.locked_color = None;
+            }
+        }
+
+        /// Set the autoscale strategy for the (non-normalized) Y bounds.
+        ///
+        /// `mode` is `"robust"` (clip to 1st/99th percentiles) or `"minmax"`
+        /// (raw data extremes; the default). Unrecognised values fall back to
+        /// MinMax. Re-fits the view via `auto_fit()` (which re-renders).
+        #[wasm_bindgen]
+        pub fn set_autoscale_mode(&mut self, mode: String) {
+            self.autoscale_mode = if mode == "robust" {
+                AutoscaleMode::Robust
+            } else {
+                AutoscaleMode::MinMax
+            };
+            self.auto_fit();
+        }
+
+        /// Set the Y-axis scale.
+        ///
+        /// `mode` is `"log"` (plot log10(y), dropping y<=0) or `"linear"` (the
+        /// default). Unrecognised values fall back to Linear. Re-fits the view
+        /// via `auto_fit()` (which re-renders).
+        #[wasm_bindgen]
+        pub fn set_y_scale(&mut self, mode: String) {
+            self.y_scale = if mode == "log" {
+                YScale::Log
+            } else {
+                YScale::Linear
+            };
+            self.auto_fit();
+        }
+
+        /// Set the downsampling (decimation) strategy for the visible render series.
+        ///
+        /// `mode` is one of `"minmax"` (default), `"lttb"`, or `"none"`.
+        /// Unrecognised values fall back to MinMax. Rebuilds the visible series
+        /// at the current view and re-renders (bounds are unchanged, so no
+        /// `auto_fit()`).
+        #[wasm_bindgen]
+        pub fn set_downsample_mode(&mut self, mode: String) {
+            self.downsample_mode = DownsampleMode::parse(&mode);
+            self.rebuild_visible();
+            self.render();
+        }
+
+        // ── Export ────────────────────────────────────────────────────────────
+
+        /// Export all source series as a CSV string.
+        ///
+        /// The header row is the X column name (from the first series, or `"x"` if
+        /// no sources are loaded) followed by each series' Y name.  All sources are
+        /// assumed to share the same X axis; for row `i` the X value from each
+        /// source's own `xs[i]` is used (they are identical in practice).
+        ///
+        /// Data rows contain the full, un-downsampled values.  For row `i`:
+        /// - column 0: `sources[0].xs[i]` (or the per-series xs[i] if you track
+        ///   independent X axes — currently all sources share one X).
+        /// - column k+1: `sources[k].ys[i]`, or an empty cell if that series is
+        ///   shorter than the maximum length.
+        ///
+        /// f64 values are formatted with up to 15 significant digits, dropping
+        /// trailing zeros (`{:.15}` then trimmed).
+        #[wasm_bindgen]
+        pub fn export_csv(&self) -> String {
+            if self.sources.is_empty() {
+                return String::new();
+            }
+
+            // Header row
+            let mut rows: Vec<String> = Vec::new();
+            let x_header = self.x_header(&self.sources[0].x_name);
+            let header = std::iter::once(x_header.as_str())
+                .chain(self.sources.iter().map(|s| s.name.as_str()))
+                .map(csv_escape)
+                .collect::<Vec<_>>()
+                .join(",");
+            rows.push(header);
+
+            // Determine the maximum row count across all series.
+            let max_len = self.sources.iter().map(|s| s.xs.len()).max().unwrap_or(0);
+
+            // Use the first source's xs as the shared X axis.
+            let ref_xs = &self.sources[0].xs;
+
+            for i in 0..max_len {
+                let x_val = if i < ref_xs.len() {
+                    format_f64(ref_xs[i])
+                } else {
+                    String::new()
+                };
+
+                let mut cols = vec![x_val];
+                for src in &self.sources {
+                    let y_val = if i < src.ys.len() {
+                        format_f64(src.ys[i])
+                    } else {
+                        String::new()
+                    };
+                    cols.push(y_val);
+                }
+                rows.push(cols.join(","));
+            }
+
+            rows.join("\n")
+        }
+
+        // ── Table API ─────────────────────────────────────────────────────────
+
+        /// Return column metadata as `[{ name: string, numeric: boolean }]`.
+        ///
+        /// Restricted to the plotted columns (`self.plotted_cols`) when any
+        /// series are plotted and at least one of their column names matched
+        /// the file; otherwise every file column is returned. Indices here
+        /// are "display indices" — positions in this returned array — which
+        /// `table_set_sort` / `table_set_column_filter` / `table_window`
+        /// accept and internally translate back to real file-column indices.
+        #[wasm_bindgen]
+        pub fn table_columns(&mut self) -> Result<JsValue, JsValue> {
+            // The table is about to show these columns — read in any that a
+            // lazily-loaded file hasn't materialized yet.
+            if let Some(d) = &self.loaded {
+                if d.lazy.is_some() {
+                    let shown = self.display_indices(d);
+                    self.materialize_cols(&shown)?;
+                    self.rebuild_table_index();
+                }
+            }
+            let x_name = self.sources.first().map(|s| s.x_name.as_str());
+            let cols: Vec<(String, bool)> = match &self.loaded {
+                None => vec![],
+                Some(d) => self
+                    .display_indices(d)
+                    .into_iter()
+                    .map(|i| {
+                        let name = if Some(d.columns[i].as_str()) == x_name {
+                            self.x_header(&d.columns[i])
+                        } else {
+                            d.columns[i].clone()
+                        };
+                        (name, self.table_query.numeric_cols.get(i).copied().unwrap_or(false))
+                    })
+                    .collect(),
+            };
+            let cols: Vec<TableColumnInfo> = decimated_columns(&cols, self.effective_decimation())
+                .into_iter()
+                .map(|(name, numeric)| TableColumnInfo { name, numeric })
+                .collect();
+            serde_wasm_bindgen::to_value(&cols).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Set sort: DISPLAY column index (position in `table_columns()`'s
+        /// output) + direction; rebuilds the view index. Translated to the
+        /// real file-column index before being stored.
+        #[wasm_bindgen]
+        pub fn table_set_sort(&mut self, col: usize, ascending: bool) {
+            let orig = self.to_orig_col(col);
+            self.table_query.sort = Some((orig, ascending));
+            self.rebuild_table_index();
+        }
+
+        /// Clear sort (restore original row order); rebuilds the view index.
+        #[wasm_bindgen]
+        pub fn table_clear_sort(&mut self) {
+            self.table_query.sort = None;
+            self.rebuild_table_index();
+        }
+
+        /// Set global search term; rebuilds the view index.
+        #[wasm_bindgen]
+        pub fn table_set_search(&mut self, term: String) {
+            self.table_query.search = term;
+            self.rebuild_table_index();
+        }
+
+        /// Set or clear a per-column filter. `col` is a DISPLAY column index
+        /// (see `table_columns`), translated to the real file-column index
+        /// before being stored.
+        ///
+        /// `spec` is a JS value of shape:
+        /// - `null` / `undefined` → remove the filter for `col`
+        /// - `{ text: string }` → `ColFilter::Text`
+        /// - `{ min?: number, max?: number }` → `ColFilter::Range`
+        #[wasm_bindgen]
+        pub fn table_set_column_filter(&mut self, col: usize, spec: JsValue) -> Result<(), JsValue> {
+            let orig = self.to_orig_col(col);
+            if spec.is_null() || spec.is_undefined() {
+                self.table_query.col_filters.remove(&orig);
+            } else {
+                let fs: FilterSpec = serde_wasm_bindgen::from_value(spec)
+                    .map_err(|e| JsValue::from_str(&e.to_string()))?;
+                if let Some(text) = fs.text {
+                    self.table_query.col_filters.insert(orig, ColFilter::Text(text));
+                } else if fs.min.is_some() || fs.max.is_some() {
+                    self.table_query.col_filters.insert(orig, ColFilter::Range { min: fs.min, max: fs.max });
+                } else {
+                    // All None → clear the filter.
+                    self.table_query.col_filters.remove(&orig);
+                }
+            }
+            self.rebuild_table_index();
+            Ok(())
+        }
+
+        /// Return the number of rows visible under the current filters/sort
+        /// (table rows: aggregated under a decimation, raw when drilled down).
+        #[wasm_bindgen]
+        pub fn table_row_count(&self) -> usize {
+            let index = self.shown_table_index();
+            self.effective_decimation().row_count(index.len())
+        }
+
+        /// Aggregate table rows: `mode` `"off"`, `"nth"` (every `n`th row) or
+        /// `"buckets"` (min/mean/max of every `n` rows; see `Decimation`).
+        /// Leaves any drill-down. Sort and filters still apply to the raw rows
+        /// underneath; while bucketed, `table_columns` lists the aggregate
+        /// columns, so display indices no longer address file columns.
+        #[wasm_bindgen]
+        pub fn table_set_decimation(&mut self, mode: String, n: usize) -> Result<(), JsValue> {
+            self.table_decimation = Decimation::parse(&mode, n)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown table decimation \"{mode}\"")))?;
+            self.table_drill = None;
+            Ok(())
+        }
+
+        /// `{ mode, n, drilled }` for the current decimation.
+        #[wasm_bindgen]
+        pub fn table_decimation(&self) -> JsValue {
+            let info = TableDecimationInfo {
+                mode: self.table_decimation.as_str(),
+                n: self.table_decimation.step(),
+                drilled: self.table_drill.is_some(),
+            };
+            serde_wasm_bindgen::to_value(&info).unwrap_or(JsValue::NULL)
+        }
+
+        /// Show the raw rows behind aggregated table rows `first..=last`
+        /// (either order) until `table_drill_clear` or the next filter/sort.
+        #[wasm_bindgen]
+        pub fn table_drill(&mut self, first: usize, last: usize) {
+            if self.table_drill.is_none() && self.table_decimation != Decimation::Off {
+                self.table_drill = Some(self.table_decimation.raw_span(first, last, self.table_index.len()));
+            }
+        }
+
+        /// Back from a drill-down to the aggregated rows.
+        #[wasm_bindgen]
+        pub fn table_drill_clear(&mut self) {
+            self.table_drill = None;
+        }
+
+        /// Return a window of rows `[start, start+count)` from the current view
+        /// as `string[][]` — each inner array is one row, each string is one
+        /// cell, projected onto the same (plotted-columns-only, or all-columns
+        /// fallback) column set and order as `table_columns()`.
+        #[wasm_bindgen]
+        pub fn table_window(&self, start: usize, count: usize) -> Result<JsValue, JsValue> {
+            let index = self.shown_table_index();
+            let dec = self.effective_decimation();
+            let rows = self
+                .loaded
+                .as_ref()
+                .map(|d| {
+                    let cols = self.display_indices(d);
+                    let mut rows = decimated_window(d, &self.table_query, index, dec, start, count, &cols);
+                    // Enumerated columns show state names (the sort/filter
+                    // still use the raw values). Aggregates stay numeric.
+                    let maps: Vec<(usize, &LabelMap)> = cols
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(j, &c)| self.column_labels(&d.columns[c]).map(|m| (j, m)))
+                        .collect();
+                    if !maps.is_empty() && !matches!(dec, Decimation::Buckets(_)) {
+                        let first = start.min(dec.row_count(index.len()));
+                        let raw = index.iter().skip(first * dec.step()).step_by(dec.step());
+                        for (row, &r) in rows.iter_mut().zip(raw) {
+                            for &(j, map) in &maps {
+                                if let Some(l) = map.label(d.column_data[cols[j]].value(r)) {
+                                    row[j] = l.to_string();
+                                }
+                            }
+                        }
+                    }
+                    rows
+                })
+                .unwrap_or_default();
+            serde_wasm_bindgen::to_value(&rows).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Table row (as counted by `table_row_count`) whose X is nearest
+        /// `x`, for highlighting the hovered sample in a split plot/table
+        /// view; `None` with no X column in the file or no rows.
+        #[wasm_bindgen]
+        pub fn table_row_at_x(&self, x: f64) -> Option<usize> {
+            let d = self.loaded.as_ref()?;
+            let c = self.table_x_col(d)?;
+            // Time-stamped logs are recorded in order; otherwise only an
+            // ascending sort on X guarantees it and every row is checked.
+            let ordered = match self.table_query.sort {
+                Some((col, ascending)) => col == c && ascending,
+                None => self.x_is_time,
+            };
+            let xs = d.column_data[c].to_f64();
+            nearest_table_row(&xs, self.shown_table_index(), self.effective_decimation(), x, ordered)
+        }
+
+        /// X of table row `row` (a bucket's first raw row), for marking the
+        /// hovered table row on the plot.
+        #[wasm_bindgen]
+        pub fn table_row_x(&self, row: usize) -> Option<f64> {
+            let d = self.loaded.as_ref()?;
+            let xs = d.column_data[self.table_x_col(d)?].to_f64();
+            table_row_x(&xs, self.shown_table_index(), self.effective_decimation(), row)
+        }
+
+        // ── Transform API ─────────────────────────────────────────────────────
+
+        /// Append a derived series built from a math transform of an existing source.
+        ///
+        /// `source_index` — index into `self.sources` (bounds-checked).
+        /// `kind`         — one of `"moving_average"`, `"median"`, `"ema"`
+        ///                  (exponential, `window` = span), `"savgol"` (Savitzky–Golay;
+        ///                  `window` + `order`), `"rolling_rms"` (`window`),
+        ///                  `"envelope"` (`mode` `"hilbert"` (default; `window`
+        ///                  = smoothing) or `"peak"` (`window` = release)),
+        ///                  `"derivative"`, `"integral"`,
+        ///                  `"normalize"`, `"resample"`, `"abs"`, `"log"`, `"sqrt"`,
+        ///                  `"log10"`, `"exp"`, `"unwrap"` (angle unwrap; `mode`
+        ///                  `"degrees"` (default) or `"radians"`), `"constant"`
+        ///                  (`y op value`), or one of the X transforms, which keep
+        ///                  Y: `"x_constant"` (`x op value`; scales must be
+        ///                  positive), `"x_zero"` (first sample at zero) and
+        ///                  `"x_unit"` (`mode` = target unit). A time X can
+        ///                  only be offset. `"fit"` plots a least-squares
+        ///                  fit (`mode` = model, `order` = polynomial degree;
+        ///                  see `series_fit`).
+        /// `params`       — optional JS object `{ window?, order?, mode?, method?,
+        ///                  points?, op?, value? }`; pass `null` / `undefined` to use defaults
+        ///                  (`"constant"` requires a finite `value`).
+        ///
+        /// The new series inherits the source's X data and `x_name`, gets a label
+        /// that describes the transform, is assigned the next palette colour, and is
+        /// immediately visible.  `auto_fit()` is called so the new series renders.
+        /// Derivative, integral and abs labels end in the derived unit
+        /// (`d/dx(bat) [V/s]`) when the source's unit is known.
+        #[wasm_bindgen]
+        pub fn add_transform(
+            &mut self,
+            source_index: usize,
+            kind: String,
+            params: JsValue,
+        ) -> Result<(), JsValue> {
+            let src = self.sources.get(source_index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+
+            let p: TransformParams = if params.is_null() || params.is_undefined() {
+                TransformParams::default()
+            } else {
+                serde_wasm_bindgen::from_value(params)
+                    .map_err(|e| JsValue::from_str(&e.to_string()))?
+            };
+
+            let xs   = src.xs.clone();
+            let base = src.name.clone();
+            let mut x_name = src.x_name.clone();
+            let unit = self.transform_unit(&kind, src);
+            // Effective parameters (after defaults) are recorded as each arm
+            // resolves them.
+            let mut deriv = Derivation::new(&kind, Some(&base));
+
+            let (new_xs, new_ys, label) = match kind.as_str() {
+                "moving_average" => {
+                    let w = p.window.unwrap_or(5).max(1);
+                    deriv = deriv.param("window", w);
+                    (xs.clone(), filters::moving_average(&src.ys, w), format!("{base} · MA({w})"))
+                }
+                "median" => {
+                    let w = p.window.unwrap_or(5).max(1);
+                    deriv = deriv.param("window", w);
+                    (xs.clone(), filters::median_filter(&src.ys, w), format!("{base} · median({w})"))
+                }
+                "ema" => {
+                    let w = p.window.unwrap_or(10).max(1);
+                    deriv = deriv.param("window", w);
+                    (xs.clone(), filters::exponential_smoothing(&src.ys, w), format!("{base} · EMA({w})"))
+                }
+                "savgol" => {
+                    let w = p.window.unwrap_or(11).max(3);
+                    let o = p.order.unwrap_or(3).min(w.saturating_sub(2));
+                    deriv = deriv.param("window", w).param("order", o);
+                    (xs.clone(), filters::savitzky_golay(&src.ys, w, o), format!("{base} · SG({w}, {o})"))
+                }
+                "rolling_rms" => {
+                    let w = p.window.unwrap_or(64).max(1);
+                    deriv = deriv.param("window", w);
+                    (xs.clone(), spectral::rolling_rms(&src.ys, w), format!("RMS({base}, {w})"))
+                }
+                "envelope" => {
+                    let w = p.window.unwrap_or(64).max(1);
+                    let mode = if p.mode.as_deref() == Some("peak") { "peak" } else { "hilbert" };
+                    deriv = deriv.param("mode", mode).param("window", w);
+                    if mode == "peak" {
+                        (xs.clone(), spectral::peak_envelope(&src.ys, w), format!("env·peak({base}, {w})"))
+                    } else {
+                        (xs.clone(), spectral::hilbert_envelope(&src.ys, w), format!("env·hilbert({base}, {w})"))
+                    }
+                }
+                "derivative" => (xs.clone(), math_ops::derivative(&src.xs, &src.ys), format!("d/dx({base})")),
+                "integral"   => (xs.clone(), math_ops::integral(&src.xs, &src.ys), format!("∫({base})")),
+                "normalize"  => {
+                    let zscore = p.mode.as_deref() == Some("zscore");
+                    deriv = deriv.param("mode", if zscore { "zscore" } else { "minmax" });
+                    let label = if zscore {
+                        format!("z({base})")
+                    } else {
+                        format!("norm({base})")
+                    };
+                    (xs.clone(), math_ops::normalize(&src.ys, zscore), label)
+                }
+                "fit" => {
+                    let f = self.fit_source(src, p.mode.as_deref().unwrap_or("linear"), p.order.unwrap_or(2))?;
+                    deriv = deriv.param("model", f.model.label()).param("x0", f.x0);
+                    let ys: Vec<f64> = src.xs.iter().map(|&x| f.eval(x)).collect();
+                    (xs.clone(), ys, format!("fit·{}({base})", f.model.label()))
+                }
+                "abs"  => (xs.clone(), math_ops::map_abs(&src.ys),  format!("|{base}|")),
+                "log"  => (xs.clone(), math_ops::map_ln(&src.ys),   format!("log({base})")),
+                "sqrt" => (xs.clone(), math_ops::map_sqrt(&src.ys), format!("√({base})")),
+                "log10" => (xs.clone(), math_ops::map_log10(&src.ys), format!("log10({base})")),
+                "exp"  => (xs.clone(), math_ops::map_exp(&src.ys),  format!("exp({base})")),
+                "unwrap" => {
+                    let period = if p.mode.as_deref() == Some("radians") { std::f64::consts::TAU } else { 360.0 };
+                    deriv = deriv.param("period", period);
+                    (xs.clone(), math_ops::unwrap(&src.ys, period), format!("unwrap({base})"))
+                }
+                "constant" => {
+                    let op_name = p.op.as_deref().unwrap_or("add");
+                    let op = math_ops::MathOp::parse(op_name)
+                        .ok_or_else(|| JsValue::from_str(&format!("unknown operation: {op_name}")))?;
+                    let c = p.value.filter(|v| v.is_finite())
+                        .ok_or_else(|| JsValue::from_str("constant must be a finite number"))?;
+                    deriv = deriv.param("op", op_name).param("value", c);
+                    (xs.clone(), math_ops::apply_constant(&src.ys, op, c), format!("{base} {} {c}", op.symbol()))
+                }
+                "resample" => {
+                    let method = match p.method.as_deref() {
+                        Some("nearest") => interpolation::Method::Nearest,
+                        Some("cubic")   => interpolation::Method::Cubic,
+                        _ => interpolation::Method::Linear,
+                    };
+                    let n = p.points.unwrap_or(500).max(2);
+                    let (grid_xs, grid_ys) = interpolation::resample(&src.xs, &src.ys, n, method);
+                    let mlabel = p.method.as_deref().unwrap_or("linear");
+                    deriv = deriv.param("method", mlabel).param("points", n);
+                    (grid_xs.into(), grid_ys, format!("{base} · resample({mlabel}, {n})"))
+                }
+                "x_zero" | "x_unit" if self.x_is_time => {
+                    return Err(JsValue::from_str("A time X can only be offset; use x_constant with add or sub"));
+                }
+                "x_constant" => {
+                    let op_name = p.op.as_deref().unwrap_or("add");
+                    let op = math_ops::MathOp::parse(op_name)
+                        .ok_or_else(|| JsValue::from_str(&format!("unknown operation: {op_name}")))?;
+                    let c = p.value.filter(|v| v.is_finite())
+                        .ok_or_else(|| JsValue::from_str("constant must be a finite number"))?;
+                    // X must stay increasing for the view's range searches.
+                    let scales = matches!(op, math_ops::MathOp::Multiply | math_ops::MathOp::Divide);
+                    if self.x_is_time && scales {
+                        return Err(JsValue::from_str("A time X can only be offset; use x_constant with add or sub"));
+                    }
+                    if scales && c <= 0.0 {
+                        return Err(JsValue::from_str("X scale must be positive"));
+                    }
+                    deriv = deriv.param("op", op_name).param("value", c);
+                    let label = format!("{base} · x {} {c}", op.symbol());
+                    (math_ops::apply_constant(&src.xs, op, c).into(), src.ys.to_vec(), label)
+                }
+                "x_zero" => (math_ops::zero_start(&src.xs).into(), src.ys.to_vec(), format!("{base} · x − x₀")),
+                "x_unit" => {
+                    let to = p.mode.as_deref().ok_or_else(|| JsValue::from_str("x_unit needs a target unit"))?;
+                    let from = match self.x_title.as_ref().and_then(|t| t.unit.clone()) {
+                        Some(u) => u,
+                        None => infer_unit(&src.x_name),
+                    };
+                    let conv = UnitConversion::new(&from, to).map_err(|e| JsValue::from_str(&e))?;
+                    deriv = deriv.param("from", &from).param("to", to);
+                    x_name = conv.rename(&src.x_name);
+                    (conv.apply(&src.xs).into(), src.ys.to_vec(), format!("{base} · x [{to}]"))
+                }
+                other  => return Err(JsValue::from_str(&format!("unknown transform: {other}"))),
+            };
+
+            let label = match unit {
+                Some(u) => format!("{label} [{u}]"),
+                None => label,
+            };
+
+            // Color: cycle the shared palette by current series count (same palette
+            // ColumnDialog.svelte uses on the JS side).
+            let color = palette_color(self.sources.len());
+
+            // Y bounds: same degenerate-safe rule as set_series.
+            let (y_min, y_max) = compute_y_bounds(&new_ys);
+
+            self.sources.push(SourceSeries {
+                name: label,
+                x_name,
+                visible: true,
+                xs: new_xs,
+                ys: new_ys.into(),
+                color,
+                draw_mode: DrawMode::Lines,
+                y_min,
+                y_max,
+                wrap_period: None,
+                derivation: Some(deriv),
+                reference: false,
+                labels: None,
+                gaps: None,
+                error: None,
+                hold_last: false,
+                no_hover: false,
+                layer: SeriesLayer::Normal,
+                fill: None,
+                locked_color: None,
+                packed: None,
+                align: SeriesAlign::default(),
+                column: None,
+            });
+
+            // Transform output rarely matches a file column name, so it's
+            // typically omitted from the Table view (acceptable per spec) —
+            // but the source's x column should still count if it's the
+            // first plotted series to reference it.
+            self.recompute_plotted_cols();
+            // auto_fit calls rebuild_visible + render — new series is immediately visible.
+            self.auto_fit();
+            Ok(())
+        }
+
+        /// Return `{ name, x_name, xs, ys }` for series `index` — the operand
+        /// side of cross-graph math, read from whichever graph plots it.
+        #[wasm_bindgen]
+        pub fn series_curve(&self, index: usize) -> Result<JsValue, JsValue> {
+            let src = self.sources.get(index)
+                .ok_or_else(|| JsValue::from_str("source index out of range"))?;
+            serde_wasm_bindgen::to_value(&SeriesCurve {
+                name: &src.name,
+                x_name: &src.x_name,
+                xs: &src.xs,
+                ys: &src.ys,
+            })
+            .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Plot `expr` over series taken from any graph (`operands`, an array
+        /// of `series_curve` results named `A`, `B`, … in order) as a new
+        /// series in this graph. Operands are aligned onto operand A's X by
+        /// `align` (`"linear"`, `"hold"` or `"nearest"` within `tolerance`);
+        /// see `oxideplot_core::processing::cross_math`.
+        ///
+        /// `name` falls back to `expr` if blank.
+        #[wasm_bindgen]
+        pub fn add_cross_math(
+            &mut self,
+            name: String,
+            operands: JsValue,
+            expr: String,
+            align: String,
+            tolerance: Option<f64>,
+        ) -> Result<(), JsValue> {
+            let operands: Vec<CrossOperand> = serde_wasm_bindgen::from_value(operands)
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            let mode = cross_math::Align::parse(&align, tolerance)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown alignment: {align}")))?;
+            let pairs: Vec<(&[f64], &[f64])> =
+                operands.iter().map(|o| (o.xs.as_slice(), o.ys.as_slice())).collect();
+            let (xs, ys) = cross_math::eval_across(&pairs, &expr, mode).map_err(|e| JsValue::from_str(&e))?;
+
+            let mut deriv = Derivation::new("cross_math", None).param("expr", &expr).param("align", &align);
+            if let cross_math::Align::Nearest { tolerance } = mode {
+                deriv = deriv.param("tolerance", tolerance);
+            }
+            for (i, o) in operands.iter().enumerate() {
+                deriv = deriv.param(&cross_math::operand_name(i), &o.name);
+            }
+            let label = if name.trim().is_empty() { expr.clone() } else { name.trim().to_string() };
+            // The result is sampled on operand A's X.
+            let x_name = operands[0].x_name.clone();
+            let (y_min, y_max) = compute_y_bounds(&ys);
+            self.sources.push(SourceSeries {
+                name: label,
+                x_name,
+                visible: true,
+                xs: xs.into(),
+                ys: ys.into(),
+                color: palette_color(self.sources.len()),
+                draw_mode: DrawMode::Lines,
+                y_min,
+                y_max,
+                wrap_period: None,
+                derivation: Some(deriv),
+                reference: false,
+                labels: None,
+                gaps: None,
+                error: None,
+                hold_last: false,
+                no_hover: false,
+                layer: SeriesLayer::Normal,
+                fill: None,
+                locked_color: None,
+                packed: None,
+                align: SeriesAlign::default(),
+                column: None,
+            });
+            self.recompute_plotted_cols();
+            self.auto_fit();
+            Ok(())
+        }
+
+        // ── Formula columns ───────────────────────────────────────────────────
+
+        /// Create a new derived column from an arithmetic/logical expression
+        /// over existing columns (see `oxideplot_core::processing::expr` for
+        /// grammar — e.g. `sqrt("raw_ax"^2 + "raw_ay"^2 + "raw_az"^2)`),
+        /// append it to the loaded dataset, and plot it against the current
+        /// X axis (the `x_name` of `self.sources[0]`).
+        ///
+        /// `name` — the new column's name; falls back to `expr` if blank.
+        /// `expr` — the formula string, parsed by `parse_expr`.
+        ///
+        /// Returns the updated `FileMeta` (dataset now includes the new
+        /// column), serialized the same way `load_file_bytes` returns its.
+        #[wasm_bindgen]
+        pub fn derive_column(&mut self, name: String, expr: String) -> Result<JsValue, JsValue> {
+            // A formula column plots against the current X — need an existing
+            // series to know what that X axis is.
+            let x_name = self.sources.first().map(|s| s.x_name.clone()).ok_or_else(|| {
+                JsValue::from_str(
+                    "Plot at least one series first — a formula column needs an X axis.",
+                )
+            })?;
+
+            // ── Immutable phase: evaluate the expression + read X, producing
+            // only owned Vecs, so the borrow of `self.loaded` ends here and we
+            // can re-borrow mutably below to append the new column/series.
+            // `values` is one entry per data row (row-aligned, for the
+            // appended column_data); `xs`/`ys` are the finite-pair-filtered
+            // subset used for the plotted series.
+            let (ast, refs) = {
+                let data = self
+                    .loaded
+                    .as_ref()
+                    .ok_or_else(|| JsValue::from_str("No file loaded."))?;
+                let ast = parse_expr(data, &expr).map_err(|e| JsValue::from_str(&e))?;
+                let mut refs = HashSet::new();
+                collect_expr_cols(&ast, &mut refs);
+                (ast, refs)
+            };
+            // Materialize the referenced columns and the X column of a
+            // lazily-loaded file before reading them.
+            {
+                let data = self.loaded.as_ref().unwrap();
+                let mut wanted: Vec<usize> = refs.iter().copied().collect();
+                wanted.extend(data.columns.iter().position(|c| c == &x_name));
+                self.materialize_cols(&wanted)?;
+            }
+
+            let (values, xs, ys, col_name) = {
+                let data = self.loaded.as_ref().unwrap();
+                let colvals: HashMap<usize, Vec<f64>> = refs
+                    .iter()
+                    .filter(|&&ci| ci < data.column_data.len())
+                    .map(|&ci| (ci, data.column_data[ci].to_f64().into_owned()))
+                    .collect();
+                let values: Vec<f64> = (0..data.row_count)
+                    .map(|r| eval_expr(&ast, &colvals, r))
+                    .collect();
+
+                // Resolve the current X column name to its file-column index,
+                // then read it as X exactly like set_series.
+                let x_col_idx = data
+                    .columns
+                    .iter()
+                    .position(|c| c == &x_name)
+                    .ok_or_else(|| JsValue::from_str("current X column not found in loaded data"))?;
+                let x_vals = data.column_data[x_col_idx].to_f64();
+
+                let (xs, ys) = gapped_pairs(&x_vals, &values);
+
+                if xs.is_empty() {
+                    return Err(JsValue::from_str("formula produced no finite values"));
+                }
+
+                // Ensure a unique column name — duplicates would make by-name
+                // resolution (resolve_col / recompute_plotted_cols) ambiguous and
+                // hide the newer column from the Table view.
+                let base_name = if name.trim().is_empty() { expr.clone() } else { name };
+                let mut col_name = base_name.clone();
+                let mut k = 2;
+                while data.columns.contains(&col_name) {
+                    col_name = format!("{base_name} ({k})");
+                    k += 1;
+                }
+                (values, xs, ys, col_name)
+            };
+
+            // Color + Y bounds only touch `self.sources`, not `self.loaded` —
+            // safe to compute before the mutable re-borrow below too.
+            let color = palette_color(self.sources.len());
+            let (y_min, y_max) = compute_y_bounds(&ys);
+
+            // ── Mutable phase: append the derived column to the dataset (so
+            // it shows up in the column list / Table view) and push a plotted
+            // series for it, mirroring add_transform's tail.
+            let data = self.loaded.as_mut().unwrap();
+            data.columns.push(col_name.clone());
+            data.column_data.push(Column::numeric(values).into());
+            // Keep numeric_cols aligned with the dataset — a derived column is
+            // always numeric, so the Table view sorts it numerically (not lexically).
+            self.table_query.numeric_cols.push(true);
+
+            let deriv = Derivation::new("formula", None).param("expr", &expr);
+            self.sources.push(SourceSeries {
+                name: col_name,
+                x_name,
+                visible: true,
+                xs: xs.into(),
+                ys: ys.into(),
+                color,
+                draw_mode: DrawMode::Lines,
+                y_min,
+                y_max,
+                wrap_period: None,
+                derivation: Some(deriv),
+                reference: false,
+                labels: None,
+                gaps: None,
+                error: None,
+                hold_last: false,
+                no_hover: false,
+                layer: SeriesLayer::Normal,
+                fill: None,
+                locked_color: None,
+                packed: None,
+                align: SeriesAlign::default(),
+                column: None,
+            });
+
+            self.recompute_plotted_cols();
+            self.rebuild_visible();
+            self.render();
+
+            let meta = FileMeta::from_loaded(self.loaded.as_ref().unwrap());
+            serde_wasm_bindgen::to_value(&meta).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Return the loaded dataset's column names, in file order (empty if
+        /// no file is loaded). Used by the formula editor UI to offer a
+        /// clickable list of column names to insert into an expression.
+        #[wasm_bindgen]
+        pub fn column_names(&self) -> Vec<String> {
+            self.loaded.as_ref().map(|d| d.columns.clone()).unwrap_or_default()
+        }
+
+        /// Return `{ xs, ys, n }` for the Scatter (XY) view: dataset columns
+        /// `x_col` and `y_col` read as f64 (X tries datetime first, like
+        /// `set_series`), zipped, and filtered to finite pairs in row order.
+        ///
+        /// Throws (JS exception) if no file is loaded, either column index is
+        /// out of range, or there are no finite pairs.
+        #[wasm_bindgen]
+        pub fn scatter_data(&mut self, x_col: usize, y_col: usize) -> Result<JsValue, JsValue> {
+            if self.loaded.is_none() {
+                return Err(JsValue::from_str("No file loaded."));
+            }
+            self.materialize_cols(&[x_col, y_col])?;
+            let data = self.loaded.as_ref().unwrap();
+
+            let num_cols = data.column_data.len();
+            if x_col >= num_cols || y_col >= num_cols {
+                return Err(JsValue::from_str(&format!(
+                    "Column index out of range: x_col={x_col}, y_col={y_col}, num_cols={num_cols}"
+                )));
+            }
+
+            // Typed at import (datetime X is epoch seconds; mirrors set_series).
+            let x_vals = data.column_data[x_col].to_f64();
+            let y_vals = data.column_data[y_col].to_f64();
+
+            let (xs, ys): (Vec<f64>, Vec<f64>) = x_vals
+                .iter()
+                .zip(y_vals.iter())
+                .filter(|(&x, &y)| x.is_finite() && y.is_finite())
+                .map(|(&x, &y)| (x, y))
+                .unzip();
+
+            if xs.is_empty() {
+                return Err(JsValue::from_str("No finite (x, y) pairs for these columns"));
+            }
+
+            let n = xs.len();
+            serde_wasm_bindgen::to_value(&ScatterData { xs, ys, n })
+                .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Return `[{ col, name, spark }]` for every numeric column of the
+        /// loaded file, in file order: a min/max sparkline of `buckets`
+        /// buckets (0 = the default) for the import overview grid. Reads in
+        /// any numeric column a lazily-loaded file hasn't materialized yet.
+        ///
+        /// Throws (JS exception) if no file is loaded.
+        #[wasm_bindgen]
+        pub fn column_overview(&mut self, buckets: usize) -> Result<JsValue, JsValue> {
+            let Some(data) = &self.loaded else {
+                return Err(JsValue::from_str("No file loaded."));
+            };
+            let numeric: Vec<usize> = (0..data.column_data.len().min(self.file_cols))
+                .filter(|&i| data.column_data[i].kind() == "numeric")
+                .collect();
+            self.materialize_cols(&numeric)?;
+            let data = self.loaded.as_ref().unwrap();
+            let buckets = if buckets == 0 { SPARKLINE_BUCKETS } else { buckets };
+            let out: Vec<ColumnSparkline> = numeric
+                .into_iter()
+                .map(|col| ColumnSparkline {
+                    col,
+                    name: data.columns[col].clone(),
+                    spark: sparkline(&data.column_data[col].to_f64(), buckets),
+                })
+                .collect();
+            serde_wasm_bindgen::to_value(&out).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        // ── X-range sync (Task 4) ─────────────────────────────────────────────
+
+        /// Set the view's X bounds without touching Y, then rebuild visible
+        /// series (LTTB re-runs for the new range) and re-render.
+        ///
+        /// Called by the TypeScript `applyXRange` path when the workspace is
+        /// propagating one graph's X-range to another.  Y stays untouched so
+        /// each graph can still have its own independent Y axis.
+        #[wasm_bindgen]
+        pub fn set_x_range(&mut self, x_min: f64, x_max: f64) {
+            // Reject degenerate/inverted ranges: a zero-width or non-finite span
+            // would divide-by-zero in the coordinate mapping and grid lines.
+            if !x_min.is_finite() || !x_max.is_finite() || x_min >= x_max {
+                return;
+            }
+            self.view.x_min = x_min;
+            self.view.x_max = x_max;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Set the full view bounds (X and Y) directly, then rebuild + render.
+        /// Used by the rubber-band zoom box. Bounds are in the same view space
+        /// that `view_state` reports (log space when Y-scale is Log), so the
+        /// frontend can derive them from a screen rectangle. Rejects
+        /// degenerate/inverted spans on either axis.
+        #[wasm_bindgen]
+        pub fn set_view_bounds(&mut self, x_min: f64, x_max: f64, y_min: f64, y_max: f64) {
+            if !x_min.is_finite() || !x_max.is_finite() || x_min >= x_max {
+                return;
+            }
+            if !y_min.is_finite() || !y_max.is_finite() || y_min >= y_max {
+                return;
+            }
+            self.view.x_min = x_min;
+            self.view.x_max = x_max;
+            self.view.y_min = y_min;
+            self.view.y_max = y_max;
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Format a position in view coordinates (as reported by `view_state`)
+        /// for the mouse readout: X as a timestamp on datetime axes, Y
+        /// de-logged in Log scale, both to one pixel's precision. Y carries
+        /// the state name under a label map, or the unit shared by all
+        /// visible series (inferred from their names); X the edited X unit.
+        #[wasm_bindgen]
+        pub fn readout(&self, x: f64, y: f64) -> JsValue {
+            let x_res = (self.view.x_max - self.view.x_min) / self.width.max(1) as f64;
+            let y_res = (self.view.y_max - self.view.y_min) / self.height.max(1) as f64;
+            let x_text = if self.x_is_time {
+                format_timestamp(if x_res < 1.0 { (x * 1000.0).round() / 1000.0 } else { x.round() })
+            } else {
+                match self.x_title.as_ref().and_then(|t| t.unit.as_ref()) {
+                    Some(u) => format!("{} {u}", format_readout(x, x_res)),
+                    None => format_readout(x, x_res),
+                }
+            };
+            let y_text = if self.normalized {
+                format!("{} (norm)", format_readout(y, y_res))
+            } else if self.y_scale == YScale::Log {
+                let v = 10f64.powf(y);
+                format_readout(v, v * y_res * std::f64::consts::LN_10)
+            } else if let Some(state) = self.active_y_labels().and_then(|m| m.nearest(y)) {
+                format!("{} ({state})", format_readout(y, y_res))
+            } else {
+                format_readout(y, y_res)
+            };
+            let unit = match &self.y_title {
+                Some(t) => t.unit.clone(),
+                None => self.inferred_y_unit(),
+            };
+            let y_text = match unit {
+                Some(u) if !self.normalized => format!("{y_text} {u}"),
+                _ => y_text,
+            };
+            serde_wasm_bindgen::to_value(&Readout { x: x_text, y: y_text }).unwrap_or(JsValue::NULL)
+        }
+
+        /// Find the visible sample nearest to view position `(x, y)` within
+        /// `radius_px` backing pixels, measured on screen. Returns `null`
+        /// when nothing is that close.
+        #[wasm_bindgen]
+        pub fn nearest_point(&self, x: f64, y: f64, radius_px: f64) -> JsValue {
+            let (w, h) = (self.width.max(1) as f64, self.height.max(1) as f64);
+            let v = &self.view;
+            let (x_span, y_span) = (v.x_max - v.x_min, v.y_max - v.y_min);
+            if !(x_span > 0.0 && y_span > 0.0) {
+                return JsValue::NULL;
+            }
+            let to_px_x = |x: f64| (x - v.x_min) / x_span * w;
+            let to_px_y = |vy: f64| (1.0 - (vy - v.y_min) / y_span) * h;
+            let query = (to_px_x(x), to_px_y(y));
+            let reach = radius_px * x_span / w;
+
+            let mut best: Option<(usize, usize, f64)> = None;
+            for (si, src) in self.sources.iter().enumerate().filter(|(_, s)| s.visible && !s.no_hover) {
+                let to_px = |sx: f64, sy: f64| self.view_y(src, sy).map(|vy| (to_px_x(sx), to_px_y(vy)));
+                let hit = nearest_on_screen(
+                    &src.xs, &src.ys, x - reach, x + reach, NEAREST_MAX_CANDIDATES, query, to_px,
+                );
+                if let Some((i, d)) = hit {
+                    if d <= radius_px && best.is_none_or(|(_, _, bd)| d < bd) {
+                        best = Some((si, i, d));
+                    }
+                }
+            }
+            let Some((si, i, dist_px)) = best else { return JsValue::NULL };
+            serde_wasm_bindgen::to_value(&self.sample_point(si, i, dist_px)).unwrap_or(JsValue::NULL)
+        }
+
+        /// The sample of series `series` nearest view X `x` (snapped cursors
+        /// and hover readout), in `nearest_point`'s shape with `dist_px` the
+        /// horizontal distance. `null` for an unknown series or one with no
+        /// finite samples.
+        #[wasm_bindgen]
+        pub fn snap_point(&self, series: usize, x: f64) -> JsValue {
+            let Some(src) = self.sources.get(series) else { return JsValue::NULL };
+            let Some(i) = interpolation::nearest_sample(&src.xs, &src.ys, x) else { return JsValue::NULL };
+            let x_span = self.view.x_max - self.view.x_min;
+            let dist_px = (src.xs[i] - x).abs() / x_span * self.width.max(1) as f64;
+            serde_wasm_bindgen::to_value(&self.sample_point(series, i, dist_px)).unwrap_or(JsValue::NULL)
+        }
+
+        /// Sample `index` of series `series` measured from the reference
+        /// sample `ref_index` of series `ref_series` (the data cursor's
+        /// delta mode): ΔX, ΔY and ΔY as a percentage of the reference Y.
+        /// `null` when either sample doesn't exist.
+        #[wasm_bindgen]
+        pub fn point_delta(&self, ref_series: usize, ref_index: usize, series: usize, index: usize) -> JsValue {
+            let sample = |si: usize, i: usize| {
+                let src = self.sources.get(si)?;
+                Some((src, *src.xs.get(i)?, *src.ys.get(i)?))
+            };
+            let (Some((_, rx, ry)), Some((src, x, y))) = (sample(ref_series, ref_index), sample(series, index)) else {
+                return JsValue::NULL;
+            };
+            let (dx, dy) = (x - rx, y - ry);
+            let x_res = (self.view.x_max - self.view.x_min) / self.width.max(1) as f64;
+            let x_unit = if self.x_is_time { Some("s") } else { self.x_title.as_ref().and_then(|t| t.unit.as_deref()) };
+            let dx_text = match x_unit {
+                Some(u) => format!("{} {u}", format_readout(dx, x_res)),
+                None => format_readout(dx, x_res),
+            };
+            let delta = PointDelta {
+                dx,
+                dy,
+                percent: (ry != 0.0).then(|| dy / ry.abs() * 100.0),
+                dx_text,
+                dy_text: series_value_text(src, dy),
+            };
+            serde_wasm_bindgen::to_value(&delta).unwrap_or(JsValue::NULL)
+        }
+
+        /// Sample `i` of source `si`, described for the frontend.
+        fn sample_point(&self, si: usize, i: usize, dist_px: f64) -> NearestPoint {
+            let src = &self.sources[si];
+            let (px, py) = (src.xs[i], src.ys[i]);
+            NearestPoint {
+                series: si,
+                index: i,
+                name: src.name.clone(),
+                x: px,
+                y: py,
+                x_text: if self.x_is_time { format_timestamp(px) } else { px.to_string() },
+                y_text: py.to_string(),
+                view_y: self.view_y(src, py).unwrap_or(py),
+                dist_px,
+            }
+        }
+
+        /// Each series' value for the legend value column, in series order:
+        /// the sample held at view X `x` (cursor 1 or the mouse), or the
+        /// latest sample when `x` is omitted (live streams). `null` for
+        /// series without a sample there.
+        #[wasm_bindgen]
+        pub fn legend_values(&self, x: Option<f64>) -> JsValue {
+            let values: Vec<Option<LegendValue>> = self
+                .sources
+                .iter()
+                .map(|src| {
+                    let value = interpolation::held_value(&src.xs, &src.ys, x)?;
+                    let text = match src.labels.as_ref().and_then(|m| m.nearest(value)) {
+                        Some(state) => state.to_string(),
+                        None => series_value_text(src, value),
+                    };
+                    Some(LegendValue { value, text })
+                })
+                .collect();
+            serde_wasm_bindgen::to_value(&values).unwrap_or(JsValue::NULL)
+        }
+
+        /// Cursor readout table: for each visible series, its interpolated
+        /// value at every view X in `xs` (the placed cursors) and, with two
+        /// cursors, the change and the area under it from the first to the
+        /// second.
+        #[wasm_bindgen]
+        pub fn cursor_table(&self, xs: Vec<f64>) -> JsValue {
+            let rows: Vec<CursorRow> = self
+                .sources
+                .iter()
+                .enumerate()
+                .filter(|(_, src)| src.visible)
+                .map(|(series, src)| {
+                    let values: Vec<Option<f64>> =
+                        xs.iter().map(|&x| interpolation::value_at(&src.xs, &src.ys, x)).collect();
+                    let texts = values
+                        .iter()
+                        .map(|v| match v {
+                            Some(v) => match src.labels.as_ref().and_then(|m| m.nearest(*v)) {
+                                Some(state) => state.to_string(),
+                                None => series_value_text(src, *v),
+                            },
+                            None => String::new(),
+                        })
+                        .collect();
+                    let delta = match values[..] {
+                        [Some(a), Some(b)] => Some(b - a),
+                        _ => None,
+                    };
+                    let (area, area_text) = match xs[..] {
+                        [a, b] => self.cursor_area(src, a, b),
+                        _ => (None, String::new()),
+                    };
+                    CursorRow {
+                        series,
+                        name: src.name.clone(),
+                        values,
+                        texts,
+                        delta,
+                        delta_text: delta.map(|d| series_value_text(src, d)).unwrap_or_default(),
+                        area,
+                        area_text,
+                    }
+                })
+                .collect();
+            serde_wasm_bindgen::to_value(&rows).unwrap_or(JsValue::NULL)
+        }
+
+        /// `CursorRow::area` of `src` between view X `a` and `b`, and its text.
+        /// Datetime X is in seconds, so a span of an hour or more is reported
+        /// per hour — energy as kW·h rather than millions of kW·s.
+        fn cursor_area(&self, src: &SourceSeries, a: f64, b: f64) -> (Option<f64>, String) {
+            let Some(mut area) = math_ops::integral_between(&src.xs, &src.ys, a, b) else {
+                return (None, String::new());
+            };
+            let mut span = (b - a).abs();
+            let mut unit = self.transform_unit("integral", src);
+            if self.x_is_time && span >= 3600.0 {
+                area /= 3600.0;
+                span /= 3600.0;
+                unit = unit.map(|u| format!("{}·h", u.strip_suffix("·s").unwrap_or(&u)));
+            }
+            let text = format_readout(area, (src.y_max - src.y_min) * span / 1000.0);
+            let text = match unit {
+                Some(u) if !text.is_empty() => format!("{text} {u}"),
+                _ => text,
+            };
+            (Some(area), text)
+        }
+
+        /// Show the last `seconds` of data: the X window ends at the latest
+        /// visible sample, and Y is fitted to the samples inside it (the
+        /// Last 1 min / 10 min / 1 h / 24 h presets for datetime axes).
+        #[wasm_bindgen]
+        pub fn set_time_window(&mut self, seconds: f64) {
+            if !seconds.is_finite() || seconds <= 0.0 {
+                return;
+            }
+            let latest = self
+                .sources
+                .iter()
+                .filter(|s| s.visible)
+                .filter_map(|s| s.xs.iter().rev().copied().find(|x| x.is_finite()))
+                .fold(f64::NEG_INFINITY, f64::max);
+            if !latest.is_finite() {
+                return;
+            }
+            self.view.x_min = latest - seconds;
+            self.view.x_max = latest;
+            if self.normalized {
+                self.view.y_min = -0.05;
+                self.view.y_max = 1.05;
+            } else {
+                self.fit_y(latest - seconds, latest);
+            }
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Where the recurring `bands` (`[{ kind: "night" | "weekend" |
+        /// "weekly", … }]`, see `oxideplot_core::render::time_bands`) fall in
+        /// the X window: `[{ band, x0, x1 }]`, `band` indexing `bands`.
+        /// Static: the bands are kept by the frontend with the graph.
+        #[wasm_bindgen]
+        pub fn time_band_intervals(bands: JsValue, x_min: f64, x_max: f64) -> Result<JsValue, JsValue> {
+            let bands: Vec<TimeBand> = serde_wasm_bindgen::from_value(bands).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            let out: Vec<BandInterval> = bands
+                .iter()
+                .enumerate()
+                .flat_map(|(band, b)| band_intervals(b, x_min, x_max).into_iter().map(move |(x0, x1)| BandInterval { band, x0, x1 }))
+                .collect();
+            serde_wasm_bindgen::to_value(&out).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        // ── Event markers ─────────────────────────────────────────────────────
+
+        /// Parse an events CSV/Excel file into `[{ x, label, color }]`
+        /// markers (see `oxideplot_core::data::events`). Static: the markers
+        /// are kept by the frontend with the graph.
+        #[wasm_bindgen]
+        pub fn parse_events(bytes: Vec<u8>, filename: String) -> Result<JsValue, JsValue> {
+            let events = load_events(&bytes, &filename).map_err(|e| JsValue::from_str(&e))?;
+            serde_wasm_bindgen::to_value(&events).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// `time,label,color` CSV of `[{ x, label, color }]` markers, times
+        /// formatted as timestamps when `x_is_time`.
+        #[wasm_bindgen]
+        pub fn events_csv(events: JsValue, x_is_time: bool) -> Result<String, JsValue> {
+            let events: Vec<EventMarker> = serde_wasm_bindgen::from_value(events).map_err(|e| JsValue::from_str(&e.to_string()))?;
+            Ok(events_to_csv(&events, x_is_time))
+        }
+
+        // ── Reference curves ──────────────────────────────────────────────────
+
+        /// Parse a small CSV/Excel file into a reference curve
+        /// `{ name, x_name, xs, ys }` (see `oxideplot_core::data::reference`).
+        /// Static: references belong to the app, not to one graph.
+        #[wasm_bindgen]
+        pub fn parse_reference(bytes: Vec<u8>, filename: String) -> Result<JsValue, JsValue> {
+            let curve = load_reference(&bytes, &filename).map_err(|e| JsValue::from_str(&e))?;
+            serde_wasm_bindgen::to_value(&curve).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Overlay a reference curve (as returned by `parse_reference`) on
+        /// this graph in colour `(r, g, b)`. It is always drawn dashed.
+        #[wasm_bindgen]
+        pub fn add_reference(&mut self, curve: JsValue, r: f32, g: f32, b: f32) -> Result<(), JsValue> {
+            let curve: ReferenceCurve = serde_wasm_bindgen::from_value(curve)
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            if curve.xs.is_empty() || curve.xs.len() != curve.ys.len() {
+                return Err(JsValue::from_str("reference curve has no points"));
+            }
+            let (y_min, y_max) = compute_y_bounds(&curve.ys);
+            self.sources.push(SourceSeries {
+                name: curve.name,
+                x_name: curve.x_name,
+                visible: true,
+                xs: curve.xs.into(),
+                ys: curve.ys.into(),
+                color: [r, g, b, 1.0],
+                draw_mode: DrawMode::Lines,
+                y_min,
+                y_max,
+                wrap_period: None,
+                derivation: None,
+                reference: true,
+                labels: None,
+                gaps: None,
+                error: None,
+                hold_last: false,
+                no_hover: true,
+                layer: SeriesLayer::Normal,
+                fill: None,
+                locked_color: None,
+                packed: None,
+                align: SeriesAlign::default(),
+                column: None,
+            });
+            self.recompute_plotted_cols();
+            self.rebuild_visible();
+            self.render();
+            Ok(())
+        }
+
+        // ── Projects ──────────────────────────────────────────────────────────
+
+        /// Snapshot this graph for a saved project (a `GraphProject`): every
+        /// series' data and style, derived series' defining operations, and
+        /// the current view. `file` is left for the frontend to fill in.
+        #[wasm_bindgen]
+        pub fn export_project(&self) -> Result<JsValue, JsValue> {
+            let graph = GraphProject {
+                file: None,
+                source: None,
+                x_is_time: self.x_is_time,
+                y_labels: self.y_labels.as_ref().map(LabelMap::to_spec),
+                x_title: self.x_title.as_ref().map(AxisTitle::to_string),
+                y_title: self.y_title.as_ref().map(AxisTitle::to_string),
+                stack_mode: (self.stack_mode != StackMode::Off).then(|| self.stack_mode.as_str().to_string()),
+                bar_layout: (self.bar_layout != BarLayout::Grouped).then(|| self.bar_layout.as_str().to_string()),
+                unit_axes: self
+                    .y_axes
+                    .iter()
+                    .filter(|(unit, axis)| **axis != AxisState::for_unit(unit))
+                    .map(|(unit, axis)| (unit.clone(), axis.clone()))
+                    .collect::<BTreeMap<_, _>>(),
+                unit_conversions: self.unit_conversions.clone(),
+                x_min: self.view.x_min,
+                x_max: self.view.x_max,
+                y_min: self.view.y_min,
+                y_max: self.view.y_max,
+                series: self
+                    .sources
+                    .iter()
+                    .map(|src| ProjectSeries {
+                        name: src.name.clone(),
+                        x_name: src.x_name.clone(),
+                        color: src.color,
+                        draw_mode: match src.draw_mode {
+                            // Bands are only built for uncertainty display.
+                            DrawMode::Lines | DrawMode::Band => "lines",
+                            DrawMode::Step => "step",
+                            DrawMode::StepPre => "step-pre",
+                            DrawMode::Points => "points",
+                            DrawMode::Smooth => "smooth",
+                            DrawMode::Bar => "bar",
+                        }
+                        .to_string(),
+                        visible: src.visible,
+                        wrap_period: src.wrap_period,
+                        derivation: src.derivation.clone(),
+                        reference: src.reference,
+                        labels: src.labels.as_ref().map(LabelMap::to_spec),
+                        gap_threshold: src.gaps.as_ref().map(|g| g.threshold),
+                        error: src.error.clone(),
+                        hold_last: src.hold_last,
+                        no_hover: src.no_hover,
+                        layer: src.layer,
+                        fill: src.fill.clone(),
+                        column: src.column.clone(),
+                        align: src.align,
+                        xs: src.full_xs(),
+                        ys: src.full_ys(),
+                    })
+                    .collect(),
+                // Annotations, time bands and event lines live in the
+                // frontend, which fills them in.
+                annotations: Vec::new(),
+                time_bands: Vec::new(),
+                events: Vec::new(),
+            };
+            serde_wasm_bindgen::to_value(&graph).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Encode a whole `Project` as a binary `.oxideplot` archive (see
+        /// `oxideplot_core::state::project_archive`). With `link`, file-column
+        /// series of graphs that have a `source` are saved without samples.
+        #[wasm_bindgen]
+        pub fn encode_project(project: JsValue, link: bool) -> Result<Vec<u8>, JsValue> {
+            let mut project: Project = serde_wasm_bindgen::from_value(project)
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            if link {
+                project.link_sources();
+            }
+            project_archive::write_archive(&project).map_err(|e| JsValue::from_str(&e))
+        }
+
+        /// Encode a `Project` as a self-contained bundle zip: every sample
+        /// embedded, plus extra files (source files, previews) concatenated
+        /// in `data`, the i-th `lengths[i]` bytes long and stored as
+        /// `names[i]`.
+        #[wasm_bindgen]
+        pub fn encode_bundle(
+            project: JsValue,
+            names: Vec<String>,
+            lengths: Vec<f64>,
+            data: &[u8],
+        ) -> Result<Vec<u8>, JsValue> {
+            let project: Project = serde_wasm_bindgen::from_value(project)
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            if names.len() != lengths.len() || lengths.iter().sum::<f64>() != data.len() as f64 {
+                return Err(JsValue::from_str("bundle: file lengths don't match the data"));
+            }
+            let mut at = 0;
+            let files: Vec<(String, Vec<u8>)> = names
+                .into_iter()
+                .zip(lengths)
+                .map(|(name, len)| {
+                    let bytes = data[at..at + len as usize].to_vec();
+                    at += len as usize;
+                    (name, bytes)
+                })
+                .collect();
+            project_archive::write_bundle(&project, &files).map_err(|e| JsValue::from_str(&e))
+        }
+
+        /// Decode a binary project archive into a `Project`. Throws on a
+        /// malformed or too-new file.
+        #[wasm_bindgen]
+        pub fn decode_project(bytes: &[u8]) -> Result<JsValue, JsValue> {
+            let project = project_archive::read_archive(bytes).map_err(|e| JsValue::from_str(&e))?;
+            serde_wasm_bindgen::to_value(&project).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// `file`'s path relative to the folder of the `project` file.
+        #[wasm_bindgen]
+        pub fn project_relative_path(project: &str, file: &str) -> String {
+            project_archive::relative_path(project, file)
+        }
+
+        /// Inverse of `project_relative_path`.
+        #[wasm_bindgen]
+        pub fn project_resolve_path(project: &str, rel: &str) -> String {
+            project_archive::resolve_path(project, rel)
+        }
+
+        /// Release notes compiled into the app, newest release first
+        /// (`oxideplot_core::state::release_notes::RELEASES`).
+        #[wasm_bindgen]
+        pub fn release_notes() -> Result<JsValue, JsValue> {
+            serde_wasm_bindgen::to_value(release_notes::RELEASES).map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// The one-time tip for `trigger` (`"two-series"`, ...) not yet in
+        /// `seen`, or null.
+        #[wasm_bindgen]
+        pub fn release_tip(trigger: &str, seen: Vec<String>) -> Result<JsValue, JsValue> {
+            let trigger = TipTrigger::parse(trigger)
+                .ok_or_else(|| JsValue::from_str(&format!("unknown tip trigger: {trigger}")))?;
+            serde_wasm_bindgen::to_value(&release_notes::tip_for(trigger, &seen))
+                .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Replace this graph's contents with a saved `GraphProject`. Series
+        /// come back from their stored data (the original file isn't needed),
+        /// so the Table view has no dataset until a file is loaded again.
+        ///
+        /// `linked`: the graph's source file was just loaded into this graph
+        /// (`load_file_bytes`); keep that dataset and re-read each file-column
+        /// series saved without samples from its `x_name` / `name` columns.
+        #[wasm_bindgen]
+        pub fn import_project(&mut self, graph: JsValue, linked: bool) -> Result<(), JsValue> {
+            let mut graph: GraphProject = serde_wasm_bindgen::from_value(graph)
+                .map_err(|e| JsValue::from_str(&e.to_string()))?;
+            if linked {
+                self.unit_conversions = graph.unit_conversions.clone();
+                self.relink_series(&mut graph.series)?;
+            }
+            self.sample_undo.clear();
+            self.despike_preview = None;
+            self.sources = graph
+                .series
+                .into_iter()
+                .map(|s| {
+                    let (y_min, y_max) = compute_y_bounds(&s.ys);
+                    SourceSeries {
+                        name: s.name,
+                        x_name: s.x_name,
+                        visible: s.visible,
+                        gaps: s.gap_threshold.map(|t| GapSplit::detect(&s.xs, t)),
+                        error: s.error,
+                        hold_last: s.hold_last,
+                        no_hover: s.no_hover,
+                        layer: s.layer,
+                        fill: s.fill,
+                        locked_color: None,
+                        packed: None,
+                        align: s.align,
+                        column: s.column,
+                        xs: s.xs.into(),
+                        ys: s.ys.into(),
+                        color: s.color,
+                        draw_mode: match s.draw_mode.as_str() {
+                            "step" => DrawMode::Step,
+                            "step-pre" => DrawMode::StepPre,
+                            "points" => DrawMode::Points,
+                            "smooth" => DrawMode::Smooth,
+                            "bar" => DrawMode::Bar,
+                            _ => DrawMode::Lines,
+                        },
+                        y_min,
+                        y_max,
+                        wrap_period: s.wrap_period,
+                        derivation: s.derivation,
+                        reference: s.reference,
+                        labels: s.labels.as_deref().and_then(|spec| LabelMap::parse(spec).ok()),
+                    }
+                })
+                .collect();
+            if !linked {
+                self.loaded = None;
+                self.file_key = 0;
+                self.file_cols = 0;
+                self.table_query = TableQuery::default();
+                self.table_index.clear();
+                self.table_drill = None;
+            }
+            self.stream = None;
+            self.recompute_plotted_cols();
+            self.x_is_time = graph.x_is_time;
+            self.y_labels = graph.y_labels.as_deref().and_then(|spec| LabelMap::parse(spec).ok());
+            self.x_title = graph.x_title.as_deref().and_then(AxisTitle::parse);
+            self.y_title = graph.y_title.as_deref().and_then(AxisTitle::parse);
+            self.stack_mode = graph.stack_mode.as_deref().and_then(StackMode::parse).unwrap_or(StackMode::Off);
+            self.bar_layout = graph.bar_layout.as_deref().and_then(BarLayout::parse).unwrap_or_default();
+            self.y_axes = graph.unit_axes.into_iter().collect();
+            self.unit_conversions = graph.unit_conversions;
+            if graph.x_min < graph.x_max && graph.y_min < graph.y_max {
+                self.view.x_min = graph.x_min;
+                self.view.x_max = graph.x_max;
+                self.view.y_min = graph.y_min;
+                self.view.y_max = graph.y_max;
+                self.rebuild_visible();
+                self.render();
+            } else {
+                self.auto_fit();
+            }
+            Ok(())
+        }
+
+        // ── Private helpers ───────────────────────────────────────────────────
+
+        /// Fill the samples of linked file-column series from the loaded
+        /// dataset's columns of the same names, repeating the graph's unit
+        /// conversions and each series' alignment. Errs naming a missing column (the source file changed
+        /// since the project was saved).
+        fn relink_series(&mut self, series: &mut [ProjectSeries]) -> Result<(), JsValue> {
+            let data = self.loaded.as_ref().ok_or_else(|| JsValue::from_str("No file loaded."))?;
+            let find = |name: &str| {
+                data.columns.iter().position(|c| c == name).ok_or_else(|| {
+                    JsValue::from_str(&format!("column \"{name}\" is no longer in the source file"))
+                })
+            };
+            let mut pairs = Vec::new();
+            for (i, s) in series.iter().enumerate() {
+                if s.is_file_column() && s.xs.is_empty() {
+                    pairs.push((i, find(&s.x_name)?, find(s.column_name())?));
+                }
+            }
+            let cols: Vec<usize> = pairs.iter().flat_map(|&(_, x, y)| [x, y]).collect();
+            self.materialize_cols(&cols)?;
+            let data = self.loaded.as_ref().unwrap();
+            for (i, x, y) in pairs {
+                let (xs, ys) = shared_pair((self.file_key, x, y), || {
+                    series_arrays(&data.column_data[x], &data.column_data[y])
+                });
+                let ys = match apply_rules(&self.unit_conversions, &data.columns[y], &ys) {
+                    Some((_, converted)) => converted,
+                    None => ys.to_vec(),
+                };
+                let (raw, align) = (SeriesAlign::default(), series[i].align);
+                series[i].xs = raw.realign_x(&align, &xs).unwrap_or_else(|| xs.to_vec());
+                series[i].ys = raw.realign_y(&align, &ys).unwrap_or(ys);
+            }
+            Ok(())
+        }
+
+        /// Make `data` this graph's loaded file (dropping any plotted series)
+        /// and return its column metadata.
+        /// Mirror the stream's channels into `sources`, matched by name so
+        /// reordered or restyled series keep their settings.
+        fn sync_stream_sources(&mut self) {
+            let Some(stream) = &self.stream else {
+                return;
+            };
+            let draw_mode = self.sources.first().map_or(DrawMode::Lines, |s| s.draw_mode);
+            for ch in stream.channels() {
+                let xs: Arc<[f64]> = ch.x.as_slice().into();
+                let ys: Arc<[f64]> = ch.y.as_slice().into();
+                let (y_min, y_max) = compute_y_bounds(&ys);
+                let existing = self
+                    .sources
+                    .iter_mut()
+                    .find(|s| s.name == ch.label && s.derivation.is_none() && !s.reference);
+                match existing {
+                    Some(src) => {
+                        // New samples keep the series' alignment.
+                        let raw = SeriesAlign::default();
+                        let xs: Arc<[f64]> = raw.realign_x(&src.align, &xs).map_or(xs, Into::into);
+                        let ys: Arc<[f64]> = raw.realign_y(&src.align, &ys).map_or(ys, Into::into);
+                        let (y_min, y_max) = compute_y_bounds(&ys);
+                        src.gaps = src.gaps.as_ref().map(|g| GapSplit::detect(&xs, g.threshold));
+                        src.xs = xs;
+                        src.ys = ys;
+                        src.y_min = y_min;
+                        src.y_max = y_max;
+                    }
+                    None => {
+                        let color = palette_color(self.sources.len());
+                        self.sources.push(SourceSeries {
+                            name: ch.label.clone(),
+                            x_name: stream.x_name().to_string(),
+                            visible: true,
+                            xs,
+                            ys,
+                            color,
+                            draw_mode,
+                            y_min,
+                            y_max,
+                            wrap_period: None,
+                            derivation: None,
+                            reference: false,
+                            labels: None,
+                            gaps: None,
+                            error: None,
+                            hold_last: false,
+                            no_hover: false,
+                            layer: SeriesLayer::Normal,
+                            fill: None,
+                            locked_color: None,
+                            packed: None,
+                            align: SeriesAlign::default(),
+                            column: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        /// Show the newest `STREAM_WINDOW_SAMPLES` samples of the channel
+        /// that received data last, with Y fitted to them.
+        fn follow_stream(&mut self) {
+            let Some(stream) = &self.stream else {
+                return;
+            };
+            let newest = stream
+                .channels()
+                .iter()
+                .filter(|c| !c.x.is_empty())
+                .max_by(|a, b| a.x[a.x.len() - 1].total_cmp(&b.x[b.x.len() - 1]));
+            let Some(ch) = newest else {
+                return;
+            };
+            let hi = ch.x[ch.x.len() - 1];
+            let lo = ch.x[ch.x.len().saturating_sub(STREAM_WINDOW_SAMPLES)];
+            let span = if hi > lo { hi - lo } else { 1.0 };
+            self.view.x_min = hi - span;
+            self.view.x_max = hi + span * 0.02;
+            if self.normalized {
+                self.view.y_min = -0.05;
+                self.view.y_max = 1.05;
+            } else {
+                self.fit_y(hi - span, hi);
+            }
+            self.view.auto_fit = false;
+            self.view.initialized = true;
+            self.rebuild_visible();
+        }
+
+        fn install_loaded(
+            &mut self,
+            file_key: u64,
+            mut data: LoadedData,
+            csv: Option<CsvMeta>,
+            decimation: Option<String>,
+        ) -> Result<JsValue, JsValue> {
+            // Reuse another graph's copy of this file's columns if it has one.
+            let all: Vec<usize> = (0..data.column_data.len()).collect();
+            COLUMNS.with(|c| share_columns(&mut c.borrow_mut(), file_key, &mut data, &all));
+            self.file_key = file_key;
+            self.file_cols = data.columns.len();
+
+            let mut meta = FileMeta::from_loaded(&data);
+            meta.csv = csv;
+            meta.decimation = decimation.clone();
+            self.import_note = decimation;
+
+            // Store parsed data for series construction (ends a live stream).
+            self.loaded = Some(data);
+            self.stream = None;
+            // Clear any previous series until the user picks new columns.
+            self.sources.clear();
+            self.sample_undo.clear();
+            self.despike_preview = None;
+            self.series.clear();
+            self.error_series.clear();
+            self.fill_series.clear();
+            // No series plotted yet — Table view falls back to all columns.
+            self.plotted_cols.clear();
+
+            // Initialise numeric_cols: a column is numeric if it parses as f64
+            // (≥ 50% success rate) OR if it parses as timestamps — exactly the
+            // ColumnMeta.kind rule, so reuse the classification from `meta`.
+            {
+                let numeric_cols = meta.numeric_mask();
+                // Reset table_query to defaults but preserve the new numeric_cols.
+                self.table_query = TableQuery {
+                    numeric_cols,
+                    ..TableQuery::default()
+                };
+                self.rebuild_table_index();
+            }
+
+            serde_wasm_bindgen::to_value(&meta)
+                .map_err(|e| JsValue::from_str(&e.to_string()))
+        }
+
+        /// Rebuild `self.series` by LTTB-downsampling each source series to the
+        /// visible X-range.  Target point count = max(width, MIN_TARGET_POINTS),
+        /// giving roughly one point per horizontal pixel.
+        ///
+        /// Uses `downsample_for_view_mode` from oxideplot-core (selecting the
+        /// current `downsample_mode`), which uses binary search on sorted X data
+        /// and extends the window by one extra point on each edge so lines don't
+        /// clip while panning. In Log Y-scale mode the surviving points are
+        /// log10-transformed (dropping y<=0) after decimation.
+        ///
+        /// Pan/zoom go through `rebuild_interactive`, which applies the
+        /// frame-budget guard on top of this.
+        fn rebuild_visible(&mut self) {
+            let target = self.full_target();
+            self.rebuild_visible_at(target);
+            self.refine_queue.clear();
+        }
+
+        /// Full-quality point target: roughly one point per horizontal pixel.
+        fn full_target(&self) -> usize {
+            (self.width as usize).max(MIN_TARGET_POINTS)
+        }
+
+        /// Rebuild every series at `target` points per series.
+        fn rebuild_visible_at(&mut self, target: usize) {
+            if self.culled {
+                self.cull_stale = true;
+                return;
+            }
+            // Large-coordinate render offset (see field docs): shift X by a stable
+            // in-range origin so epoch-second timestamps survive the f32 cast. Any
+            // X inside the data window works; the first finite X is cheapest. The
+            // same origin is applied to the view uniforms in render(), so it
+            // cancels in the shader's (p - view_min)/(view_max - view_min).
+            self.x_origin = self
+                .sources
+                .iter()
+                .chain(&self.ghosts)
+                .flat_map(|s| s.xs.iter())
+                .copied()
+                .find(|x| x.is_finite())
+                .unwrap_or(0.0);
+
+            self.refresh_unit_axes();
+            if self.color_by_unit {
+                self.apply_unit_colors();
+            }
+            let drawn = self.drawn_mask();
+            self.error_series = self.build_error_layer(&drawn, target);
+            self.fill_series = self.build_fill_layer(&drawn, target);
+            self.series = self
+                .sources
+                .iter()
+                .zip(&drawn)
+                .map(|(src, &d)| if d { self.build_visible_series(src, target) } else { self.empty_series(src) })
+                .collect();
+            self.apply_stack(&drawn, target);
+            self.apply_bars(&drawn, target);
+            self.gap_series = self.build_gap_layer(&drawn);
+            self.ghost_series = self
+                .ghosts
+                .iter()
+                .map(|src| self.build_visible_series(src, target))
+                .collect();
+            self.preview_series = self
+                .despike_preview
+                .iter()
+                .map(|src| self.build_visible_series(src, target))
+                .collect();
+        }
+
+        /// Record a sample edit for `undo_sample_edit` (dropping the oldest
+        /// past `MAX_SAMPLE_UNDO`) and redraw; nothing when it changed no
+        /// series.
+        fn push_sample_undo(&mut self, step: Vec<SampleUndo>) {
+            if step.is_empty() {
+                return;
+            }
+            if self.sample_undo.len() == MAX_SAMPLE_UNDO {
+                self.sample_undo.remove(0);
+            }
+            self.sample_undo.push(step);
+            self.rebuild_visible();
+            self.render();
+        }
+
+        /// Which sources get drawn: visible ones, up to `max_drawn_series`.
+        fn drawn_mask(&self) -> Vec<bool> {
+            let mut left = self.max_drawn_series;
+            self.sources
+                .iter()
+                .map(|s| {
+                    let drawn = s.visible && left > 0;
+                    left -= drawn as usize;
+                    drawn
+                })
+                .collect()
+        }
+
+        /// Series in draw order: ghosts first (underneath), then stacked
+        /// layer fills, error bars and bands, gap bridges, then live series
+        /// (by layer, see `set_series_layer`), then any despike preview on
+        /// top. Past `BATCH_MIN_SERIES` non-empty
+        /// series, same-style series are merged into shared buffers to cut
+        /// the draw-call count.
+        fn drawn_series(&self) -> std::borrow::Cow<'_, [SeriesGpuData]> {
+            let layers: Vec<SeriesLayer> = self.sources.iter().map(|s| s.layer).collect();
+            let order = draw_order(&layers);
+            let in_list_order = order.iter().enumerate().all(|(k, &i)| k == i);
+            let all = self
+                .ghost_series
+                .iter()
+                .chain(&self.stack_series)
+                .chain(&self.fill_series)
+                .chain(&self.error_series)
+                .chain(&self.gap_series)
+                .chain(order.iter().filter_map(|&i| self.series.get(i)))
+                .chain(&self.preview_series);
+            if all.clone().filter(|s| !s.points.is_empty()).count() > BATCH_MIN_SERIES {
+                let merged: Vec<SeriesGpuData> = all.cloned().collect();
+                return std::borrow::Cow::Owned(batch_by_style(&merged));
+            }
+            if in_list_order
+                && self.ghost_series.is_empty()
+                && self.stack_series.is_empty()
+                && self.fill_series.is_empty()
+                && self.error_series.is_empty()
+                && self.gap_series.is_empty()
+                && self.preview_series.is_empty()
+            {
+                return std::borrow::Cow::Borrowed(&self.series);
+            }
+            std::borrow::Cow::Owned(all.cloned().collect())
+        }
+
+        /// This frame's grid: a line per major tick (Y label positions on an
+        /// enumerated axis), in render space like the series.
+        fn frame_grid(&self) -> GridGpuData {
+            let mut grid = self.grid.clone();
+            if !self.show_grid {
+                return grid;
+            }
+            let (x0, x1) = (self.view.x_min - self.x_origin, self.view.x_max - self.x_origin);
+            let (y0, y1) = (self.view.y_min, self.view.y_max);
+            for (x, _) in compute_grid_lines(self.view.x_min, self.view.x_max).into_iter().filter(|t| t.1) {
+                let x = (x - self.x_origin) as f32;
+                grid.segments.extend([[x, y0 as f32], [x, y1 as f32]]);
+            }
+            let ys: Vec<f64> = match self.active_y_labels() {
+                Some(map) => map.entries().iter().map(|(v, _)| *v).filter(|v| (y0..=y1).contains(v)).collect(),
+                None => compute_grid_lines(y0, y1).into_iter().filter(|t| t.1).map(|t| t.0).collect(),
+            };
+            for y in ys {
+                grid.segments.extend([[x0 as f32, y as f32], [x1 as f32, y as f32]]);
+            }
+            grid
+        }
+
+        /// Pan/zoom rebuild under the frame-budget guard: build at the
+        /// guard's current quality, time the prep, and queue the degraded
+        /// series for `refine_step` once interaction stops.
+        fn rebuild_interactive(&mut self) {
+            let full = self.full_target();
+            let target = self.frame_budget.scaled_target(full, MIN_INTERACTIVE_POINTS);
+            let t0 = now_ms();
+            self.rebuild_visible_at(target);
+            self.frame_budget.record(now_ms() - t0);
+            self.refine_queue = if target < full {
+                let drawn = self.drawn_mask();
+                (0..self.sources.len()).filter(|&i| drawn[i]).collect()
+            } else {
+                vec![]
+            };
+        }
+
+        /// Background colour as RGB 0–1.
+        fn bg_rgb(&self) -> [f32; 3] {
+            [self.bg_color[0] as f32, self.bg_color[1] as f32, self.bg_color[2] as f32]
+        }
+
+        /// `color` as drawn: pushed away from the background when
+        /// `auto_contrast` is on and it's too faint against it.
+        fn shown_color(&self, color: [f32; 4]) -> [f32; 4] {
+            if self.auto_contrast {
+                readable_color(color, self.bg_rgb(), MIN_SERIES_CONTRAST)
+            } else {
+                color
+            }
+        }
+
+        /// Placeholder for a series that isn't drawn (hidden, or past the draw
+        /// cap), so self.series stays index-aligned with self.sources;
+        /// build_draw_calls skips empty point buffers.
+        fn empty_series(&self, src: &SourceSeries) -> SeriesGpuData {
+            SeriesGpuData {
+                points: vec![],
+                color: self.shown_color(src.color),
+                line_width: self.line_width,
+                point_radius: self.point_radius,
+                draw_mode: src.draw_mode,
+            }
+        }
+
+        /// Decimate one source to the current view at `target` points and map
+        /// it to GPU coordinates (normalize / log / x_origin shift).
+        fn build_visible_series(&self, src: &SourceSeries, target: usize) -> SeriesGpuData {
+            let x_origin = self.x_origin;
+            if !src.visible {
+                return self.empty_series(src);
+            }
+
+            let (mut vis_x, mut vis_y) = self.visible_samples(src, target);
+            if src.hold_last {
+                math_ops::hold_last(vis_x.to_mut(), vis_y.to_mut(), self.view.x_max);
+            }
+
+            let points: Vec<[f32; 2]> = if self.normalized {
+                // Normalize Y using the unit's range.
+                // Guard: if the range is degenerate, map to 0.5.
+                let (lo, hi) = self.norm_range(src);
+                let range = hi - lo;
+                vis_x
+                    .iter()
+                    .zip(vis_y.iter())
+                    .map(|(&x, &y)| {
+                        let yn = if range.abs() < 1e-15 && !y.is_nan() {
+                            0.5
+                        } else {
+                            (y - lo) / range
+                        };
+                        [(x - x_origin) as f32, yn as f32]
+                    })
+                    .collect()
+            } else if self.y_scale == YScale::Log {
+                // Log Y: plot log10(y), dropping non-positive samples
+                // (log undefined). NaN gaps pass through as breaks.
+                vis_x
+                    .iter()
+                    .zip(vis_y.iter())
+                    .filter(|&(_, &y)| y > 0.0 || y.is_nan())
+                    .map(|(&x, &y)| [(x - x_origin) as f32, y.log10() as f32])
+                    .collect()
+            } else {
+                vis_x
+                    .iter()
+                    .zip(vis_y.iter())
+                    .map(|(&x, &y)| [(x - x_origin) as f32, y as f32])
+                    .collect()
+            };
+
+            if src.reference {
+                let px_per_unit = [
+                    self.width as f64 / (self.view.x_max - self.view.x_min),
+                    self.height as f64 / (self.view.y_max - self.view.y_min),
+                ];
+                return SeriesGpuData {
+                    points: dash_polyline(&points, px_per_unit, REFERENCE_DASH_PX, REFERENCE_GAP_PX),
+                    color: self.shown_color(src.color),
+                    line_width: self.line_width,
+                    point_radius: self.point_radius,
+                    draw_mode: DrawMode::Lines,
+                };
+            }
+
+            SeriesGpuData {
+                points,
+                color: self.shown_color(src.color),
+                line_width: self.line_width,
+                point_radius: self.point_radius,
+                draw_mode: src.draw_mode,
+            }
+        }
+
+        /// Decimate one source to the current view at `target` points, with
+        /// NaN breaks at wraps and gaps; raw X/Y, before any view mapping.
+        /// Borrowed from the source when nothing needed changing.
+        fn visible_samples<'s>(&self, src: &'s SourceSeries, target: usize) -> (Cow<'s, [f64]>, Cow<'s, [f64]>) {
+            // Decimate first (in raw Y-space), then log-transform the
+            // survivors — per the Global Constraints ordering.
+            let (vis_x, vis_y) = downsample_for_view_mode(
+                &src.xs,
+                &src.ys,
+                self.view.x_min,
+                self.view.x_max,
+                target,
+                self.downsample_mode,
+            );
+            // Modular data: break the line where it wraps (NaN = gap).
+            let (vis_x, vis_y) = match src.wrap_period {
+                Some(period) => {
+                    let (x, y) = math_ops::break_wraps(&vis_x, &vis_y, period);
+                    (Cow::Owned(x), Cow::Owned(y))
+                }
+                None => (vis_x, vis_y),
+            };
+            // Logger sessions: break between segments.
+            match &src.gaps {
+                Some(split) if !split.gaps.is_empty() => {
+                    let (x, y) = split.break_lines(&vis_x, &vis_y);
+                    (Cow::Owned(x), Cow::Owned(y))
+                }
+                _ => (vis_x, vis_y),
+            }
+        }
+
+        /// Uncertainty geometry of every drawn source that has some (none
+        /// while stacking, where lines no longer sit at the raw values).
+        fn build_error_layer(&self, drawn: &[bool], target: usize) -> Vec<SeriesGpuData> {
+            if self.stack_mode != StackMode::Off {
+                return Vec::new();
+            }
+            self.sources
+                .iter()
+                .zip(drawn)
+                .filter(|&(_, &d)| d)
+                .filter_map(|(src, _)| self.build_error_series(src, target))
+                .collect()
+        }
+
+        /// Faint dashed bridges over the breaks in every drawn line series,
+        /// per `gap_style` (none while stacking). Wrapped series are left
+        /// out: their breaks are wrap-arounds, not missing data.
+        fn build_gap_layer(&self, drawn: &[bool]) -> Vec<SeriesGpuData> {
+            let Some((dash_px, gap_px)) = self.gap_style.pattern_px() else {
+                return Vec::new();
+            };
+            if self.stack_mode != StackMode::Off {
+                return Vec::new();
+            }
+            let px_per_unit = [
+                self.width as f64 / (self.view.x_max - self.view.x_min),
+                self.height as f64 / (self.view.y_max - self.view.y_min),
+            ];
+            self.sources
+                .iter()
+                .zip(&self.series)
+                .zip(drawn)
+                .filter(|&((src, _), &d)| {
+                    d && !src.reference
+                        && src.wrap_period.is_none()
+                        && matches!(src.draw_mode, DrawMode::Lines | DrawMode::Step | DrawMode::StepPre | DrawMode::Smooth)
+                })
+                .filter_map(|((_, s), _)| {
+                    let bridges = gap_bridges(&s.points);
+                    (!bridges.is_empty()).then(|| SeriesGpuData {
+                        points: dash_polyline(&bridges, px_per_unit, dash_px, gap_px),
+                        color: [s.color[0], s.color[1], s.color[2], s.color[3] * GAP_BRIDGE_ALPHA],
+                        line_width: self.line_width,
+                        point_radius: self.point_radius,
+                        draw_mode: DrawMode::Lines,
+                    })
+                })
+                .collect()
+        }
+
+        /// Area fills of every drawn source that has one (none while
+        /// stacking, which already fills its layers).
+        fn build_fill_layer(&self, drawn: &[bool], target: usize) -> Vec<SeriesGpuData> {
+            if self.stack_mode != StackMode::Off {
+                return Vec::new();
+            }
+            self.sources
+                .iter()
+                .zip(drawn)
+                .filter(|&(_, &d)| d)
+                .filter_map(|(src, _)| self.build_fill_series(src, target))
+                .collect()
+        }
+
+        /// The filled area of a visible source, mapped like its line. The
+        /// other edge is zero (the view bottom where zero isn't drawn, as in
+        /// Log scale) or the other series interpolated at this one's X.
+        fn build_fill_series(&self, src: &SourceSeries, target: usize) -> Option<SeriesGpuData> {
+            let fill = src.fill.as_ref().filter(|_| src.visible)?;
+            let (vis_x, vis_y) = self.visible_samples(src, target);
+            let edge: Vec<f64> = match fill {
+                AreaFill::Baseline => {
+                    let base = self.view_y(src, 0.0).unwrap_or(self.view.y_min);
+                    vec![base; vis_x.len()]
+                }
+                AreaFill::Between(name) => {
+                    let other = self.sources.iter().find(|s| s.name == *name)?;
+                    cross_math::align_to(&vis_x, &other.xs, &other.ys, cross_math::Align::Linear)
+                        .into_iter()
+                        .map(|v| self.view_y(other, v).unwrap_or(f64::NAN))
+                        .collect()
+                }
+            };
+            let ys: Vec<f64> = vis_y.iter().map(|&y| self.view_y(src, y).unwrap_or(f64::NAN)).collect();
+            let xs: Vec<f64> = vis_x.iter().map(|x| x - self.x_origin).collect();
+            let mut color = self.shown_color(src.color);
+            color[3] *= AREA_FILL_ALPHA;
+            Some(SeriesGpuData {
+                points: area_pairs(&xs, &ys, &edge).into_iter().map(|[x, y]| [x as f32, y as f32]).collect(),
+                color,
+                line_width: self.line_width,
+                point_radius: self.point_radius,
+                draw_mode: DrawMode::Band,
+            })
+        }
+
+        /// Error bars or band for a visible source with uncertainty, mapped
+        /// like its line (normalize / log / x_origin shift). In log scale a
+        /// lower bound at or below zero runs to the bottom of the view.
+        fn build_error_series(&self, src: &SourceSeries, target: usize) -> Option<SeriesGpuData> {
+            let err = src.error.as_ref().filter(|_| src.visible)?;
+            let (vis_x, vis_y) = self.visible_samples(src, target);
+            let bounds: Vec<[f64; 2]> = err
+                .bounds(&src.xs, &vis_x, &vis_y)
+                .into_iter()
+                .map(|[lo, hi]| match self.view_y(src, hi) {
+                    Some(hi) if lo.is_finite() => [self.view_y(src, lo).unwrap_or(self.view.y_min), hi],
+                    _ => [f64::NAN; 2],
+                })
+                .collect();
+            let vis_x: Vec<f64> = vis_x.iter().map(|x| x - self.x_origin).collect();
+            let (points, color, draw_mode) = match err.style {
+                ErrorStyle::Bars => {
+                    let px_per_x = self.width as f64 / (self.view.x_max - self.view.x_min);
+                    let cap = if px_per_x.is_finite() && px_per_x > 0.0 { ERROR_CAP_PX / px_per_x } else { 0.0 };
+                    (bar_segments(&vis_x, &bounds, cap), self.shown_color(src.color), DrawMode::Lines)
+                }
+                ErrorStyle::Band => {
+                    let mut color = self.shown_color(src.color);
+                    color[3] *= ERROR_BAND_ALPHA;
+                    (band_pairs(&vis_x, &bounds), color, DrawMode::Band)
+                }
+            };
+            Some(SeriesGpuData {
+                points: points.into_iter().map(|[x, y]| [x as f32, y as f32]).collect(),
+                color,
+                line_width: (self.line_width * 0.75).max(1.0),
+                point_radius: self.point_radius,
+                draw_mode,
+            })
+        }
+
+        /// The uncertainty `p` describes for the series at `index`.
+        fn uncertainty(&mut self, index: usize, p: &ErrorParams) -> Result<Uncertainty, JsValue> {
+            let style = ErrorStyle::parse(&p.style)
+                .ok_or_else(|| JsValue::from_str(&format!("Unknown uncertainty style {:?}", p.style)))?;
+            match p.column {
+                Some(col) => {
+                    let (name, values) = self.aligned_error_column(index, col)?;
+                    Ok(Uncertainty::from_column(style, &name, values))
+                }
+                None => {
+                    let value = p.value.filter(|v| v.is_finite()).ok_or_else(|| {
+                        JsValue::from_str("Uncertainty needs a column or a finite ± value")
+                    })?;
+                    Ok(Uncertainty::constant(style, value))
+                }
+            }
+        }
+
+        /// The per-sample values of loaded-file column `col`, aligned with
+        /// the samples of the series at `index` by re-applying `set_series`'
+        /// finite-pair filter to the series' own X/Y columns.
+        fn aligned_error_column(&mut self, index: usize, col: usize) -> Result<(String, Arc<[f64]>), JsValue> {
+            let src = &self.sources[index];
+            if src.derivation.is_some() || src.reference {
+                return Err(JsValue::from_str(
+                    "Uncertainty columns apply to series plotted from file columns; use a constant",
+                ));
+            }
+            let data = self.loaded.as_ref().ok_or_else(|| JsValue::from_str("No file loaded"))?;
+            if col >= data.columns.len() {
+                return Err(JsValue::from_str("Uncertainty column out of range"));
+            }
+            let y_col = data
+                .columns
+                .iter()
+                .position(|c| *c == *src.column.as_ref().unwrap_or(&src.name))
+                .ok_or_else(|| JsValue::from_str("The series' column isn't in the loaded file"))?;
+            // No X column by that name: a synthetic X, which is always finite.
+            let x_col = data.columns.iter().position(|c| *c == src.x_name);
+            let (name, n) = (data.columns[col].clone(), src.xs.len());
+            let wanted: Vec<usize> = [Some(y_col), Some(col), x_col].into_iter().flatten().collect();
+            self.materialize_cols(&wanted)?;
+
+            let data = self.loaded.as_ref().unwrap();
+            let ys = data.column_data[y_col].to_f64();
+            let es = data.column_data[col].to_f64();
+            let xs = x_col.map(|c| data.column_data[c].to_f64());
+            let values: Vec<f64> = (0..data.row_count)
+                .filter(|&i| ys[i].is_finite() && xs.as_ref().is_none_or(|xs| xs[i].is_finite()))
+                .map(|i| es[i])
+                .collect();
+            if values.len() != n {
+                return Err(JsValue::from_str("The uncertainty column doesn't line up with this series' samples"));
+            }
+            Ok((name, values.into()))
+        }
+
+        /// Drawn sources grouped into stacks by inferred unit, each group in
+        /// list order (the first source at the bottom).
+        fn stack_groups(&self, drawn: &[bool]) -> Vec<Vec<usize>> {
+            let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+            for (i, src) in self.sources.iter().enumerate().filter(|&(i, _)| drawn[i]) {
+                let unit = infer_unit(&src.name);
+                match groups.iter_mut().find(|(u, _)| *u == unit) {
+                    Some((_, members)) => members.push(i),
+                    None => groups.push((unit, vec![i])),
+                }
+            }
+            groups.into_iter().map(|(_, members)| members).collect()
+        }
+
+        /// The stack of `members` on `n` grid points over `[x_lo, x_hi]`.
+        fn stack_of(&self, members: &[usize], x_lo: f64, x_hi: f64, n: usize) -> Option<Stack> {
+            let pairs: Vec<(&[f64], &[f64])> =
+                members.iter().map(|&i| (&self.sources[i].xs[..], &self.sources[i].ys[..])).collect();
+            build_stack(&pairs, x_lo, x_hi, n, self.stack_mode)
+        }
 
-                let (xs, ys): (Vec<f64>, Vec<f64>) = x_vals
-                    .iter()
-                    .zip(values.iter())
-                    .filter(|(&x, &y)| x.is_finite() && y.is_finite())
-                    .map(|(&x, &y)| (x, y))
-                    .unzip();
+        /// A stacked value in view space: log10 in Log scale (`None` at or
+        /// below zero), else as-is.
+        fn stack_view_y(&self, v: f64) -> Option<f64> {
+            if self.y_scale == YScale::Log {
+                (v > 0.0).then(|| v.log10())
+            } else {
+                Some(v)
+            }
+        }
 
-                if xs.is_empty() {
-                    return Err(JsValue::from_str("formula produced no finite values"));
+        /// Stacked mode: replace each drawn series' line with the top edge of
+        /// its layer and rebuild the layer fills; clears the fills otherwise.
+        fn apply_stack(&mut self, drawn: &[bool], target: usize) {
+            self.stack_series.clear();
+            if self.stack_mode == StackMode::Off {
+                return;
+            }
+            let mut lines: Vec<(usize, Vec<[f32; 2]>)> = Vec::new();
+            for members in self.stack_groups(drawn) {
+                let Some(stack) = self.stack_of(&members, self.view.x_min, self.view.x_max, target) else {
+                    continue;
+                };
+                for (&i, layer) in members.iter().zip(&stack.layers) {
+                    let mut line = Vec::with_capacity(stack.grid.len());
+                    let mut fill = Vec::with_capacity(stack.grid.len() * 2);
+                    for (&x, &[lo, hi]) in stack.grid.iter().zip(layer) {
+                        let x = (x - self.x_origin) as f32;
+                        let hi = self.stack_view_y(hi).map_or(f32::NAN, |v| v as f32);
+                        let lo = self.stack_view_y(lo).unwrap_or(self.view.y_min) as f32;
+                        line.push([x, hi]);
+                        fill.extend_from_slice(&[[x, lo], [x, hi]]);
+                    }
+                    let src = &self.sources[i];
+                    let mut color = self.shown_color(src.color);
+                    color[3] *= STACK_FILL_ALPHA;
+                    self.stack_series.push(SeriesGpuData {
+                        points: fill,
+                        color,
+                        line_width: self.line_width,
+                        point_radius: self.point_radius,
+                        draw_mode: DrawMode::Band,
+                    });
+                    lines.push((i, line));
                 }
-
-                // Ensure a unique column name — duplicates would make by-name
-                // resolution (resolve_col / recompute_plotted_cols) ambiguous and
-                // hide the newer column from the Table view.
-                let base_name = if name.trim().is_empty() { expr.clone() } else { name };
-                let mut col_name = base_name.clone();
-                let mut k = 2;
-                while data.columns.iter().any(|c| *c == col_name) {
-                    col_name = format!("{base_name} ({k})");
-                    k += 1;
+            }
+            for (i, line) in lines {
+                if let Some(s) = self.series.get_mut(i) {
+                    s.points = line;
+                    s.draw_mode = DrawMode::Lines;
                 }
-                (values, xs, ys, col_name)
-            };
-
-            // Color + Y bounds only touch `self.sources`, not `self.loaded` —
-            // safe to compute before the mutable re-borrow below too.
-            let color = palette_color(self.sources.len());
-            let (y_min, y_max) = compute_y_bounds(&ys);
+            }
+        }
 
-            // ── Mutable phase: append the derived column to the dataset (so
-            // it shows up in the column list / Table view) and push a plotted
-            // series for it, mirroring add_transform's tail.
-            let data = self.loaded.as_mut().unwrap();
-            data.columns.push(col_name.clone());
-            data.column_data.push(
-                values
-                    .iter()
-                    .map(|v| if v.is_finite() { format!("{v}") } else { String::new() })
-                    .collect(),
-            );
-            // Keep numeric_cols aligned with the dataset — a derived column is
-            // always numeric, so the Table view sorts it numerically (not lexically).
-            self.table_query.numeric_cols.push(true);
+        /// Drawn bar sources, in list order; none while area-stacking, which
+        /// draws every series as a layer edge.
+        fn bar_sources(&self, drawn: &[bool]) -> Vec<usize> {
+            if self.stack_mode != StackMode::Off {
+                return vec![];
+            }
+            (0..self.sources.len())
+                .filter(|&i| drawn[i] && self.sources[i].draw_mode == DrawMode::Bar && !self.sources[i].reference)
+                .collect()
+        }
 
-            self.sources.push(SourceSeries {
-                name: col_name,
-                x_name,
-                visible: true,
-                xs,
-                ys,
-                color,
-                draw_mode: DrawMode::Lines,
-                y_min,
-                y_max,
-            });
+        /// Bars rise from zero, or from the bottom of the view in Log scale.
+        fn bar_baseline(&self) -> f64 {
+            if self.y_scale == YScale::Log && !self.normalized {
+                self.view.y_min
+            } else {
+                0.0
+            }
+        }
 
-            self.recompute_plotted_cols();
-            self.rebuild_visible();
-            self.render();
+        /// Replace each drawn bar series' points with its bar quads, all bar
+        /// series laid out together (`bar_layout`) so their slots line up.
+        /// Rebuilds the samples from the sources, so it is safe to repeat.
+        fn apply_bars(&mut self, drawn: &[bool], target: usize) {
+            let bars = self.bar_sources(drawn);
+            if bars.is_empty() {
+                return;
+            }
+            let samples: Vec<Vec<[f32; 2]>> =
+                bars.iter().map(|&i| self.build_visible_series(&self.sources[i], target).points).collect();
+            let quads = layout_bars(&samples, self.bar_layout, self.bar_baseline() as f32);
+            for (&i, q) in bars.iter().zip(quads) {
+                if let Some(s) = self.series.get_mut(i) {
+                    s.points = q;
+                }
+            }
+        }
 
-            let meta = FileMeta::from_loaded(self.loaded.as_ref().unwrap());
-            serde_wasm_bindgen::to_value(&meta).map_err(|e| JsValue::from_str(&e.to_string()))
+        /// Min/max of the bar tops over `[x_lo, x_hi]` with the baseline, in
+        /// view space: stacked bars reach the per-X sums of their values.
+        fn bar_extent(&self, x_lo: f64, x_hi: f64) -> Option<(f64, f64)> {
+            let bars = self.bar_sources(&self.drawn_mask());
+            if bars.is_empty() {
+                return None;
+            }
+            let base = self.bar_baseline();
+            let (mut lo, mut hi) = (base, base);
+            let mut sums: HashMap<u64, (f64, f64)> = HashMap::new();
+            for &i in &bars {
+                let src = &self.sources[i];
+                for (&x, &y) in src.xs.iter().zip(src.ys.iter()).filter(|&(x, _)| (x_lo..=x_hi).contains(x)) {
+                    let Some(v) = self.view_y(src, y) else { continue };
+                    let v = match self.bar_layout {
+                        BarLayout::Grouped => v,
+                        BarLayout::Stacked => {
+                            let (up, down) = sums.entry(x.to_bits()).or_insert((base, base));
+                            let sum = if v >= base { up } else { down };
+                            *sum += v - base;
+                            *sum
+                        }
+                    };
+                    lo = lo.min(v);
+                    hi = hi.max(v);
+                }
+            }
+            Some((lo, hi))
         }
 
-        /// Return the loaded dataset's column names, in file order (empty if
-        /// no file is loaded). Used by the formula editor UI to offer a
-        /// clickable list of column names to insert into an expression.
-        #[wasm_bindgen]
-        pub fn column_names(&self) -> Vec<String> {
-            self.loaded.as_ref().map(|d| d.columns.clone()).unwrap_or_default()
+        /// Min/max of the stacked layers over `[x_lo, x_hi]` in view space.
+        fn stack_extent(&self, x_lo: f64, x_hi: f64) -> Option<(f64, f64)> {
+            let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+            for members in self.stack_groups(&self.drawn_mask()) {
+                let Some(stack) = self.stack_of(&members, x_lo, x_hi, STACK_FIT_POINTS) else {
+                    continue;
+                };
+                for v in stack.layers.iter().flatten().flatten().filter_map(|&v| self.stack_view_y(v)) {
+                    lo = lo.min(v);
+                    hi = hi.max(v);
+                }
+            }
+            (lo.is_finite() && hi.is_finite()).then_some((lo, hi))
         }
 
-        /// Return `{ xs, ys, n }` for the Scatter (XY) view: dataset columns
-        /// `x_col` and `y_col` read as f64 (X tries datetime first, like
-        /// `set_series`), zipped, and filtered to finite pairs in row order.
+        /// Per-frame uniforms for the current view.
         ///
-        /// Throws (JS exception) if no file is loaded, either column index is
-        /// out of range, or there are no finite pairs.
-        #[wasm_bindgen]
-        pub fn scatter_data(&self, x_col: usize, y_col: usize) -> Result<JsValue, JsValue> {
-            let data = self
-                .loaded
-                .as_ref()
-                .ok_or_else(|| JsValue::from_str("No file loaded."))?;
+        /// Shifts the X view bounds by the same origin used for series vertices
+        /// (rebuild_visible) so epoch-second X survives the f32 cast; the origin
+        /// cancels in the shader's (p - view_min)/(view_max - view_min).
+        fn frame_uniforms(&self) -> PlotUniforms {
+            PlotUniforms {
+                view_min: [(self.view.x_min - self.x_origin) as f32, self.view.y_min as f32],
+                view_max: [(self.view.x_max - self.x_origin) as f32, self.view.y_max as f32],
+                resolution: [self.width as f32, self.height as f32],
+                line_width: self.line_width,
+                point_radius: self.point_radius,
+                color: [0.0, 0.0, 0.0, 0.0], // overridden per-series inside build_draw_calls
+                pixel_snap: 0.0,
+                _padding: [0.0; 3],
+            }
+        }
 
-            let num_cols = data.column_data.len();
-            if x_col >= num_cols || y_col >= num_cols {
-                return Err(JsValue::from_str(&format!(
-                    "Column index out of range: x_col={x_col}, y_col={y_col}, num_cols={num_cols}"
-                )));
+        /// Materialize still-deferred columns of a lazily-loaded file
+        /// (no-op for eagerly-loaded data or already-read columns).
+        fn materialize_cols(&mut self, cols: &[usize]) -> Result<(), JsValue> {
+            match self.loaded.as_mut() {
+                Some(d) => {
+                    d.materialize(cols).map_err(|e| JsValue::from_str(&e))?;
+                    let file: Vec<usize> = cols.iter().copied().filter(|&c| c < self.file_cols).collect();
+                    COLUMNS.with(|c| share_columns(&mut c.borrow_mut(), self.file_key, d, &file));
+                    Ok(())
+                }
+                None => Ok(()),
+            }
+        }
+
+        /// Recompute the filtered+sorted row index from the current table_query.
+        /// Fit the Y view to visible data with X in `[x_lo, x_hi]` (5%
+        /// padding), honoring the Y-scale and autoscale mode. Returns false,
+        /// leaving the view alone, when there are no values to fit.
+        fn fit_y(&mut self, x_lo: f64, x_hi: f64) -> bool {
+            // Fit Y from raw data values, honoring the Y-scale.
+            // In Log mode each y>0 contributes log10(y) and non-positive y is
+            // dropped; in Linear mode every finite y contributes as-is.
+            if self.stack_mode != StackMode::Off {
+                let Some((lo, hi)) = self.stack_extent(x_lo, x_hi) else {
+                    return false;
+                };
+                let pad = ((hi - lo) * 0.05).max(1e-9);
+                self.view.y_min = lo - pad;
+                self.view.y_max = hi + pad;
+                return true;
             }
+            let log = self.y_scale == YScale::Log;
+            let robust = self.autoscale_mode == AutoscaleMode::Robust;
 
-            let x_col_data = &data.column_data[x_col];
-            let y_col_data = &data.column_data[y_col];
+            let mut v_min = f64::INFINITY;
+            let mut v_max = f64::NEG_INFINITY;
+            // Only the Robust path needs the full stream for percentiles.
+            let mut vals: Vec<f64> = Vec::new();
+
+            for s in &self.sources {
+                if !s.visible {
+                    continue; // fit to what's shown, not hidden series
+                }
+                for (&x, &y) in s.xs.iter().zip(s.ys.iter()) {
+                    if x < x_lo || x > x_hi {
+                        continue;
+                    }
+                    if !y.is_finite() {
+                        continue;
+                    }
+                    let v = if log {
+                        if y > 0.0 { y.log10() } else { continue }
+                    } else {
+                        y
+                    };
+                    v_min = v_min.min(v);
+                    v_max = v_max.max(v);
+                    if robust {
+                        vals.push(v);
+                    }
+                }
+            }
 
-            // Convert X: try datetime first, fall back to f64 (mirrors set_series).
-            let x_vals: Vec<f64> = if let Some((ts, _)) = column_to_timestamps(x_col_data) {
-                ts
+            let (y_min, y_max) = if robust {
+                // Clip to the 1st/99th percentiles so outliers don't dominate.
+                // Fall back to the min/max of the stream, then to (0, 1) if the
+                // percentile range is degenerate (non-finite or y_max <= y_min).
+                vals.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+                let lo = percentile(&vals, 0.01);
+                let hi = percentile(&vals, 0.99);
+                if lo.is_finite() && hi.is_finite() && hi > lo {
+                    (lo, hi)
+                } else if v_min.is_finite() && v_max.is_finite() && v_max > v_min {
+                    (v_min, v_max)
+                } else {
+                    (0.0, 1.0)
+                }
             } else {
-                column_to_f64(x_col_data).0
+                // MinMax: raw extremes of the stream (existing behavior for linear).
+                if !v_min.is_finite() || !v_max.is_finite() {
+                    return false;
+                }
+                (v_min, v_max)
+            };
+            // Bars are drawn from their baseline (and stacked bars reach
+            // their sums), so the view has to take those in too.
+            let (y_min, y_max) = match self.bar_extent(x_lo, x_hi) {
+                Some((lo, hi)) if !log => (y_min.min(lo), y_max.max(hi)),
+                _ => (y_min, y_max),
             };
-            let (y_vals, _) = column_to_f64(y_col_data);
 
-            let (xs, ys): (Vec<f64>, Vec<f64>) = x_vals
-                .iter()
-                .zip(y_vals.iter())
-                .filter(|(&x, &y)| x.is_finite() && y.is_finite())
-                .map(|(&x, &y)| (x, y))
-                .unzip();
+            let y_pad = ((y_max - y_min) * 0.05).max(1e-9);
+            self.view.y_min = y_min - y_pad;
+            self.view.y_max = y_max + y_pad;
+            true
+        }
 
-            if xs.is_empty() {
-                return Err(JsValue::from_str("No finite (x, y) pairs for these columns"));
+        /// A raw sample's Y in view space (normalized / log10 / as-is), or
+        /// `None` when it isn't drawn (y ≤ 0 in Log scale).
+        fn view_y(&self, src: &SourceSeries, y: f64) -> Option<f64> {
+            if !y.is_finite() {
+                None
+            } else if self.normalized {
+                let (lo, hi) = self.norm_range(src);
+                let range = hi - lo;
+                Some(if range.abs() < 1e-15 { 0.5 } else { (y - lo) / range })
+            } else if self.y_scale == YScale::Log {
+                (y > 0.0).then(|| y.log10())
+            } else {
+                Some(y)
             }
+        }
 
-            let n = xs.len();
-            serde_wasm_bindgen::to_value(&ScatterData { xs, ys, n })
-                .map_err(|e| JsValue::from_str(&e.to_string()))
+        /// The unit shared by every visible series, inferred from their
+        /// names; `None` when they differ or none is recognised.
+        fn inferred_y_unit(&self) -> Option<String> {
+            let mut units = self.sources.iter().filter(|s| s.visible).map(|s| infer_unit(&s.name));
+            match units.next() {
+                Some(u) if u != "units" && units.all(|o| o == u) => Some(u),
+                _ => None,
+            }
         }
 
-        // ── X-range sync (Task 4) ─────────────────────────────────────────────
+        /// Fit `kind` (see `series_fit`) to `src`. On a datetime axis the
+        /// coefficients are relative to the first sample, not to 1970.
+        fn fit_source(&self, src: &SourceSeries, kind: &str, degree: usize) -> Result<Fit, JsValue> {
+            let model = FitModel::parse(kind, degree).ok_or_else(|| JsValue::from_str(&format!("unknown fit: {kind} (degree {degree})")))?;
+            let x0 = if self.x_is_time { src.xs.iter().copied().find(|x| x.is_finite()).unwrap_or(0.0) } else { 0.0 };
+            fit(model, &src.xs, &src.ys, x0).map_err(|e| JsValue::from_str(&e))
+        }
 
-        /// Set the view's X bounds without touching Y, then rebuild visible
-        /// series (LTTB re-runs for the new range) and re-render.
-        ///
-        /// Called by the TypeScript `applyXRange` path when the workspace is
-        /// propagating one graph's X-range to another.  Y stays untouched so
-        /// each graph can still have its own independent Y axis.
-        #[wasm_bindgen]
-        pub fn set_x_range(&mut self, x_min: f64, x_max: f64) {
-            // Reject degenerate/inverted ranges: a zero-width or non-finite span
-            // would divide-by-zero in the coordinate mapping and grid lines.
-            if !x_min.is_finite() || !x_max.is_finite() || x_min >= x_max {
-                return;
+        /// Unit for the label of transform `kind` of `src` (see `add_transform`):
+        /// the source's inferred unit combined with the X unit — seconds on
+        /// datetime axes, else the edited X unit, else the X column's own
+        /// (or its name). `None` for other kinds or an unknown source unit.
+        fn transform_unit(&self, kind: &str, src: &SourceSeries) -> Option<String> {
+            let y = infer_unit(&src.name);
+            if y == UNKNOWN_UNIT {
+                return None;
+            }
+            let x = || {
+                if self.x_is_time {
+                    return "s".to_string();
+                }
+                if let Some(u) = self.x_title.as_ref().and_then(|t| t.unit.clone()) {
+                    return u;
+                }
+                match infer_unit(&src.x_name) {
+                    u if u == UNKNOWN_UNIT => src.x_name.clone(),
+                    u => u,
+                }
+            };
+            match kind {
+                "derivative" => Some(math_ops::derivative_unit(&y, &x())),
+                "integral" => Some(math_ops::integral_unit(&y, &x())),
+                "abs" | "fit" | "x_constant" | "x_zero" | "x_unit" => Some(y),
+                _ => None,
             }
-            self.view.x_min = x_min;
-            self.view.x_max = x_max;
-            self.rebuild_visible();
-            self.render();
         }
 
-        /// Set the full view bounds (X and Y) directly, then rebuild + render.
-        /// Used by the rubber-band zoom box. Bounds are in the same view space
-        /// that `view_state` reports (log space when Y-scale is Log), so the
-        /// frontend can derive them from a screen rectangle. Rejects
-        /// degenerate/inverted spans on either axis.
-        #[wasm_bindgen]
-        pub fn set_view_bounds(&mut self, x_min: f64, x_max: f64, y_min: f64, y_max: f64) {
-            if !x_min.is_finite() || !x_max.is_finite() || x_min >= x_max {
-                return;
+        /// Keep one Y axis per unit of the sources (see `sync_unit_axes`)
+        /// and recompute the range each unit normalizes over.
+        fn refresh_unit_axes(&mut self) {
+            let units: Vec<String> = self.sources.iter().map(|s| infer_unit(&s.name)).collect();
+            sync_unit_axes(&mut self.y_axes, units.iter().map(String::as_str));
+            self.unit_ranges.clear();
+            for (src, unit) in self.sources.iter().zip(&units).filter(|(s, _)| s.visible) {
+                let r = self.unit_ranges.entry(unit.clone()).or_insert((src.y_min, src.y_max));
+                *r = (r.0.min(src.y_min), r.1.max(src.y_max));
             }
-            if !y_min.is_finite() || !y_max.is_finite() || y_min >= y_max {
-                return;
+            for (unit, r) in &mut self.unit_ranges {
+                *r = self.y_axes[unit].range(*r);
             }
-            self.view.x_min = x_min;
-            self.view.x_max = x_max;
-            self.view.y_min = y_min;
-            self.view.y_max = y_max;
-            self.rebuild_visible();
-            self.render();
         }
 
-        // ── Private helpers ───────────────────────────────────────────────────
-
-        /// Rebuild `self.series` by LTTB-downsampling each source series to the
-        /// visible X-range.  Target point count = max(width, MIN_TARGET_POINTS),
-        /// giving roughly one point per horizontal pixel.
-        ///
-        /// Uses `downsample_for_view_mode` from oxideplot-core (selecting the
-        /// current `downsample_mode`), which uses binary search on sorted X data
-        /// and extends the window by one extra point on each edge so lines don't
-        /// clip while panning. In Log Y-scale mode the surviving points are
-        /// log10-transformed (dropping y<=0) after decimation.
-        ///
-        /// Performance note: this runs on every pan/zoom event (per pointermove
-        /// during a drag).  For very large datasets (~1M points) the O(n) visible
-        /// scan + LTTB may be noticeable.  For this MVP the straightforward
-        /// implementation is acceptable; debouncing or spatial indices can be
-        /// added in a future task if profiling warrants it.
-        fn rebuild_visible(&mut self) {
-            let target = (self.width as usize).max(MIN_TARGET_POINTS);
-            let x_min = self.view.x_min;
-            let x_max = self.view.x_max;
-
-            // Large-coordinate render offset (see field docs): shift X by a stable
-            // in-range origin so epoch-second timestamps survive the f32 cast. Any
-            // X inside the data window works; the first finite X is cheapest. The
-            // same origin is applied to the view uniforms in render(), so it
-            // cancels in the shader's (p - view_min)/(view_max - view_min).
-            let x_origin = self
-                .sources
-                .iter()
-                .flat_map(|s| s.xs.iter())
+        /// The raw range `unit` maps to [0, 1] in normalized mode, for the
+        /// per-axis zoom and pan.
+        fn shown_unit_range(&self, unit: &str) -> Result<(f64, f64), JsValue> {
+            if !self.normalized {
+                return Err(JsValue::from_str("Per-unit axes only exist in normalized mode"));
+            }
+            self.unit_ranges
+                .get(unit)
                 .copied()
-                .find(|x| x.is_finite())
-                .unwrap_or(0.0);
-            self.x_origin = x_origin;
+                .ok_or_else(|| JsValue::from_str(&format!("no visible series has unit {unit:?}")))
+        }
 
-            self.series = self
+        /// Recolour the non-reference sources by unit (`color_by_unit`).
+        fn apply_unit_colors(&mut self) {
+            let units: Vec<String> = self
                 .sources
                 .iter()
-                .map(|src| {
-                    // Invisible series get an empty SeriesGpuData so self.series
-                    // stays index-aligned with self.sources; build_draw_calls
-                    // skips empty point buffers.
-                    if !src.visible {
-                        return SeriesGpuData {
-                            points: vec![],
-                            color: src.color,
-                            line_width: self.line_width,
-                            point_radius: self.point_radius,
-                            draw_mode: src.draw_mode,
-                        };
-                    }
+                .filter(|s| !s.reference)
+                .map(|s| infer_unit(&s.name))
+                .collect();
+            let colors = unit_colors(&units.iter().map(String::as_str).collect::<Vec<_>>());
+            for (src, color) in self.sources.iter_mut().filter(|s| !s.reference).zip(colors) {
+                src.color = color;
+            }
+        }
 
-                    // Decimate first (in raw Y-space), then log-transform the
-                    // survivors below — per the Global Constraints ordering.
-                    let (vis_x, vis_y) = downsample_for_view_mode(
-                        &src.xs,
-                        &src.ys,
-                        x_min,
-                        x_max,
-                        target,
-                        self.downsample_mode,
-                    );
+        /// Raw Y range normalized mode maps to [0, 1] for `src`: its unit's
+        /// range, or its own for series outside the sources (ghosts of a
+        /// unit no longer plotted).
+        fn norm_range(&self, src: &SourceSeries) -> (f64, f64) {
+            self.unit_ranges.get(&infer_unit(&src.name)).copied().unwrap_or((src.y_min, src.y_max))
+        }
 
-                    let points: Vec<[f32; 2]> = if self.normalized {
-                        // Normalize Y using this source's global min/max.
-                        // Guard: if y_max == y_min (degenerate), map to 0.5.
-                        let range = src.y_max - src.y_min;
-                        vis_x
-                            .iter()
-                            .zip(vis_y.iter())
-                            .map(|(&x, &y)| {
-                                let yn = if range.abs() < 1e-15 {
-                                    0.5
-                                } else {
-                                    (y - src.y_min) / range
-                                };
-                                [(x - x_origin) as f32, yn as f32]
-                            })
-                            .collect()
-                    } else if self.y_scale == YScale::Log {
-                        // Log Y: plot log10(y), dropping non-positive samples
-                        // (log undefined). Non-finite y was excluded in set_series.
-                        vis_x
-                            .iter()
-                            .zip(vis_y.iter())
-                            .filter(|&(_, &y)| y > 0.0)
-                            .map(|(&x, &y)| [(x - x_origin) as f32, y.log10() as f32])
-                            .collect()
-                    } else {
-                        vis_x
-                            .iter()
-                            .zip(vis_y.iter())
-                            .map(|(&x, &y)| [(x - x_origin) as f32, y as f32])
-                            .collect()
-                    };
+        /// Units of the sources (only the visible ones with `visible_only`),
+        /// once each, in axis order (see `sort_unit_axes`).
+        fn axis_units(&self, visible_only: bool) -> Vec<String> {
+            let mut units: Vec<String> = Vec::new();
+            for src in self.sources.iter().filter(|s| s.visible || !visible_only) {
+                let unit = infer_unit(&src.name);
+                if !units.contains(&unit) {
+                    units.push(unit);
+                }
+            }
+            sort_unit_axes(&mut units, &self.y_axes);
+            units
+        }
 
-                    SeriesGpuData {
-                        points,
-                        color: src.color,
-                        line_width: self.line_width,
-                        point_radius: self.point_radius,
-                        draw_mode: src.draw_mode,
-                    }
-                })
-                .collect();
+        /// Normalized-mode axes: for each unit of the visible series, ticks at
+        /// round values of the unit's range, positioned in view space.
+        fn unit_axis_ticks(&self) -> Vec<UnitAxisTicks> {
+            let mut out: Vec<UnitAxisTicks> = Vec::new();
+            for unit in self.axis_units(true) {
+                let Some(src) = self.sources.iter().find(|s| s.visible && infer_unit(&s.name) == unit) else {
+                    continue;
+                };
+                let Some(&(lo, hi)) = self.unit_ranges.get(&unit) else {
+                    continue;
+                };
+                let span = hi - lo;
+                if !span.is_finite() || span.abs() < 1e-15 {
+                    continue;
+                }
+                let ticks = compute_grid_lines(lo + self.view.y_min * span, lo + self.view.y_max * span)
+                    .into_iter()
+                    .map(|(val, major)| TickEntry { value: (val - lo) / span, label: format_tick_value(val), major })
+                    .collect();
+                let axis = self.y_axes.get(&unit);
+                out.push(UnitAxisTicks {
+                    label: axis.map(|a| a.label.clone()).unwrap_or_else(|| unit.clone()),
+                    side: axis.map(|a| a.side).unwrap_or_default(),
+                    unit,
+                    color: self.shown_color(src.color),
+                    ticks,
+                });
+            }
+            out
+        }
+
+        /// The label map that applies to the Y axis: the axis map if set,
+        /// else the map of the only visible labelled series. None in
+        /// normalized or log mode, where Y is no longer in raw units.
+        fn active_y_labels(&self) -> Option<&LabelMap> {
+            if self.normalized || self.y_scale == YScale::Log {
+                return None;
+            }
+            if self.y_labels.is_some() {
+                return self.y_labels.as_ref();
+            }
+            let mut labelled = self.sources.iter().filter(|s| s.visible).filter_map(|s| s.labels.as_ref());
+            match (labelled.next(), labelled.next()) {
+                (Some(m), None) => Some(m),
+                _ => None,
+            }
+        }
+
+        /// Label map of the series plotting file column `name`, if any.
+        fn column_labels(&self, name: &str) -> Option<&LabelMap> {
+            self.sources.iter().filter(|s| s.name == name).find_map(|s| s.labels.as_ref())
         }
 
-        /// Recompute the filtered+sorted row index from the current table_query.
         fn rebuild_table_index(&mut self) {
             if let Some(d) = &self.loaded {
                 self.table_index = compute_view_index(d, &self.table_query);
             }
+            self.table_drill = None;
         }
 
         /// Recompute `plotted_cols` — the union of each plotted source's
@@ -1684,7 +6159,7 @@ mod wasm_impl {
                         if let Some(i) = d.columns.iter().position(|c| c == &src.x_name) {
                             set.insert(i);
                         }
-                        if let Some(i) = d.columns.iter().position(|c| c == &src.name) {
+                        if let Some(i) = d.columns.iter().position(|c| c == src.column()) {
                             set.insert(i);
                         }
                     }
@@ -1698,6 +6173,12 @@ mod wasm_impl {
 
         /// Column indices to display in the table, in display order:
         /// `plotted_cols` when non-empty, else every file column.
+        /// Header for the X column `name`: with the edited X axis name/unit
+        /// when set.
+        fn x_header(&self, name: &str) -> String {
+            self.x_title.as_ref().map_or_else(|| name.to_string(), |t| t.header(name))
+        }
+
         fn display_indices(&self, d: &LoadedData) -> Vec<usize> {
             if self.plotted_cols.is_empty() {
                 (0..d.columns.len()).collect()
@@ -1706,6 +6187,26 @@ mod wasm_impl {
             }
         }
 
+        /// File column holding the plotted X, if the X is a file column.
+        fn table_x_col(&self, d: &LoadedData) -> Option<usize> {
+            let x_name = &self.sources.first()?.x_name;
+            d.columns.iter().position(|c| c == x_name)
+        }
+
+        /// The part of `table_index` the table shows: the drilled-down span,
+        /// or all of it.
+        fn shown_table_index(&self) -> &[usize] {
+            match &self.table_drill {
+                Some(span) => &self.table_index[span.start.min(self.table_index.len())..span.end.min(self.table_index.len())],
+                None => &self.table_index,
+            }
+        }
+
+        /// The decimation in effect: none while drilled down.
+        fn effective_decimation(&self) -> Decimation {
+            if self.table_drill.is_some() { Decimation::Off } else { self.table_decimation }
+        }
+
         /// Translate a DISPLAY column index (position in `table_columns()`'s
         /// output) to the real file-column index, for sort/filter targeting.
         fn to_orig_col(&self, display_col: usize) -> usize {
@@ -1,4 +1,9 @@
-use tauri::Manager;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tauri::{Emitter, Manager};
 
 #[tauri::command]
 pub fn load_prefs(app: tauri::AppHandle) -> Result<String, String> {
@@ -22,7 +27,9 @@ pub fn save_prefs(app: tauri::AppHandle, contents: String) -> Result<(), String>
 #[tauri::command]
 pub fn pick_file() -> Option<String> {
     rfd::FileDialog::new()
-        .add_filter("data", &["csv", "dat", "txt", "tsv", "xlsx", "xls"])
+        .add_filter("data", &["csv", "dat", "txt", "tsv", "xlsx", "xls", "mf4", "mdf", "ulg", "db", "sqlite", "sqlite3", "parquet", "json", "ndjson", "jsonl"])
+        .add_filter("OxidePlot project", &["oxideplot", "oxproj"])
+        .add_filter("OxidePlot settings", &["oxsettings"])
         .pick_file()
         .map(|p| p.to_string_lossy().into_owned())
 }
@@ -32,6 +39,25 @@ pub fn read_file(path: String) -> Result<Vec<u8>, String> {
     std::fs::read(&path).map_err(|e| e.to_string())
 }
 
+/// Size in bytes of the file at `path` (decides chunked import).
+#[tauri::command]
+pub fn file_size(path: String) -> Result<u64, String> {
+    std::fs::metadata(&path).map(|m| m.len()).map_err(|e| e.to_string())
+}
+
+/// Up to `len` bytes of the file at `path` from `offset` (fewer at the end
+/// of the file), sent as a raw binary response rather than a JSON array —
+/// the chunked import reads multi-gigabyte files this way.
+#[tauri::command]
+pub fn read_file_range(path: String, offset: u64, len: usize) -> Result<tauri::ipc::Response, String> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = std::fs::File::open(&path).map_err(|e| e.to_string())?;
+    file.seek(SeekFrom::Start(offset)).map_err(|e| e.to_string())?;
+    let mut buf = Vec::with_capacity(len);
+    file.take(len as u64).read_to_end(&mut buf).map_err(|e| e.to_string())?;
+    Ok(tauri::ipc::Response::new(buf))
+}
+
 /// Open a native save-file dialog and write `contents` to the chosen path.
 ///
 /// Returns the chosen path as a string, or `Ok(None)` if the user cancelled.
@@ -50,3 +76,460 @@ pub fn save_file(default_name: String, contents: Vec<u8>) -> Result<Option<Strin
         }
     }
 }
+
+/// Open a native save-file dialog without writing anything: the chosen
+/// path, or `None` if the user cancelled. For saves whose contents depend on
+/// where they go (projects storing relative file links).
+#[tauri::command]
+pub fn pick_save_path(default_name: String) -> Option<String> {
+    rfd::FileDialog::new()
+        .set_file_name(&default_name)
+        .save_file()
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+/// Write `contents` to `path` (a path from `pick_save_path`).
+#[tauri::command]
+pub fn write_file(path: String, contents: Vec<u8>) -> Result<(), String> {
+    std::fs::write(&path, &contents).map_err(|e| e.to_string())
+}
+
+/// Open a native folder picker; `None` if the user cancelled.
+#[tauri::command]
+pub fn pick_folder() -> Option<String> {
+    rfd::FileDialog::new()
+        .pick_folder()
+        .map(|p| p.to_string_lossy().into_owned())
+}
+
+/// Write `contents` to `name` inside the folder `dir` (no dialog), for
+/// scheduled exports. `name` must be a plain file name, not a path.
+/// Returns the written path.
+#[tauri::command]
+pub fn write_file_in(dir: String, name: String, contents: Vec<u8>) -> Result<String, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name == "." || name == ".." {
+        return Err(format!("invalid file name {name:?}"));
+    }
+    let path = std::path::Path::new(&dir).join(&name);
+    std::fs::write(&path, &contents).map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// A table (or view) of a SQLite database and its columns, for the query
+/// prompt.
+#[derive(serde::Serialize)]
+pub struct SqlTable {
+    pub name: String,
+    pub columns: Vec<String>,
+}
+
+/// Open the database at `path` read-only. SQLite itself reads it, so a
+/// `-wal` file next to it (writes not yet checkpointed) is applied.
+fn open_database(path: &str) -> Result<rusqlite::Connection, String> {
+    use rusqlite::OpenFlags;
+    rusqlite::Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX)
+        .map_err(|e| format!("{path}: {e}"))
+}
+
+/// Tables and views of the SQLite database at `path`, in schema order.
+#[tauri::command]
+pub fn sqlite_tables(path: String) -> Result<Vec<SqlTable>, String> {
+    let db = open_database(&path)?;
+    let mut names = db
+        .prepare("SELECT name FROM sqlite_schema WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' ORDER BY rowid")
+        .map_err(|e| e.to_string())?;
+    let names: Vec<String> =
+        names.query_map([], |row| row.get(0)).and_then(|rows| rows.collect()).map_err(|e| e.to_string())?;
+    let mut info = db.prepare("SELECT name FROM pragma_table_info(?1)").map_err(|e| e.to_string())?;
+    names
+        .into_iter()
+        .map(|name| {
+            let columns =
+                info.query_map([&name], |row| row.get(0)).and_then(|rows| rows.collect()).map_err(|e| e.to_string())?;
+            Ok(SqlTable { name, columns })
+        })
+        .collect()
+}
+
+/// Append `field` to a CSV row, quoted when it holds a separator, quote or
+/// line break.
+fn push_csv_field(out: &mut Vec<u8>, field: &str) {
+    if field.contains([',', '"', '\n', '\r']) {
+        out.push(b'"');
+        out.extend_from_slice(field.replace('"', "\"\"").as_bytes());
+        out.push(b'"');
+    } else {
+        out.extend_from_slice(field.as_bytes());
+    }
+}
+
+/// Run the read-only query `sql` against the SQLite database at `path`
+/// (any SQL SQLite accepts: joins, aggregates, views …) and return its
+/// result as CSV with a header row, for the frontend to load like a file.
+/// NULLs and BLOBs are left blank. Runs on a worker thread.
+#[tauri::command]
+pub async fn sqlite_query(path: String, sql: String) -> Result<tauri::ipc::Response, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        use rusqlite::types::ValueRef;
+        let db = open_database(&path)?;
+        let mut stmt = db.prepare(&sql).map_err(|e| e.to_string())?;
+        if !stmt.readonly() {
+            return Err("only read-only queries (SELECT …) can be run".to_string());
+        }
+        let width = stmt.column_count();
+        let mut out = Vec::new();
+        for (i, name) in stmt.column_names().into_iter().enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            push_csv_field(&mut out, name);
+        }
+        out.push(b'\n');
+        let mut rows = stmt.query([]).map_err(|e| e.to_string())?;
+        let mut count = 0usize;
+        while let Some(row) = rows.next().map_err(|e| e.to_string())? {
+            for i in 0..width {
+                if i > 0 {
+                    out.push(b',');
+                }
+                match row.get_ref(i).map_err(|e| e.to_string())? {
+                    ValueRef::Null | ValueRef::Blob(_) => {}
+                    ValueRef::Integer(v) => out.extend_from_slice(v.to_string().as_bytes()),
+                    ValueRef::Real(v) => out.extend_from_slice(v.to_string().as_bytes()),
+                    ValueRef::Text(t) => push_csv_field(&mut out, &String::from_utf8_lossy(t)),
+                }
+            }
+            out.push(b'\n');
+            count += 1;
+        }
+        if count == 0 {
+            return Err("query returned no rows".to_string());
+        }
+        Ok(tauri::ipc::Response::new(out))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Temp files handed out by `temp_file`, removed when the app exits.
+static TEMP_FILES: Mutex<Vec<std::path::PathBuf>> = Mutex::new(Vec::new());
+
+/// A new, not yet created file in the system temp directory, deleted by
+/// `remove_temp_files`.
+fn temp_file() -> Result<std::path::PathBuf, String> {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let n = NEXT.fetch_add(1, Ordering::Relaxed);
+    let path = std::env::temp_dir().join(format!("oxideplot-{}-{n}.tmp", std::process::id()));
+    TEMP_FILES.lock().map_err(|e| e.to_string())?.push(path.clone());
+    Ok(path)
+}
+
+/// Delete the files `temp_file` handed out, with the `-wal` / `-shm` files
+/// SQLite may have created beside them.
+pub fn remove_temp_files() {
+    if let Ok(mut files) = TEMP_FILES.lock() {
+        for path in files.drain(..) {
+            for suffix in ["", "-wal", "-shm"] {
+                let mut name = path.clone().into_os_string();
+                name.push(suffix);
+                let _ = std::fs::remove_file(name);
+            }
+        }
+    }
+}
+
+/// Largest download `fetch_url` accepts.
+const MAX_FETCH_BYTES: u64 = 1 << 30;
+
+/// A downloaded data file, saved to a temp file (`size` bytes at `path`,
+/// read back with `read_file_range`; databases are queried there), plus a
+/// filename whose extension picks the loader, exactly as for a file opened
+/// from disk.
+#[derive(serde::Serialize)]
+pub struct FetchedFile {
+    pub filename: String,
+    pub path: String,
+    pub size: u64,
+}
+
+/// Download `url` with the given extra request headers (e.g.
+/// `Authorization`). Runs on a worker thread so the UI stays responsive.
+/// Fails on HTTP error statuses, with the status and reason in the message,
+/// and on downloads over `MAX_FETCH_BYTES`.
+#[tauri::command]
+pub async fn fetch_url(url: String, headers: Vec<(String, String)>) -> Result<FetchedFile, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut req = ureq::get(&url);
+        for (name, value) in &headers {
+            req = req.set(name, value);
+        }
+        let resp = req.call().map_err(|e| match e {
+            ureq::Error::Status(code, r) => format!("HTTP {code} {}", r.status_text()),
+            e => e.to_string(),
+        })?;
+        let too_big = || format!("download exceeds {} GiB", MAX_FETCH_BYTES >> 30);
+        let length = resp.header("content-length").and_then(|n| n.parse::<u64>().ok());
+        if length.is_some_and(|n| n > MAX_FETCH_BYTES) {
+            return Err(too_big());
+        }
+        let filename = url_filename(&url, resp.header("content-type"));
+        let path = temp_file()?;
+        let mut file = std::fs::File::create(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        // One byte past the limit tells an oversized download from one that
+        // fits exactly.
+        let size = std::io::copy(&mut resp.into_reader().take(MAX_FETCH_BYTES + 1), &mut file)
+            .map_err(|e| e.to_string())?;
+        if size > MAX_FETCH_BYTES {
+            drop(file);
+            let _ = std::fs::remove_file(&path);
+            return Err(too_big());
+        }
+        Ok(FetchedFile { filename, path: path.to_string_lossy().into_owned(), size })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+/// Last path segment of `url` (query and fragment dropped); when it has no
+/// extension, one is chosen from the response content type (CSV by default).
+fn url_filename(url: &str, content_type: Option<&str>) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+    let name = path.split_once('/').map_or("", |(_, p)| p).rsplit('/').next().unwrap_or("");
+    if name.contains('.') {
+        return name.to_string();
+    }
+    let mime = content_type.unwrap_or("").split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    let ext = match mime.as_str() {
+        "application/json" | "text/json" => "json",
+        "application/x-ndjson" | "application/jsonl" => "ndjson",
+        "text/tab-separated-values" => "tsv",
+        "text/plain" => "txt",
+        _ => "csv",
+    };
+    format!("{}.{ext}", if name.is_empty() { "download" } else { name })
+}
+
+/// Stop flag and handle of the latest serial reader thread, kept after
+/// `serial_close` so the next `serial_open` can wait for it to exit.
+static SERIAL: Mutex<Option<(Arc<AtomicBool>, std::thread::JoinHandle<()>)>> = Mutex::new(None);
+
+#[derive(Clone, serde::Serialize)]
+struct SerialData {
+    id: u32,
+    text: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct SerialClosed {
+    id: u32,
+    message: Option<String>,
+}
+
+/// Names of the serial ports on this machine (`COM3`, `/dev/ttyUSB0`, …).
+#[tauri::command]
+pub fn serial_ports() -> Result<Vec<String>, String> {
+    let ports = serialport::available_ports().map_err(|e| e.to_string())?;
+    Ok(ports.into_iter().map(|p| p.port_name).collect())
+}
+
+/// Open `port` at `baud` as connection `id` and forward what it sends as
+/// `serial-data` events `{ id, text }` (text chunks, split anywhere). A
+/// `serial-closed` event `{ id, message }` ends the stream, carrying the
+/// error message, or null after `serial_close`. Replaces any connection
+/// already open, waiting for its reader thread to release the port first.
+#[tauri::command]
+pub fn serial_open(app: tauri::AppHandle, id: u32, port: String, baud: u32) -> Result<(), String> {
+    let old = SERIAL.lock().map_err(|e| e.to_string())?.take();
+    if let Some((stop, reader)) = old {
+        stop.store(true, Ordering::Relaxed);
+        let _ = reader.join();
+    }
+    let mut dev = serialport::new(&port, baud)
+        .timeout(Duration::from_millis(100))
+        .open()
+        .map_err(|e| format!("{port}: {e}"))?;
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    let reader = std::thread::spawn(move || {
+        let mut buf = [0u8; 4096];
+        let reason = loop {
+            if flag.load(Ordering::Relaxed) {
+                break None;
+            }
+            match dev.read(&mut buf) {
+                Ok(0) => {}
+                // Devices print ASCII numbers; a stray invalid byte is replaced.
+                Ok(n) => {
+                    let text = String::from_utf8_lossy(&buf[..n]).into_owned();
+                    let _ = app.emit("serial-data", SerialData { id, text });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => break Some(format!("{port}: {e}")),
+            }
+        };
+        let _ = app.emit("serial-closed", SerialClosed { id, message: reason });
+    });
+    *SERIAL.lock().map_err(|e| e.to_string())? = Some((stop, reader));
+    Ok(())
+}
+
+/// Close the serial connection opened by `serial_open` (no-op if none).
+#[tauri::command]
+pub fn serial_close() {
+    if let Ok(serial) = SERIAL.lock() {
+        if let Some((stop, _)) = serial.as_ref() {
+            stop.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Stop flags of the running socket reader threads, by connection id.
+static SOCKETS: Mutex<Vec<(u32, Arc<AtomicBool>)>> = Mutex::new(Vec::new());
+
+/// Delay before the first TCP reconnect attempt; doubles up to
+/// `MAX_RECONNECT_DELAY` while the server stays unreachable.
+const RECONNECT_DELAY: Duration = Duration::from_millis(500);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(8);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+/// Read timeout, so reader threads notice `socket_close` promptly.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, serde::Serialize)]
+struct SocketData {
+    id: u32,
+    text: String,
+}
+
+#[derive(Clone, serde::Serialize)]
+struct SocketStatus {
+    id: u32,
+    /// `connecting`, `connected`, `reconnecting` or `closed`.
+    state: &'static str,
+    message: Option<String>,
+}
+
+/// Subscribe connection `id` to a socket emitting CSV or JSON lines: `tcp`
+/// connects to `address` (`host:port`) and reconnects with backoff whenever
+/// the connection drops; `udp` binds `address` (e.g. `0.0.0.0:5005`) and
+/// takes each datagram as complete lines. Text arrives as `socket-data`
+/// events `{ id, text }`; `socket-status` events `{ id, state, message }`
+/// track the connection. Replaces an open connection with the same id.
+#[tauri::command]
+pub fn socket_open(app: tauri::AppHandle, id: u32, protocol: String, address: String) -> Result<(), String> {
+    socket_close(id);
+    let stop = Arc::new(AtomicBool::new(false));
+    let flag = stop.clone();
+    match protocol.as_str() {
+        "tcp" => {
+            std::thread::spawn(move || run_tcp(&app, id, &address, &flag));
+        }
+        "udp" => {
+            let sock = std::net::UdpSocket::bind(&address).map_err(|e| format!("{address}: {e}"))?;
+            sock.set_read_timeout(Some(POLL_INTERVAL)).map_err(|e| e.to_string())?;
+            std::thread::spawn(move || run_udp(&app, id, &sock, &flag));
+        }
+        other => return Err(format!("unknown protocol {other:?} (expected tcp or udp)")),
+    }
+    SOCKETS.lock().map_err(|e| e.to_string())?.push((id, stop));
+    Ok(())
+}
+
+/// Close socket connection `id` (no-op if it isn't open).
+#[tauri::command]
+pub fn socket_close(id: u32) {
+    if let Ok(mut sockets) = SOCKETS.lock() {
+        sockets.retain(|(i, stop)| {
+            if *i == id {
+                stop.store(true, Ordering::Relaxed);
+            }
+            *i != id
+        });
+    }
+}
+
+fn emit_status(app: &tauri::AppHandle, id: u32, state: &'static str, message: Option<String>) {
+    let _ = app.emit("socket-status", SocketStatus { id, state, message });
+}
+
+/// Sleep `delay`, waking early when `stop` is set.
+fn sleep_unless_stopped(delay: Duration, stop: &AtomicBool) {
+    let until = std::time::Instant::now() + delay;
+    while !stop.load(Ordering::Relaxed) && std::time::Instant::now() < until {
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn tcp_connect(address: &str) -> Result<std::net::TcpStream, String> {
+    use std::net::ToSocketAddrs;
+    let addr = address
+        .to_socket_addrs()
+        .map_err(|e| e.to_string())?
+        .next()
+        .ok_or_else(|| format!("{address} did not resolve"))?;
+    let stream = std::net::TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT).map_err(|e| e.to_string())?;
+    stream.set_read_timeout(Some(POLL_INTERVAL)).map_err(|e| e.to_string())?;
+    Ok(stream)
+}
+
+fn run_tcp(app: &tauri::AppHandle, id: u32, address: &str, stop: &AtomicBool) {
+    let mut buf = [0u8; 8192];
+    let mut delay = RECONNECT_DELAY;
+    while !stop.load(Ordering::Relaxed) {
+        emit_status(app, id, "connecting", None);
+        let mut stream = match tcp_connect(address) {
+            Ok(s) => s,
+            Err(e) => {
+                emit_status(app, id, "reconnecting", Some(e));
+                sleep_unless_stopped(delay, stop);
+                delay = (delay * 2).min(MAX_RECONNECT_DELAY);
+                continue;
+            }
+        };
+        emit_status(app, id, "connected", None);
+        delay = RECONNECT_DELAY;
+        let reason = loop {
+            if stop.load(Ordering::Relaxed) {
+                break None;
+            }
+            match stream.read(&mut buf) {
+                Ok(0) => break Some("connection closed by the server".to_string()),
+                Ok(n) => {
+                    let text = String::from_utf8_lossy(&buf[..n]).into_owned();
+                    let _ = app.emit("socket-data", SocketData { id, text });
+                }
+                Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+                Err(e) => break Some(e.to_string()),
+            }
+        };
+        if let Some(reason) = reason {
+            emit_status(app, id, "reconnecting", Some(reason));
+            sleep_unless_stopped(delay, stop);
+        }
+    }
+    emit_status(app, id, "closed", None);
+}
+
+fn run_udp(app: &tauri::AppHandle, id: u32, sock: &std::net::UdpSocket, stop: &AtomicBool) {
+    let mut buf = [0u8; 65536];
+    emit_status(app, id, "connected", None);
+    let mut message = None;
+    while !stop.load(Ordering::Relaxed) {
+        match sock.recv(&mut buf) {
+            Ok(n) => {
+                let mut text = String::from_utf8_lossy(&buf[..n]).into_owned();
+                // A datagram holds whole lines; terminate the last one.
+                if !text.ends_with('\n') {
+                    text.push('\n');
+                }
+                let _ = app.emit("socket-data", SocketData { id, text });
+            }
+            Err(e) if matches!(e.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+            Err(e) => {
+                message = Some(e.to_string());
+                break;
+            }
+        }
+    }
+    emit_status(app, id, "closed", message);
+}
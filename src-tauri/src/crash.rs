@@ -0,0 +1,127 @@
+//! Crash reports and the autosaved project.
+//!
+//! The frontend autosaves the workspace as a binary project and keeps a
+//! short summary of the app's state (graph and series counts, last actions)
+//! here. A panic — native, or in the wasm renderer via [`report_crash`] —
+//! writes a text report next to them and leaves a `pending` marker, so the
+//! next launch can show what happened and offer to restore the autosave.
+
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tauri::Manager;
+
+/// `<config>/crash`, set once by [`install`].
+static DIR: OnceLock<PathBuf> = OnceLock::new();
+/// Latest app-state summary from the frontend (JSON text).
+static STATE: Mutex<String> = Mutex::new(String::new());
+
+const AUTOSAVE: &str = "autosave.oxideplot";
+const PENDING: &str = "pending";
+
+/// Chain a panic hook that writes a crash report after the default output.
+pub fn install(app: &tauri::App) {
+    if let Ok(config) = app.path().app_config_dir() {
+        let _ = DIR.set(config.join("crash"));
+    }
+    let default = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default(info);
+        let backtrace = std::backtrace::Backtrace::force_capture();
+        let _ = write_report("native", &info.to_string(), &backtrace.to_string());
+    }));
+}
+
+fn dir() -> Result<&'static PathBuf, String> {
+    let dir = DIR.get().ok_or("no config folder for crash reports")?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+/// Write `crash-<unix seconds>.txt` and mark it pending. Returns its path.
+fn write_report(kind: &str, message: &str, backtrace: &str) -> Result<PathBuf, String> {
+    let dir = dir()?;
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    // A panic while the lock is held still gets the last state written.
+    let state = match STATE.lock() {
+        Ok(s) => s.clone(),
+        Err(poisoned) => poisoned.into_inner().clone(),
+    };
+    let report = format!(
+        "OxidePlot {} crash report ({kind} panic, {} {})\nTime: {secs} (Unix seconds)\n\n\
+         Panic: {message}\n\nApp state: {state}\n\nBacktrace:\n{backtrace}\n",
+        env!("CARGO_PKG_VERSION"),
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+    );
+    let path = dir.join(format!("crash-{secs}.txt"));
+    std::fs::write(&path, report).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(PENDING), path.to_string_lossy().as_bytes()).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Keep the frontend's state summary for the next crash report.
+#[tauri::command]
+pub fn record_app_state(state: String) {
+    if let Ok(mut s) = STATE.lock() {
+        *s = state;
+    }
+}
+
+/// Save a wasm panic (message and JS stack) as a crash report. Returns the
+/// report's path.
+#[tauri::command]
+pub fn report_crash(message: String, stack: String) -> Result<String, String> {
+    write_report("renderer", &message, &stack).map(|p| p.to_string_lossy().into_owned())
+}
+
+/// Replace the autosaved project. Written to a temporary file first so a
+/// crash mid-write keeps the previous autosave.
+#[tauri::command]
+pub fn autosave_project(contents: Vec<u8>) -> Result<(), String> {
+    let dir = dir()?;
+    let tmp = dir.join(format!("{AUTOSAVE}.tmp"));
+    std::fs::write(&tmp, &contents).map_err(|e| e.to_string())?;
+    std::fs::rename(&tmp, dir.join(AUTOSAVE)).map_err(|e| e.to_string())
+}
+
+/// The autosaved project's bytes.
+#[tauri::command]
+pub fn read_autosave() -> Result<Vec<u8>, String> {
+    std::fs::read(dir()?.join(AUTOSAVE)).map_err(|e| e.to_string())
+}
+
+/// A crash from a previous session not yet acknowledged.
+#[derive(serde::Serialize)]
+pub struct PendingCrash {
+    /// Path of the crash report.
+    pub report: String,
+    /// Its `Panic:` line.
+    pub message: String,
+    /// Whether an autosaved project can be restored.
+    pub autosave: bool,
+}
+
+/// The last crash, if the previous session ended in one.
+#[tauri::command]
+pub fn pending_crash() -> Option<PendingCrash> {
+    let dir = dir().ok()?;
+    let report = std::fs::read_to_string(dir.join(PENDING)).ok()?;
+    let text = std::fs::read_to_string(&report).unwrap_or_default();
+    let message = text
+        .lines()
+        .find_map(|l| l.strip_prefix("Panic: "))
+        .unwrap_or("unknown error")
+        .to_string();
+    Some(PendingCrash { report, message, autosave: dir.join(AUTOSAVE).exists() })
+}
+
+/// Acknowledge the pending crash; its report stays on disk.
+#[tauri::command]
+pub fn dismiss_crash() -> Result<(), String> {
+    match std::fs::remove_file(dir()?.join(PENDING)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+        _ => Ok(()),
+    }
+}
@@ -1,9 +1,11 @@
 mod commands;
+mod crash;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
   tauri::Builder::default()
     .setup(|app| {
+      crash::install(app);
       if cfg!(debug_assertions) {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
@@ -16,10 +18,35 @@ pub fn run() {
     .invoke_handler(tauri::generate_handler![
       commands::pick_file,
       commands::read_file,
+      commands::file_size,
+      commands::read_file_range,
       commands::save_file,
+      commands::pick_save_path,
+      commands::write_file,
+      commands::pick_folder,
+      commands::write_file_in,
       commands::load_prefs,
-      commands::save_prefs
+      commands::save_prefs,
+      commands::sqlite_tables,
+      commands::sqlite_query,
+      commands::fetch_url,
+      commands::serial_ports,
+      commands::serial_open,
+      commands::serial_close,
+      commands::socket_open,
+      commands::socket_close,
+      crash::record_app_state,
+      crash::report_crash,
+      crash::autosave_project,
+      crash::read_autosave,
+      crash::pending_crash,
+      crash::dismiss_crash
     ])
-    .run(tauri::generate_context!())
-    .expect("error while running tauri application");
+    .build(tauri::generate_context!())
+    .expect("error while building tauri application")
+    .run(|_, event| {
+      if let tauri::RunEvent::Exit = event {
+        commands::remove_temp_files();
+      }
+    });
 }
@@ -6,6 +6,8 @@
  */
 
 import { invoke } from '@tauri-apps/api/core';
+import { listen, type UnlistenFn } from '@tauri-apps/api/event';
+import type { SqlTable } from './renderer.js';
 
 /**
  * Open a native file-picker dialog and return the chosen path, or null if the
@@ -26,6 +28,17 @@ export const pickFile = (): Promise<string | null> =>
 export const readFile = (path: string): Promise<number[]> =>
   invoke<number[]>('read_file', { path });
 
+/** Size in bytes of the file at `path`. */
+export const fileSize = (path: string): Promise<number> =>
+  invoke<number>('file_size', { path });
+
+/**
+ * Up to `len` bytes of the file at `path` starting at `offset` (fewer at the
+ * end). Arrives as raw binary, not a number array, for chunked imports.
+ */
+export const readFileRange = async (path: string, offset: number, len: number): Promise<Uint8Array> =>
+  new Uint8Array(await invoke<ArrayBuffer>('read_file_range', { path, offset, len }));
+
 /**
  * Open a native save-file dialog and write `contents` to the chosen path.
  * Returns the saved path, or null if the user cancelled the dialog.
@@ -42,6 +55,35 @@ export const saveFile = (
     contents: Array.from(contents),
   });
 
+/** Open a native save-file dialog without writing; the chosen path, or
+ *  null if the user cancelled. Pair with `writeFile`. */
+export const pickSavePath = (defaultName: string): Promise<string | null> =>
+  invoke<string | null>('pick_save_path', { defaultName });
+
+/** Write `contents` to `path` without a dialog. */
+export const writeFile = (path: string, contents: Uint8Array): Promise<void> =>
+  invoke<void>('write_file', { path, contents: Array.from(contents) });
+
+/** Open a native folder picker; null if the user cancelled. */
+export const pickFolder = (): Promise<string | null> =>
+  invoke<string | null>('pick_folder');
+
+/**
+ * Write `contents` to the file `name` inside folder `dir` without a dialog
+ * (scheduled exports). Resolves to the written path.
+ */
+export const writeFileIn = (dir: string, name: string, contents: Uint8Array): Promise<string> =>
+  invoke<string>('write_file_in', { dir, name, contents: Array.from(contents) });
+
+/** A graph's scheduled export: snapshots written to `folder` every `minutes`. */
+export interface AutoExportConfig {
+  enabled: boolean;
+  folder: string | null;
+  minutes: number;
+  png: boolean;
+  csv: boolean;
+}
+
 /**
  * Load the persisted preferences JSON string from the app config dir.
  * Returns '{}' if no prefs file exists yet.
@@ -54,3 +96,142 @@ export const loadPrefs = (): Promise<string> =>
  */
 export const savePrefs = (contents: string): Promise<void> =>
   invoke<void>('save_prefs', { contents });
+
+/**
+ * Read just the `gpuBackend` preference ('auto' when unset or unreadable).
+ * Graphs call this before creating their renderer, which happens before
+ * App's own prefs load finishes.
+ */
+export const loadGpuBackendPref = async (): Promise<string> => {
+  try {
+    const parsed = JSON.parse(await loadPrefs());
+    return typeof parsed.gpuBackend === 'string' ? parsed.gpuBackend : 'auto';
+  } catch (_) {
+    return 'auto';
+  }
+};
+
+/** Tables (and views) of the SQLite database at `path`, read natively. */
+export const sqliteTables = (path: string): Promise<SqlTable[]> =>
+  invoke<SqlTable[]>('sqlite_tables', { path });
+
+/**
+ * Run the read-only query `sql` against the SQLite database at `path` with
+ * SQLite itself (full SQL; a `-wal` file is applied). The result arrives as
+ * CSV bytes with a header row, loaded like a file (`Graph.loadSqlResult`).
+ */
+export const sqliteQuery = async (path: string, sql: string): Promise<Uint8Array> =>
+  new Uint8Array(await invoke<ArrayBuffer>('sqlite_query', { path, sql }));
+
+/** A file downloaded by `fetchUrl`, saved to a temp file (removed when the
+ *  app exits) whose bytes `readFileRange` returns. */
+export interface FetchedFile {
+  /** Name taken from the URL (or content type) — its extension picks the loader. */
+  filename: string;
+  path: string;
+  size: number;
+}
+
+/**
+ * Download `url` in the backend (no browser CORS restrictions), sending the
+ * extra `[name, value]` request headers, e.g. `['Authorization', 'Bearer …']`.
+ * Rejects with `HTTP <status> <reason>` on error responses, and on
+ * downloads over 1 GiB.
+ */
+export const fetchUrl = (url: string, headers: [string, string][]): Promise<FetchedFile> =>
+  invoke<FetchedFile>('fetch_url', { url, headers });
+
+// ── Serial streaming ─────────────────────────────────────────────────────────
+
+/** Serial port names (`COM3`, `/dev/ttyUSB0`, …). */
+export const serialPorts = (): Promise<string[]> =>
+  invoke<string[]>('serial_ports');
+
+/**
+ * Open `port` at `baud` as connection `id`. What the device sends arrives
+ * through `onSerialData` as text chunks, split anywhere; `onSerialClosed`
+ * fires when the connection ends. Opening replaces any connection already
+ * open, whose late events still carry its own id.
+ */
+export const serialOpen = (id: number, port: string, baud: number): Promise<void> =>
+  invoke<void>('serial_open', { id, port, baud });
+
+/** Close the serial connection (no-op if none is open). */
+export const serialClose = (): Promise<void> =>
+  invoke<void>('serial_close');
+
+/** Subscribe to received serial text of every connection; resolves to the
+ *  unsubscribe function. */
+export const onSerialData = (cb: (id: number, chunk: string) => void): Promise<UnlistenFn> =>
+  listen<{ id: number; text: string }>('serial-data', (e) => cb(e.payload.id, e.payload.text));
+
+/** Subscribe to a connection ending: an error message, or null when closed
+ *  by `serialClose`. */
+export const onSerialClosed = (cb: (id: number, reason: string | null) => void): Promise<UnlistenFn> =>
+  listen<{ id: number; message: string | null }>('serial-closed', (e) => cb(e.payload.id, e.payload.message));
+
+// ── Socket streaming ─────────────────────────────────────────────────────────
+
+export type SocketProtocol = 'tcp' | 'udp';
+
+/** Connection states reported by `onSocketStatus`. */
+export interface SocketStatusEvent {
+  id: number;
+  state: 'connecting' | 'connected' | 'reconnecting' | 'closed';
+  message: string | null;
+}
+
+/**
+ * Subscribe connection `id` to a socket emitting CSV or JSON lines. TCP
+ * connects to `address` (`host:port`) and reconnects when it drops; UDP
+ * binds `address` (e.g. `0.0.0.0:5005`). Text arrives via `onSocketData`.
+ */
+export const socketOpen = (id: number, protocol: SocketProtocol, address: string): Promise<void> =>
+  invoke<void>('socket_open', { id, protocol, address });
+
+/** Close socket connection `id` (no-op if it isn't open). */
+export const socketClose = (id: number): Promise<void> =>
+  invoke<void>('socket_close', { id });
+
+/** Subscribe to received socket text of every connection. */
+export const onSocketData = (cb: (id: number, chunk: string) => void): Promise<UnlistenFn> =>
+  listen<{ id: number; text: string }>('socket-data', (e) => cb(e.payload.id, e.payload.text));
+
+/** Subscribe to connection state changes of every connection. */
+export const onSocketStatus = (cb: (status: SocketStatusEvent) => void): Promise<UnlistenFn> =>
+  listen<SocketStatusEvent>('socket-status', (e) => cb(e.payload));
+
+// ── Crash reports and autosave ───────────────────────────────────────────────
+
+/** Keep a short app-state summary (JSON) for the next crash report. */
+export const recordAppState = (state: string): Promise<void> =>
+  invoke<void>('record_app_state', { state });
+
+/** Save a renderer panic as a crash report; resolves to the report's path. */
+export const reportCrash = (message: string, stack: string): Promise<string> =>
+  invoke<string>('report_crash', { message, stack });
+
+/** Replace the autosaved project (binary `.oxideplot` bytes). */
+export const autosaveProject = (contents: Uint8Array): Promise<void> =>
+  invoke<void>('autosave_project', { contents: Array.from(contents) });
+
+/** The autosaved project's bytes. */
+export const readAutosave = async (): Promise<Uint8Array> =>
+  new Uint8Array(await invoke<number[]>('read_autosave'));
+
+/** A crash from the previous session, not yet acknowledged. */
+export interface PendingCrash {
+  /** Path of the crash report. */
+  report: string;
+  message: string;
+  /** Whether an autosaved project can be restored. */
+  autosave: boolean;
+}
+
+/** The previous session's crash, or null when it ended normally. */
+export const pendingCrash = (): Promise<PendingCrash | null> =>
+  invoke<PendingCrash | null>('pending_crash');
+
+/** Acknowledge the pending crash (its report stays on disk). */
+export const dismissCrash = (): Promise<void> =>
+  invoke<void>('dismiss_crash');
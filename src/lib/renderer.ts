@@ -8,24 +8,64 @@
  * canvas-driven interaction.
  */
 
-import initWasm, { OxidePlot } from './wasm/oxideplot_wasm.js';
+import initWasm, { OxidePlot, HeatmapPlot } from './wasm/oxideplot_wasm.js';
 import wasmUrl from './wasm/oxideplot_wasm_bg.wasm?url';
 
 export interface ColumnMeta {
   name: string;
   kind: 'numeric' | 'datetime' | 'text';
+  /** Fraction of cells that hold a valid value for `kind` (0–1), from import. */
+  valid: number;
 }
 
 export interface FileMeta {
   columns: ColumnMeta[];
   rows: number;
+  /** Separators a delimited-text file was read with (detected ones filled in). */
+  csv?: { delimiter: string; decimal: string; thousands: string | null };
+  /** Channel-name and value columns when the file looks like a long
+   *  (timestamp, channel, value) log to pivot. */
+  long_format?: { key_col: number; value_col: number };
+  /** How a decimated chunked import thinned the rows, e.g. `1 of every 10 rows`. */
+  decimation?: string;
+}
+
+/**
+ * Row thinning for a chunked import (X is the first numeric or time
+ * column): every `n`th row, one mean row per `width`-wide X bucket (seconds
+ * for a time X), or only the rows with X in `min`…`max` at full resolution.
+ */
+export type ImportDecimation =
+  | { mode: 'every'; n: number }
+  | { mode: 'bucket'; width: number }
+  | { mode: 'window'; min: number; max: number };
+
+/**
+ * Import overrides for delimited text; absent or `'auto'` fields are
+ * detected. `delimiter` is `'comma'`, `'semicolon'`, `'tab'`, `'space'` or a
+ * single character; `thousands` is never guessed (`''` for none).
+ */
+export interface CsvImportOptions {
+  delimiter?: string;
+  decimal?: 'auto' | '.' | ',';
+  thousands?: string;
 }
 
 export interface SeriesSpec {
   x_col: number;
   y_col: number;
   color: [number, number, number, number];
-  draw_mode: 'lines' | 'step' | 'points';
+  draw_mode: 'lines' | 'step' | 'step-pre' | 'points' | 'smooth' | 'bar';
+  /** Generated X (row i at start + i × interval) used instead of `x_col`. */
+  x_synth?: { start: number; interval: number };
+  /** Category column: one series per distinct value (e.g. per test phase). */
+  split_col?: number;
+  /** With `split_col`: pivot a long-format log, naming each series after
+   *  its channel value alone. */
+  pivot?: boolean;
+  /** ± uncertainty shown as error bars or a band (a column applies to
+   *  unsplit series only). */
+  error?: SeriesErrorSpec;
 }
 
 export interface ViewState {
@@ -40,10 +80,460 @@ export interface TableColumn {
   numeric: boolean;
 }
 
+/** Table row aggregation: every row, every `n`th row, or `n`-row buckets. */
+export type TableDecimationMode = 'off' | 'nth' | 'buckets';
+
+export interface TableDecimation {
+  mode: TableDecimationMode;
+  n: number;
+  /** True while showing the raw rows behind a selection (`tableDrill`). */
+  drilled: boolean;
+}
+
 export interface SeriesInfoEntry {
   name: string;
   color: [number, number, number, number];
   visible: boolean;
+  /** Modular-display wrap period (e.g. 360), or null when off. */
+  wrap_period: number | null;
+  /** Defining operation of a derived series (transform or formula), or null
+   *  for a series plotted straight from a file column. */
+  formula: string | null;
+  /** Reference (baseline) curve overlaid from the app's list; drawn dashed. */
+  reference: boolean;
+  /** Value→label map (`"0=OFF, 1=ON"`) for an enumerated channel, or null. */
+  labels: string | null;
+  /** X-gap threshold splitting the series into segments, or null when off. */
+  gap_threshold: number | null;
+  /** Number of segments (1 unless split at gaps). */
+  segments: number;
+  /** Uncertainty display, or null when off. */
+  error_style: ErrorStyle | null;
+  /** Column holding the per-sample ± values, or null for a constant. */
+  error_column: string | null;
+  /** Constant ± value (used when `error_column` is null). */
+  error_value: number | null;
+  /** Last value extended to the right edge of the view. */
+  hold_last: boolean;
+  /** Left out of the nearest-point search (see `setSeriesHover`). */
+  no_hover: boolean;
+  /** Draw-order band (see `setSeriesLayer`). */
+  layer: SeriesLayer;
+  /** Area fill, or null when off (see `setSeriesFill`). */
+  fill: AreaFillMode | null;
+  /** Name of the series a 'between' fill reaches to. */
+  fill_to: string | null;
+  /** How a file series' rows were thinned at import (null at full resolution). */
+  decimated: string | null;
+  /** X offset and Y scale/offset applied to the samples (see `setSeriesAlign`). */
+  align: SeriesAlign;
+}
+
+/** Spike test for `previewDespike` / `despikeSeries`. */
+export type DespikeMethod = 'hampel' | 'zscore';
+
+/** Per-series alignment: `x + x_offset`, `y * y_scale + y_offset`. */
+export interface SeriesAlign {
+  x_offset: number;
+  y_offset: number;
+  y_scale: number;
+}
+
+/** Options for `startStream`. */
+export interface StreamConfig {
+  /** 'auto' (spaces, commas or semicolons), 'comma', 'semicolon', 'tab',
+   *  'space', or a single character. */
+  delimiter?: string;
+  /** Field holding each sample's X; omitted: the sample index. */
+  x_column?: number | null;
+  /** Samples kept per channel (default 1 000 000); older ones are dropped. */
+  max_points?: number;
+}
+
+/** Stacked-area display: off, cumulative sums, or shares of 100 %. */
+export type StackMode = 'off' | 'stacked' | 'percent';
+
+/** How bar series share an X slot: side by side, or piled up. */
+export type BarLayout = 'grouped' | 'stacked';
+
+/** Filled area of a series: down to zero, or between it and another series. */
+export type AreaFillMode = 'baseline' | 'between';
+
+/** Draw-order band of a series: under, in list order, or over the rest. */
+export type SeriesLayer = 'back' | 'normal' | 'front';
+
+/** Where grid lines draw relative to the series. */
+export type GridLayer = 'below' | 'above';
+
+/** How ± uncertainty is drawn: error bars with caps, or a shaded band. */
+export type ErrorStyle = 'bars' | 'band';
+
+/** How a break in a line (missing samples) is marked: left blank, or
+ *  bridged by a faint dashed / dotted line. */
+export type GapStyle = 'none' | 'dashed' | 'dotted';
+
+/** Uncertainty for `setSeriesError`: a loaded-file column or a constant ±. */
+export interface SeriesErrorSpec {
+  style: ErrorStyle;
+  column?: number;
+  value?: number;
+}
+
+/** Statistics of one gap-split segment (see `seriesSegments`). */
+/** One series' statistics over a view window (`windowStats`). */
+export interface WindowStatsRow {
+  name: string;
+  count: number;
+  min: number;
+  max: number;
+  peak_to_peak: number;
+  mean: number;
+  median: number;
+  std_dev: number;
+}
+
+/** Every visible series' statistics over a window, bounds formatted like
+ *  the X axis. */
+export interface WindowStats {
+  start_text: string;
+  end_text: string;
+  rows: WindowStatsRow[];
+}
+
+/** One visible series' statistics over a dragged X region. */
+export interface RegionStatsRow {
+  /** Index into the series list. */
+  series: number;
+  name: string;
+  /** Inferred unit, or null. */
+  unit: string | null;
+  count: number;
+  mean: number;
+  min: number;
+  max: number;
+  std_dev: number;
+  rms: number;
+  peak_to_peak: number;
+  /** Trapezoidal area under the curve over the region. */
+  integral: number;
+  /** Unit of `integral` (`"A·s"`), or null. */
+  integral_unit: string | null;
+}
+
+/** Region statistics tool report, bounds formatted like the X axis. */
+export interface RegionStats {
+  start_text: string;
+  end_text: string;
+  rows: RegionStatsRow[];
+}
+
+/** Recurring shading of a datetime X axis; hours 0–24, UTC like the ticks.
+ *  An end at or before the start runs past midnight. */
+export type TimeBand =
+  | { kind: 'night'; start_hour: number; end_hour: number }
+  | { kind: 'weekend' }
+  /** `days`: 0 = Monday … 6 = Sunday. */
+  | { kind: 'weekly'; days: number[]; start_hour: number; end_hour: number };
+
+/** One shaded stretch of `bands[band]` within the view. */
+export interface BandInterval {
+  band: number;
+  x0: number;
+  x1: number;
+}
+
+export interface SegmentStats {
+  x_start: number;
+  x_end: number;
+  /** Segment bounds formatted like the X axis. */
+  start_text: string;
+  end_text: string;
+  count: number;
+  min: number;
+  max: number;
+  mean: number;
+  std_dev: number;
+}
+
+/** A named baseline curve kept by the app and overlaid on any graph. */
+export interface ReferenceCurve {
+  name: string;
+  x_name: string;
+  xs: number[];
+  ys: number[];
+}
+
+/** A SQLite table offered by the query prompt. */
+export interface SqlTable {
+  name: string;
+  columns: string[];
+}
+
+/** How a derived series was computed (saved in projects). */
+export interface Derivation {
+  op: string;
+  source: string | null;
+  params: Record<string, string>;
+}
+
+export interface ProjectSeries {
+  name: string;
+  x_name: string;
+  color: [number, number, number, number];
+  draw_mode: string;
+  visible: boolean;
+  wrap_period: number | null;
+  derivation: Derivation | null;
+  reference: boolean;
+  labels: string | null;
+  gap_threshold?: number | null;
+  /** ± uncertainty; `values` holds a column's per-sample half-widths. */
+  error?: { style: ErrorStyle; value: number; column: string | null; values: number[] | null } | null;
+  hold_last?: boolean;
+  no_hover?: boolean;
+  layer?: SeriesLayer;
+  /** Area fill: to zero, or between it and the named series. */
+  fill?: 'baseline' | { between: string } | null;
+  /** File column re-read for the series once a unit conversion renamed it. */
+  column?: string | null;
+  /** Alignment already applied to `xs`/`ys` (omitted when none). */
+  align?: SeriesAlign;
+  xs: number[];
+  ys: number[];
+}
+
+/** A text note pinned to a plot position (view coordinates, like cursors). */
+export interface Annotation {
+  x: number;
+  y: number;
+  text: string;
+}
+
+/** An imported event line (operator note, trouble code) at `x`. */
+export interface EventMarker {
+  x: number;
+  label: string;
+  /** RGB 0–1; null draws in the theme accent. */
+  color: [number, number, number] | null;
+}
+
+/** Sample nearest the mouse (see `nearestPoint`). */
+export interface NearestPoint {
+  series: number;
+  index: number;
+  name: string;
+  x: number;
+  y: number;
+  /** Full-precision text for copying (timestamp on datetime axes). */
+  x_text: string;
+  y_text: string;
+  /** Y where the sample is drawn (view space). */
+  view_y: number;
+  dist_px: number;
+}
+
+/** A sample measured from a reference sample (`pointDelta`). */
+export interface PointDelta {
+  dx: number;
+  dy: number;
+  /** ΔY as a percentage of the reference Y; null when that is zero. */
+  percent: number | null;
+  /** ΔX with the X unit (seconds on a datetime axis). */
+  dx_text: string;
+  /** ΔY with the series unit. */
+  dy_text: string;
+}
+
+/** One graph of a saved project. */
+/** The file a graph's columns were read from, for linked projects. */
+export interface SourceFile {
+  /** Relative to the project file (absolute while the graph is open). */
+  path: string;
+  sql?: string | null;
+  csv?: CsvImportOptions;
+  /** Row thinning of a chunked import, re-applied when the link is read. */
+  decimate?: ImportDecimation | null;
+}
+
+/** A recorded conversion: series detected as `from` rescaled to `to`. */
+export interface UnitConversion {
+  from: string;
+  to: string;
+}
+
+/** A convertible unit among the plotted series (see `detectedUnits`). */
+export interface DetectedUnit {
+  unit: string;
+  count: number;
+  targets: string[];
+}
+
+export interface GraphProject {
+  file: string | null;
+  /** Set when the graph's data came from a file on disk. */
+  source?: SourceFile | null;
+  x_is_time: boolean;
+  y_labels: string | null;
+  x_title: string | null;
+  y_title: string | null;
+  /** 'stacked' / 'percent' when the graph was stacked. */
+  stack_mode?: StackMode | null;
+  /** 'stacked' when bar series were piled up; absent when grouped. */
+  bar_layout?: BarLayout | null;
+  /** Edited per-unit Y axes, by unit. */
+  unit_axes?: Record<string, UnitAxis>;
+  /** Unit conversions repeated on the graph's file columns, in order. */
+  unit_conversions?: UnitConversion[];
+  x_min: number;
+  x_max: number;
+  y_min: number;
+  y_max: number;
+  series: ProjectSeries[];
+  annotations: Annotation[];
+  /** Night/weekend/weekly shading of a datetime X axis. */
+  time_bands?: TimeBand[];
+  /** Imported event lines. */
+  events?: EventMarker[];
+}
+
+/** A saved project file (`.oxproj`, JSON). */
+export interface Project {
+  version: number;
+  graphs: GraphProject[];
+}
+
+/** Synthetic benchmark dataset shapes. */
+export type SyntheticKind = 'sine-sweep' | 'noise' | 'step-train';
+
+/** One step of the benchmark's pan/zoom script: pan fractions of the plot
+ *  (`dx` right, `dy` up), zoom steps (> 0 zooms in), or a fit. */
+export type BenchStep =
+  | { op: 'fit' }
+  | { op: 'pan'; dx: number; dy: number }
+  | { op: 'zoom'; steps: number };
+
+/** Frame-time summary from `benchFrameStats`, in milliseconds. */
+export interface FrameStats {
+  frames: number;
+  mean_ms: number;
+  p50_ms: number;
+  p95_ms: number;
+  p99_ms: number;
+  max_ms: number;
+  fps: number;
+}
+
+/** A situation that can show a one-time feature tip. */
+export type TipTrigger = 'data-loaded' | 'two-series' | 'two-graphs' | 'time-x' | 'large-file';
+
+/** One release-note entry (`releaseNotes`). */
+export interface ReleaseNote {
+  /** Stable id, remembered once seen. */
+  id: string;
+  title: string;
+  body: string;
+  /** Where the feature lives in the UI. */
+  where: string;
+  trigger: TipTrigger | null;
+}
+
+/** The notes of one released version. */
+export interface Release {
+  version: string;
+  notes: ReleaseNote[];
+}
+
+/** A plotted series' full data, from `seriesCurve` — an operand for
+ *  cross-graph math. */
+export interface SeriesCurve {
+  name: string;
+  x_name: string;
+  xs: number[];
+  ys: number[];
+}
+
+/** How cross-graph math reads operands B, C, … at operand A's X. */
+export type CrossMathAlign = 'linear' | 'hold' | 'nearest';
+
+/** Least-squares model families (`seriesFit`, transform `'fit'`). */
+export type FitKind = 'linear' | 'polynomial' | 'exponential' | 'power';
+
+/** A fitted curve's parameters and goodness of fit. */
+export interface FitReport {
+  /** 'linear', 'poly3', 'exp' or 'power'. */
+  model: string;
+  /** Polynomial c₀ … c_d, or [a, b] for exponential / power law. */
+  coefficients: number[];
+  /** Origin of `x − x₀` (the first sample on a datetime axis, else 0). */
+  x0: number;
+  x0_text: string;
+  r_squared: number;
+  count: number;
+  equation: string;
+}
+
+/** Parameters for `addTransform`; each kind reads only the keys it needs. */
+export interface TransformParams {
+  window?: number;
+  order?: number;
+  mode?: string;
+  method?: string;
+  points?: number;
+  op?: string;
+  value?: number;
+}
+
+/** Mouse position formatted for the plot-corner readout. */
+export interface Readout {
+  x: string;
+  y: string;
+}
+
+/** What the series list shows next to each name: off, the value at cursor
+ *  1, at the mouse X, or the latest sample (live streams). */
+export type LegendValueMode = 'off' | 'cursor' | 'mouse' | 'latest';
+
+/** A graph's lock: `edits` freezes its series and data but still allows
+ *  pan/zoom; `all` freezes the view too. */
+export type GraphLock = 'off' | 'edits' | 'all';
+
+/** One series' entry in the legend value column (`legendValues`). */
+export interface LegendValue {
+  value: number;
+  /** Formatted with the series' unit, or its state name. */
+  text: string;
+}
+
+/** One visible series' line in the cursor readout table (`cursorTable`). */
+export interface CursorRow {
+  /** Index into the series list. */
+  series: number;
+  name: string;
+  /** Interpolated value at each cursor; null off the series' X span. */
+  values: (number | null)[];
+  /** `values` formatted with the series' unit or state name ('' for none). */
+  texts: string[];
+  /** Cursor 2 minus cursor 1, with two cursors placed. */
+  delta: number | null;
+  delta_text: string;
+  /** Area under the series from cursor 1 to cursor 2 (trapezoidal), per
+   *  hour on a datetime axis once the cursors are an hour apart. */
+  area: number | null;
+  /** `area` with its unit (`kW·h`) when the series' unit is known. */
+  area_text: string;
+}
+
+/** Axis titles shown on the plot (`axisTitles`); `*_custom` marks a title
+ *  the user edited rather than one derived from the data. */
+export interface AxisTitles {
+  x: string;
+  y: string;
+  x_custom: boolean;
+  y_custom: boolean;
+  /** Edited X display name ('' when derived from the X column). */
+  x_name: string;
+  /** Edited X unit, shown in readouts and export/table headers. */
+  x_unit: string | null;
 }
 
 export interface TickEntry {
@@ -55,6 +545,38 @@ export interface TickEntry {
 export interface AxisTicksData {
   x: TickEntry[];
   y: TickEntry[];
+  /** Normalized mode: one axis per unit (tick values in view space). */
+  units: UnitAxisTicks[];
+}
+
+export type AxisSide = 'left' | 'right';
+
+/** Y axis settings of one unit (see `unitAxes`). */
+export interface UnitAxis {
+  label: string;
+  /** Fit to the unit's series; otherwise `min`..`max` is used. */
+  auto_range: boolean;
+  min: number;
+  max: number;
+  side: AxisSide;
+  /** Place among the unit axes (0 = outermost on its edge); null follows
+   *  the order units were first used. */
+  order: number | null;
+}
+
+export interface UnitAxisEntry extends UnitAxis {
+  unit: string;
+  /** Range drawn now (manual or fitted); null while the unit is hidden. */
+  range: [number, number] | null;
+}
+
+export interface UnitAxisTicks {
+  unit: string;
+  label: string;
+  side: AxisSide;
+  /** Colour of the unit's first visible series. */
+  color: [number, number, number, number];
+  ticks: TickEntry[];
 }
 
 export interface HistogramData {
@@ -71,6 +593,42 @@ export interface SpectrumData {
   sample_rate: number;
 }
 
+/** Workspace-wide label → RGB colour table (keys are trimmed, lowercased
+ *  labels); `overrides` are hand-picked and win over `assigned`. */
+export interface ColorRegistry {
+  assigned: Record<string, [number, number, number]>;
+  overrides: Record<string, [number, number, number]>;
+}
+
+/** Taper applied before the FFT in `seriesAmplitudeSpectrum`. */
+export type FftWindow = 'hann' | 'hamming' | 'rectangular';
+
+export interface AmplitudeSpectrumData {
+  freqs: number[];
+  amplitude: number[];
+  window: FftWindow;
+  sample_rate: number;
+}
+
+/** Box-and-whisker summary of one series (see `seriesBoxPlot`). */
+export interface BoxPlotData {
+  n: number;
+  min: number;
+  q1: number;
+  median: number;
+  q3: number;
+  max: number;
+  mean: number;
+  /** Furthest samples within 1.5 IQR of the box. */
+  whisker_lo: number;
+  whisker_hi: number;
+  /** Samples beyond the whiskers, thinned to at most 500. */
+  outliers: number[];
+  n_outliers: number;
+  /** Peak-normalised density, evenly spaced from `min` to `max` (violin). */
+  density: number[];
+}
+
 export interface SpectrogramData {
   frames: number[][];
   bins: number;
@@ -80,12 +638,87 @@ export interface SpectrogramData {
   duration_s: number;
 }
 
+/** Colour lookup for the spectrogram heatmap. */
+export type Colormap = 'magma' | 'viridis' | 'gray';
+
+/**
+ * GPU heatmap on its own canvas (see `Renderer.createHeatmap`). `draw` paints
+ * spectrogram `frames` into the plot rect `(left, top, w, h)` in canvas pixels.
+ */
+export interface HeatmapSurface {
+  draw(frames: number[][], colormap: Colormap, left: number, top: number, w: number, h: number): void;
+  resize(w: number, h: number): void;
+  setBackground(r: number, g: number, b: number, a: number): void;
+  free(): void;
+}
+
+/** Adapter/device details for the diagnostics panel (see `gpuDiagnostics()`). */
+export interface GpuAdapterInfo {
+  adapter_name: string;
+  vendor: number;
+  device: number;
+  device_type: string;
+  backend: string;
+  driver: string;
+  driver_info: string;
+  surface_format: string;
+  max_texture_dimension_2d: number;
+  max_buffer_size: number;
+  max_storage_buffer_binding_size: number;
+  max_bind_groups: number;
+}
+
+/** Result of `gpuDiagnostics()`. `adapter` is null in CPU fallback mode. */
+export interface GpuDiagnostics {
+  requested_backend: string;
+  fallback_reason: string | null;
+  adapter: GpuAdapterInfo | null;
+}
+
+/** Result of `gpuUpload()`: storage-buffer limits (bytes), what the last
+ *  frame uploaded under them, and the first wgpu error the device raised. */
+export interface GpuUploadStatus {
+  budget: { device_max_bytes: number; max_buffer_bytes: number; frame_upload_bytes: number };
+  upload: { uploaded_bytes: number; requested_bytes: number; decimated_series: number };
+  error: string | null;
+}
+
+/** One series from `cpuPolylines()` — canvas pixels, origin top-left. */
+export interface CpuPolyline {
+  points: [number, number][];
+  color: [number, number, number, number];
+  line_width: number;
+  point_radius: number;
+  points_only: boolean;
+  /** Fill the closed outline instead of stroking it (uncertainty bands). */
+  filled: boolean;
+}
+
 export interface ScatterData {
   xs: number[];
   ys: number[];
   n: number;
 }
 
+/** A numeric column reduced to min/max buckets for the import overview
+ *  (`lo`/`hi` are NaN for all-missing buckets; `min`/`max`/`mean` are NaN
+ *  when the column has no values). */
+export interface Sparkline {
+  lo: number[];
+  hi: number[];
+  min: number;
+  max: number;
+  mean: number;
+  count: number;
+}
+
+/** One entry of `columnOverview()`. */
+export interface ColumnSparkline {
+  col: number;
+  name: string;
+  spark: Sparkline;
+}
+
 /**
  * Wrapper around the WASM `OxidePlot` GPU renderer.
  *
@@ -101,134 +734,617 @@ export interface ScatterData {
 export class Renderer {
   private plot: OxidePlot | null = null;
   private ready = false;
+  /** What `create` was called with, to rebuild the GPU side after device loss. */
+  private canvas: HTMLCanvasElement | null = null;
+  private backend = 'auto';
+
+  /** Initialise the WASM module.  Must be called before `create`. */
+  async init(): Promise<void> {
+    await initWasm({ module_or_path: wasmUrl });
+    this.ready = true;
+  }
+
+  /**
+   * Create the GPU plot surface bound to `canvas`. `backend` is the persisted
+   * preference ('auto' | 'gl' | 'software').
+   */
+  async create(canvas: HTMLCanvasElement, backend = 'auto'): Promise<void> {
+    if (!this.ready) throw new Error('Call init() before create()');
+    this.canvas = canvas;
+    this.backend = backend;
+    this.plot = await (OxidePlot as any).create(canvas, backend);
+  }
+
+  /**
+   * Build a GPU heatmap surface on `canvas` with the same backend preference
+   * as the plot. Throws when no GPU device is available; the caller should
+   * paint the heatmap on a 2D canvas instead.
+   */
+  async createHeatmap(canvas: HTMLCanvasElement): Promise<HeatmapSurface> {
+    if (!this.ready) throw new Error('Call init() before createHeatmap()');
+    const heat = await (HeatmapPlot as any).create(canvas, this.backend);
+    return {
+      draw: (frames, colormap, left, top, w, h) => heat.draw(frames, colormap, left, top, w, h),
+      resize: (w, h) => heat.resize(w, h),
+      setBackground: (r, g, b, a) => heat.set_background(r, g, b, a),
+      free: () => heat.free(),
+    };
+  }
+
+  /** Why the GPU device was lost (driver reset, sleep/resume), or null. */
+  gpuDeviceLost(): string | null {
+    this.assertPlot();
+    return ((this.plot as any).gpu_device_lost() as string | undefined) ?? null;
+  }
+
+  /**
+   * Replace a lost GPU renderer with a new one on the same canvas and redraw.
+   * Loaded data, series, view and settings are kept. Throws if no GPU device
+   * can be created (the caller should fall back to CPU rendering).
+   */
+  async recoverGpu(): Promise<void> {
+    this.assertPlot();
+    const gpu = await (OxidePlot as any).rebuild_gpu(this.canvas, this.backend);
+    (this.plot as any).attach_gpu(gpu);
+  }
+
+  /**
+   * Parse file bytes and return column metadata.
+   * Throws a string error if parsing fails or WASM returns an error.
+   */
+  loadFileBytes(bytes: Uint8Array, filename: string, options?: CsvImportOptions): FileMeta {
+    this.assertPlot();
+    const result = (this.plot as any).load_file_bytes(bytes as unknown as Uint8Array, filename, options ?? null);
+    if (result === undefined || result === null) {
+      throw new Error('load_file_bytes returned nothing');
+    }
+    return result as FileMeta;
+  }
+
+  /**
+   * Chunked import of a delimited-text file too big to load whole: call
+   * `pushChunk` with the file's bytes in order, then `finishChunked` for
+   * the column metadata (or `cancelChunked`). `decimation` thins the rows
+   * as they're parsed. Throws on invalid decimation parameters.
+   */
+  beginChunked(options?: CsvImportOptions, decimation?: ImportDecimation): void {
+    this.assertPlot();
+    (this.plot as any).chunked_begin(options ?? null, decimation ?? null);
+  }
+
+  /** Parse the next piece of a chunked import. Returns false once an X
+   *  window import is past its window (X ascending): the rest of the file
+   *  can't add rows. Throws on a parse error. */
+  pushChunk(bytes: Uint8Array): boolean {
+    this.assertPlot();
+    return (this.plot as any).chunked_push(bytes) as boolean;
+  }
+
+  finishChunked(filename: string): FileMeta {
+    this.assertPlot();
+    return (this.plot as any).chunked_finish(filename) as FileMeta;
+  }
+
+  cancelChunked(): void {
+    this.assertPlot();
+    (this.plot as any).chunked_cancel();
+  }
+
+  /**
+   * Start plotting a live line stream (serial port): clears the graph; each
+   * `pushStream` then appends received text. Throws on a bad delimiter.
+   */
+  startStream(config: StreamConfig): void {
+    this.assertPlot();
+    (this.plot as any).stream_start(config);
+  }
+
+  /**
+   * Append streamed text (split anywhere; a partial line waits for its
+   * newline) and re-render, auto-scrolling while following. Returns the
+   * channel count, which grows when a new field appears.
+   */
+  pushStream(text: string): number {
+    this.assertPlot();
+    return (this.plot as any).stream_push(text) as number;
+  }
+
+  /** End the live stream; the received data stays plotted. */
+  stopStream(): void {
+    this.assertPlot();
+    (this.plot as any).stream_stop();
+  }
+
+  isStreaming(): boolean {
+    this.assertPlot();
+    return (this.plot as any).is_streaming() as boolean;
+  }
+
+  /** Resume (true) or stop auto-scrolling to the newest samples; pan and
+   *  zoom stop it. */
+  setStreamFollow(on: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_stream_follow(on);
+  }
+
+  streamFollow(): boolean {
+    this.assertPlot();
+    return (this.plot as any).stream_follow() as boolean;
+  }
+
+  /**
+   * Build GPU series from specs, auto-fit the view, and re-render.
+   * Throws if no file has been loaded or the spec JSON is invalid.
+   */
+  setSeries(specs: SeriesSpec[]): void {
+    this.assertPlot();
+    const json = JSON.stringify(specs);
+    this.plot!.set_series(json);
+  }
+
+  /**
+   * Why the WebGPU renderer couldn't start, or null when the GPU path is live.
+   * When set, `render()` is a no-op and the caller should paint
+   * `cpuPolylines()` onto a 2D canvas instead.
+   */
+  gpuFallbackReason(): string | null {
+    this.assertPlot();
+    return ((this.plot as any).gpu_fallback_reason() as string | undefined) ?? null;
+  }
+
+  /** Requested backend, fallback reason, and adapter/limits/surface-format details. */
+  gpuDiagnostics(): GpuDiagnostics {
+    this.assertPlot();
+    return (this.plot as any).gpu_diagnostics() as GpuDiagnostics;
+  }
+
+  /** Cap GPU storage uploads per series buffer and per frame, in MiB. Series
+   *  over the cap are drawn decimated instead of failing the device. */
+  setGpuLimits(maxBufferMb: number, frameUploadMb: number): void {
+    this.assertPlot();
+    (this.plot as any).set_gpu_limits(maxBufferMb, frameUploadMb);
+  }
+
+  /** Upload limits and last-frame usage, or null in CPU fallback mode. */
+  gpuUpload(): GpuUploadStatus | null {
+    this.assertPlot();
+    return ((this.plot as any).gpu_upload() as GpuUploadStatus | null) ?? null;
+  }
+
+  /** Visible series as decimated screen-space polylines for the CPU fallback painter. */
+  cpuPolylines(): CpuPolyline[] {
+    this.assertPlot();
+    return (this.plot as any).cpu_polylines() as CpuPolyline[];
+  }
+
+  /** Render one frame (use after pan/zoom events, if not called by setSeries). */
+  render(): void {
+    this.assertPlot();
+    this.plot!.render();
+  }
+
+  /** Notify the renderer of a canvas resize. */
+  resize(w: number, h: number): void {
+    this.assertPlot();
+    this.plot!.resize(w, h);
+  }
+
+  /**
+   * Auto-fit the view to encompass all series data and re-render.
+   * Delegates to `auto_fit` on the WASM side (which calls render internally).
+   */
+  autoFit(): void {
+    this.assertPlot();
+    this.plot!.auto_fit();
+  }
+
+  /** Nearest visible sample to view position (x, y) within `radiusPx`
+   *  backing pixels, or null. */
+  nearestPoint(x: number, y: number, radiusPx: number): NearestPoint | null {
+    this.assertPlot();
+    return ((this.plot as any).nearest_point(x, y, radiusPx) as NearestPoint | null) ?? null;
+  }
+
+  /** Every visible series' statistics between view X `x0` and `x1`. */
+  regionStats(x0: number, x1: number): RegionStats {
+    this.assertPlot();
+    return (this.plot as any).region_stats(x0, x1) as RegionStats;
+  }
+
+  /** Sample of series `series` nearest view X `x` (snapped cursors), or null. */
+  snapPoint(series: number, x: number): NearestPoint | null {
+    this.assertPlot();
+    return ((this.plot as any).snap_point(series, x) as NearestPoint | null) ?? null;
+  }
+
+  /** Sample `index` of series `series` measured from sample `refIndex` of
+   *  series `refSeries` (delta cursor), or null when either is gone. */
+  pointDelta(refSeries: number, refIndex: number, series: number, index: number): PointDelta | null {
+    this.assertPlot();
+    return ((this.plot as any).point_delta(refSeries, refIndex, series, index) as PointDelta | null) ?? null;
+  }
+
+  /** Format a view-space position for the mouse readout. */
+  readout(x: number, y: number): Readout {
+    this.assertPlot();
+    return (this.plot as any).readout(x, y) as Readout;
+  }
+
+  /** Each series' held value at view X `x`, or its latest sample when `x`
+   *  is omitted; null for series without a sample there. */
+  legendValues(x?: number): (LegendValue | null)[] {
+    this.assertPlot();
+    return (this.plot as any).legend_values(x) as (LegendValue | null)[];
+  }
+
+  /** Each visible series' value at the cursor X positions `xs`. */
+  cursorTable(xs: number[]): CursorRow[] {
+    this.assertPlot();
+    return (this.plot as any).cursor_table(new Float64Array(xs)) as CursorRow[];
+  }
+
+  axisTitles(): AxisTitles {
+    this.assertPlot();
+    return (this.plot as any).axis_titles() as AxisTitles;
+  }
+
+  /** Set the `'x'` or `'y'` axis title from `"name [unit]"` text; blank
+   *  restores the derived title. Does not re-render (titles are DOM). */
+  setAxisTitle(axis: 'x' | 'y', text: string): void {
+    this.assertPlot();
+    (this.plot as any).set_axis_title(axis, text);
+  }
+
+  /** Set the X axis display name and unit (blank fields unset; both blank
+   *  restores the derived title). Also used in CSV export and table headers
+   *  and in the readouts. Does not re-render. */
+  setXAxis(name: string, unit: string): void {
+    this.assertPlot();
+    (this.plot as any).set_x_axis(name, unit);
+  }
+
+  /** The Y axis of each unit inferred from the series names, in order of
+   *  first use. Normalized mode draws one axis per unit. */
+  unitAxes(): UnitAxisEntry[] {
+    this.assertPlot();
+    return (this.plot as any).unit_axes() as UnitAxisEntry[];
+  }
+
+  /** Convert every series detected as `from` to `to`, renaming them
+   *  `"name [to]"`, and record the conversion so later imports into this
+   *  graph repeat it. Returns the number of series converted; throws for
+   *  unknown or incompatible units. Re-fits the view. */
+  convertUnit(from: string, to: string): number {
+    this.assertPlot();
+    return (this.plot as any).convert_unit(from, to) as number;
+  }
+
+  /** Convertible units of the plotted series, with counts and targets. */
+  detectedUnits(): DetectedUnit[] {
+    this.assertPlot();
+    return (this.plot as any).detected_units() as DetectedUnit[];
+  }
+
+  /** The recorded conversions, in the order they run. */
+  unitConversions(): UnitConversion[] {
+    this.assertPlot();
+    return (this.plot as any).unit_conversions() as UnitConversion[];
+  }
+
+  /** Stop repeating the recorded conversions on later imports. */
+  clearUnitConversions(): void {
+    this.assertPlot();
+    (this.plot as any).clear_unit_conversions();
+  }
+
+  /** Edit the Y axis of `unit` and re-render. Throws when a manual range
+   *  has min ≥ max. */
+  setUnitAxis(unit: string, axis: UnitAxis): void {
+    this.assertPlot();
+    (this.plot as any).set_unit_axis(unit, axis);
+  }
+
+  /** Order the unit axes as `units` lists them (first = outermost on its
+   *  edge). Does not re-render (axes are DOM). */
+  setUnitAxisOrder(units: string[]): void {
+    this.assertPlot();
+    (this.plot as any).set_unit_axis_order(units);
+  }
+
+  /** Show the last `seconds` of data (X ends at the latest sample, Y fitted
+   *  to the window) and re-render. */
+  setTimeWindow(seconds: number): void {
+    this.assertPlot();
+    (this.plot as any).set_time_window(seconds);
+  }
+
+  /**
+   * Pan the view by a backing-store pixel delta and re-render.
+   * @param dx - horizontal drag delta in canvas backing-store pixels
+   * @param dy - vertical drag delta in canvas backing-store pixels
+   */
+  pan(dx: number, dy: number): void {
+    this.assertPlot();
+    this.plot!.pan(dx, dy);
+  }
+
+  /**
+   * Zoom around a screen-space anchor and re-render.
+   * @param scrollY - scroll magnitude; positive = zoom in.  Pass `-event.deltaY`.
+   * @param x - anchor X in canvas backing-store pixels
+   * @param y - anchor Y in canvas backing-store pixels
+   */
+  zoom(scrollY: number, x: number, y: number): void {
+    this.assertPlot();
+    this.plot!.zoom(scrollY, x, y);
+  }
+
+  /**
+   * Zoom only the Y axis of `unit` (normalized mode) about canvas row `y`,
+   * with `zoom`'s wheel step. Throws outside normalized mode.
+   */
+  zoomUnitAxis(unit: string, scrollY: number, y: number): void {
+    this.assertPlot();
+    (this.plot as any).zoom_unit_axis(unit, scrollY, y);
+  }
+
+  /** Pan only the Y axis of `unit` (normalized mode) by a vertical drag of
+   *  `dy` backing-store pixels. Throws outside normalized mode. */
+  panUnitAxis(unit: string, dy: number): void {
+    this.assertPlot();
+    (this.plot as any).pan_unit_axis(unit, dy);
+  }
+
+  /**
+   * Rebuild up to `maxSeries` series that pan/zoom drew at reduced quality
+   * (frame-budget guard) and re-render. Returns true while more remain.
+   */
+  refineStep(maxSeries = 4): boolean {
+    this.assertPlot();
+    return (this.plot as any).refine_step(maxSeries) as boolean;
+  }
+
+  /** Return the current view bounds as `{ x_min, x_max, y_min, y_max }`. */
+  viewState(): ViewState {
+    this.assertPlot();
+    return this.plot!.view_state() as ViewState;
+  }
+
+  /** Return tick data for both axes as `{ x: [...], y: [...] }`. */
+  axisTicks(): AxisTicksData {
+    this.assertPlot();
+    return (this.plot as any).axis_ticks() as AxisTicksData;
+  }
+
+  /** True when the plotted X axis is datetime data (spectral sample rate is then
+   *  inferred reliably, so the manual rate field is hidden). */
+  xIsTime(): boolean {
+    this.assertPlot();
+    return (this.plot as any).x_is_time() as boolean;
+  }
+
+  /**
+   * Set the draw mode for all existing series and re-render.
+   * @param mode - one of 'lines', 'step' (hold until the next point), 'step-pre'
+   *   (step at the previous point), 'points', 'smooth', or 'bar'
+   */
+  setDrawMode(mode: 'lines' | 'step' | 'step-pre' | 'points' | 'smooth' | 'bar'): void {
+    this.assertPlot();
+    this.plot!.set_draw_mode(mode);
+  }
+
+  /**
+   * Return an array of `{ name, color, visible }` for each series in render order.
+   */
+  seriesInfo(): SeriesInfoEntry[] {
+    this.assertPlot();
+    return (this.plot as any).series_info() as SeriesInfoEntry[];
+  }
+
+  /**
+   * Set the visibility of a series by index and re-render.
+   */
+  setSeriesVisible(index: number, visible: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_series_visible(index, visible);
+  }
 
-  /** Initialise the WASM module.  Must be called before `create`. */
-  async init(): Promise<void> {
-    await initWasm({ module_or_path: wasmUrl });
-    this.ready = true;
+  /** Show only the series at `index`; soloing the lone visible series
+   *  unmutes all. Re-renders. */
+  soloSeries(index: number): void {
+    this.assertPlot();
+    (this.plot as any).solo_series(index);
   }
 
-  /** Create the GPU plot surface bound to `canvas`. */
-  async create(canvas: HTMLCanvasElement): Promise<void> {
-    if (!this.ready) throw new Error('Call init() before create()');
-    this.plot = await OxidePlot.create(canvas);
+  /** Make every series visible and re-render. */
+  unmuteAll(): void {
+    this.assertPlot();
+    (this.plot as any).unmute_all();
   }
 
   /**
-   * Parse file bytes and return column metadata.
-   * Throws a string error if parsing fails or WASM returns an error.
+   * Set the RGB color of a series by index (components in 0..1) and re-render.
    */
-  loadFileBytes(bytes: Uint8Array, filename: string): FileMeta {
+  setSeriesColor(index: number, r: number, g: number, b: number): void {
     this.assertPlot();
-    const result = this.plot!.load_file_bytes(bytes as unknown as Uint8Array, filename);
-    if (result === undefined || result === null) {
-      throw new Error('load_file_bytes returned nothing');
-    }
-    return result as FileMeta;
+    (this.plot as any).set_series_color(index, r, g, b);
   }
 
   /**
-   * Build GPU series from specs, auto-fit the view, and re-render.
-   * Throws if no file has been loaded or the spec JSON is invalid.
+   * Display a series as modular data wrapping every `period` Y units (360 for
+   * compass headings, 2π for radians) — wraps draw as gaps, not vertical
+   * jumps. Pass `null` to turn it off.
    */
-  setSeries(specs: SeriesSpec[]): void {
+  setSeriesWrap(index: number, period: number | null): void {
     this.assertPlot();
-    const json = JSON.stringify(specs);
-    this.plot!.set_series(json);
+    (this.plot as any).set_series_wrap(index, period ?? undefined);
   }
 
-  /** Render one frame (use after pan/zoom events, if not called by setSeries). */
-  render(): void {
+  /**
+   * Split series `index` into segments wherever X jumps by more than
+   * `threshold` (X units); `null` turns it off. Segments draw unconnected.
+   */
+  setSeriesGap(index: number, threshold: number | null): void {
     this.assertPlot();
-    this.plot!.render();
+    (this.plot as any).set_series_gap(index, threshold ?? undefined);
   }
 
-  /** Notify the renderer of a canvas resize. */
-  resize(w: number, h: number): void {
+  /**
+   * Extend the last value of series `index` to the right edge of the view,
+   * for status/level channels whose final state still holds.
+   */
+  setSeriesHoldLast(index: number, on: boolean): void {
     this.assertPlot();
-    this.plot!.resize(w, h);
+    (this.plot as any).set_series_hold_last(index, on);
   }
 
   /**
-   * Auto-fit the view to encompass all series data and re-render.
-   * Delegates to `auto_fit` on the WASM side (which calls render internally).
+   * Align series `index` with another logger's recording: X shifted by
+   * `xOffset`, Y mapped to `y * yScale + yOffset`, replacing any earlier
+   * alignment. Throws on a zero scale or a non-finite value.
    */
-  autoFit(): void {
+  setSeriesAlign(index: number, xOffset: number, yOffset: number, yScale: number): void {
     this.assertPlot();
-    this.plot!.auto_fit();
+    (this.plot as any).set_series_align(index, xOffset, yOffset, yScale);
   }
 
   /**
-   * Pan the view by a backing-store pixel delta and re-render.
-   * @param dx - horizontal drag delta in canvas backing-store pixels
-   * @param dy - vertical drag delta in canvas backing-store pixels
+   * Permanently delete the samples of series `index` (every visible series
+   * when `null`) outside `xMin..xMax`. Returns how many were removed; throws,
+   * changing nothing, when a series would be left empty.
    */
-  pan(dx: number, dy: number): void {
+  cropSeries(index: number | null, xMin: number, xMax: number): number {
     this.assertPlot();
-    this.plot!.pan(dx, dy);
+    return (this.plot as any).crop_series(index ?? undefined, xMin, xMax) as number;
   }
 
   /**
-   * Zoom around a screen-space anchor and re-render.
-   * @param scrollY - scroll magnitude; positive = zoom in.  Pass `-event.deltaY`.
-   * @param x - anchor X in canvas backing-store pixels
-   * @param y - anchor Y in canvas backing-store pixels
+   * Mark the spikes in series `index` found by `method` — `'hampel'`
+   * (beyond `threshold` robust σ of the median of a `window`-sample
+   * neighbourhood) or `'zscore'` (beyond `threshold` σ of the series mean) —
+   * drawn over the plot until applied or cleared. Returns how many.
    */
-  zoom(scrollY: number, x: number, y: number): void {
+  previewDespike(index: number, method: DespikeMethod, window: number, threshold: number): number {
     this.assertPlot();
-    this.plot!.zoom(scrollY, x, y);
+    return (this.plot as any).preview_despike(index, method, window, threshold) as number;
   }
 
-  /** Return the current view bounds as `{ x_min, x_max, y_min, y_max }`. */
-  viewState(): ViewState {
+  /** Remove the `previewDespike` markers. */
+  clearDespikePreview(): void {
     this.assertPlot();
-    return this.plot!.view_state() as ViewState;
+    (this.plot as any).clear_despike_preview();
   }
 
-  /** Return tick data for both axes as `{ x: [...], y: [...] }`. */
-  axisTicks(): AxisTicksData {
+  /** Fix the spikes `previewDespike` would mark: bridged by a straight line
+   *  when `interpolate`, else deleted. Returns how many; undoable. */
+  despikeSeries(index: number, method: DespikeMethod, window: number, threshold: number, interpolate: boolean): number {
     this.assertPlot();
-    return (this.plot as any).axis_ticks() as AxisTicksData;
+    return (this.plot as any).despike_series(index, method, window, threshold, interpolate) as number;
   }
 
-  /** True when the plotted X axis is datetime data (spectral sample rate is then
-   *  inferred reliably, so the manual rate field is hidden). */
-  xIsTime(): boolean {
+  /** Restore the samples before the last `cropSeries` / `despikeSeries`;
+   *  false when there is nothing to undo. */
+  undoSampleEdit(): boolean {
     this.assertPlot();
-    return (this.plot as any).x_is_time() as boolean;
+    return (this.plot as any).undo_sample_edit() as boolean;
   }
 
-  /**
-   * Set the draw mode for all existing series and re-render.
-   * @param mode - one of 'lines', 'step', or 'points'
-   */
-  setDrawMode(mode: 'lines' | 'step' | 'points'): void {
+  /** How many crops / despikes `undoSampleEdit` can take back. */
+  sampleUndoDepth(): number {
     this.assertPlot();
-    this.plot!.set_draw_mode(mode);
+    return (this.plot as any).sample_undo_depth() as number;
+  }
+
+  /** Include series `index` in the nearest-point search (the point menu)
+   *  or leave it out. Reference curves start out left out. */
+  setSeriesHover(index: number, on: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_series_hover(index, on);
   }
 
   /**
-   * Return an array of `{ name, color, visible }` for each series in render order.
+   * Fill the area of series `index` down to zero ('baseline'), between it
+   * and series `other` ('between', e.g. a min/max envelope), or 'off'.
    */
-  seriesInfo(): SeriesInfoEntry[] {
+  setSeriesFill(index: number, mode: AreaFillMode | 'off', other: number | null = null): void {
     this.assertPlot();
-    return (this.plot as any).series_info() as SeriesInfoEntry[];
+    (this.plot as any).set_series_fill(index, mode, other ?? undefined);
   }
 
   /**
-   * Set the visibility of a series by index and re-render.
+   * Bring series `index` to the front, send it to the back, or return it to
+   * list order; its place in the list is unchanged.
    */
-  setSeriesVisible(index: number, visible: boolean): void {
+  setSeriesLayer(index: number, layer: SeriesLayer): void {
     this.assertPlot();
-    (this.plot as any).set_series_visible(index, visible);
+    (this.plot as any).set_series_layer(index, layer);
   }
 
   /**
-   * Set the RGB color of a series by index (components in 0..1) and re-render.
+   * Show ± uncertainty on series `index` as error bars or a band, from a
+   * column of the loaded file (index into `columnNames()`) or a constant;
+   * `null` turns it off. Throws if the column doesn't line up with the series.
    */
-  setSeriesColor(index: number, r: number, g: number, b: number): void {
+  setSeriesError(index: number, spec: SeriesErrorSpec | null): void {
     this.assertPlot();
-    (this.plot as any).set_series_color(index, r, g, b);
+    (this.plot as any).set_series_error(index, spec);
+  }
+
+  /** Suggested gap threshold for series `index` (10× its median X step). */
+  suggestSeriesGap(index: number): number | null {
+    this.assertPlot();
+    return (this.plot as any).suggest_series_gap(index) ?? null;
+  }
+
+  /** Statistics of every visible series over `[xMin, xMax]`, for the
+   *  cross-graph window report. */
+  windowStats(xMin: number, xMax: number): WindowStats {
+    this.assertPlot();
+    return (this.plot as any).window_stats(xMin, xMax) as WindowStats;
+  }
+
+  /** Per-segment statistics of series `index` (one entry when not split). */
+  seriesSegments(index: number): SegmentStats[] {
+    this.assertPlot();
+    return ((this.plot as any).series_segments(index) ?? []) as SegmentStats[];
+  }
+
+  /** Least-squares fit of series `index` (`degree` for 'polynomial').
+   *  Throws when the series has too few usable points. */
+  seriesFit(index: number, kind: FitKind, degree: number): FitReport {
+    this.assertPlot();
+    return (this.plot as any).series_fit(index, kind, degree) as FitReport;
+  }
+
+  /** Series `index` as CSV with a leading 1-based `segment` column. */
+  exportSegmentsCsv(index: number): string {
+    this.assertPlot();
+    return (this.plot as any).export_segments_csv(index) as string;
+  }
+
+  /** Attach a value→label map (`"0=OFF, 1=IDLE"`) to series `index`; blank
+   *  removes it. Throws on a malformed map. */
+  setSeriesLabels(index: number, spec: string): void {
+    this.assertPlot();
+    (this.plot as any).set_series_labels(index, spec);
+  }
+
+  /** Attach a value→label map to the Y axis (overrides series maps). */
+  setYLabels(spec: string): void {
+    this.assertPlot();
+    (this.plot as any).set_y_labels(spec);
+  }
+
+  /** Current Y-axis label map, or null. */
+  yLabels(): string | null {
+    this.assertPlot();
+    return ((this.plot as any).y_labels() as string | undefined) ?? null;
+  }
+
+  /** State name for a Y value under the active label map, or null. */
+  yLabel(value: number): string | null {
+    this.assertPlot();
+    return ((this.plot as any).y_label(value) as string | undefined) ?? null;
   }
 
   /**
@@ -239,6 +1355,38 @@ export class Renderer {
     (this.plot as any).remove_series(index);
   }
 
+  /** Cap how many visible series are drawn at once (list order) and re-render. */
+  setMaxDrawnSeries(n: number): void {
+    this.assertPlot();
+    (this.plot as any).set_max_drawn_series(n);
+  }
+
+  /** Visible series skipped because of the draw cap. */
+  seriesOverflow(): number {
+    this.assertPlot();
+    return (this.plot as any).series_overflow() as number;
+  }
+
+  /**
+   * Freeze the visible series as greyed ghost traces drawn beneath whatever
+   * is plotted next. Returns the number of traces frozen.
+   */
+  freezeTraces(): number {
+    this.assertPlot();
+    return (this.plot as any).freeze_traces() as number;
+  }
+
+  /** Remove every ghost trace and re-render. */
+  clearFrozen(): void {
+    this.assertPlot();
+    (this.plot as any).clear_frozen();
+  }
+
+  frozenCount(): number {
+    this.assertPlot();
+    return (this.plot as any).frozen_count() as number;
+  }
+
   /**
    * Remove all series from the plot and re-render (empty state).
    */
@@ -247,6 +1395,41 @@ export class Renderer {
     (this.plot as any).clear_series();
   }
 
+  /** Replace the series with `series` synthetic ones of `points` samples
+   *  each (benchmark data). Throws past the engine's point limit. */
+  loadSynthetic(kind: SyntheticKind, points: number, series: number): void {
+    this.assertPlot();
+    (this.plot as any).load_synthetic(kind, points, series);
+  }
+
+  /** The benchmark's pan/zoom script, `frames` steps long. */
+  benchScript(frames: number): BenchStep[] {
+    return (OxidePlot as any).bench_script(frames) as BenchStep[];
+  }
+
+  /** Mean and percentiles of frame times (ms). */
+  benchFrameStats(times: number[]): FrameStats {
+    return (OxidePlot as any).bench_frame_stats(new Float64Array(times)) as FrameStats;
+  }
+
+  /** Plotting engine version, for benchmark reports. */
+  engineVersion(): string {
+    return (OxidePlot as any).engine_version() as string;
+  }
+
+  /** Pack the samples of an idle off-screen graph to save memory; returns
+   *  the bytes saved (0 when nothing could be packed). */
+  hibernate(): number {
+    this.assertPlot();
+    return (this.plot as any).hibernate() as number;
+  }
+
+  /** Unpack series packed by `hibernate` and re-render (no-op otherwise). */
+  wake(): void {
+    this.assertPlot();
+    (this.plot as any).wake();
+  }
+
   /**
    * Move the series at `from` to position `to` (reorders z-order) and re-render.
    */
@@ -273,6 +1456,15 @@ export class Renderer {
     (this.plot as any).set_point_radius(r);
   }
 
+  /**
+   * Grid display: shown at all, below or above the series, and snapped to
+   * whole pixels. Does NOT call render().
+   */
+  setGrid(show: boolean, layer: GridLayer, crisp: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_grid(show, layer, crisp);
+  }
+
   /**
    * Set the WebGPU clear colour to match the current theme.
    *
@@ -317,6 +1509,86 @@ export class Renderer {
     (this.plot as any).set_normalized(on);
   }
 
+  /**
+   * Stack the drawn series as filled areas ('stacked': cumulative sums,
+   * 'percent': each X scaled to 100 %), or 'off'. Series stack per unit in
+   * list order; stacking turns normalized mode off and re-fits Y.
+   */
+  setStackMode(mode: StackMode): void {
+    this.assertPlot();
+    (this.plot as any).set_stack_mode(mode);
+  }
+
+  /** Current stack mode. */
+  stackMode(): StackMode {
+    this.assertPlot();
+    return (this.plot as any).stack_mode() as StackMode;
+  }
+
+  /**
+   * Lay out the graph's bar series side by side in each X slot ('grouped')
+   * or piled on each other ('stacked'); re-fits Y.
+   */
+  setBarLayout(layout: BarLayout): void {
+    this.assertPlot();
+    (this.plot as any).set_bar_layout(layout);
+  }
+
+  /** Current bar layout. */
+  barLayout(): BarLayout {
+    this.assertPlot();
+    return (this.plot as any).bar_layout() as BarLayout;
+  }
+
+  /** Colour series by unit (a hue family per unit, lightness per series),
+   *  kept up as series are added; off restores the palette. Re-renders. */
+  setColorByUnit(on: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_color_by_unit(on);
+  }
+
+  /** Whether unit colouring is on (picking a series colour turns it off). */
+  colorByUnit(): boolean {
+    this.assertPlot();
+    return (this.plot as any).color_by_unit() as boolean;
+  }
+
+  /** Draw series too faint against the background darkened or lightened
+   *  just enough to read, re-checked on `setBackground`; the series keep
+   *  their own colours. Re-renders. */
+  setAutoContrast(on: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_auto_contrast(on);
+  }
+
+  /** Mark breaks in line series (missing samples, logger session gaps)
+   *  with a faint bridge in the series colour, or not at all. Re-renders. */
+  setGapStyle(style: GapStyle): void {
+    this.assertPlot();
+    (this.plot as any).set_gap_style(style);
+  }
+
+  /** Indices of the series below 3:1 contrast with the background as drawn
+   *  (none while auto contrast is on). */
+  lowContrastSeries(): number[] {
+    this.assertPlot();
+    return Array.from((this.plot as any).low_contrast_series() as Uint32Array);
+  }
+
+  /** Colour series from the workspace colour registry (same colour per
+   *  label in every graph); hand-picked colours since the last call become
+   *  overrides. Returns the updated registry. Re-renders. */
+  applyColorRegistry(registry: ColorRegistry | null): ColorRegistry {
+    this.assertPlot();
+    return (this.plot as any).apply_color_registry(registry ?? undefined) as ColorRegistry;
+  }
+
+  /** Stop tracking this graph's series against the colour registry. */
+  releaseColorRegistry(): void {
+    this.assertPlot();
+    (this.plot as any).release_color_registry();
+  }
+
   /**
    * Set the autoscale mode used when auto-fitting the view.
    * @param mode - one of 'minmax' or 'robust'
@@ -347,9 +1619,16 @@ export class Renderer {
   /**
    * Append a derived series built from a math transform of the source at `sourceIndex`.
    *
-   * `kind` — one of `'moving_average'`, `'derivative'`, `'integral'`,
-   *           `'normalize'`, `'abs'`, `'log'`, `'sqrt'`.
-   * `params` — optional `{ window?, mode? }`; pass `null` to use defaults.
+   * `kind` — one of `'moving_average'`, `'median'`, `'ema'` (exponential,
+   *           `window` = span), `'savgol'` (Savitzky–Golay: `window`,
+   *           `order`), `'rolling_rms'` (`window`), `'envelope'` (`mode`
+   *           'hilbert' | 'peak', `window`), `'derivative'`, `'integral'`,
+   *           `'normalize'`, `'resample'`, `'abs'`, `'log'`, `'sqrt'`,
+   *           `'log10'`, `'exp'`, `'unwrap'` (mode 'degrees' | 'radians'),
+   *           or `'constant'` (series op constant).
+   * `params` — optional `{ window?, order?, mode?, method?, points?, op?, value? }`;
+   *           pass `null` to use defaults. `'constant'` takes
+   *           `op` ('add' | 'sub' | 'mul' | 'div') and a finite `value`.
    *
    * The new series is added, auto-fitted, and rendered immediately.
    * Throws if `sourceIndex` is out of range or `kind` is unrecognised.
@@ -357,12 +1636,34 @@ export class Renderer {
   addTransform(
     sourceIndex: number,
     kind: string,
-    params: { window?: number; mode?: string } | null,
+    params: TransformParams | null,
   ): void {
     this.assertPlot();
     (this.plot as any).add_transform(sourceIndex, kind, params);
   }
 
+  /** Full data of series `index`, to use as a cross-graph math operand. */
+  seriesCurve(index: number): SeriesCurve {
+    this.assertPlot();
+    return (this.plot as any).series_curve(index) as SeriesCurve;
+  }
+
+  /**
+   * Plot `expr` over `operands` (named A, B, … in order, possibly from other
+   * graphs) as a new series here, aligned onto operand A's X. Throws on a
+   * bad expression or when the operands don't overlap.
+   */
+  addCrossMath(
+    name: string,
+    operands: SeriesCurve[],
+    expr: string,
+    align: CrossMathAlign,
+    tolerance?: number,
+  ): void {
+    this.assertPlot();
+    (this.plot as any).add_cross_math(name, operands, expr, align, tolerance);
+  }
+
   /** Create + plot a derived column from an expression over existing columns.
    *  Returns the updated FileMeta (dataset now includes the new column). */
   deriveColumn(name: string, expr: string): FileMeta {
@@ -370,6 +1671,106 @@ export class Renderer {
     return (this.plot as any).derive_column(name, expr) as FileMeta;
   }
 
+  /** Parse a small CSV/Excel file into a reference curve. Needs only the
+   *  WASM module (no plot), since references belong to the app. */
+  parseReference(bytes: Uint8Array, filename: string): ReferenceCurve {
+    if (!this.ready) throw new Error('Call init() before parseReference()');
+    return (OxidePlot as any).parse_reference(bytes, filename) as ReferenceCurve;
+  }
+
+  /** Where `bands` fall within `[xMin, xMax]` (epoch seconds). Needs only
+   *  the WASM module; the bands are kept with the graph by the frontend. */
+  timeBandIntervals(bands: TimeBand[], xMin: number, xMax: number): BandInterval[] {
+    if (!this.ready) throw new Error('Call init() before timeBandIntervals()');
+    return (OxidePlot as any).time_band_intervals(bands, xMin, xMax) as BandInterval[];
+  }
+
+  /** Parse an events CSV/Excel file (time, label, optional `color` column)
+   *  into markers sorted by time. Needs only the WASM module. */
+  parseEvents(bytes: Uint8Array, filename: string): EventMarker[] {
+    if (!this.ready) throw new Error('Call init() before parseEvents()');
+    return (OxidePlot as any).parse_events(bytes, filename) as EventMarker[];
+  }
+
+  /** `time,label,color` CSV of `events`, readable by `parseEvents`. */
+  eventsCsv(events: EventMarker[], xIsTime: boolean): string {
+    if (!this.ready) throw new Error('Call init() before eventsCsv()');
+    return (OxidePlot as any).events_csv(events, xIsTime) as string;
+  }
+
+  /** Overlay a reference curve on this graph, dashed, in `color` (RGB 0–1). */
+  addReference(curve: ReferenceCurve, color: [number, number, number]): void {
+    this.assertPlot();
+    (this.plot as any).add_reference(curve, color[0], color[1], color[2]);
+  }
+
+  /** Snapshot this graph (series data, derivations, view) for a project. */
+  exportProject(): GraphProject {
+    this.assertPlot();
+    return (this.plot as any).export_project() as GraphProject;
+  }
+
+  /**
+   * Replace this graph's contents with a saved project graph. `linked`: the
+   * graph's source file was just loaded here; its file-column series saved
+   * without samples are re-read from it.
+   */
+  importProject(graph: GraphProject, linked = false): void {
+    this.assertPlot();
+    (this.plot as any).import_project(graph, linked);
+  }
+
+  /** Encode a project as a binary `.oxideplot` archive; with `link`, file
+   *  columns of graphs with a `source` are saved as references only. */
+  encodeProject(project: Project, link: boolean): Uint8Array {
+    if (!this.ready) throw new Error('Call init() before encodeProject()');
+    return (OxidePlot as any).encode_project(project, link) as Uint8Array;
+  }
+
+  /** Encode `project` as a self-contained bundle zip with every sample
+   *  embedded, plus `files` (source files, previews) stored by name. */
+  encodeBundle(project: Project, files: { name: string; bytes: Uint8Array }[]): Uint8Array {
+    if (!this.ready) throw new Error('Call init() before encodeBundle()');
+    const data = new Uint8Array(files.reduce((n, f) => n + f.bytes.length, 0));
+    let at = 0;
+    for (const f of files) {
+      data.set(f.bytes, at);
+      at += f.bytes.length;
+    }
+    return (OxidePlot as any).encode_bundle(
+      project,
+      files.map(f => f.name),
+      new Float64Array(files.map(f => f.bytes.length)),
+      data,
+    ) as Uint8Array;
+  }
+
+  /** Decode a binary project archive. Throws on a malformed or too-new file. */
+  decodeProject(bytes: Uint8Array): Project {
+    if (!this.ready) throw new Error('Call init() before decodeProject()');
+    return (OxidePlot as any).decode_project(bytes) as Project;
+  }
+
+  /** `file` relative to the folder of `project` (a file path). */
+  projectRelativePath(project: string, file: string): string {
+    return (OxidePlot as any).project_relative_path(project, file) as string;
+  }
+
+  /** Where a project-relative path points. */
+  projectResolvePath(project: string, rel: string): string {
+    return (OxidePlot as any).project_resolve_path(project, rel) as string;
+  }
+
+  /** Release notes compiled into the app, newest release first. */
+  releaseNotes(): Release[] {
+    return (OxidePlot as any).release_notes() as Release[];
+  }
+
+  /** The one-time tip for `trigger` whose id isn't in `seen`, or null. */
+  releaseTip(trigger: TipTrigger, seen: string[]): ReleaseNote | null {
+    return ((OxidePlot as any).release_tip(trigger, seen) as ReleaseNote | undefined) ?? null;
+  }
+
   /** Return the loaded dataset's column names, in file order (empty if no
    *  file is loaded). Used by the formula editor's clickable column list. */
   columnNames(): string[] {
@@ -377,6 +1778,13 @@ export class Renderer {
     return (this.plot as any).column_names() as string[];
   }
 
+  /** Skip downsampling and drawing while the graph is scrolled out of view;
+   *  `false` catches up and re-renders. */
+  setCulled(culled: boolean): void {
+    this.assertPlot();
+    (this.plot as any).set_culled(culled);
+  }
+
   /**
    * Set the view's X bounds (leaving Y unchanged), rebuild visible series,
    * and re-render.  Called by Graph.applyXRange when syncing X across graphs.
@@ -386,7 +1794,17 @@ export class Renderer {
     (this.plot as any).set_x_range(xMin, xMax);
   }
 
-  /** Set the full view bounds (X and Y) — used by the rubber-band zoom box.
+  /**
+   * Box zoom to the screen rectangle with corners (x0, y0) and (x1, y1), in
+   * canvas backing-store pixels. Returns false (view unchanged) when the
+   * rectangle has no width or height.
+   */
+  zoomToRect(x0: number, y0: number, x1: number, y1: number): boolean {
+    this.assertPlot();
+    return (this.plot as any).zoom_to_rect(x0, y0, x1, y1) as boolean;
+  }
+
+  /** Set the full view bounds (X and Y).
    *  Bounds are in the same space `viewState()` reports (log space for log-Y). */
   setViewBounds(xMin: number, xMax: number, yMin: number, yMax: number): void {
     this.assertPlot();
@@ -412,6 +1830,23 @@ export class Renderer {
     return (this.plot as any).series_spectrum(sourceIndex, sampleRate ?? undefined) as SpectrumData;
   }
 
+  /**
+   * Compute the one-sided FFT amplitude spectrum of the series at
+   * `sourceIndex` after applying `window`. Peaks read in the series' Y
+   * units. `sampleRate` (Hz) is used if given; otherwise it is inferred from
+   * the series' xs. Returns `{ freqs, amplitude, window, sample_rate }`.
+   */
+  seriesAmplitudeSpectrum(sourceIndex: number, window: FftWindow, sampleRate?: number): AmplitudeSpectrumData {
+    this.assertPlot();
+    return (this.plot as any).series_amplitude_spectrum(sourceIndex, window, sampleRate ?? undefined) as AmplitudeSpectrumData;
+  }
+
+  /** Box-plot summary (quartiles, whiskers, outliers, violin density) of source `sourceIndex`. */
+  seriesBoxPlot(sourceIndex: number): BoxPlotData {
+    this.assertPlot();
+    return (this.plot as any).series_box_plot(sourceIndex) as BoxPlotData;
+  }
+
   /**
    * Compute a short-time FFT magnitude spectrogram for the series at
    * `sourceIndex` with the given `window` size. `sampleRate` (Hz) is used
@@ -433,6 +1868,13 @@ export class Renderer {
     return (this.plot as any).scatter_data(xCol, yCol) as ScatterData;
   }
 
+  /** Sparklines of every numeric column of the loaded file, for the import
+   *  overview grid. `buckets` = 0 uses the default resolution. */
+  columnOverview(buckets = 0): ColumnSparkline[] {
+    this.assertPlot();
+    return (this.plot as any).column_overview(buckets) as ColumnSparkline[];
+  }
+
   // ── Table API ─────────────────────────────────────────────────────────────
 
   /** Return column metadata `[{ name, numeric }]` for the loaded file. */
@@ -487,6 +1929,46 @@ export class Renderer {
     return this.plot!.table_window(start, count) as string[][];
   }
 
+  /**
+   * Aggregate table rows: `'nth'` keeps every `n`th row, `'buckets'` shows
+   * min/mean/max columns per `n` rows. Sort and filters still apply to the
+   * raw rows; `tableColumns()` lists the aggregate columns while bucketed.
+   */
+  tableSetDecimation(mode: TableDecimationMode, n: number): void {
+    this.assertPlot();
+    (this.plot as any).table_set_decimation(mode, n);
+  }
+
+  /** Current decimation and whether the table is drilled down. */
+  tableDecimation(): TableDecimation {
+    this.assertPlot();
+    return (this.plot as any).table_decimation() as TableDecimation;
+  }
+
+  /** Show the raw rows behind aggregated rows `first..=last`. */
+  tableDrill(first: number, last: number): void {
+    this.assertPlot();
+    (this.plot as any).table_drill(first, last);
+  }
+
+  /** Back from a drill-down to the aggregated rows. */
+  tableDrillClear(): void {
+    this.assertPlot();
+    (this.plot as any).table_drill_clear();
+  }
+
+  /** Table row nearest plot X `x`, or null (split-view hover sync). */
+  tableRowAtX(x: number): number | null {
+    this.assertPlot();
+    return (this.plot as any).table_row_at_x(x) ?? null;
+  }
+
+  /** X of table row `row`, or null past the end or on a missing value. */
+  tableRowX(row: number): number | null {
+    this.assertPlot();
+    return (this.plot as any).table_row_x(row) ?? null;
+  }
+
   private assertPlot(): void {
     if (!this.plot) throw new Error('Renderer not created — call create(canvas) first');
   }